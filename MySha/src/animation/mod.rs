@@ -0,0 +1,355 @@
+//! Module for step by step walkthroughs of the [sha256 algorithm]
+//!
+//! This module provides the [walkthrough()] function, that hashes a message like [sha256()][crate::sha256::sha256()],
+//! but also describes every step of the process as a [Step], given in order to a [Renderer].
+//! The renderer decides how each step is shown, so the same walkthrough can drive the animation of the cli tool,
+//! a graphical interface, a web page, or just be collected, with the [steps()] function.
+//!
+//! The walkthrough is not tied to sha256: it is parameterized over an [Algorithm], that gives the block size, the word size and the round functions,
+//! and [walkthrough_with()] walks through any of them.
+//!
+//! # Examples
+//! ```
+//! use mysha::animation::{walkthrough, Step};
+//! use mysha::sha256::{HashError, InputType};
+//! # fn main() -> Result<(), HashError>{
+//! let mut rounds = 0;
+//! let hash = walkthrough("abc", InputType::Text, &mut |step: &Step| {
+//!     if let Step::Round{..} = step{
+//!         rounds += 1;
+//!     }
+//! })?;
+//!
+//! assert_eq!(rounds, 64);
+//! assert_eq!(hash.get_hex(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [sha256 algorithm]: https://en.wikipedia.org/wiki/SHA-2
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::sha256::{get_bits, Hash256, HashError, InputType, ParseOptions};
+
+mod sha256;
+pub use sha256::Sha256;
+
+/// Phases of the walkthrough, in the order they happen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase{
+    /// Message bits, padding and message blocks
+    Padding,
+    /// Explanation of the operations and of the constants K
+    Constants,
+    /// Message schedule of the block
+    Schedule,
+    /// Compression rounds of the block
+    Compression,
+    /// Adding the compressed block to the hash values, and the final hash
+    Finalization,
+}
+
+impl Phase{
+    /// Every phase, in order.
+    pub const ALL: [Phase; 5] = [Phase::Padding, Phase::Constants, Phase::Schedule, Phase::Compression, Phase::Finalization];
+
+    /// Name of the phase, as it is parsed by [FromStr].
+    pub fn name(&self) -> &'static str{
+        match self{
+            Phase::Padding => "padding",
+            Phase::Constants => "constants",
+            Phase::Schedule => "schedule",
+            Phase::Compression => "compression",
+            Phase::Finalization => "finalization",
+        }
+    }
+
+    /// Short description of what happens in the phase.
+    pub fn description(&self) -> &'static str{
+        match self{
+            Phase::Padding => "Message bits, padding and message blocks",
+            Phase::Constants => "Explanation of the operations and of the constants K",
+            Phase::Schedule => "Message schedule of the block",
+            Phase::Compression => "Compression rounds of the block",
+            Phase::Finalization => "Adding the compressed block to the hash values, and the final hash",
+        }
+    }
+}
+
+impl fmt::Display for Phase{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Phase{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Phase, String>{
+        Phase::ALL.into_iter().find(|phase| phase.name() == s).ok_or(format!("invalid phase: {}", s))
+    }
+}
+
+/// A word of a hash algorithm, the unit its operations work on.
+pub trait Word: Copy + Default + PartialEq + fmt::Debug + fmt::Binary + fmt::LowerHex{
+    /// Size of the word in bits.
+    const BITS: usize;
+
+    /// Parses a word from exactly [BITS][Word::BITS] binary digits.
+    fn from_bits(bits: &str) -> Self;
+
+    /// Addition modulo 2**[BITS][Word::BITS].
+    fn add_mod(self, other: Self) -> Self;
+}
+
+impl Word for u32{
+    const BITS: usize = 32;
+
+    fn from_bits(bits: &str) -> u32{
+        u32::from_str_radix(bits, 2).unwrap()
+    }
+
+    fn add_mod(self, other: u32) -> u32{
+        self.wrapping_add(other)
+    }
+}
+
+impl Word for u64{
+    const BITS: usize = 64;
+
+    fn from_bits(bits: &str) -> u64{
+        u64::from_str_radix(bits, 2).unwrap()
+    }
+
+    fn add_mod(self, other: u64) -> u64{
+        self.wrapping_add(other)
+    }
+}
+
+/// A hash algorithm built like sha256, that can be walked through.
+///
+/// The message is padded with a 1, zeros, and its size in [LENGTH_BITS][Algorithm::LENGTH_BITS] bits,
+/// and divided in blocks of [BLOCK_BITS][Algorithm::BLOCK_BITS] bits.
+/// Every block is expanded to a message schedule of [ROUNDS][Algorithm::ROUNDS] words, that are mixed into the working variables
+/// by the compression rounds, and the compressed block is added to the hash values.
+///
+/// The block size, word size and round functions are all that change between algorithms,
+/// so implementing this trait is enough to have a [walkthrough_with()] of another algorithm, like SHA-512 or SHA-1.
+/// [Sha256] is the implementation used by [walkthrough()].
+pub trait Algorithm: Clone + PartialEq + fmt::Debug{
+    /// Word the algorithm works on.
+    type Word: Word;
+    /// Type of the final hash.
+    type Output: Clone + PartialEq + fmt::Debug + fmt::Display;
+
+    /// Size of the message blocks in bits.
+    const BLOCK_BITS: usize;
+    /// Bits used for the size of the message, at the end of the padding.
+    const LENGTH_BITS: usize;
+    /// Number of compression rounds, and of words of the message schedule.
+    const ROUNDS: usize;
+
+    /// Primes the constants are derived from, empty if they are not derived from primes.
+    fn primes() -> Vec<u32>{
+        Vec::new()
+    }
+
+    /// Initial hash values.
+    fn initial() -> Vec<Self::Word>;
+
+    /// Round constants, one for every round.
+    fn constants() -> Vec<Self::Word>;
+
+    /// Calculates the next word of the message schedule from the words before it.
+    ///
+    /// Returns the word, and the two terms it is made of that are shown by renderers,
+    /// sigma0 and sigma1 for the SHA-2 family.
+    fn schedule_word(schedule: &[Self::Word]) -> (Self::Word, Self::Word, Self::Word);
+
+    /// Does a compression round on the working variables, returning its two temporary words, T1 and T2 for the SHA-2 family.
+    fn round(state: &mut [Self::Word], w: Self::Word, k: Self::Word) -> (Self::Word, Self::Word);
+
+    /// Final hash from the hash values.
+    fn output(hash: &[Self::Word]) -> Self::Output;
+}
+
+/// A step of the hashing process.
+///
+/// Hash values and working variables are always in order, from a to h.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step<A: Algorithm = Sha256>{
+    /// A new phase begins. [Phase::Schedule], [Phase::Compression] and [Phase::Finalization] begin once for every block.
+    Phase(Phase),
+    /// The message converted to bits.
+    Bits(String),
+    /// The bits after padding: the message bits, a 1, zeros, and the size of the message.
+    Padded(String),
+    /// The padded message divided in blocks.
+    Blocks(Vec<String>),
+    /// The primes the constants are derived from, the initial hash values and the round constants K.
+    /// For sha256 the initial values are the fractional part of the square roots of the first 8 primes,
+    /// and K the fractional part of the cube roots of the first 64 primes.
+    Constants{primes: Vec<u32>, initial: Vec<A::Word>, k: Vec<A::Word>},
+    /// A message block begins to be processed, with the hash values before it.
+    Block{index: usize, block: String, hash: Vec<A::Word>},
+    /// The first words of the message schedule, taken from the block.
+    BlockWords(Vec<A::Word>),
+    /// A word of the message schedule is calculated, for sha256 w = sigma1(w[i-2]) + w[i-7] + sigma0(w[i-15]) + w[i-16].
+    ScheduleWord{index: usize, w: A::Word, sigma0: A::Word, sigma1: A::Word},
+    /// A compression round, with the working variables after it.
+    Round{index: usize, w: A::Word, k: A::Word, t1: A::Word, t2: A::Word, state: Vec<A::Word>},
+    /// The compressed block is added to the hash values.
+    BlockHash{index: usize, compressed: Vec<A::Word>, hash: Vec<A::Word>},
+    /// The final hash.
+    Hash(A::Output),
+}
+
+/// Shows the [steps][Step] of a [walkthrough()].
+///
+/// It is implemented for closures, so a renderer can be as simple as `|step: &Step| println!("{:?}", step)`.
+pub trait Renderer<A: Algorithm = Sha256>{
+    fn render(&mut self, step: &Step<A>);
+}
+
+impl<A: Algorithm, F: FnMut(&Step<A>)> Renderer<A> for F{
+    fn render(&mut self, step: &Step<A>){
+        self(step)
+    }
+}
+
+/// Pads the bits with a 1, zeros, and the size of the message, to a multiple of the block size.
+fn pad<A: Algorithm>(bits: &mut String){
+    let size = format!("{:0width$b}", bits.len(), width = A::LENGTH_BITS);
+    *bits += "1";
+    while !(bits.len() + A::LENGTH_BITS).is_multiple_of(A::BLOCK_BITS){
+        *bits += "0";
+    }
+    *bits += &size;
+}
+
+/// Hashes the message with the sha256 algorithm, giving every step of the process to the renderer.
+///
+/// # Examples
+/// ```
+/// use mysha::animation::{walkthrough, Phase, Step};
+/// use mysha::sha256::{HashError, InputType};
+/// # fn main() -> Result<(), HashError>{
+/// let mut phases = Vec::new();
+/// walkthrough("abc", InputType::Text, &mut |step: &Step| {
+///     if let Step::Phase(phase) = step{
+///         phases.push(*phase);
+///     }
+/// })?;
+///
+/// assert_eq!(phases, Phase::ALL);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns a [HashError] if the message is invalid for the input type, before giving any step to the renderer.
+pub fn walkthrough(message: &str, input_type: InputType, renderer: &mut impl Renderer) -> Result<Hash256, HashError>{
+    walkthrough_with::<Sha256>(message, input_type, renderer)
+}
+
+/// Hashes the message with the algorithm `A`, giving every step of the process to the renderer.
+///
+/// # Examples
+/// ```
+/// use mysha::animation::{walkthrough_with, Algorithm, Sha256, Step};
+/// use mysha::sha256::{HashError, InputType};
+/// # fn main() -> Result<(), HashError>{
+/// let mut blocks = 0;
+/// let hash = walkthrough_with::<Sha256>("abc", InputType::Text, &mut |step: &Step<Sha256>| {
+///     if let Step::Blocks(b) = step{
+///         blocks = b.len();
+///         assert!(b.iter().all(|block| block.len() == Sha256::BLOCK_BITS));
+///     }
+/// })?;
+///
+/// assert_eq!(blocks, 1);
+/// assert_eq!(hash.get_hex(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns a [HashError] if the message is invalid for the input type, before giving any step to the renderer.
+pub fn walkthrough_with<A: Algorithm>(message: &str, input_type: InputType, renderer: &mut impl Renderer<A>) -> Result<A::Output, HashError>{
+    let bits = get_bits(message, input_type, &ParseOptions::default())?;
+
+    renderer.render(&Step::Phase(Phase::Padding));
+    renderer.render(&Step::Bits(bits.clone()));
+    let mut padded = bits;
+    pad::<A>(&mut padded);
+    renderer.render(&Step::Padded(padded.clone()));
+    let message_blocks: Vec<String> = (0..padded.len()).step_by(A::BLOCK_BITS).map(|i| String::from(&padded[i..i + A::BLOCK_BITS])).collect();
+    renderer.render(&Step::Blocks(message_blocks.clone()));
+
+    let mut hash = A::initial();
+    let k = A::constants();
+    renderer.render(&Step::Phase(Phase::Constants));
+    renderer.render(&Step::Constants{primes: A::primes(), initial: hash.clone(), k: k.clone()});
+
+    for (index, block) in message_blocks.into_iter().enumerate(){
+        let mut message_schedule: Vec<A::Word> = (0..block.len()).step_by(A::Word::BITS).map(|i| A::Word::from_bits(&block[i..i + A::Word::BITS])).collect();
+        renderer.render(&Step::Block{index, block, hash: hash.clone()});
+
+        renderer.render(&Step::Phase(Phase::Schedule));
+        renderer.render(&Step::BlockWords(message_schedule.clone()));
+        for i in message_schedule.len()..A::ROUNDS{
+            let (w, sigma0, sigma1) = A::schedule_word(&message_schedule);
+            message_schedule.push(w);
+            renderer.render(&Step::ScheduleWord{index: i, w, sigma0, sigma1});
+        }
+
+        renderer.render(&Step::Phase(Phase::Compression));
+        let mut state = hash.clone();
+        for (i, w) in message_schedule.iter().enumerate(){
+            let (t1, t2) = A::round(&mut state, *w, k[i]);
+            renderer.render(&Step::Round{index: i, w: *w, k: k[i], t1, t2, state: state.clone()});
+        }
+
+        renderer.render(&Step::Phase(Phase::Finalization));
+        for (h, s) in hash.iter_mut().zip(&state){
+            *h = h.add_mod(*s);
+        }
+        renderer.render(&Step::BlockHash{index, compressed: state, hash: hash.clone()});
+    }
+
+    let output = A::output(&hash);
+    renderer.render(&Step::Hash(output.clone()));
+    Ok(output)
+}
+
+/// Collects every step of the [walkthrough()] of the message.
+///
+/// # Examples
+/// ```
+/// use mysha::animation::{steps, Step};
+/// use mysha::sha256::{HashError, InputType};
+/// # fn main() -> Result<(), HashError>{
+/// let steps = steps("abc", InputType::Text)?;
+///
+/// assert_eq!(steps[1], Step::Bits(String::from("011000010110001001100011")));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns a [HashError] if the message is invalid for the input type.
+pub fn steps(message: &str, input_type: InputType) -> Result<Vec<Step>, HashError>{
+    steps_with::<Sha256>(message, input_type)
+}
+
+/// Collects every step of the [walkthrough_with()] of the message, with the algorithm `A`.
+///
+/// # Errors
+/// Returns a [HashError] if the message is invalid for the input type.
+pub fn steps_with<A: Algorithm>(message: &str, input_type: InputType) -> Result<Vec<Step<A>>, HashError>{
+    let mut steps = Vec::new();
+    walkthrough_with::<A>(message, input_type, &mut |step: &Step<A>| steps.push(step.clone()))?;
+    Ok(steps)
+}
@@ -0,0 +1,48 @@
+use super::Algorithm;
+use crate::sha256::Hash256;
+use crate::sha256::helper_functions::{constants, operations};
+
+/// The sha256 [Algorithm], with blocks of 512 bits, words of 32 bits and 64 rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sha256;
+
+impl Algorithm for Sha256{
+    type Word = u32;
+    type Output = Hash256;
+
+    const BLOCK_BITS: usize = 512;
+    const LENGTH_BITS: usize = 64;
+    const ROUNDS: usize = 64;
+
+    fn primes() -> Vec<u32>{
+        constants::get_primes(64).iter().map(|prime| *prime as u32).collect()
+    }
+
+    fn initial() -> Vec<u32>{
+        constants::initialize_a()
+    }
+
+    fn constants() -> Vec<u32>{
+        constants::initialize_k()
+    }
+
+    fn schedule_word(schedule: &[u32]) -> (u32, u32, u32){
+        let i = schedule.len();
+        let sigma0 = operations::l_sigma0(schedule[i - 15]);
+        let sigma1 = operations::l_sigma1(schedule[i - 2]);
+        (operations::addn(vec![sigma1, schedule[i - 7], sigma0, schedule[i - 16]]), sigma0, sigma1)
+    }
+
+    fn round(state: &mut [u32], w: u32, k: u32) -> (u32, u32){
+        let t1 = operations::addn(vec![operations::u_sigma1(state[4]), operations::choice(state[4], state[5], state[6]), state[7], k, w]);
+        let t2 = operations::add(operations::u_sigma0(state[0]), operations::majority(state[0], state[1], state[2]));
+        state.rotate_right(1);
+        state[4] = operations::add(state[4], t1);
+        state[0] = operations::add(t1, t2);
+        (t1, t2)
+    }
+
+    fn output(hash: &[u32]) -> Hash256{
+        Hash256(hash.iter().map(|h| format!("{:08x}", h)).collect())
+    }
+}
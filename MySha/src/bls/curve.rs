@@ -0,0 +1,328 @@
+//! The groups G1 and G2 of BLS12-381.
+//!
+//! G1 are the points of `y² = x³ + 4` over [Fp], and G2 the points of the twist `y² = x³ + 4 (1 + u)` over [Fp2].
+//! Both groups have the same prime order r, and the arithmetic is the same affine one as in the [ecc][crate::ecc] module,
+//! written once for both fields.
+
+use std::ops::{Add, Neg};
+use std::sync::OnceLock;
+
+use num_bigint::BigUint;
+
+use super::field::{modulus, Field, Fp, Fp2};
+use super::BlsError;
+
+const ORDER: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+/// The prime order r of G1, G2 and the pairing results, secret keys are numbers below it.
+pub fn order() -> &'static BigUint{
+    static R: OnceLock<BigUint> = OnceLock::new();
+    R.get_or_init(|| BigUint::parse_bytes(ORDER.as_bytes(), 16).unwrap())
+}
+
+/// Field of the coordinates of a curve, with the constant of its equation and what compression needs.
+pub(crate) trait CurveField: Field{
+    /// Size of a coordinate in bytes.
+    const SIZE: usize;
+
+    /// The b of `y² = x³ + b`.
+    fn b() -> Self;
+    fn sqrt(&self) -> Option<Self>;
+    fn is_largest(&self) -> bool;
+    fn to_bytes(&self) -> Vec<u8>;
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Big endian bytes of the number, padded to 48 bytes.
+fn fp_to_bytes(n: &Fp) -> Vec<u8>{
+    let bytes = n.value().to_bytes_be();
+    let mut padded = vec![0; 48 - bytes.len()];
+    padded.extend(bytes);
+    padded
+}
+
+/// The number of the big endian bytes, None if it isn't below p.
+fn fp_from_bytes(bytes: &[u8]) -> Option<Fp>{
+    let n = BigUint::from_bytes_be(bytes);
+    if n < *modulus() {Some(Fp::new(n))} else {None}
+}
+
+impl CurveField for Fp{
+    const SIZE: usize = 48;
+
+    fn b() -> Fp{
+        Fp::from_u64(4)
+    }
+
+    fn sqrt(&self) -> Option<Fp>{
+        Fp::sqrt(self)
+    }
+
+    fn is_largest(&self) -> bool{
+        Fp::is_largest(self)
+    }
+
+    fn to_bytes(&self) -> Vec<u8>{
+        fp_to_bytes(self)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Fp>{
+        fp_from_bytes(bytes)
+    }
+}
+
+impl CurveField for Fp2{
+    const SIZE: usize = 96;
+
+    fn b() -> Fp2{
+        Fp2::from_u64(4).mul_by_nonresidue()
+    }
+
+    fn sqrt(&self) -> Option<Fp2>{
+        Fp2::sqrt(self)
+    }
+
+    fn is_largest(&self) -> bool{
+        Fp2::is_largest(self)
+    }
+
+    // the u part goes first
+    fn to_bytes(&self) -> Vec<u8>{
+        let mut bytes = fp_to_bytes(&self.c1);
+        bytes.extend(fp_to_bytes(&self.c0));
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Fp2>{
+        Some(Fp2::new(fp_from_bytes(&bytes[48..])?, fp_from_bytes(&bytes[..48])?))
+    }
+}
+
+/// Point of a curve `y² = x³ + b`, in affine coordinates.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Point<F>{
+    Infinity,
+    Affine{x: F, y: F},
+}
+
+impl<F: CurveField> Point<F>{
+    pub(crate) fn neg(&self) -> Point<F>{
+        match self{
+            Point::Infinity => Point::Infinity,
+            Point::Affine{x, y} => Point::Affine{x: x.clone(), y: y.neg()},
+        }
+    }
+
+    pub(crate) fn add(&self, other: &Point<F>) -> Point<F>{
+        let (x1, y1, x2, y2) = match (self, other){
+            (Point::Infinity, _) => return other.clone(),
+            (_, Point::Infinity) => return self.clone(),
+            (Point::Affine{x: x1, y: y1}, Point::Affine{x: x2, y: y2}) => (x1, y1, x2, y2),
+        };
+        let slope = if x1 == x2{
+            if y1.add(y2).is_zero(){
+                return Point::Infinity;
+            }
+            // tangent, 3x² / 2y
+            x1.square().mul(&F::from_u64(3)).mul(&y1.double().inverse().unwrap())
+        }else{
+            y2.sub(y1).mul(&x2.sub(x1).inverse().unwrap())
+        };
+        let x = slope.square().sub(x1).sub(x2);
+        let y = slope.mul(&x1.sub(&x)).sub(y1);
+        Point::Affine{x, y}
+    }
+
+    /// Double and add, from the most significant bit of the scalar.
+    pub(crate) fn multiply(&self, scalar: &BigUint) -> Point<F>{
+        let mut result = Point::Infinity;
+        for i in (0..scalar.bits()).rev(){
+            result = result.add(&result);
+            if scalar.bit(i){
+                result = result.add(self);
+            }
+        }
+        result
+    }
+
+    /// The x coordinate with three flags in its first bits: compressed, infinity, and whether y is the largest of its two values.
+    pub(crate) fn compress(&self) -> Vec<u8>{
+        match self{
+            Point::Infinity => {
+                let mut bytes = vec![0; F::SIZE];
+                bytes[0] = 0b1100_0000;
+                bytes
+            },
+            Point::Affine{x, y} => {
+                let mut bytes = x.to_bytes();
+                bytes[0] |= 0b1000_0000;
+                if y.is_largest(){
+                    bytes[0] |= 0b0010_0000;
+                }
+                bytes
+            },
+        }
+    }
+
+    /// Recovers the point from [compress][Point::compress], checking that it is on the curve and in the group of order r.
+    pub(crate) fn decompress(bytes: &[u8]) -> Result<Point<F>, BlsError>{
+        if bytes.len() != F::SIZE || bytes[0] & 0b1000_0000 == 0{
+            return Err(BlsError::InvalidEncoding);
+        }
+        let infinity = bytes[0] & 0b0100_0000 != 0;
+        let largest = bytes[0] & 0b0010_0000 != 0;
+        let mut x_bytes = bytes.to_vec();
+        x_bytes[0] &= 0b0001_1111;
+        if infinity{
+            return if !largest && x_bytes.iter().all(|byte| *byte == 0) {Ok(Point::Infinity)} else {Err(BlsError::InvalidEncoding)};
+        }
+
+        let x = F::from_bytes(&x_bytes).ok_or(BlsError::InvalidEncoding)?;
+        let y = x.square().mul(&x).add(&F::b()).sqrt().ok_or(BlsError::NotOnCurve)?;
+        let y = if y.is_largest() == largest {y} else {y.neg()};
+        let point = Point::Affine{x, y};
+        // the curves have other points, of orders that divide the cofactor, which would allow small subgroup attacks
+        if point.multiply(order()) != Point::Infinity{
+            return Err(BlsError::NotInSubgroup);
+        }
+        Ok(point)
+    }
+}
+
+/// Point of G1, on the curve over Fp, 48 bytes compressed.
+///
+/// # Examples
+/// ```
+/// use mysha::bls::G1;
+/// use num_bigint::BigUint;
+///
+/// let a = G1::generator().multiply(&BigUint::from(2_u8));
+/// let b = &G1::generator() + &G1::generator();
+///
+/// assert_eq!(a, b);
+/// assert_eq!(G1::from_bytes(&a.to_bytes()).unwrap(), a);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct G1(pub(crate) Point<Fp>);
+
+/// Point of G2, on the twist over Fp2, 96 bytes compressed.
+///
+/// # Examples
+/// ```
+/// use mysha::bls::{order, G2};
+///
+/// assert!(G2::generator().multiply(order()).is_identity());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct G2(pub(crate) Point<Fp2>);
+
+impl G1{
+    /// The standard generator of G1.
+    pub fn generator() -> G1{
+        G1(Point::Affine{
+            x: Fp::from_hex("17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"),
+            y: Fp::from_hex("08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"),
+        })
+    }
+
+    /// The point at infinity, the identity of the group.
+    pub fn identity() -> G1{
+        G1(Point::Infinity)
+    }
+
+    pub fn is_identity(&self) -> bool{
+        self.0 == Point::Infinity
+    }
+
+    pub fn multiply(&self, scalar: &BigUint) -> G1{
+        G1(self.0.multiply(scalar))
+    }
+
+    /// Compressed encoding, in the format used by zcash and Ethereum.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        self.0.compress()
+    }
+
+    /// Decodes a compressed point.
+    ///
+    /// # Errors
+    /// Returns [BlsError::InvalidEncoding] if the bytes aren't a compressed point, [BlsError::NotOnCurve] if the x has no point,
+    /// and [BlsError::NotInSubgroup] if the point isn't in G1.
+    pub fn from_bytes(bytes: &[u8]) -> Result<G1, BlsError>{
+        Point::decompress(bytes).map(G1)
+    }
+}
+
+impl G2{
+    /// The standard generator of G2.
+    pub fn generator() -> G2{
+        G2(Point::Affine{
+            x: Fp2::new(
+                Fp::from_hex("024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"),
+                Fp::from_hex("13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"),
+            ),
+            y: Fp2::new(
+                Fp::from_hex("0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"),
+                Fp::from_hex("0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"),
+            ),
+        })
+    }
+
+    /// The point at infinity, the identity of the group.
+    pub fn identity() -> G2{
+        G2(Point::Infinity)
+    }
+
+    pub fn is_identity(&self) -> bool{
+        self.0 == Point::Infinity
+    }
+
+    pub fn multiply(&self, scalar: &BigUint) -> G2{
+        G2(self.0.multiply(scalar))
+    }
+
+    /// Compressed encoding, in the format used by zcash and Ethereum.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        self.0.compress()
+    }
+
+    /// Decodes a compressed point.
+    ///
+    /// # Errors
+    /// Same as [G1::from_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<G2, BlsError>{
+        Point::decompress(bytes).map(G2)
+    }
+}
+
+impl Add for &G1{
+    type Output = G1;
+
+    fn add(self, other: &G1) -> G1{
+        G1(self.0.add(&other.0))
+    }
+}
+
+impl Add for &G2{
+    type Output = G2;
+
+    fn add(self, other: &G2) -> G2{
+        G2(self.0.add(&other.0))
+    }
+}
+
+impl Neg for &G1{
+    type Output = G1;
+
+    fn neg(self) -> G1{
+        G1(self.0.neg())
+    }
+}
+
+impl Neg for &G2{
+    type Output = G2;
+
+    fn neg(self) -> G2{
+        G2(self.0.neg())
+    }
+}
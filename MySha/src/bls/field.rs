@@ -0,0 +1,340 @@
+//! Fields of BLS12-381: the prime field the curve is defined over, and its extensions of degree 2 and 12.
+//!
+//! - [Fp] are the numbers modulo the 381 bit prime p.
+//! - [Fp2] are the numbers `c0 + c1 * u`, with `u² = -1`, like the complex numbers, the coordinates of G2.
+//! - [Fp12] are the polynomials of degree 5 in `w` with [Fp2] coefficients, with `w⁶ = 1 + u`, where the pairing lands.
+//!
+//! Optimized implementations build Fp12 as a tower of degree 2 over degree 3 over Fp2, and keep the numbers in Montgomery form.
+//! Here every element is reduced with a plain remainder and Fp12 is multiplied like polynomials, which is slower but easy to follow.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+
+const MODULUS: &str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+
+/// The prime p of the base field.
+pub(crate) fn modulus() -> &'static BigUint{
+    static P: OnceLock<BigUint> = OnceLock::new();
+    P.get_or_init(|| BigUint::parse_bytes(MODULUS.as_bytes(), 16).unwrap())
+}
+
+/// Operations shared by the fields, so the curve and the exponentiation can be written once for all of them.
+pub(crate) trait Field: Clone + PartialEq + fmt::Debug{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_u64(n: u64) -> Self;
+    fn is_zero(&self) -> bool;
+    fn add(&self, other: &Self) -> Self;
+    fn sub(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+    fn neg(&self) -> Self;
+    /// Multiplicative inverse, None for zero.
+    fn inverse(&self) -> Option<Self>;
+
+    fn square(&self) -> Self{
+        self.mul(self)
+    }
+
+    fn double(&self) -> Self{
+        self.add(self)
+    }
+
+    /// Square and multiply, from the most significant bit of the exponent.
+    fn pow(&self, exponent: &BigUint) -> Self{
+        let mut result = Self::one();
+        for i in (0..exponent.bits()).rev(){
+            result = result.square();
+            if exponent.bit(i){
+                result = result.mul(self);
+            }
+        }
+        result
+    }
+}
+
+/// Element of the prime field, always reduced modulo p.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Fp(BigUint);
+
+impl Fp{
+    pub(crate) fn new(n: BigUint) -> Fp{
+        Fp(n % modulus())
+    }
+
+    pub(crate) fn from_hex(hex: &str) -> Fp{
+        Fp::new(BigUint::parse_bytes(hex.as_bytes(), 16).unwrap())
+    }
+
+    pub(crate) fn value(&self) -> &BigUint{
+        &self.0
+    }
+
+    /// Square root, since p = 3 mod 4 it is `a^((p + 1) / 4)`, when a has one.
+    pub(crate) fn sqrt(&self) -> Option<Fp>{
+        let root = self.pow(&((modulus() + 1_u32) >> 2));
+        if root.square() == *self {Some(root)} else {None}
+    }
+
+    /// If the number is bigger than its negative, which decides the sign of y in compressed points.
+    pub(crate) fn is_largest(&self) -> bool{
+        self.0 > (modulus() - 1_u32) >> 1
+    }
+}
+
+impl Field for Fp{
+    fn zero() -> Fp{
+        Fp(BigUint::zero())
+    }
+
+    fn one() -> Fp{
+        Fp(BigUint::one())
+    }
+
+    fn from_u64(n: u64) -> Fp{
+        Fp::new(BigUint::from(n))
+    }
+
+    fn is_zero(&self) -> bool{
+        self.0.is_zero()
+    }
+
+    fn add(&self, other: &Fp) -> Fp{
+        Fp::new(&self.0 + &other.0)
+    }
+
+    fn sub(&self, other: &Fp) -> Fp{
+        Fp::new(&self.0 + modulus() - &other.0)
+    }
+
+    fn mul(&self, other: &Fp) -> Fp{
+        Fp::new(&self.0 * &other.0)
+    }
+
+    fn neg(&self) -> Fp{
+        Fp::zero().sub(self)
+    }
+
+    // extended euclidean algorithm, like the one of the ecc module, much faster than a^(p - 2)
+    fn inverse(&self) -> Option<Fp>{
+        if self.is_zero(){
+            return None;
+        }
+        let (mut a, mut m) = (BigInt::from(self.0.clone()), BigInt::from(modulus().clone()));
+        let (mut x, mut x_next) = (BigInt::one(), BigInt::zero());
+        while !m.is_zero(){
+            let q = &a / &m;
+            (a, m) = (m.clone(), a - &q * &m);
+            (x, x_next) = (x_next.clone(), x - &q * &x_next);
+        }
+        let p = BigInt::from(modulus().clone());
+        Some(Fp::new(((x % &p + &p) % &p).to_biguint().unwrap()))
+    }
+}
+
+/// Element `c0 + c1 * u` of the quadratic extension, with `u² = -1`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Fp2{
+    pub(crate) c0: Fp,
+    pub(crate) c1: Fp,
+}
+
+impl Fp2{
+    pub(crate) fn new(c0: Fp, c1: Fp) -> Fp2{
+        Fp2{c0, c1}
+    }
+
+    /// `c0 - c1 * u`, which is also the element raised to p.
+    pub(crate) fn conjugate(&self) -> Fp2{
+        Fp2::new(self.c0.clone(), self.c1.neg())
+    }
+
+    /// Multiplies by `1 + u`, the non residue that defines Fp12 and the twist of G2.
+    pub(crate) fn mul_by_nonresidue(&self) -> Fp2{
+        Fp2::new(self.c0.sub(&self.c1), self.c0.add(&self.c1))
+    }
+
+    pub(crate) fn mul_by_fp(&self, n: &Fp) -> Fp2{
+        Fp2::new(self.c0.mul(n), self.c1.mul(n))
+    }
+
+    /// Square root, with algorithm 9 of [eprint 2012/685](https://eprint.iacr.org/2012/685), when the element has one.
+    pub(crate) fn sqrt(&self) -> Option<Fp2>{
+        if self.is_zero(){
+            return Some(Fp2::zero());
+        }
+        let a1 = self.pow(&((modulus() - 3_u32) >> 2));
+        let alpha = a1.mul(&a1.mul(self));
+        let x0 = a1.mul(self);
+        let minus_one = Fp2::one().neg();
+        let root = if alpha == minus_one{
+            x0.mul(&Fp2::new(Fp::zero(), Fp::one()))
+        }else{
+            Fp2::one().add(&alpha).pow(&((modulus() - 1_u32) >> 1)).mul(&x0)
+        };
+        if root.square() == *self {Some(root)} else {None}
+    }
+
+    /// Compares the `u` parts first, and the other parts if they are zero.
+    pub(crate) fn is_largest(&self) -> bool{
+        if self.c1.is_zero() {self.c0.is_largest()} else {self.c1.is_largest()}
+    }
+}
+
+impl Field for Fp2{
+    fn zero() -> Fp2{
+        Fp2::new(Fp::zero(), Fp::zero())
+    }
+
+    fn one() -> Fp2{
+        Fp2::new(Fp::one(), Fp::zero())
+    }
+
+    fn from_u64(n: u64) -> Fp2{
+        Fp2::new(Fp::from_u64(n), Fp::zero())
+    }
+
+    fn is_zero(&self) -> bool{
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    fn add(&self, other: &Fp2) -> Fp2{
+        Fp2::new(self.c0.add(&other.c0), self.c1.add(&other.c1))
+    }
+
+    fn sub(&self, other: &Fp2) -> Fp2{
+        Fp2::new(self.c0.sub(&other.c0), self.c1.sub(&other.c1))
+    }
+
+    // (a0 + a1 u)(b0 + b1 u) = a0 b0 - a1 b1 + (a0 b1 + a1 b0) u, with three multiplications instead of four
+    fn mul(&self, other: &Fp2) -> Fp2{
+        let aa = self.c0.mul(&other.c0);
+        let bb = self.c1.mul(&other.c1);
+        let cross = self.c0.add(&self.c1).mul(&other.c0.add(&other.c1));
+        Fp2::new(aa.sub(&bb), cross.sub(&aa).sub(&bb))
+    }
+
+    fn neg(&self) -> Fp2{
+        Fp2::new(self.c0.neg(), self.c1.neg())
+    }
+
+    // (a0 + a1 u)(a0 - a1 u) = a0² + a1² is in Fp
+    fn inverse(&self) -> Option<Fp2>{
+        let norm = self.c0.square().add(&self.c1.square()).inverse()?;
+        Some(self.conjugate().mul_by_fp(&norm))
+    }
+}
+
+/// Element `c[0] + c[1] w + ... + c[5] w⁵` of the degree 12 extension, with `w⁶ = 1 + u`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Fp12{
+    pub(crate) c: [Fp2; 6],
+}
+
+impl Fp12{
+    pub(crate) fn new(c: [Fp2; 6]) -> Fp12{
+        Fp12{c}
+    }
+
+    /// Negates the odd powers of w, which is the element raised to p⁶, and its inverse when its norm to Fp6 is one.
+    pub(crate) fn conjugate(&self) -> Fp12{
+        let mut c = self.c.clone();
+        for coefficient in c.iter_mut().skip(1).step_by(2){
+            *coefficient = coefficient.neg();
+        }
+        Fp12::new(c)
+    }
+
+    /// The element raised to p, applied `power` times.
+    ///
+    /// Raising to p is linear, `(c w^i)^p = c^p w^(ip)`, and `w^p = w (1 + u)^((p - 1) / 6)`,
+    /// so it only conjugates every coefficient and multiplies it by a constant.
+    pub(crate) fn frobenius(&self, power: usize) -> Fp12{
+        let constants = frobenius_constants();
+        let mut result = self.clone();
+        for _ in 0..power{
+            result = Fp12::new(std::array::from_fn(|i| result.c[i].conjugate().mul(&constants[i])));
+        }
+        result
+    }
+}
+
+/// `(1 + u)^(i (p - 1) / 6)`, the constants of [Fp12::frobenius].
+fn frobenius_constants() -> &'static [Fp2; 6]{
+    static CONSTANTS: OnceLock<[Fp2; 6]> = OnceLock::new();
+    CONSTANTS.get_or_init(||{
+        let xi = Fp2::one().mul_by_nonresidue();
+        let base = xi.pow(&((modulus() - 1_u32) / 6_u32));
+        let mut constants = std::array::from_fn(|_| Fp2::one());
+        for i in 1..6{
+            constants[i] = constants[i - 1].mul(&base);
+        }
+        constants
+    })
+}
+
+impl Field for Fp12{
+    fn zero() -> Fp12{
+        Fp12::new(std::array::from_fn(|_| Fp2::zero()))
+    }
+
+    fn one() -> Fp12{
+        Fp12::from_u64(1)
+    }
+
+    fn from_u64(n: u64) -> Fp12{
+        let mut result = Fp12::zero();
+        result.c[0] = Fp2::from_u64(n);
+        result
+    }
+
+    fn is_zero(&self) -> bool{
+        self.c.iter().all(Fp2::is_zero)
+    }
+
+    fn add(&self, other: &Fp12) -> Fp12{
+        Fp12::new(std::array::from_fn(|i| self.c[i].add(&other.c[i])))
+    }
+
+    fn sub(&self, other: &Fp12) -> Fp12{
+        Fp12::new(std::array::from_fn(|i| self.c[i].sub(&other.c[i])))
+    }
+
+    // multiplied like polynomials, then w⁶ is replaced by 1 + u in the terms of degree 6 to 10
+    fn mul(&self, other: &Fp12) -> Fp12{
+        let mut terms: [Fp2; 11] = std::array::from_fn(|_| Fp2::zero());
+        for (i, a) in self.c.iter().enumerate(){
+            if a.is_zero(){
+                continue;
+            }
+            for (j, b) in other.c.iter().enumerate(){
+                terms[i + j] = terms[i + j].add(&a.mul(b));
+            }
+        }
+        Fp12::new(std::array::from_fn(|i| match terms.get(i + 6){
+            Some(high) => terms[i].add(&high.mul_by_nonresidue()),
+            None => terms[i].clone(),
+        }))
+    }
+
+    fn neg(&self) -> Fp12{
+        Fp12::new(std::array::from_fn(|i| self.c[i].neg()))
+    }
+
+    // the product of the element raised to p, p², ..., p¹¹ is in Fp, so the rest of the product is the inverse times it
+    fn inverse(&self) -> Option<Fp12>{
+        if self.is_zero(){
+            return None;
+        }
+        let mut others = Fp12::one();
+        let mut conjugate = self.clone();
+        for _ in 1..12{
+            conjugate = conjugate.frobenius(1);
+            others = others.mul(&conjugate);
+        }
+        let norm = self.mul(&others).c[0].c0.inverse()?;
+        Some(Fp12::new(std::array::from_fn(|i| others.c[i].mul_by_fp(&norm))))
+    }
+}
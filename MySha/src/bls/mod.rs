@@ -0,0 +1,281 @@
+//! Module for [BLS signatures] over the pairing friendly curve [BLS12-381].
+//!
+//! A BLS signature is the hash of the message, as a point of G1, multiplied by the secret key, and the public key is the generator of G2
+//! multiplied by the secret key. The [pairing] checks that both were multiplied by the same number, without knowing it:
+//! `e(sk H(m), G) = e(H(m), sk G)`.
+//!
+//! Since the pairing is bilinear, signatures can be added together, and so can public keys:
+//! - signatures of different messages, by different keys, [aggregate][Signature::aggregate] into a single signature of 48 bytes,
+//!   checked with [aggregate_verify], which is what blockchains use to shrink blocks;
+//! - signatures of the same message [aggregate][Signature::aggregate] together with the [public keys][PublicKey::aggregate],
+//!   and the sum is checked like a single signature with [fast_aggregate_verify], which is how multi-signatures work.
+//!
+//! ECDSA and Schnorr signatures can't be combined like this, every signature has to be sent and checked on its own.
+//!
+//! Adding public keys has a catch, the rogue key attack: someone who publishes `pk' = sk G - pk` makes the sum `pk + pk'` a key
+//! they know the secret of, and can sign for both. So keys are only aggregated after their owners
+//! [prove they know the secret key][SecretKey::prove_possession].
+//!
+//! The fields, curves and pairing are written to be read, with every element a plain [BigUint], so they are slow,
+//! a verification takes about a second, and not constant time, so they must not be used to protect anything real.
+//! The hash of messages to G1 is also a simple one, [try and increment][hash_to_g1], instead of the standard hash to curve,
+//! so the signatures aren't compatible with other libraries, even if the keys are.
+//!
+//! # Examples
+//! ```
+//! use mysha::bls::{SecretKey, Signature, PublicKey, aggregate_verify};
+//! use num_bigint::BigUint;
+//!
+//! let alice = SecretKey::new(BigUint::from(1234_u32)).unwrap();
+//! let bob = SecretKey::new(BigUint::from(5678_u32)).unwrap();
+//!
+//! let signatures = [alice.sign(b"alice pays bob"), bob.sign(b"bob pays carol")];
+//! let signature = Signature::aggregate(&signatures).unwrap();
+//!
+//! let signed = [(&alice.public_key(), &b"alice pays bob"[..]), (&bob.public_key(), &b"bob pays carol"[..])];
+//! assert!(aggregate_verify(&signed, &signature));
+//! ```
+//!
+//! [BLS signatures]: https://en.wikipedia.org/wiki/BLS_digital_signature
+//! [BLS12-381]: https://hackmd.io/@benjaminion/bls12-381
+
+use std::collections::HashSet;
+use std::fmt;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+mod curve;
+mod field;
+mod pairing;
+
+pub use curve::{order, G1, G2};
+pub use pairing::{final_exponentiation, miller_loop, multi_pairing, pairing, Gt};
+
+use crate::rand;
+use crate::sha256::Sha256;
+use curve::{CurveField, Point};
+use field::{Field, Fp};
+
+/// Domain separation tag of the hashes of messages.
+const SIGNATURE_TAG: &[u8] = b"MYSHA_BLS_SIG_BLS12381G1_SHA256_TAI_";
+/// Domain separation tag of the hashes of public keys, in proofs of possession, so they can't be confused with signatures.
+const POSSESSION_TAG: &[u8] = b"MYSHA_BLS_POP_BLS12381G1_SHA256_TAI_";
+/// `1 - x`, multiplying by it takes any point of the curve over Fp into G1.
+const COFACTOR: u64 = 0xd201000000010001;
+
+/// The error type implemented for this module, with all possible BLS errors.
+#[derive(Debug, PartialEq)]
+pub enum BlsError{
+    /// Happens when a secret key is zero or not below the [order].
+    InvalidSecretKey,
+    /// Happens when the bytes of a point don't have its length or flags, or the coordinate isn't below p.
+    InvalidEncoding,
+    /// Happens when the coordinate of an encoded point doesn't have a point on the curve.
+    NotOnCurve,
+    /// Happens when an encoded point is on the curve, but not in the group of order r.
+    NotInSubgroup,
+}
+
+impl fmt::Display for BlsError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            BlsError::InvalidSecretKey => write!(f, "Secret key must be from 1 to the order of the curve minus 1."),
+            BlsError::InvalidEncoding => write!(f, "Invalid encoding of a compressed point."),
+            BlsError::NotOnCurve => write!(f, "Encoded point is not on the curve."),
+            BlsError::NotInSubgroup => write!(f, "Encoded point is not in the group of order r."),
+        }
+    }
+}
+
+/// Hashes the message to a point of G1, by try and increment.
+///
+/// The message is hashed with a counter into a number x modulo p, until `x³ + 4` is a square, so there is a point with that x.
+/// The point is then multiplied by the cofactor, to land in G1. About half of the counters work, but how many tries
+/// are needed depends on the message, which the standard hash to curve avoids, and it is why this hash isn't used in practice.
+pub fn hash_to_g1(message: &[u8], tag: &[u8]) -> G1{
+    for counter in 0..=u8::MAX{
+        // two hashes, so x is a 512 bit number, and reducing it modulo p is close to uniform
+        let mut bytes = Vec::new();
+        for half in 0..2{
+            let mut hasher = Sha256::new();
+            hasher.update(tag);
+            hasher.update(&[counter, half]);
+            hasher.update(message);
+            bytes.extend(hasher.finalize_bytes());
+        }
+        let x = Fp::new(BigUint::from_bytes_be(&bytes));
+        if let Some(y) = x.square().mul(&x).add(&Fp::b()).sqrt(){
+            let y = if y.is_largest() {y.neg()} else {y};
+            return G1(Point::Affine{x, y}.multiply(&BigUint::from(COFACTOR)));
+        }
+    }
+    unreachable!("every counter failing has a probability of 2^-256")
+}
+
+/// Secret key, a number from 1 to the [order] minus 1.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(BigUint);
+
+impl fmt::Debug for SecretKey{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "SecretKey(..)")
+    }
+}
+
+/// Public key, the generator of G2 multiplied by the secret key, 96 bytes compressed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PublicKey(G2);
+
+/// Signature, the hash of the message multiplied by the secret key, 48 bytes compressed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Signature(G1);
+
+impl SecretKey{
+    /// Creates the key from the number.
+    ///
+    /// # Errors
+    /// Returns [BlsError::InvalidSecretKey] if the number is zero or not below the [order].
+    pub fn new(key: BigUint) -> Result<SecretKey, BlsError>{
+        if key.is_zero() || key >= *order(){
+            return Err(BlsError::InvalidSecretKey);
+        }
+        Ok(SecretKey(key))
+    }
+
+    /// Random key, from the random generator of the operating system.
+    pub fn random() -> SecretKey{
+        let mut bytes = [0; 64];
+        rand::fill_random(&mut bytes);
+        SecretKey(BigUint::from_bytes_be(&bytes) % (order() - 1_u32) + 1_u32)
+    }
+
+    /// The key as a 32 bytes big endian number.
+    pub fn to_bytes(&self) -> [u8; 32]{
+        let bytes = self.0.to_bytes_be();
+        let mut padded = [0; 32];
+        padded[32 - bytes.len()..].copy_from_slice(&bytes);
+        padded
+    }
+
+    /// Reads a key from a big endian number.
+    ///
+    /// # Errors
+    /// Same as [new][SecretKey::new].
+    pub fn from_bytes(bytes: &[u8]) -> Result<SecretKey, BlsError>{
+        SecretKey::new(BigUint::from_bytes_be(bytes))
+    }
+
+    pub fn public_key(&self) -> PublicKey{
+        PublicKey(G2::generator().multiply(&self.0))
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature{
+        Signature(hash_to_g1(message, SIGNATURE_TAG).multiply(&self.0))
+    }
+
+    /// Signs the public key itself, which proves the key was generated and not computed from the keys of others.
+    pub fn prove_possession(&self) -> Signature{
+        Signature(hash_to_g1(&self.public_key().to_bytes(), POSSESSION_TAG).multiply(&self.0))
+    }
+}
+
+impl PublicKey{
+    /// Sum of the public keys, which verifies the sum of their signatures of the same message.
+    ///
+    /// Returns None if there are no keys.
+    pub fn aggregate(keys: &[PublicKey]) -> Option<PublicKey>{
+        let (first, rest) = keys.split_first()?;
+        Some(PublicKey(rest.iter().fold(first.0.clone(), |sum, key| &sum + &key.0)))
+    }
+
+    /// The point of G2.
+    pub fn point(&self) -> &G2{
+        &self.0
+    }
+
+    /// Checks `e(signature, G) = e(H(message), pk)`, as `e(-signature, G) e(H(message), pk) = 1`, with one final exponentiation.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool{
+        self.verify_tagged(message, SIGNATURE_TAG, signature)
+    }
+
+    /// Verifies a proof of possession made with [SecretKey::prove_possession].
+    pub fn verify_possession(&self, proof: &Signature) -> bool{
+        self.verify_tagged(&self.to_bytes(), POSSESSION_TAG, proof)
+    }
+
+    fn verify_tagged(&self, message: &[u8], tag: &[u8], signature: &Signature) -> bool{
+        if self.0.is_identity(){
+            return false;
+        }
+        let hash = hash_to_g1(message, tag);
+        multi_pairing(&[(&-&signature.0, &G2::generator()), (&hash, &self.0)]).is_one()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8>{
+        self.0.to_bytes()
+    }
+
+    /// Decodes a compressed public key.
+    ///
+    /// # Errors
+    /// Same as [G2::from_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, BlsError>{
+        G2::from_bytes(bytes).map(PublicKey)
+    }
+}
+
+impl Signature{
+    /// Sum of the signatures, checked with [aggregate_verify] or [fast_aggregate_verify].
+    ///
+    /// Returns None if there are no signatures.
+    pub fn aggregate(signatures: &[Signature]) -> Option<Signature>{
+        let (first, rest) = signatures.split_first()?;
+        Some(Signature(rest.iter().fold(first.0.clone(), |sum, signature| &sum + &signature.0)))
+    }
+
+    /// The point of G1.
+    pub fn point(&self) -> &G1{
+        &self.0
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8>{
+        self.0.to_bytes()
+    }
+
+    /// Decodes a compressed signature.
+    ///
+    /// # Errors
+    /// Same as [G1::from_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, BlsError>{
+        G1::from_bytes(bytes).map(Signature)
+    }
+}
+
+/// Verifies an aggregate signature of different messages, each signed by its key.
+///
+/// Checks `e(signature, G) = e(H(m1), pk1) e(H(m2), pk2) ...`. The messages must all be different, otherwise
+/// the rogue key attack works on the keys that signed the same message, so false is returned if any repeats.
+pub fn aggregate_verify(signed: &[(&PublicKey, &[u8])], signature: &Signature) -> bool{
+    let mut messages = HashSet::new();
+    if signed.is_empty() || !signed.iter().all(|(key, message)| messages.insert(*message) && !key.0.is_identity()){
+        return false;
+    }
+    let hashes: Vec<G1> = signed.iter().map(|(_, message)| hash_to_g1(message, SIGNATURE_TAG)).collect();
+    let negative = -&signature.0;
+    let generator = G2::generator();
+    let mut pairs = vec![(&negative, &generator)];
+    pairs.extend(hashes.iter().zip(signed).map(|(hash, (key, _))| (hash, &key.0)));
+    multi_pairing(&pairs).is_one()
+}
+
+/// Verifies an aggregate signature of the same message by every key, as a signature by the sum of the keys.
+///
+/// Every key must have been checked with [PublicKey::verify_possession] before, because of the rogue key attack.
+pub fn fast_aggregate_verify(keys: &[PublicKey], message: &[u8], signature: &Signature) -> bool{
+    match PublicKey::aggregate(keys){
+        Some(key) => key.verify(message, signature),
+        None => false,
+    }
+}
+
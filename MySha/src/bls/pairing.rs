@@ -0,0 +1,136 @@
+//! The optimal ate pairing of BLS12-381.
+//!
+//! The pairing is a function `e(P, Q)` from a point of G1 and a point of G2 to an element of [Gt], the numbers of Fp12 whose
+//! r-th power is one, that is bilinear: `e(aP, bQ) = e(P, Q)^(ab)`. It lets anyone check a relation between secret
+//! multiples of points, like `e(sk H, G) = e(H, sk G)`, without knowing the secret, which is what BLS signatures verify.
+//!
+//! It is computed in two parts:
+//! - the [Miller loop][miller_loop], which multiplies the lines of the double and add steps of `x Q`,
+//!   evaluated at P, where x is the parameter of the curve, -0xd201000000010000;
+//! - the [final exponentiation][final_exponentiation], which raises the result to `(p¹² - 1) / r`,
+//!   to remove the factors that depend on how the lines were chosen, so the result is the same for equal inputs.
+
+use std::ops::Mul;
+use std::sync::OnceLock;
+
+use num_bigint::BigUint;
+
+use super::curve::{order, Point, G1, G2};
+use super::field::{modulus, Field, Fp, Fp2, Fp12};
+
+/// Absolute value of the parameter x of BLS12-381, which is negative.
+const X: u64 = 0xd201000000010000;
+
+/// Element of the group of order r where the pairing lands.
+///
+/// # Examples
+/// ```
+/// use mysha::bls::{pairing, G1, G2};
+/// use num_bigint::BigUint;
+///
+/// let (a, b) = (BigUint::from(6_u8), BigUint::from(7_u8));
+///
+/// let left = pairing(&G1::generator().multiply(&a), &G2::generator().multiply(&b));
+/// let right = pairing(&G1::generator(), &G2::generator()).pow(&(a * b));
+/// assert_eq!(left, right);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Gt(Fp12);
+
+impl Gt{
+    /// The identity, the pairing of anything with the point at infinity.
+    pub fn one() -> Gt{
+        Gt(Fp12::one())
+    }
+
+    pub fn is_one(&self) -> bool{
+        *self == Gt::one()
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> Gt{
+        Gt(self.0.pow(exponent))
+    }
+}
+
+impl Mul for &Gt{
+    type Output = Gt;
+
+    fn mul(self, other: &Gt) -> Gt{
+        Gt(self.0.mul(&other.0))
+    }
+}
+
+/// Line through a point T of the twist with the slope, evaluated at P, multiplied by w³.
+///
+/// The twist is mapped to the curve over Fp12 by `(x, y) -> (x / w², y / w³)`, so on the curve the slope is the one on the twist over w,
+/// and the line `yP - yT - slope (xP - xT)` becomes `(slope xT - yT + yP w³ - slope xP w²) / w³`.
+/// The w³ is in a smaller field, so the final exponentiation removes it, and it isn't computed.
+fn line(t: (&Fp2, &Fp2), slope: &Fp2, p: (&Fp, &Fp)) -> Fp12{
+    let mut line = Fp12::zero();
+    line.c[0] = slope.mul(t.0).sub(t.1);
+    line.c[2] = slope.mul_by_fp(p.0).neg();
+    line.c[3] = Fp2::new(p.1.clone(), Fp::zero());
+    line
+}
+
+/// Miller loop of the optimal ate pairing, before the final exponentiation.
+///
+/// Computes `x Q` by double and add on the twist, and multiplies the lines of every step evaluated at P.
+/// The vertical lines of the usual algorithm are in a smaller field, so they are skipped too.
+pub fn miller_loop(p: &G1, q: &G2) -> Gt{
+    let ((xp, yp), (xq, yq)) = match (&p.0, &q.0){
+        (Point::Affine{x: xp, y: yp}, Point::Affine{x: xq, y: yq}) => ((xp, yp), (xq, yq)),
+        _ => return Gt::one(),
+    };
+    let mut f = Fp12::one();
+    let (mut xt, mut yt) = (xq.clone(), yq.clone());
+    for i in (0..X.ilog2()).rev(){
+        let slope = xt.square().mul(&Fp2::from_u64(3)).mul(&yt.double().inverse().unwrap());
+        f = f.square().mul(&line((&xt, &yt), &slope, (xp, yp)));
+        let x = slope.square().sub(&xt.double());
+        (xt, yt) = (x.clone(), slope.mul(&xt.sub(&x)).sub(&yt));
+
+        if X >> i & 1 == 1{
+            // T is a multiple of Q smaller than r, so it is never Q or -Q
+            let slope = yq.sub(&yt).mul(&xq.sub(&xt).inverse().unwrap());
+            f = f.mul(&line((&xt, &yt), &slope, (xp, yp)));
+            let x = slope.square().sub(&xt).sub(xq);
+            (xt, yt) = (x.clone(), slope.mul(&xt.sub(&x)).sub(&yt));
+        }
+    }
+    // x is negative, and the loop of -x is the inverse of the loop of x, up to a vertical line
+    Gt(f.conjugate())
+}
+
+/// `(p⁴ - p² + 1) / r`, the hard part of the final exponentiation.
+fn hard_exponent() -> &'static BigUint{
+    static EXPONENT: OnceLock<BigUint> = OnceLock::new();
+    EXPONENT.get_or_init(||{
+        let p2 = modulus() * modulus();
+        (&p2 * &p2 - &p2 + 1_u32) / order()
+    })
+}
+
+/// Raises the result of the Miller loop to `(p¹² - 1) / r`.
+///
+/// The exponent is split as `(p⁶ - 1) (p² + 1) (p⁴ - p² + 1) / r`. The first two parts are cheap with the frobenius,
+/// raising to p⁶ is a conjugate, and the last part is done with square and multiply,
+/// where real implementations use a faster chain of powers of x.
+pub fn final_exponentiation(f: &Gt) -> Gt{
+    let f = f.0.conjugate().mul(&f.0.inverse().unwrap());
+    let f = f.frobenius(2).mul(&f);
+    Gt(f.pow(hard_exponent()))
+}
+
+/// The pairing `e(P, Q)`.
+pub fn pairing(p: &G1, q: &G2) -> Gt{
+    final_exponentiation(&miller_loop(p, q))
+}
+
+/// Product of the pairings of the pairs, with a single final exponentiation.
+///
+/// Checking that a product of pairings is one is how signatures are verified, and it is much faster than computing every pairing.
+pub fn multi_pairing(pairs: &[(&G1, &G2)]) -> Gt{
+    let f = pairs.iter().fold(Gt::one(), |f, (p, q)| &f * &miller_loop(p, q));
+    final_exponentiation(&f)
+}
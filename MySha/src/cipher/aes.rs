@@ -0,0 +1,186 @@
+//! The [AES] block cipher, with 128, 192 and 256 bit keys.
+//!
+//! AES encrypts blocks of exactly 16 bytes, so by itself it can't encrypt messages,
+//! the [modes of operation](super#modes-of-operation) use it to encrypt data of any length.
+//!
+//! # Examples
+//! ```
+//! use mysha::cipher::{aes::Aes, CipherError};
+//! # fn main() -> Result<(), CipherError>{
+//! let key: Vec<u8> = (0..16).collect();
+//! let aes = Aes::new(&key)?;
+//!
+//! let mut block = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+//! aes.encrypt_block(&mut block);
+//! assert_eq!(block, [0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a]);
+//!
+//! aes.decrypt_block(&mut block);
+//! assert_eq!(block, [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [AES]: https://csrc.nist.gov/pubs/fips/197/final
+
+use super::CipherError;
+
+/// Size of the blocks, in bytes.
+pub const BLOCK_SIZE: usize = 16;
+
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+const INV_SBOX: [u8; 256] = invert(&SBOX);
+
+const fn invert(sbox: &[u8; 256]) -> [u8; 256]{
+    let mut inverse = [0; 256];
+    let mut i = 0;
+    while i < 256{
+        inverse[sbox[i] as usize] = i as u8;
+        i += 1;
+    }
+    inverse
+}
+
+/// Multiplication in the field of AES, GF(2^8) reduced by x^8 + x^4 + x^3 + x + 1.
+fn gmul(mut a: u8, mut b: u8) -> u8{
+    let mut product = 0;
+    while b != 0{
+        if b & 1 == 1{
+            product ^= a;
+        }
+        a = (a << 1) ^ if a & 0x80 != 0 {0x1b} else {0};
+        b >>= 1;
+    }
+    product
+}
+
+/// AES with the round keys expanded from the key.
+#[derive(Debug, Clone)]
+pub struct Aes{
+    round_keys: Vec<[u8; BLOCK_SIZE]>,
+}
+
+impl Aes{
+    /// Expands the key to the round keys, the number of rounds depends on the size of the key.
+    ///
+    /// # Errors
+    /// Returns [CipherError::InvalidKeyLength] if the key isn't 16, 24 or 32 bytes long.
+    pub fn new(key: &[u8]) -> Result<Aes, CipherError>{
+        if ![16, 24, 32].contains(&key.len()){
+            return Err(CipherError::InvalidKeyLength);
+        }
+        let nk = key.len() / 4;
+        let rounds = nk + 6;
+        let mut words: Vec<[u8; 4]> = key.chunks(4).map(|word| [word[0], word[1], word[2], word[3]]).collect();
+        let mut rcon = 1_u8;
+        for i in nk..4 * (rounds + 1){
+            let mut word = words[i - 1];
+            if i % nk == 0{
+                word.rotate_left(1);
+                word = word.map(|byte| SBOX[byte as usize]);
+                word[0] ^= rcon;
+                rcon = gmul(rcon, 2);
+            }else if nk > 6 && i % nk == 4{
+                word = word.map(|byte| SBOX[byte as usize]);
+            }
+            let previous = words[i - nk];
+            words.push([0, 1, 2, 3].map(|j| word[j] ^ previous[j]));
+        }
+
+        let round_keys = words.chunks(4).map(|round|{
+            let mut round_key = [0; BLOCK_SIZE];
+            for (i, word) in round.iter().enumerate(){
+                round_key[4 * i..4 * i + 4].copy_from_slice(word);
+            }
+            round_key
+        }).collect();
+        Ok(Aes{round_keys})
+    }
+
+    fn add_round_key(&self, block: &mut [u8; BLOCK_SIZE], round: usize){
+        for (byte, key) in block.iter_mut().zip(self.round_keys[round]){
+            *byte ^= key;
+        }
+    }
+
+    /// Encrypts the block in place.
+    pub fn encrypt_block(&self, block: &mut [u8; BLOCK_SIZE]){
+        let rounds = self.round_keys.len() - 1;
+        self.add_round_key(block, 0);
+        for round in 1..=rounds{
+            *block = block.map(|byte| SBOX[byte as usize]);
+            shift_rows(block);
+            if round != rounds{
+                mix_columns(block);
+            }
+            self.add_round_key(block, round);
+        }
+    }
+
+    /// Decrypts the block in place.
+    pub fn decrypt_block(&self, block: &mut [u8; BLOCK_SIZE]){
+        let rounds = self.round_keys.len() - 1;
+        for round in (1..=rounds).rev(){
+            self.add_round_key(block, round);
+            if round != rounds{
+                inv_mix_columns(block);
+            }
+            inv_shift_rows(block);
+            *block = block.map(|byte| INV_SBOX[byte as usize]);
+        }
+        self.add_round_key(block, 0);
+    }
+}
+
+/// The block is the state by columns, so row `r` of column `c` is `block[r + 4 * c]`.
+fn shift_rows(block: &mut [u8; BLOCK_SIZE]){
+    let state = *block;
+    for r in 1..4{
+        for c in 0..4{
+            block[r + 4 * c] = state[r + 4 * ((c + r) % 4)];
+        }
+    }
+}
+
+fn inv_shift_rows(block: &mut [u8; BLOCK_SIZE]){
+    let state = *block;
+    for r in 1..4{
+        for c in 0..4{
+            block[r + 4 * ((c + r) % 4)] = state[r + 4 * c];
+        }
+    }
+}
+
+fn mix(block: &mut [u8; BLOCK_SIZE], coefficients: [u8; 4]){
+    for column in block.chunks_mut(4){
+        let a = [column[0], column[1], column[2], column[3]];
+        for (r, byte) in column.iter_mut().enumerate(){
+            *byte = (0..4).fold(0, |acc, i| acc ^ gmul(coefficients[(4 + i - r) % 4], a[i]));
+        }
+    }
+}
+
+fn mix_columns(block: &mut [u8; BLOCK_SIZE]){
+    mix(block, [2, 3, 1, 1]);
+}
+
+fn inv_mix_columns(block: &mut [u8; BLOCK_SIZE]){
+    mix(block, [14, 11, 13, 9]);
+}
@@ -0,0 +1,90 @@
+//! [CBC] mode, cipher block chaining.
+//!
+//! Every block of the message is xored with the previous encrypted block, or with the iv for the first block, before being encrypted,
+//! so equal blocks of the message give different encrypted blocks. The message is padded with [PKCS#7] to whole blocks,
+//! so the encrypted data is always longer than the message.
+//!
+//! The iv must be unpredictable, a random iv for every message, see [crate::rand::fill_random()].
+//! CBC doesn't detect changes to the encrypted data, use [GCM](super::gcm) when that is needed.
+//!
+//! [CBC]: https://csrc.nist.gov/pubs/sp/800/38/a/final
+//! [PKCS#7]: https://datatracker.ietf.org/doc/html/rfc5652#section-6.3
+
+use super::aes::{Aes, BLOCK_SIZE};
+use super::CipherError;
+
+/// Size of the iv, in bytes.
+pub const IV_SIZE: usize = BLOCK_SIZE;
+
+fn get_iv(iv: &[u8]) -> Result<[u8; BLOCK_SIZE], CipherError>{
+    iv.try_into().map_err(|_| CipherError::InvalidNonceLength)
+}
+
+/// Encrypts the message with the key and the iv.
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{cbc, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// let key = [7; 16];
+/// let iv = [1; 16];
+///
+/// let encrypted = cbc::encrypt(&key, &iv, b"attack at dawn")?;
+/// assert_eq!(encrypted.len(), 16);
+/// assert_eq!(cbc::decrypt(&key, &iv, &encrypted)?, b"attack at dawn");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key isn't a valid AES key,
+/// and [CipherError::InvalidNonceLength] if the iv isn't 16 bytes long.
+pub fn encrypt(key: &[u8], iv: &[u8], message: &[u8]) -> Result<Vec<u8>, CipherError>{
+    let aes = Aes::new(key)?;
+    let mut previous = get_iv(iv)?;
+    let padding = BLOCK_SIZE - message.len() % BLOCK_SIZE;
+    let mut message = message.to_vec();
+    message.extend(vec![padding as u8; padding]);
+
+    let mut encrypted = Vec::with_capacity(message.len());
+    for chunk in message.chunks(BLOCK_SIZE){
+        for (byte, m) in previous.iter_mut().zip(chunk){
+            *byte ^= m;
+        }
+        aes.encrypt_block(&mut previous);
+        encrypted.extend(previous);
+    }
+    Ok(encrypted)
+}
+
+/// Decrypts the data with the key and the iv, and removes the padding.
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] and [CipherError::InvalidNonceLength] like [encrypt()],
+/// [CipherError::InvalidLength] if the data isn't made of whole blocks,
+/// and [CipherError::InvalidPadding] if the padding isn't valid after decrypting, usually because the key or iv are wrong.
+pub fn decrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>, CipherError>{
+    let aes = Aes::new(key)?;
+    let mut previous = get_iv(iv)?;
+    if data.is_empty() || !data.len().is_multiple_of(BLOCK_SIZE){
+        return Err(CipherError::InvalidLength);
+    }
+
+    let mut message = Vec::with_capacity(data.len());
+    for chunk in data.chunks(BLOCK_SIZE){
+        let mut block: [u8; BLOCK_SIZE] = chunk.try_into().unwrap();
+        aes.decrypt_block(&mut block);
+        for (byte, p) in block.iter_mut().zip(previous){
+            *byte ^= p;
+        }
+        message.extend(block);
+        previous = chunk.try_into().unwrap();
+    }
+
+    let padding = *message.last().unwrap() as usize;
+    if padding == 0 || padding > BLOCK_SIZE || message[message.len() - padding..].iter().any(|byte| *byte as usize != padding){
+        return Err(CipherError::InvalidPadding);
+    }
+    message.truncate(message.len() - padding);
+    Ok(message)
+}
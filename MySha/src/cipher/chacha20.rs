@@ -0,0 +1,117 @@
+//! The [ChaCha20] stream cipher.
+//!
+//! ChaCha20 generates a keystream of 64 byte blocks from the key, the nonce and a block counter,
+//! using only additions, rotations and xors on 32 bit words, so it is fast without special hardware,
+//! and takes the same time for any key. The keystream is xored with the message, so encrypting and decrypting are the same operation.
+//!
+//! The same nonce must never be used twice with the same key. ChaCha20 doesn't detect changes to the encrypted data,
+//! use [ChaCha20-Poly1305](super::chacha20poly1305) when that is needed.
+//!
+//! [ChaCha20]: https://datatracker.ietf.org/doc/html/rfc8439#section-2.4
+
+use super::CipherError;
+
+/// Size of the key, in bytes.
+pub const KEY_SIZE: usize = 32;
+/// Size of the nonce, in bytes.
+pub const NONCE_SIZE: usize = 12;
+/// Size of the blocks of the keystream, in bytes.
+pub const BLOCK_SIZE: usize = 64;
+
+/// "expand 32-byte k" as little endian words.
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize){
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+fn words(bytes: &[u8]) -> impl Iterator<Item = u32> + '_{
+    bytes.chunks(4).map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+}
+
+/// The keystream block of the key and nonce, for the counter.
+///
+/// The key must be [KEY_SIZE] bytes and the nonce [NONCE_SIZE] bytes long.
+pub(crate) fn block(key: &[u8], counter: u32, nonce: &[u8]) -> [u8; BLOCK_SIZE]{
+    let mut state = [0; 16];
+    state[..4].copy_from_slice(&CONSTANTS);
+    for (word, k) in state[4..12].iter_mut().zip(words(key)){
+        *word = k;
+    }
+    state[12] = counter;
+    for (word, n) in state[13..].iter_mut().zip(words(nonce)){
+        *word = n;
+    }
+
+    let mut working = state;
+    for _ in 0..10{
+        // columns
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        // diagonals
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+
+    let mut keystream = [0; BLOCK_SIZE];
+    for (i, (w, s)) in working.iter().zip(state).enumerate(){
+        keystream[4 * i..4 * i + 4].copy_from_slice(&w.wrapping_add(s).to_le_bytes());
+    }
+    keystream
+}
+
+pub(crate) fn check(key: &[u8], nonce: &[u8]) -> Result<(), CipherError>{
+    if key.len() != KEY_SIZE{
+        return Err(CipherError::InvalidKeyLength);
+    }
+    if nonce.len() != NONCE_SIZE{
+        return Err(CipherError::InvalidNonceLength);
+    }
+    Ok(())
+}
+
+/// Xors the data with the keystream of the key and nonce, starting at the block of the counter.
+///
+/// Encrypting and decrypting are the same operation. The counter is usually 0, or 1 when block 0 is used for something else,
+/// like the Poly1305 key of [ChaCha20-Poly1305](super::chacha20poly1305).
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{chacha20, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// let key = [7; 32];
+/// let nonce = [1; 12];
+///
+/// let encrypted = chacha20::apply(&key, &nonce, 0, b"attack at dawn")?;
+/// assert_eq!(chacha20::apply(&key, &nonce, 0, &encrypted)?, b"attack at dawn");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key isn't 32 bytes long,
+/// [CipherError::InvalidNonceLength] if the nonce isn't 12 bytes long,
+/// and [CipherError::InvalidLength] if the data is too long for the counter, over 256 GiB.
+pub fn apply(key: &[u8], nonce: &[u8], counter: u32, data: &[u8]) -> Result<Vec<u8>, CipherError>{
+    check(key, nonce)?;
+    if data.len().div_ceil(BLOCK_SIZE) as u64 > (u32::MAX - counter) as u64 + 1{
+        return Err(CipherError::InvalidLength);
+    }
+    let mut output = Vec::with_capacity(data.len());
+    for (i, chunk) in data.chunks(BLOCK_SIZE).enumerate(){
+        let keystream = block(key, counter.wrapping_add(i as u32), nonce);
+        output.extend(chunk.iter().zip(keystream).map(|(byte, k)| byte ^ k));
+    }
+    Ok(output)
+}
@@ -0,0 +1,80 @@
+//! The [ChaCha20-Poly1305] authenticated encryption.
+//!
+//! The message is encrypted with [ChaCha20](super::chacha20) from block 1, and block 0 of the keystream is the one-time key
+//! of a [Poly1305](super::poly1305) tag over the encrypted data and the associated data, so like [GCM](super::gcm)
+//! any change to them is detected, but without needing AES.
+//!
+//! The nonce is 12 bytes long and must never be used twice with the same key.
+//!
+//! [ChaCha20-Poly1305]: https://datatracker.ietf.org/doc/html/rfc8439#section-2.8
+
+use super::{chacha20, poly1305, CipherError};
+
+/// Size of the key, in bytes.
+pub const KEY_SIZE: usize = chacha20::KEY_SIZE;
+/// Size of the nonce, in bytes.
+pub const NONCE_SIZE: usize = chacha20::NONCE_SIZE;
+/// Size of the tag added after the encrypted data, in bytes.
+pub const TAG_SIZE: usize = poly1305::TAG_SIZE;
+
+/// The data authenticated by the tag, each part padded with zeros to 16 bytes, followed by their lengths.
+fn mac_data(aad: &[u8], encrypted: &[u8]) -> Vec<u8>{
+    let mut data = Vec::with_capacity(aad.len() + encrypted.len() + 48);
+    for part in [aad, encrypted]{
+        data.extend(part);
+        data.extend(vec![0; (16 - part.len() % 16) % 16]);
+    }
+    data.extend((aad.len() as u64).to_le_bytes());
+    data.extend((encrypted.len() as u64).to_le_bytes());
+    data
+}
+
+/// Encrypts the message with the key and the nonce, returning the encrypted data followed by the tag.
+///
+/// The associated data is authenticated by the tag but isn't encrypted, nor part of the output.
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{chacha20poly1305, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// let key = [7; 32];
+/// let nonce = [1; 12];
+///
+/// let encrypted = chacha20poly1305::encrypt(&key, &nonce, b"attack at dawn", b"to: general")?;
+/// assert_eq!(encrypted.len(), 14 + chacha20poly1305::TAG_SIZE);
+///
+/// assert_eq!(chacha20poly1305::decrypt(&key, &nonce, &encrypted, b"to: general")?, b"attack at dawn");
+/// assert_eq!(chacha20poly1305::decrypt(&key, &nonce, &encrypted, b"to: soldier"), Err(CipherError::AuthenticationFailed));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key isn't 32 bytes long,
+/// [CipherError::InvalidNonceLength] if the nonce isn't 12 bytes long,
+/// and [CipherError::InvalidLength] if the message is too long for the counter.
+pub fn encrypt(key: &[u8], nonce: &[u8], message: &[u8], aad: &[u8]) -> Result<Vec<u8>, CipherError>{
+    chacha20::check(key, nonce)?;
+    let one_time_key = chacha20::block(key, 0, nonce);
+    let mut encrypted = chacha20::apply(key, nonce, 1, message)?;
+    let tag = poly1305::mac(&one_time_key[..poly1305::KEY_SIZE], &mac_data(aad, &encrypted))?;
+    encrypted.extend(tag);
+    Ok(encrypted)
+}
+
+/// Checks the tag at the end of the data, and decrypts the data with the key and the nonce.
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] and [CipherError::InvalidNonceLength] like [encrypt()],
+/// [CipherError::InvalidLength] if the data is shorter than the tag,
+/// and [CipherError::AuthenticationFailed] if the tag doesn't match, and nothing is decrypted.
+pub fn decrypt(key: &[u8], nonce: &[u8], data: &[u8], aad: &[u8]) -> Result<Vec<u8>, CipherError>{
+    chacha20::check(key, nonce)?;
+    if data.len() < TAG_SIZE{
+        return Err(CipherError::InvalidLength);
+    }
+    let (encrypted, tag) = data.split_at(data.len() - TAG_SIZE);
+    let one_time_key = chacha20::block(key, 0, nonce);
+    poly1305::verify(&one_time_key[..poly1305::KEY_SIZE], &mac_data(aad, encrypted), tag)?;
+    chacha20::apply(key, nonce, 1, encrypted)
+}
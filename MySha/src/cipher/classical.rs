@@ -0,0 +1,233 @@
+//! Classical ciphers, and the frequency analysis that breaks them.
+//!
+//! The [Caesar], [Vigenère] and [substitution] ciphers replace every letter by another, so the frequencies of the letters
+//! of the language show through the encrypted text. Letters keep their case, and other characters are kept as they are.
+//!
+//! - Caesar shifts every letter by the same amount, so there are only 26 keys, and the one whose decryption
+//!   looks most like English, by [chi_squared()], is the right one.
+//! - Vigenère shifts every letter by the letter of the key at its position, so every column of letters is a Caesar cipher,
+//!   and the length of the key is found with the [index_of_coincidence()] of the columns.
+//! - A substitution has 26! keys, too many to try, but the most frequent letters still give most of the key away.
+//!
+//! # Examples
+//! ```
+//! use mysha::cipher::classical;
+//!
+//! let encrypted = classical::caesar_encrypt("The quick brown fox jumps over the lazy dog", 3);
+//! assert_eq!(encrypted, "Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj");
+//!
+//! let (shift, decrypted) = classical::crack_caesar(&encrypted);
+//! assert_eq!(shift, 3);
+//! assert_eq!(decrypted, "The quick brown fox jumps over the lazy dog");
+//! ```
+//!
+//! [Caesar]: https://en.wikipedia.org/wiki/Caesar_cipher
+//! [Vigenère]: https://en.wikipedia.org/wiki/Vigen%C3%A8re_cipher
+//! [substitution]: https://en.wikipedia.org/wiki/Substitution_cipher
+
+use super::CipherError;
+
+/// Frequencies of the letters in English text, from a to z.
+pub const ENGLISH: [f64; 26] = [
+    0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153, 0.00772, 0.04025, 0.02406,
+    0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056, 0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
+];
+
+/// Replaces every letter by the letter at the same position of the key, the key being 26 lowercase letters.
+fn substitute(text: &str, key: &[u8; 26]) -> String{
+    text.chars().map(|c|{
+        if !c.is_ascii_alphabetic(){
+            return c;
+        }
+        let substituted = key[(c.to_ascii_lowercase() as u8 - b'a') as usize] as char;
+        if c.is_ascii_uppercase() {substituted.to_ascii_uppercase()} else {substituted}
+    }).collect()
+}
+
+fn shift_letter(c: char, shift: u8) -> char{
+    match c{
+        'a'..='z' => ((c as u8 - b'a' + shift) % 26 + b'a') as char,
+        'A'..='Z' => ((c as u8 - b'A' + shift) % 26 + b'A') as char,
+        _ => c,
+    }
+}
+
+/// Shifts every letter forward by the shift, wrapping from z to a.
+pub fn caesar_encrypt(text: &str, shift: u8) -> String{
+    text.chars().map(|c| shift_letter(c, shift % 26)).collect()
+}
+
+/// Shifts every letter back by the shift.
+pub fn caesar_decrypt(text: &str, shift: u8) -> String{
+    caesar_encrypt(text, 26 - shift % 26)
+}
+
+/// The shifts of the letters of the key, a being 0.
+fn get_shifts(key: &str) -> Result<Vec<u8>, CipherError>{
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphabetic()){
+        return Err(CipherError::InvalidKey);
+    }
+    Ok(key.bytes().map(|b| b.to_ascii_lowercase() - b'a').collect())
+}
+
+fn vigenere(text: &str, shifts: &[u8]) -> String{
+    let mut position = 0;
+    text.chars().map(|c|{
+        if !c.is_ascii_alphabetic(){
+            return c;
+        }
+        let shifted = shift_letter(c, shifts[position % shifts.len()]);
+        position += 1;
+        shifted
+    }).collect()
+}
+
+/// Shifts every letter by the letter of the key at its position, counting only letters, the key repeating as needed.
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{classical, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// assert_eq!(classical::vigenere_encrypt("Attack at dawn", "lemon")?, "Lxfopv ef rnhr");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidKey] if the key is empty or has characters that aren't letters.
+pub fn vigenere_encrypt(text: &str, key: &str) -> Result<String, CipherError>{
+    Ok(vigenere(text, &get_shifts(key)?))
+}
+
+/// Shifts every letter back by the letter of the key at its position.
+///
+/// # Errors
+/// Same as [vigenere_encrypt()].
+pub fn vigenere_decrypt(text: &str, key: &str) -> Result<String, CipherError>{
+    let shifts: Vec<u8> = get_shifts(key)?.iter().map(|shift| (26 - shift) % 26).collect();
+    Ok(vigenere(text, &shifts))
+}
+
+/// The key as the letters a to z are replaced by, checking that it is a permutation of the alphabet.
+fn get_alphabet(key: &str) -> Result<[u8; 26], CipherError>{
+    let key = key.to_ascii_lowercase();
+    let alphabet: [u8; 26] = key.as_bytes().try_into().map_err(|_| CipherError::InvalidKey)?;
+    let mut sorted = alphabet;
+    sorted.sort();
+    if sorted != *b"abcdefghijklmnopqrstuvwxyz"{
+        return Err(CipherError::InvalidKey);
+    }
+    Ok(alphabet)
+}
+
+/// Replaces every letter by the letter of the key at its position in the alphabet, so a by the first letter of the key.
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{classical, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// let key = "qwertyuiopasdfghjklzxcvbnm";
+///
+/// assert_eq!(classical::substitution_encrypt("Hello", key)?, "Itssg");
+/// assert_eq!(classical::substitution_decrypt("Itssg", key)?, "Hello");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidKey] if the key doesn't have every letter exactly once.
+pub fn substitution_encrypt(text: &str, key: &str) -> Result<String, CipherError>{
+    Ok(substitute(text, &get_alphabet(key)?))
+}
+
+/// Replaces every letter of the key by the letter of the alphabet at its position.
+///
+/// # Errors
+/// Same as [substitution_encrypt()].
+pub fn substitution_decrypt(text: &str, key: &str) -> Result<String, CipherError>{
+    let alphabet = get_alphabet(key)?;
+    let mut inverse = [0; 26];
+    for (i, letter) in alphabet.iter().enumerate(){
+        inverse[(letter - b'a') as usize] = b'a' + i as u8;
+    }
+    Ok(substitute(text, &inverse))
+}
+
+/// Counts of every letter of the text, from a to z, ignoring case.
+pub fn letter_counts(text: &str) -> [usize; 26]{
+    let mut counts = [0; 26];
+    for c in text.chars().filter(|c| c.is_ascii_alphabetic()){
+        counts[(c.to_ascii_lowercase() as u8 - b'a') as usize] += 1;
+    }
+    counts
+}
+
+/// Frequencies of every letter of the text, from a to z, adding up to 1 if the text has letters.
+pub fn letter_frequencies(text: &str) -> [f64; 26]{
+    let counts = letter_counts(text);
+    let total: usize = counts.iter().sum();
+    counts.map(|count| if total == 0 {0.0} else {count as f64 / total as f64})
+}
+
+/// How far the letters of the text are from English, the lower the more it looks like English.
+///
+/// It is the sum, for every letter, of the squared difference between its count and its expected count in English,
+/// divided by the expected count.
+pub fn chi_squared(text: &str) -> f64{
+    let counts = letter_counts(text);
+    let total: usize = counts.iter().sum();
+    counts.iter().zip(ENGLISH).map(|(count, frequency)|{
+        let expected = frequency * total as f64;
+        (*count as f64 - expected).powi(2) / expected
+    }).sum()
+}
+
+/// Chance that two letters taken from the text are equal, about 0.066 for English, and 0.038 for random letters.
+///
+/// Substitutions don't change it, so it tells if a column of letters was encrypted with a single shift.
+pub fn index_of_coincidence(text: &str) -> f64{
+    let counts = letter_counts(text);
+    let total: usize = counts.iter().sum();
+    if total < 2{
+        return 0.0;
+    }
+    counts.iter().map(|count| count * count.saturating_sub(1)).sum::<usize>() as f64 / (total * (total - 1)) as f64
+}
+
+/// Finds the shift of a Caesar cipher, trying every shift, returning the shift and the decrypted text.
+///
+/// Frequency analysis needs text long enough to have the frequencies of the language, a sentence or more.
+pub fn crack_caesar(text: &str) -> (u8, String){
+    let shift = (0..26).min_by(|a, b| chi_squared(&caesar_decrypt(text, *a)).total_cmp(&chi_squared(&caesar_decrypt(text, *b)))).unwrap();
+    (shift, caesar_decrypt(text, shift))
+}
+
+/// Letters of the text in the column, when the letters are split in the number of columns.
+fn column(letters: &[char], columns: usize, index: usize) -> String{
+    letters.iter().skip(index).step_by(columns).collect()
+}
+
+/// Average [index_of_coincidence()] of the columns of the text, for every key length from 1 to the maximum.
+///
+/// The length of the key is the one where the columns look like English, while the others look random.
+pub fn key_length_scores(text: &str, max_length: usize) -> Vec<f64>{
+    let letters: Vec<char> = text.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+    (1..=max_length).map(|length|{
+        (0..length).map(|i| index_of_coincidence(&column(&letters, length, i))).sum::<f64>() / length as f64
+    }).collect()
+}
+
+/// Finds the key of a Vigenère cipher of up to the maximum length, returning the key and the decrypted text.
+///
+/// Multiples of the key length also look like English, so the shortest length close to the best score is chosen.
+/// Every column is then cracked as a Caesar cipher, so the text needs many times more letters than the key.
+pub fn crack_vigenere(text: &str, max_length: usize) -> (String, String){
+    let scores = key_length_scores(text, max_length.max(1));
+    let best = scores.iter().cloned().fold(0.0, f64::max);
+    let length = scores.iter().position(|score| *score >= best * 0.9).unwrap() + 1;
+
+    let letters: Vec<char> = text.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+    let key: String = (0..length).map(|i| (b'a' + crack_caesar(&column(&letters, length, i)).0) as char).collect();
+    let decrypted = vigenere_decrypt(text, &key).unwrap();
+    (key, decrypted)
+}
@@ -0,0 +1,72 @@
+//! [CTR] mode, counter mode.
+//!
+//! The counter block, starting at the nonce and incremented for every block, is encrypted,
+//! and the result is xored with the message, so AES works as a stream cipher.
+//! The encrypted data has the same length as the message, and encrypting and decrypting are the same operation.
+//!
+//! The same nonce must never be used twice with the same key, since the xor of the two encrypted messages
+//! is the xor of the two messages. CTR doesn't detect changes to the encrypted data, use [GCM](super::gcm) when that is needed.
+//!
+//! [CTR]: https://csrc.nist.gov/pubs/sp/800/38/a/final
+
+use super::aes::{Aes, BLOCK_SIZE};
+use super::CipherError;
+
+/// Size of the nonce, the first counter block, in bytes.
+pub const NONCE_SIZE: usize = BLOCK_SIZE;
+
+/// Xors the data with the encrypted counter blocks, starting at the counter block.
+/// The whole block is incremented as a big endian number.
+pub(crate) fn apply_keystream(aes: &Aes, counter: [u8; BLOCK_SIZE], data: &[u8], increment: fn(&mut [u8; BLOCK_SIZE])) -> Vec<u8>{
+    let mut counter = counter;
+    let mut output = Vec::with_capacity(data.len());
+    for chunk in data.chunks(BLOCK_SIZE){
+        let mut keystream = counter;
+        aes.encrypt_block(&mut keystream);
+        output.extend(chunk.iter().zip(keystream).map(|(byte, k)| byte ^ k));
+        increment(&mut counter);
+    }
+    output
+}
+
+fn increment(counter: &mut [u8; BLOCK_SIZE]){
+    for byte in counter.iter_mut().rev(){
+        *byte = byte.wrapping_add(1);
+        if *byte != 0{
+            break;
+        }
+    }
+}
+
+/// Encrypts the message with the key and the nonce.
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{ctr, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// let key = [7; 32];
+/// let nonce = [1; 16];
+///
+/// let encrypted = ctr::encrypt(&key, &nonce, b"attack at dawn")?;
+/// assert_eq!(encrypted.len(), 14);
+/// assert_eq!(ctr::decrypt(&key, &nonce, &encrypted)?, b"attack at dawn");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key isn't a valid AES key,
+/// and [CipherError::InvalidNonceLength] if the nonce isn't 16 bytes long.
+pub fn encrypt(key: &[u8], nonce: &[u8], message: &[u8]) -> Result<Vec<u8>, CipherError>{
+    let aes = Aes::new(key)?;
+    let counter = nonce.try_into().map_err(|_| CipherError::InvalidNonceLength)?;
+    Ok(apply_keystream(&aes, counter, message, increment))
+}
+
+/// Decrypts the data with the key and the nonce, the same as [encrypt()].
+///
+/// # Errors
+/// Same as [encrypt()].
+pub fn decrypt(key: &[u8], nonce: &[u8], data: &[u8]) -> Result<Vec<u8>, CipherError>{
+    encrypt(key, nonce, data)
+}
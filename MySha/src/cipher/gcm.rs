@@ -0,0 +1,126 @@
+//! [GCM] mode, Galois/counter mode.
+//!
+//! The message is encrypted in counter mode, and a 16 byte tag is computed over the encrypted data and the associated data,
+//! data that isn't encrypted but must not be changed, like headers. Decrypting checks the tag first,
+//! so any change to the encrypted data, the associated data, the key or the nonce is detected.
+//!
+//! The nonce is 12 bytes long and must never be used twice with the same key, since that reveals the xor of the messages,
+//! and lets the tags be forged.
+//!
+//! [GCM]: https://csrc.nist.gov/pubs/sp/800/38/d/final
+
+use super::aes::{Aes, BLOCK_SIZE};
+use super::ctr::apply_keystream;
+use super::CipherError;
+
+/// Size of the nonce, in bytes.
+pub const NONCE_SIZE: usize = 12;
+/// Size of the tag added after the encrypted data, in bytes.
+pub const TAG_SIZE: usize = 16;
+
+/// Multiplication in GF(2^128), with the bit order of GCM.
+fn gf_mul(x: u128, y: u128) -> u128{
+    let mut product = 0;
+    let mut v = y;
+    for i in 0..128{
+        if (x >> (127 - i)) & 1 == 1{
+            product ^= v;
+        }
+        v = if v & 1 == 1 {(v >> 1) ^ (0xe1 << 120)} else {v >> 1};
+    }
+    product
+}
+
+fn ghash(h: u128, aad: &[u8], data: &[u8]) -> u128{
+    let mut x = 0;
+    for input in [aad, data]{
+        for chunk in input.chunks(BLOCK_SIZE){
+            let mut block = [0; BLOCK_SIZE];
+            block[..chunk.len()].copy_from_slice(chunk);
+            x = gf_mul(x ^ u128::from_be_bytes(block), h);
+        }
+    }
+    let lengths = ((aad.len() as u128 * 8) << 64) | (data.len() as u128 * 8);
+    gf_mul(x ^ lengths, h)
+}
+
+/// Only the last 4 bytes of the counter are incremented in GCM.
+fn increment(counter: &mut [u8; BLOCK_SIZE]){
+    let count = u32::from_be_bytes(counter[12..].try_into().unwrap()).wrapping_add(1);
+    counter[12..].copy_from_slice(&count.to_be_bytes());
+}
+
+/// The aes, the first counter block, and the hash key.
+fn setup(key: &[u8], nonce: &[u8]) -> Result<(Aes, [u8; BLOCK_SIZE], u128), CipherError>{
+    let aes = Aes::new(key)?;
+    if nonce.len() != NONCE_SIZE{
+        return Err(CipherError::InvalidNonceLength);
+    }
+    let mut j0 = [0; BLOCK_SIZE];
+    j0[..NONCE_SIZE].copy_from_slice(nonce);
+    j0[BLOCK_SIZE - 1] = 1;
+    let mut h = [0; BLOCK_SIZE];
+    aes.encrypt_block(&mut h);
+    Ok((aes, j0, u128::from_be_bytes(h)))
+}
+
+fn tag(aes: &Aes, j0: [u8; BLOCK_SIZE], h: u128, aad: &[u8], data: &[u8]) -> [u8; TAG_SIZE]{
+    let mut mask = j0;
+    aes.encrypt_block(&mut mask);
+    (ghash(h, aad, data) ^ u128::from_be_bytes(mask)).to_be_bytes()
+}
+
+/// Encrypts the message with the key and the nonce, returning the encrypted data followed by the tag.
+///
+/// The associated data is authenticated by the tag but isn't encrypted, nor part of the output.
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{gcm, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// let key = [7; 16];
+/// let nonce = [1; 12];
+///
+/// let encrypted = gcm::encrypt(&key, &nonce, b"attack at dawn", b"to: general")?;
+/// assert_eq!(encrypted.len(), 14 + gcm::TAG_SIZE);
+///
+/// assert_eq!(gcm::decrypt(&key, &nonce, &encrypted, b"to: general")?, b"attack at dawn");
+/// assert_eq!(gcm::decrypt(&key, &nonce, &encrypted, b"to: soldier"), Err(CipherError::AuthenticationFailed));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key isn't a valid AES key,
+/// and [CipherError::InvalidNonceLength] if the nonce isn't 12 bytes long.
+pub fn encrypt(key: &[u8], nonce: &[u8], message: &[u8], aad: &[u8]) -> Result<Vec<u8>, CipherError>{
+    let (aes, j0, h) = setup(key, nonce)?;
+    let mut counter = j0;
+    increment(&mut counter);
+    let mut encrypted = apply_keystream(&aes, counter, message, increment);
+    let tag = tag(&aes, j0, h, aad, &encrypted);
+    encrypted.extend(tag);
+    Ok(encrypted)
+}
+
+/// Checks the tag at the end of the data, and decrypts the data with the key and the nonce.
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] and [CipherError::InvalidNonceLength] like [encrypt()],
+/// [CipherError::InvalidLength] if the data is shorter than the tag,
+/// and [CipherError::AuthenticationFailed] if the tag doesn't match, and nothing is decrypted.
+pub fn decrypt(key: &[u8], nonce: &[u8], data: &[u8], aad: &[u8]) -> Result<Vec<u8>, CipherError>{
+    let (aes, j0, h) = setup(key, nonce)?;
+    if data.len() < TAG_SIZE{
+        return Err(CipherError::InvalidLength);
+    }
+    let (encrypted, received) = data.split_at(data.len() - TAG_SIZE);
+    let expected = tag(&aes, j0, h, aad, encrypted);
+    // compares every byte, so the time taken doesn't tell how many bytes matched
+    if expected.iter().zip(received).fold(0, |diff, (a, b)| diff | (a ^ b)) != 0{
+        return Err(CipherError::AuthenticationFailed);
+    }
+    let mut counter = j0;
+    increment(&mut counter);
+    Ok(apply_keystream(&aes, counter, encrypted, increment))
+}
@@ -0,0 +1,74 @@
+//! Module for symmetric encryption.
+//!
+//! Symmetric ciphers use the same secret key to encrypt and to decrypt.
+//! The [AES](aes) block cipher only encrypts blocks of 16 bytes, so it is used through a mode of operation.
+//! [ChaCha20-Poly1305](chacha20poly1305) is an alternative to AES in GCM mode, built from the [chacha20] stream cipher
+//! and the [poly1305] authentication code, that is fast and safe on devices without AES instructions.
+//! The [one-time pad](otp) shows why keys must not be reused, and the [classical] ciphers why modern ciphers are needed.
+//!
+//! # Modes of operation
+//! - [cbc], chains the blocks, and pads the message to whole blocks.
+//! - [ctr], uses AES as a stream cipher, the encrypted data has the length of the message.
+//! - [gcm], counter mode with a tag, so changes to the encrypted data are detected. It is the one to use when in doubt.
+//!
+//! Every mode takes the key, the iv or nonce, and the data explicitly. The nonce doesn't need to be secret,
+//! and is usually sent with the encrypted data, but a nonce must never be used twice with the same key.
+//!
+//! # Examples
+//! ```
+//! use mysha::cipher::{gcm, CipherError};
+//! use mysha::rand;
+//! # fn main() -> Result<(), CipherError>{
+//! let mut key = [0; 32];
+//! rand::fill_random(&mut key);
+//! let mut nonce = [0; gcm::NONCE_SIZE];
+//! rand::fill_random(&mut nonce);
+//!
+//! let encrypted = gcm::encrypt(&key, &nonce, b"secret message", &[])?;
+//!
+//! assert_eq!(gcm::decrypt(&key, &nonce, &encrypted, &[])?, b"secret message");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+pub mod aes;
+pub mod cbc;
+pub mod chacha20;
+pub mod classical;
+pub mod chacha20poly1305;
+pub mod ctr;
+pub mod gcm;
+pub mod otp;
+pub mod poly1305;
+
+/// The error type implemented for this module, with all possible encryption and decryption errors.
+#[derive(Debug, PartialEq)]
+pub enum CipherError{
+    /// Happens when the key doesn't have a length supported by the cipher.
+    InvalidKeyLength,
+    /// Happens when the key has characters that aren't valid for the cipher.
+    InvalidKey,
+    /// Happens when the nonce or iv doesn't have the length required by the mode.
+    InvalidNonceLength,
+    /// Happens when the encrypted data doesn't have a valid length for the mode.
+    InvalidLength,
+    /// Happens when the padding is invalid after decrypting, usually because of a wrong key.
+    InvalidPadding,
+    /// Happens when the tag doesn't match the data, because the data, key or nonce are wrong, or the data was changed.
+    AuthenticationFailed,
+}
+
+impl fmt::Display for CipherError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            CipherError::InvalidKeyLength => write!(f, "Invalid key length for the cipher."),
+            CipherError::InvalidKey => write!(f, "Invalid key for the cipher."),
+            CipherError::InvalidNonceLength => write!(f, "Invalid nonce length for the mode."),
+            CipherError::InvalidLength => write!(f, "Invalid length of the encrypted data."),
+            CipherError::InvalidPadding => write!(f, "Invalid padding, the key or iv may be wrong."),
+            CipherError::AuthenticationFailed => write!(f, "Authentication failed, the key or nonce are wrong, or the data was changed."),
+        }
+    }
+}
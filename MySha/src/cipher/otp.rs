@@ -0,0 +1,80 @@
+//! The [one-time pad], xor of the message with a key as long as it.
+//!
+//! When the key is random, as long as the message, and used only once, the encrypted data gives no information about the message,
+//! since for every message of the same length there is a key that encrypts it to the same data, this is called perfect secrecy.
+//! When a key is used twice, the xor of the two encrypted messages is the xor of the two messages, so the key cancels out,
+//! and knowing part of one message reveals the same part of the other.
+//!
+//! # Examples
+//! ```
+//! use mysha::cipher::{otp, CipherError};
+//! # fn main() -> Result<(), CipherError>{
+//! let key = otp::random_key(5);
+//!
+//! let a = otp::encrypt(b"hello", &key)?;
+//! let b = otp::encrypt(b"world", &key)?;
+//!
+//! // the key is reused, so knowing one message reveals the other
+//! let leak = otp::xor(&a, &b);
+//! assert_eq!(otp::xor(&leak, b"hello"), b"world");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [one-time pad]: https://en.wikipedia.org/wiki/One-time_pad
+
+use super::CipherError;
+
+/// Xors the bytes of `a` and `b`, up to the length of the shorter one.
+pub fn xor(a: &[u8], b: &[u8]) -> Vec<u8>{
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+/// Returns a random key of the length, from [crate::rand::fill_random()].
+pub fn random_key(length: usize) -> Vec<u8>{
+    let mut key = vec![0; length];
+    crate::rand::fill_random(&mut key);
+    key
+}
+
+/// Encrypts the message with the key, only the first bytes of the key that are needed are used.
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key is shorter than the message.
+pub fn encrypt(message: &[u8], key: &[u8]) -> Result<Vec<u8>, CipherError>{
+    if key.len() < message.len(){
+        return Err(CipherError::InvalidKeyLength);
+    }
+    Ok(xor(message, key))
+}
+
+/// Decrypts the data with the key, the same as [encrypt()].
+///
+/// # Errors
+/// Same as [encrypt()].
+pub fn decrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, CipherError>{
+    encrypt(data, key)
+}
+
+/// Returns the key that decrypts the data to the message, which always exists, showing that the data alone says nothing about the message.
+///
+/// # Examples
+/// ```
+/// use mysha::cipher::{otp, CipherError};
+/// # fn main() -> Result<(), CipherError>{
+/// let encrypted = otp::encrypt(b"attack", &otp::random_key(6))?;
+///
+/// let key = otp::key_for(&encrypted, b"defend")?;
+/// assert_eq!(otp::decrypt(&encrypted, &key)?, b"defend");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [CipherError::InvalidLength] if the message doesn't have the length of the data.
+pub fn key_for(data: &[u8], message: &[u8]) -> Result<Vec<u8>, CipherError>{
+    if data.len() != message.len(){
+        return Err(CipherError::InvalidLength);
+    }
+    Ok(xor(data, message))
+}
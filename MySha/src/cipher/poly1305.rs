@@ -0,0 +1,72 @@
+//! The [Poly1305] message authentication code.
+//!
+//! Poly1305 computes a 16 byte tag of the message with a 32 byte one-time key, evaluating a polynomial,
+//! with the blocks of the message as coefficients, at a point given by the key, modulo the prime 2^130 - 5.
+//! Whoever has the key can check that the message wasn't changed, but the key must only be used for one message,
+//! so it is usually generated by a cipher for every message, like in [ChaCha20-Poly1305](super::chacha20poly1305).
+//!
+//! # Examples
+//! ```
+//! use mysha::cipher::{poly1305, CipherError};
+//! # fn main() -> Result<(), CipherError>{
+//! let key = [
+//!     0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5, 0x06, 0xa8,
+//!     0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf, 0x41, 0x49, 0xf5, 0x1b,
+//! ];
+//!
+//! let tag = poly1305::mac(&key, b"Cryptographic Forum Research Group")?;
+//! assert_eq!(tag, [0xa8, 0x06, 0x1d, 0xc1, 0x30, 0x51, 0x36, 0xc6, 0xc2, 0x2b, 0x8b, 0xaf, 0x0c, 0x01, 0x27, 0xa9]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Poly1305]: https://datatracker.ietf.org/doc/html/rfc8439#section-2.5
+
+use num_bigint::BigUint;
+
+use super::CipherError;
+
+/// Size of the one-time key, in bytes.
+pub const KEY_SIZE: usize = 32;
+/// Size of the tag, in bytes.
+pub const TAG_SIZE: usize = 16;
+
+/// Computes the tag of the message with the one-time key.
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key isn't 32 bytes long.
+pub fn mac(key: &[u8], message: &[u8]) -> Result<[u8; TAG_SIZE], CipherError>{
+    if key.len() != KEY_SIZE{
+        return Err(CipherError::InvalidKeyLength);
+    }
+    let p = (BigUint::from(1_u8) << 130_u32) - 5_u8;
+    // some bits of r are cleared, as the algorithm requires
+    let r = BigUint::from_bytes_le(&key[..16]) & BigUint::from(0x0ffffffc0ffffffc0ffffffc0fffffff_u128);
+    let s = BigUint::from_bytes_le(&key[16..]);
+
+    let mut accumulator = BigUint::from(0_u8);
+    for chunk in message.chunks(16){
+        // the block with a 1 byte after it
+        let mut block = chunk.to_vec();
+        block.push(1);
+        accumulator = (accumulator + BigUint::from_bytes_le(&block)) * &r % &p;
+    }
+
+    let mut tag = [0; TAG_SIZE];
+    let bytes = ((accumulator + s) % (BigUint::from(1_u8) << 128_u32)).to_bytes_le();
+    tag[..bytes.len()].copy_from_slice(&bytes);
+    Ok(tag)
+}
+
+/// Checks if the tag is the tag of the message, comparing every byte, so the time taken doesn't tell how many bytes matched.
+///
+/// # Errors
+/// Returns [CipherError::InvalidKeyLength] if the key isn't 32 bytes long,
+/// and [CipherError::AuthenticationFailed] if the tag doesn't match.
+pub fn verify(key: &[u8], message: &[u8], tag: &[u8]) -> Result<(), CipherError>{
+    let expected = mac(key, message)?;
+    if tag.len() != TAG_SIZE || expected.iter().zip(tag).fold(0, |diff, (a, b)| diff | (a ^ b)) != 0{
+        return Err(CipherError::AuthenticationFailed);
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! The format of data encrypted to a recipient or with a passphrase: a small header, that says how to decrypt it,
+//! followed by the encrypted data.
+//!
+//! | field | bytes |
+//! |---|---|
+//! | magic, `MYSHA` | 5 |
+//! | version, 1 | 1 |
+//! | kind, 1 for a recipient and 2 for a passphrase | 1 |
+//! | argon2id memory in KiB, iterations and lanes, only with a passphrase | 3 * 4, big endian |
+//! | salt and nonce, only with a passphrase | 16 and 12 |
+//!
+//! With a recipient the data is encrypted with ECIES, and starts with its ephemeral public key, with a passphrase
+//! it is encrypted with ChaCha20-Poly1305 and the argon2id key of the passphrase. Either way the header is the
+//! associated data, so changing it makes decrypting fail.
+
+use mysha::cipher::chacha20poly1305;
+use mysha::ecc::{PrivKey, PubKey};
+use mysha::ecies;
+use mysha::kdf::argon2::{self, Params, Variant};
+use mysha::rand;
+
+use crate::Exit;
+
+const MAGIC: &[u8] = b"MYSHA";
+const VERSION: u8 = 1;
+const RECIPIENT: u8 = 1;
+const PASSPHRASE: u8 = 2;
+const SALT_SIZE: usize = 16;
+/// Most memory a file can ask argon2 for, 4 GiB, so a changed header can't make decrypting take all the memory.
+const MAX_MEMORY: u32 = 4 * 1024 * 1024;
+
+/// What the data is encrypted with, and decrypted with the matching private key, or the same passphrase.
+pub enum Protection{
+    Recipient(PubKey),
+    Passphrase(String),
+}
+
+/// The key, or what decrypts it.
+pub enum Unlock{
+    Private(PrivKey),
+    Passphrase(String),
+}
+
+fn passphrase_key(passphrase: &str, salt: &[u8], params: &Params) -> Vec<u8>{
+    argon2::derive(passphrase.as_bytes(), salt, params, chacha20poly1305::KEY_SIZE).exit("Error while deriving the key of the passphrase.")
+}
+
+/// Encrypts the message, returning the header followed by the encrypted data.
+pub fn seal(message: &[u8], protection: &Protection) -> Vec<u8>{
+    let mut header = MAGIC.to_vec();
+    header.push(VERSION);
+    match protection{
+        Protection::Recipient(public) => {
+            header.push(RECIPIENT);
+            let encrypted = ecies::encrypt(public, message, &header).exit("Error while encrypting.");
+            header.extend(encrypted);
+        },
+        Protection::Passphrase(passphrase) => {
+            header.push(PASSPHRASE);
+            let params = Params::default();
+            for value in [params.memory, params.iterations, params.parallelism]{
+                header.extend(value.to_be_bytes());
+            }
+            let mut salt = [0; SALT_SIZE];
+            rand::fill_random(&mut salt);
+            let mut nonce = [0; chacha20poly1305::NONCE_SIZE];
+            rand::fill_random(&mut nonce);
+            header.extend(salt);
+            header.extend(nonce);
+
+            let key = passphrase_key(passphrase, &salt, &params);
+            let encrypted = chacha20poly1305::encrypt(&key, &nonce, message, &header).exit("Error while encrypting.");
+            header.extend(encrypted);
+        },
+    }
+    header
+}
+
+/// Reads the header, and decrypts the data after it.
+pub fn open(data: &[u8], unlock: &Unlock) -> Vec<u8>{
+    if !data.starts_with(MAGIC) || data.len() < MAGIC.len() + 2{
+        None.exit("The data wasn't encrypted to a recipient or with a passphrase, it doesn't start with the header.")
+    }
+    let version = data[MAGIC.len()];
+    if version != VERSION{
+        None.exit(&format!("Unsupported version {} of the encrypted data, only version {} can be read.", version, VERSION))
+    }
+    let kind = data[MAGIC.len() + 1];
+    let (header, encrypted) = data.split_at(MAGIC.len() + 2);
+
+    match (kind, unlock){
+        (RECIPIENT, Unlock::Private(private)) => {
+            ecies::decrypt(private, encrypted, header).exit("Error while decrypting.")
+        },
+        (PASSPHRASE, Unlock::Passphrase(passphrase)) => {
+            let fields = 3 * 4 + SALT_SIZE + chacha20poly1305::NONCE_SIZE;
+            if encrypted.len() < fields{
+                None.exit("The header of the encrypted data is too short.")
+            }
+            let header = &data[..header.len() + fields];
+            let (params, rest) = encrypted.split_at(3 * 4);
+            let (salt, rest) = rest.split_at(SALT_SIZE);
+            let (nonce, encrypted) = rest.split_at(chacha20poly1305::NONCE_SIZE);
+
+            let value = |i: usize| u32::from_be_bytes(params[4 * i..4 * i + 4].try_into().unwrap());
+            let params = Params{variant: Variant::Argon2id, memory: value(0), iterations: value(1), parallelism: value(2)};
+            if params.memory > MAX_MEMORY{
+                None.exit(&format!("The data asks for {} KiB for argon2, more than the {} KiB allowed.", params.memory, MAX_MEMORY))
+            }
+            let key = passphrase_key(passphrase, salt, &params);
+            chacha20poly1305::decrypt(&key, nonce, encrypted, header).exit("Error while decrypting, the passphrase may be wrong.")
+        },
+        (RECIPIENT, _) => None.exit("The data was encrypted to a recipient, it needs --private to be decrypted."),
+        (PASSPHRASE, _) => None.exit("The data was encrypted with a passphrase, it needs --passphrase to be decrypted."),
+        (kind, _) => None.exit(&format!("Unknown kind {} of encrypted data.", kind)),
+    }
+}
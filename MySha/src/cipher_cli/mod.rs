@@ -0,0 +1,206 @@
+use clap::{Args, ValueEnum};
+use std::fs::{self, File};
+use std::io::Read;
+
+use mysha::cipher::{cbc, chacha20poly1305, ctr, gcm};
+use mysha::encoding::hex;
+use mysha::rand;
+
+use crate::ecc_cli::{read_private_key, read_public_key};
+use crate::encode_cli::{get_bytes, get_text, print_bytes, Input, Output};
+use crate::Exit;
+
+mod envelope;
+use envelope::{Protection, Unlock};
+
+#[derive(Args, Debug)]
+pub struct EncryptArgs{
+    /// data to be encrypted, read from stdin if not provided
+    data: Option<String>,
+
+    /// key in hex, of 16, 24 or 32 bytes for AES, and 32 bytes for chacha20-poly1305
+    #[arg(short, long, required_unless_present_any = ["recipient", "passphrase"])]
+    key: Option<String>,
+
+    /// ecc toml file with the public key the data is encrypted to, with ECIES, only its private key decrypts it
+    #[arg(long, conflicts_with_all = ["key", "passphrase", "mode", "nonce", "aad"])]
+    recipient: Option<String>,
+
+    /// passphrase the data is encrypted with, through argon2id and chacha20-poly1305
+    #[arg(long, conflicts_with_all = ["key", "mode", "nonce", "aad"])]
+    passphrase: Option<String>,
+
+    /// mode of operation
+    #[arg(short, long, default_value_t = Mode::Gcm, value_enum)]
+    mode: Mode,
+
+    /// data type
+    #[arg(short, long, default_value_t = Input::Text, value_enum)]
+    r#type: Input,
+
+    /// nonce or iv in hex, random if not provided
+    #[arg(long)]
+    nonce: Option<String>,
+
+    /// associated data, authenticated but not encrypted, only with gcm and chacha20-poly1305
+    #[arg(long)]
+    aad: Option<String>,
+
+    /// writes the encrypted data to the file as bytes, instead of printing it as hex
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DecryptArgs{
+    /// encrypted data in hex, read from stdin if not provided
+    data: Option<String>,
+
+    /// key in hex, of 16, 24 or 32 bytes for AES, and 32 bytes for chacha20-poly1305
+    #[arg(short, long, required_unless_present_any = ["private", "passphrase"])]
+    key: Option<String>,
+
+    /// ecc toml file with the private key of the recipient, for data encrypted with --recipient
+    #[arg(long, conflicts_with_all = ["key", "passphrase", "mode", "nonce", "aad"])]
+    private: Option<String>,
+
+    /// passphrase of data encrypted with --passphrase
+    #[arg(long, conflicts_with_all = ["key", "mode", "nonce", "aad"])]
+    passphrase: Option<String>,
+
+    /// mode of operation
+    #[arg(short, long, default_value_t = Mode::Gcm, value_enum)]
+    mode: Mode,
+
+    /// how the decrypted data is shown
+    #[arg(short, long, default_value_t = Output::Text, value_enum)]
+    r#type: Output,
+
+    /// nonce or iv in hex, if it isn't at the start of the data
+    #[arg(long)]
+    nonce: Option<String>,
+
+    /// associated data, only with gcm and chacha20-poly1305
+    #[arg(long)]
+    aad: Option<String>,
+
+    /// reads the encrypted data as bytes from the file
+    #[arg(short, long, conflicts_with = "data")]
+    file: Option<String>,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+enum Mode{
+    /// Galois/counter mode, detects changes to the encrypted data
+    Gcm,
+    /// Cipher block chaining, with PKCS#7 padding
+    Cbc,
+    /// Counter mode
+    Ctr,
+    /// ChaCha20-Poly1305 instead of AES, detects changes to the encrypted data
+    Chacha20Poly1305,
+}
+
+impl Mode{
+    fn nonce_size(&self) -> usize{
+        match self{
+            Mode::Gcm => gcm::NONCE_SIZE,
+            Mode::Cbc => cbc::IV_SIZE,
+            Mode::Ctr => ctr::NONCE_SIZE,
+            Mode::Chacha20Poly1305 => chacha20poly1305::NONCE_SIZE,
+        }
+    }
+}
+
+fn get_aad(aad: Option<String>, mode: &Mode) -> Vec<u8>{
+    if aad.is_some() && ![Mode::Gcm, Mode::Chacha20Poly1305].contains(mode){
+        Err::<(), &str>("associated data can only be used with gcm and chacha20-poly1305.").exit("Error while reading the associated data.");
+    }
+    aad.unwrap_or_default().into_bytes()
+}
+
+fn write_encrypted(output: Vec<u8>, path: Option<String>){
+    match path{
+        Some(path) => fs::write(path, output).exit("Error while writing the file."),
+        None => println!("{}", hex::encode(&output)),
+    }
+}
+
+/// The encrypted data starts with the nonce, so it can be decrypted with only the key,
+/// or with the header of [envelope] when it is encrypted to a recipient or with a passphrase.
+pub fn encrypt(args: EncryptArgs){
+    let message = get_bytes(args.data, &args.r#type);
+    let protection = match (args.recipient, args.passphrase){
+        (Some(path), _) => Some(Protection::Recipient(read_public_key(&path))),
+        (None, Some(passphrase)) => Some(Protection::Passphrase(passphrase)),
+        (None, None) => None,
+    };
+    if let Some(protection) = protection{
+        write_encrypted(envelope::seal(&message, &protection), args.output);
+        return;
+    }
+
+    let key = hex::decode(&args.key.unwrap()).exit("Error while parsing the key.");
+    let aad = get_aad(args.aad, &args.mode);
+    let nonce = match args.nonce{
+        Some(nonce) => hex::decode(&nonce).exit("Error while parsing the nonce."),
+        None => {
+            let mut nonce = vec![0; args.mode.nonce_size()];
+            rand::fill_random(&mut nonce);
+            nonce
+        },
+    };
+
+    let encrypted = match args.mode{
+        Mode::Gcm => gcm::encrypt(&key, &nonce, &message, &aad),
+        Mode::Cbc => cbc::encrypt(&key, &nonce, &message),
+        Mode::Ctr => ctr::encrypt(&key, &nonce, &message),
+        Mode::Chacha20Poly1305 => chacha20poly1305::encrypt(&key, &nonce, &message, &aad),
+    }.exit("Error while encrypting.");
+
+    let mut output = nonce;
+    output.extend(encrypted);
+    write_encrypted(output, args.output);
+}
+
+pub fn decrypt(args: DecryptArgs){
+    let data = match args.file{
+        Some(path) => {
+            let mut data = Vec::new();
+            File::open(path).and_then(|mut file| file.read_to_end(&mut data)).exit("Error while reading the file.");
+            data
+        },
+        None => hex::decode(&get_text(args.data)).exit("Error while parsing the encrypted data."),
+    };
+    let unlock = match (args.private, args.passphrase){
+        (Some(path), _) => Some(Unlock::Private(read_private_key(&path))),
+        (None, Some(passphrase)) => Some(Unlock::Passphrase(passphrase)),
+        (None, None) => None,
+    };
+    if let Some(unlock) = unlock{
+        print_bytes(&envelope::open(&data, &unlock), &args.r#type);
+        return;
+    }
+
+    let key = hex::decode(&args.key.unwrap()).exit("Error while parsing the key.");
+    let aad = get_aad(args.aad, &args.mode);
+
+    let (nonce, encrypted) = match args.nonce{
+        Some(nonce) => (hex::decode(&nonce).exit("Error while parsing the nonce."), data.as_slice()),
+        None => {
+            if data.len() < args.mode.nonce_size(){
+                Err::<(), &str>("the data is shorter than the nonce.").exit("Error while decrypting.");
+            }
+            let (nonce, encrypted) = data.split_at(args.mode.nonce_size());
+            (nonce.to_vec(), encrypted)
+        },
+    };
+
+    let message = match args.mode{
+        Mode::Gcm => gcm::decrypt(&key, &nonce, encrypted, &aad),
+        Mode::Cbc => cbc::decrypt(&key, &nonce, encrypted),
+        Mode::Ctr => ctr::decrypt(&key, &nonce, encrypted),
+        Mode::Chacha20Poly1305 => chacha20poly1305::decrypt(&key, &nonce, encrypted, &aad),
+    }.exit("Error while decrypting.");
+    print_bytes(&message, &args.r#type);
+}
@@ -0,0 +1,151 @@
+use clap::{Args, Subcommand, ValueEnum};
+
+use mysha::cipher::classical::{self, ENGLISH};
+
+use crate::encode_cli::get_text;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct ClassicalArgs{
+    #[command(subcommand)]
+    subcommand: SubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommand{
+    /// Shifts every letter by the same amount
+    Caesar(CaesarArgs),
+    /// Shifts every letter by the letter of the key at its position
+    Vigenere(KeyArgs),
+    /// Replaces every letter by the letter of the key at its position in the alphabet
+    Substitution(KeyArgs),
+    /// Shows the frequencies of the letters of the text, next to the ones of English
+    Frequencies(TextArgs),
+    /// Finds the key of encrypted text with frequency analysis
+    Crack(CrackArgs),
+}
+
+#[derive(Args, Debug)]
+struct CaesarArgs{
+    /// text, read from stdin if not provided
+    text: Option<String>,
+
+    /// number of letters every letter is shifted by
+    #[arg(short, long, default_value_t = 3)]
+    shift: u8,
+
+    /// decrypts the text
+    #[arg(short, long)]
+    decrypt: bool,
+}
+
+#[derive(Args, Debug)]
+struct KeyArgs{
+    /// text, read from stdin if not provided
+    text: Option<String>,
+
+    /// key, letters for vigenere, and the 26 letters in the order they replace a to z for substitution
+    #[arg(short, long)]
+    key: String,
+
+    /// decrypts the text
+    #[arg(short, long)]
+    decrypt: bool,
+}
+
+#[derive(Args, Debug)]
+struct TextArgs{
+    /// text, read from stdin if not provided
+    text: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct CrackArgs{
+    /// encrypted text, read from stdin if not provided
+    text: Option<String>,
+
+    /// cipher the text was encrypted with
+    #[arg(short, long, default_value_t = Cipher::Caesar, value_enum)]
+    cipher: Cipher,
+
+    /// longest vigenere key tried
+    #[arg(long, default_value_t = 16)]
+    max_key_length: usize,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+enum Cipher{
+    /// Caesar cipher, every shift is tried
+    Caesar,
+    /// Vigenère cipher, the key length is found first
+    Vigenere,
+}
+
+/// Bar of the value, with the width for the maximum.
+fn get_bar(value: f64, max: f64, width: usize) -> String{
+    "#".repeat((value / max * width as f64).round() as usize)
+}
+
+pub fn classical(args: ClassicalArgs){
+    match args.subcommand{
+        SubCommand::Caesar(args) => {
+            let text = get_text(args.text);
+            if args.decrypt{
+                println!("{}", classical::caesar_decrypt(&text, args.shift));
+            }else{
+                println!("{}", classical::caesar_encrypt(&text, args.shift));
+            }
+        },
+        SubCommand::Vigenere(args) => {
+            let text = get_text(args.text);
+            if args.decrypt{
+                println!("{}", classical::vigenere_decrypt(&text, &args.key).exit("Error while decrypting."));
+            }else{
+                println!("{}", classical::vigenere_encrypt(&text, &args.key).exit("Error while encrypting."));
+            }
+        },
+        SubCommand::Substitution(args) => {
+            let text = get_text(args.text);
+            if args.decrypt{
+                println!("{}", classical::substitution_decrypt(&text, &args.key).exit("Error while decrypting."));
+            }else{
+                println!("{}", classical::substitution_encrypt(&text, &args.key).exit("Error while encrypting."));
+            }
+        },
+        SubCommand::Frequencies(args) => {
+            let text = get_text(args.text);
+            let frequencies = classical::letter_frequencies(&text);
+            let max = frequencies.iter().chain(&ENGLISH).cloned().fold(0.0, f64::max);
+            println!("letter  text                            english");
+            for (i, (frequency, english)) in frequencies.iter().zip(ENGLISH).enumerate(){
+                println!("{}  {:5.1}% {:<24}  {:5.1}% {}", (b'a' + i as u8) as char, frequency * 100.0, get_bar(*frequency, max, 24), english * 100.0, get_bar(english, max, 24));
+            }
+            println!("\nindex of coincidence: {:.4} (english: 0.0660, random: 0.0385)", classical::index_of_coincidence(&text));
+        },
+        SubCommand::Crack(args) => {
+            let text = get_text(args.text);
+            match args.cipher{
+                Cipher::Caesar => {
+                    println!("chi-squared of the decryption with every shift, lower looks more like english:");
+                    let scores: Vec<f64> = (0..26).map(|shift| classical::chi_squared(&classical::caesar_decrypt(&text, shift))).collect();
+                    let max = scores.iter().cloned().filter(|score| score.is_finite()).fold(0.0, f64::max);
+                    for (shift, score) in scores.iter().enumerate(){
+                        println!("{:>2}  {:>10.1}  {}", shift, score, get_bar(*score, max, 40));
+                    }
+                    let (shift, decrypted) = classical::crack_caesar(&text);
+                    println!("\nshift: {}\n{}", shift, decrypted);
+                },
+                Cipher::Vigenere => {
+                    println!("average index of coincidence of the columns for every key length, the key length looks like english (0.066):");
+                    let scores = classical::key_length_scores(&text, args.max_key_length.max(1));
+                    let max = scores.iter().cloned().fold(0.0, f64::max);
+                    for (i, score) in scores.iter().enumerate(){
+                        println!("{:>2}  {:.4}  {}", i + 1, score, get_bar(*score, max, 40));
+                    }
+                    let (key, decrypted) = classical::crack_vigenere(&text, args.max_key_length);
+                    println!("\nevery column cracked as a caesar cipher gives the key: {}\n{}", key, decrypted);
+                },
+            }
+        },
+    }
+}
@@ -0,0 +1,92 @@
+//! User configuration, read from `~/.config/mysha/config.toml`.
+//!
+//! The file is optional, and every section and key in it is optional too, missing ones keep their default value.
+//! If `XDG_CONFIG_HOME` is set, the file is read from `$XDG_CONFIG_HOME/mysha/config.toml` instead.
+//!
+//! Values in the file are defaults for the flags of the commands, flags given on the command line always override them.
+//! Only the sha256 and ecc commands read the file, so an invalid one doesn't break the others.
+//! The output formats, like --json-lines or --output, have no key, they are still chosen on the command line for each run.
+//!
+//! ```toml
+//! [sha256]
+//! type = "hex"
+//! little_endian = true
+//! faster = true
+//! speed = 2.0
+//!
+//! [ecc]
+//! curve = "/home/user/curves/p256.toml"
+//! hex = true
+//! ```
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::Exit;
+use crate::sha256_cli::{Theme, Type};
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config{
+    /// Defaults of the sha256 command
+    pub sha256: Sha256Config,
+    /// Defaults of the ecc command
+    pub ecc: EccConfig,
+    /// Colors and styles of the sha256 animation
+    pub theme: Theme,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Sha256Config{
+    /// Input type, same values as --type
+    pub r#type: Option<Type>,
+    /// Displays output as little endian
+    pub little_endian: bool,
+    /// Disables extra explanations on animation
+    pub faster: bool,
+    /// Speed of the animation, 2.0 is twice as fast
+    pub speed: Option<f64>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct EccConfig{
+    /// Path to the toml file of the curve used instead of secp256k1
+    pub curve: Option<String>,
+    /// Displays output as hex
+    pub hex: bool,
+    /// Displays output as little endian, needs hex
+    pub little_endian: bool,
+}
+
+/// Value of a flag that can be turned on in the config file, and turned on or off with the `--flag` and `--no-flag` command line flags.
+pub fn flag(on: bool, off: bool, config: bool) -> bool{
+    ! off && (on || config)
+}
+
+impl Config{
+    /// Path of the config file, if there is a config directory.
+    pub fn path() -> Option<PathBuf>{
+        let dir = match std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()){
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?).join(".config"),
+        };
+        Some(dir.join("mysha").join("config.toml"))
+    }
+
+    /// Reads the config file, the default config is used if there is no file.
+    pub fn load() -> Config{
+        let Some(path) = Config::path() else{
+            return Config::default();
+        };
+        match fs::read_to_string(&path){
+            Ok(content) => toml::from_str(&content).exit(&format!("Error while parsing the config file {}.", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Config::default(),
+            Err(e) => Err(e).exit(&format!("Error while reading the config file {}.", path.display())),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use clap::Args;
+
+use mysha::encoding::hex;
+use mysha::kdf::password::{Algorithm, PasswordHash};
+use mysha::sha256::{Hash256, Sha256};
+
+use crate::demo_cli::{format_duration, format_rate};
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct CrackArgs{
+    /// file with a candidate password on every line
+    #[arg(short, long)]
+    wordlist: String,
+
+    /// sha256 of the password, in hex, or a hash of `password hash -a sha256`, with its salt
+    #[arg(long)]
+    hash: String,
+
+    /// salt written before every candidate, as text
+    #[arg(short, long, conflicts_with = "salt_hex")]
+    salt: Option<String>,
+
+    /// salt written before every candidate, in hex
+    #[arg(long)]
+    salt_hex: Option<String>,
+
+    /// times sha256 is applied, the first to the salt and the candidate, the others to the previous hash
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Doesn't show the progress
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+/// Time between updates of the progress.
+const PROGRESS: Duration = Duration::from_millis(200);
+
+/// Iterations of PBKDF2 recommended by OWASP, to compare the cost of a guess with.
+const PBKDF2_ITERATIONS: f64 = 600_000.0;
+
+/// Size of a large list of leaked passwords, to show how long trying all of them takes.
+const LEAKED: f64 = 1e9;
+
+/// Hash of the candidate with the scheme, continuing from the hasher that already has the salt.
+fn hash_candidate(salted: &Sha256, candidate: &str, iterations: u32) -> [u8; 32]{
+    let mut hasher = salted.clone();
+    hasher.update(candidate.as_bytes());
+    let mut hash = hasher.finalize_bytes();
+    for _ in 1..iterations{
+        hash = Sha256::digest(&hash);
+    }
+    hash
+}
+
+/// The hash and the salt to crack, from the encoded hash, or from the hex hash and the salt arguments.
+fn get_target(args: &CrackArgs) -> ([u8; 32], Vec<u8>){
+    if args.hash.starts_with('$'){
+        let encoded: PasswordHash = args.hash.parse().exit("Invalid hash.");
+        if encoded.algorithm != Algorithm::Sha256{
+            None.exit("Only hashes of a single sha256 can be cracked, the others are slow on purpose.")
+        }
+        if args.salt.is_some() || args.salt_hex.is_some(){
+            None.exit("The encoded hash already has its salt.")
+        }
+        return (encoded.hash.try_into().unwrap(), encoded.salt);
+    }
+    let target = Hash256::from_hex(&args.hash.to_lowercase(), false).exit("Invalid hash.").get_bytes();
+    let salt = match (&args.salt, &args.salt_hex){
+        (Some(salt), _) => salt.as_bytes().to_vec(),
+        (_, Some(salt)) => hex::decode(salt).exit("Error while parsing the salt."),
+        _ => Vec::new(),
+    };
+    (target, salt)
+}
+
+/// Tries every word of the list against the hash, showing how fast sha256 guesses are, and why a password hash must be slow.
+pub fn crack(args: CrackArgs){
+    let (target, salt) = get_target(&args);
+    let text = fs::read_to_string(&args.wordlist).exit("Error while reading the wordlist.");
+    let words: Vec<&str> = text.lines().collect();
+    let progress = !args.quiet && io::stderr().is_terminal();
+
+    // the salt is the same for every candidate, so it is hashed only once
+    let mut salted = Sha256::new();
+    salted.update(&salt);
+
+    let start = Instant::now();
+    let mut shown = start;
+    let mut found = None;
+    for (i, word) in words.iter().enumerate(){
+        if hash_candidate(&salted, word, args.iterations) == target{
+            found = Some(i);
+            break;
+        }
+        if progress && i % 1024 == 0 && shown.elapsed() >= PROGRESS{
+            shown = Instant::now();
+            let rate = (i + 1) as f64 / start.elapsed().as_secs_f64();
+            eprint!("\rtried {} of {} words, {}   ", i + 1, words.len(), format_rate(rate * args.iterations as f64));
+            io::stderr().flush().unwrap();
+        }
+    }
+    if progress && shown > start{
+        eprint!("\r{}\r", " ".repeat(60));
+    }
+    let elapsed = start.elapsed().max(Duration::from_nanos(1));
+    let tried = found.map_or(words.len(), |i| i + 1);
+    let guesses = tried as f64 / elapsed.as_secs_f64();
+
+    match found{
+        Some(i) => println!("found:      {} (line {})", words[i], i + 1),
+        None => println!("not found, no word of the list hashes to {}", Hash256::from(target)),
+    }
+    println!("tried:      {} of {} words", tried, words.len());
+    println!("time:       {:.3} s", elapsed.as_secs_f64());
+    println!("rate:       {:.0} guesses/s, {}", guesses, format_rate(guesses * args.iterations as f64));
+    println!();
+    println!("At this rate the {:.0e} passwords of the largest leaked lists take {}.", LEAKED, format_duration(LEAKED / guesses));
+    if salt.is_empty(){
+        println!("Without a salt, every hash of the same password is the same, so one pass over the list cracks every user,");
+        println!("and the hashes of whole lists can be computed once and looked up.");
+    }else{
+        println!("The salt makes the hashes of the same password different, so every user needs a pass over the list of their own,");
+        println!("but it doesn't make a guess any slower.");
+    }
+    let slowdown = PBKDF2_ITERATIONS / args.iterations as f64;
+    println!("PBKDF2 with {:.0} iterations would make every guess {:.0} times slower, {} for the same list,",
+        PBKDF2_ITERATIONS, slowdown, format_duration(LEAKED / guesses * slowdown));
+    println!("and Argon2 also needs memory for every guess, which is what `mysha password hash` does.");
+
+    if found.is_none(){
+        Failure::Verification.exit();
+    }
+}
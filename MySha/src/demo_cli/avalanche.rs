@@ -0,0 +1,100 @@
+use std::io::{self, IsTerminal};
+
+use clap::Args;
+
+use mysha::encoding::hex;
+use mysha::sha256::Sha256;
+
+use crate::sha256_cli::terminal::{Color, ResetStyle, SetStyle, Style};
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct AvalancheArgs{
+    /// first message
+    message: String,
+
+    /// second message, the first one with a bit flipped if not provided
+    other: Option<String>,
+
+    /// bit of the first message flipped when there is no second one, counting from the most significant bit of the first byte.
+    /// The last bit by default
+    #[arg(short, long, conflicts_with = "other")]
+    flip: Option<usize>,
+
+    /// treats the messages as hex
+    #[arg(short = 'x', long)]
+    hex: bool,
+}
+
+/// Bits shown in every line of the diff.
+const LINE: usize = 64;
+
+fn bits(bytes: &[u8]) -> Vec<bool>{
+    bytes.iter().flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1 == 1)).collect()
+}
+
+fn message_bytes(message: &str, is_hex: bool) -> Vec<u8>{
+    if is_hex{
+        hex::decode(message).exit("Invalid hex message.")
+    }else{
+        message.as_bytes().to_vec()
+    }
+}
+
+/// Shows the two hashes bit by bit, the bits of the second one that differ colored red, and marked below.
+fn print_diff(a: &[bool], b: &[bool], color: bool){
+    for start in (0..a.len()).step_by(LINE){
+        let range = start..(start + LINE).min(a.len());
+        let first: String = a[range.clone()].iter().map(|&bit| if bit {'1'} else {'0'}).collect();
+        let second: String = range.clone().map(|i|{
+            let bit = if b[i] {'1'} else {'0'};
+            if color && a[i] != b[i] {format!("{}{}{}", SetStyle(Style::color(Color::Red)), bit, ResetStyle)} else {bit.to_string()}
+        }).collect();
+        let marks: String = range.map(|i| if a[i] != b[i] {'^'} else {' '}).collect();
+        println!("  {:3}  {}", start, first);
+        println!("       {}", second);
+        println!("       {}", marks);
+    }
+}
+
+/// Hashes two messages that differ a little, and counts the bits of the hashes that differ, about half of them for a good hash.
+pub fn avalanche(args: AvalancheArgs){
+    let first = message_bytes(&args.message, args.hex);
+    let second = match &args.other{
+        Some(other) => message_bytes(other, args.hex),
+        None => {
+            if first.is_empty(){
+                None.exit("The message is empty, there is no bit to flip.")
+            }
+            let bit = args.flip.unwrap_or(8 * first.len() - 1);
+            if bit >= 8 * first.len(){
+                None.exit(&format!("The message only has {} bits, bit {} can't be flipped.", 8 * first.len(), bit))
+            }
+            let mut flipped = first.clone();
+            flipped[bit / 8] ^= 0x80 >> (bit % 8);
+            flipped
+        },
+    };
+
+    let input_bits = bits(&first).iter().zip(bits(&second)).filter(|(a, b)| **a != *b).count()
+        + 8 * first.len().abs_diff(second.len());
+    println!("message 1: {}", hex::encode(&first));
+    println!("message 2: {}", hex::encode(&second));
+    println!("the messages differ in {} {}", input_bits, if input_bits == 1 {"bit"} else {"bits"});
+    println!();
+
+    let hash_1 = Sha256::digest(&first);
+    let hash_2 = Sha256::digest(&second);
+    println!("sha256 1:  {}", hex::encode(&hash_1));
+    println!("sha256 2:  {}", hex::encode(&hash_2));
+    println!();
+
+    let (a, b) = (bits(&hash_1), bits(&hash_2));
+    print_diff(&a, &b, io::stdout().is_terminal());
+    println!();
+
+    let differing = a.iter().zip(&b).filter(|(a, b)| a != b).count();
+    println!("{} of {} bits of the hashes differ, {:.1}%", differing, a.len(), 100.0 * differing as f64 / a.len() as f64);
+    // for a random function every bit differs with a chance of a half, a binomial with a mean of 128 and a deviation of 8
+    println!("A random function would change 128 bits on average, and between 112 and 144 about 95% of the time.");
+}
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+use std::time::Instant;
+
+use clap::Args;
+
+use mysha::rand;
+use mysha::sha256::{Sha256, TruncatedHash};
+
+#[derive(Args, Debug)]
+pub struct BirthdayArgs{
+    /// number of bits the hashes are truncated to
+    #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..=MAX_BITS as i64))]
+    bits: u32,
+
+    /// number of collision searches
+    #[arg(short, long, default_value_t = 200, value_parser = clap::value_parser!(u64).range(1..))]
+    trials: u64,
+}
+
+/// Largest number of bits, every trial takes about 2^16 hashes.
+const MAX_BITS: u32 = 32;
+
+/// Width of the bars of the plot.
+const WIDTH: usize = 50;
+
+/// Hashes of the plot, in multiples of 2^(bits/2).
+const STEPS: [f64; 12] = [0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.25, 2.5, 2.75, 3.0];
+
+/// Hashes random messages, a random seed followed by a counter, until two of them have the same truncated hash,
+/// and returns the number of hashes.
+fn trial(bits: u32) -> u64{
+    let mut seed = [0; 16];
+    rand::fill_random(&mut seed);
+    let mut seen = HashSet::new();
+    for i in 1_u64..{
+        let hash = Sha256::digest(&[&seed[..], &i.to_be_bytes()].concat());
+        if !seen.insert(TruncatedHash::new(&hash, bits).unwrap()){
+            return i;
+        }
+    }
+    unreachable!("a truncated hash repeats after at most 2^bits + 1 hashes")
+}
+
+/// The chance of a collision among k hashes of the bits, 1 - e^(-k(k - 1) / 2^(bits + 1)).
+fn theory(hashes: f64, bits: u32) -> f64{
+    1.0 - (-hashes * (hashes - 1.0) / 2_f64.powi(bits as i32 + 1)).exp()
+}
+
+/// Measures how many hashes it takes to find a collision of truncated hashes, many times,
+/// and plots how often a collision was found by every number of hashes next to the birthday paradox.
+pub fn birthday(args: BirthdayArgs){
+    let start = Instant::now();
+    let mut attempts: Vec<u64> = (0..args.trials).map(|_| trial(args.bits)).collect();
+    let elapsed = start.elapsed();
+    attempts.sort_unstable();
+
+    let bound = 2_f64.powf(args.bits as f64 / 2.0);
+    let mean = attempts.iter().sum::<u64>() as f64 / args.trials as f64;
+    println!("{} collision searches of sha256 truncated to {} bits, {} hashes in {:.2?}.",
+        args.trials, args.bits, attempts.iter().sum::<u64>(), elapsed);
+    println!();
+    println!("hashes until a collision:");
+    println!("  fewest:  {}", attempts[0]);
+    println!("  median:  {}", attempts[attempts.len() / 2]);
+    println!("  most:    {}", attempts[attempts.len() - 1]);
+    println!("  mean:    {:.1}, expected sqrt(pi/2 * 2^{}) = {:.1}", mean, args.bits, (std::f64::consts::PI / 2.0).sqrt() * bound);
+    println!();
+
+    println!("Searches that found a collision by every number of hashes, # measured and | expected:");
+    for step in STEPS{
+        let hashes = step * bound;
+        let measured = attempts.partition_point(|&a| a as f64 <= hashes) as f64 / args.trials as f64;
+        let expected = theory(hashes, args.bits);
+        let filled = (measured * WIDTH as f64).round() as usize;
+        let mark = ((expected * WIDTH as f64).round() as usize).min(WIDTH - 1);
+        let plot: String = (0..WIDTH).map(|i| if i == mark {'|'} else if i < filled {'#'} else {' '}).collect();
+        println!("  {:4.2} * 2^{:<4} {:>10.0}  {}  {:5.1}% {:5.1}%", step, args.bits as f64 / 2.0, hashes, plot, 100.0 * measured, 100.0 * expected);
+    }
+    println!();
+    println!("Half of the searches need about 1.18 * 2^{} hashes, the square root of the 2^{} possible hashes.", args.bits as f64 / 2.0, args.bits);
+}
@@ -0,0 +1,68 @@
+use clap::Args;
+
+use mysha::ecc::{blind::*, Curve, KeyPair, Point};
+use mysha::rand;
+
+use crate::ecc_cli::read_private_key;
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct BlindSignArgs{
+    /// message the user gets signed, which the signer never sees
+    message: String,
+
+    /// ecc toml file with the private key of the signer, a random key of secp256k1 by default
+    #[arg(short, long)]
+    key: Option<String>,
+}
+
+/// x of the point in hex, enough to tell points apart.
+fn x(point: &Point) -> String{
+    point.get_x().map_or(String::from("infinity"), |x| format!("{:x}", x))
+}
+
+/// Runs both sides of the protocol, printing what each one sends and keeps, and that the signer can't link the signature.
+pub fn blind_sign(args: BlindSignArgs){
+    let private = match args.key{
+        Some(path) => read_private_key(&path),
+        None => KeyPair::new(rand::random_scalar(&Curve::secp256k1()), Curve::secp256k1()).exit("Error while generating the key.").private(),
+    };
+    let public = KeyPair::from_private(&private).exit("Invalid private key.").public();
+    println!("signer public key P, x: {}", x(public.get_public()));
+    println!();
+
+    println!("1. signer: picks a random nonce k, and sends R = kG");
+    let signer = Signer::new(&private).exit("Error while starting the session.");
+    let commitment = signer.commitment().clone();
+    println!("   R, x: {}", x(&commitment));
+    println!();
+
+    println!("2. user: picks random α and β, computes R' = R + αG + βP and c' = H(R' || P || message), and sends c = c' + β");
+    let blinding = Blinding::new(&public, &commitment, args.message.as_bytes()).exit("Invalid commitment.");
+    let challenge = blinding.challenge().clone();
+    println!("   c:  {:x}", challenge);
+    println!("   c' (kept by the user): {:x}", blinding.unblinded_challenge());
+    println!();
+
+    println!("3. signer: answers s = k + cx, having only seen c");
+    let s = signer.sign(&challenge);
+    println!("   s:  {:x}", s);
+    println!();
+
+    println!("4. user: checks that sG = R + cP, and unblinds s' = s + α");
+    let signature = blinding.unblind(&s).exit("The answer of the signer isn't valid.");
+    println!("   R', x: {}", x(signature.get_r()));
+    println!("   s':    {:x}", signature.get_s());
+    println!();
+
+    println!("signature (R', s') of {:?}:", args.message);
+    if signature.verify(&public, args.message.as_bytes()){
+        println!("   s'G = R' + c'P, VALID for the public key of the signer");
+    }else{
+        None.exit_as(Failure::Internal, "   the signature is NOT valid")
+    }
+    println!();
+    println!("the signer saw R, c and s, and the signature has R', c' and s', which differ by the random α and β,");
+    println!("so the signer can't tell which session it was signed in, and never saw the message.");
+}
@@ -0,0 +1,82 @@
+use clap::Args;
+
+use mysha::encoding::hex;
+use mysha::sha256::{sha256, Hash256, InputType};
+
+use crate::Exit;
+
+/// The fields of the header, the genesis block's by default.
+#[derive(Args, Debug)]
+pub struct BlockHeaderArgs{
+    /// version of the block
+    #[arg(long = "block-version", default_value_t = 1)]
+    version: i32,
+
+    /// hash of the previous block, in hex as shown by block explorers
+    #[arg(long, default_value_t = String::from("0000000000000000000000000000000000000000000000000000000000000000"))]
+    prev_hash: String,
+
+    /// merkle root of the transactions, in hex as shown by block explorers
+    #[arg(long, default_value_t = String::from("4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"))]
+    merkle_root: String,
+
+    /// time of the block, in seconds since 1970
+    #[arg(long, default_value_t = 1231006505)]
+    time: u32,
+
+    /// target in compact form, in hex
+    #[arg(long, default_value_t = String::from("1d00ffff"))]
+    bits: String,
+
+    /// nonce found by the miner
+    #[arg(long, default_value_t = 2083236893)]
+    nonce: u32,
+}
+
+fn show_field(name: &str, value: &str, serialized: &str){
+    println!("{:<13}{:<66}{}", name, value, serialized);
+}
+
+pub fn block_header(args: BlockHeaderArgs){
+    let prev_hash = Hash256::from_hex(&args.prev_hash.to_lowercase(), false).exit("Invalid previous block hash.");
+    let merkle_root = Hash256::from_hex(&args.merkle_root.to_lowercase(), false).exit("Invalid merkle root.");
+    let bits = u32::from_str_radix(&args.bits, 16).exit("Invalid bits.");
+    let target = Hash256::from_compact(bits).exit("Invalid bits.");
+
+    // hashes are stored in the order sha256 outputs them, the reverse of how they are shown
+    let fields = [
+        ("version", args.version.to_string(), hex::encode_le(&args.version.to_be_bytes())),
+        ("prev hash", args.prev_hash.to_lowercase(), prev_hash.get_hex_le()),
+        ("merkle root", args.merkle_root.to_lowercase(), merkle_root.get_hex_le()),
+        ("time", args.time.to_string(), hex::encode_le(&args.time.to_be_bytes())),
+        ("bits", format!("{:08x}", bits), hex::encode_le(&bits.to_be_bytes())),
+        ("nonce", args.nonce.to_string(), hex::encode_le(&args.nonce.to_be_bytes())),
+    ];
+    show_field("field", "value", "serialized, little endian");
+    for (name, value, serialized) in &fields{
+        show_field(name, value, serialized);
+    }
+
+    let header: String = fields.iter().map(|(_, _, serialized)| serialized.as_str()).collect();
+    println!("\nheader, 80 bytes:\n{}", header);
+
+    let first = sha256(&header, InputType::Hex).exit("Error while hashing the header.");
+    let hash = sha256(first.get_hex(), InputType::Hex).exit("Error while hashing the header.");
+    println!("\nsha256(header):         {}", first);
+    println!("sha256(sha256(header)): {}", hash);
+    println!("block hash, reversed:   {}", hash.get_hex_le());
+
+    println!("\nBitcoin reads the hash as a 256 bit little endian number, the first byte being the least significant,");
+    println!("and shows numbers with the most significant byte first, so the hash is shown reversed.");
+    println!("That way the zeros of the proof of work, at the end of the hash, are shown at the start, like in a number below the target.");
+
+    let mut reversed = hash.get_bytes();
+    reversed.reverse();
+    println!("\ntarget from the bits:   {}", target);
+    println!("block hash:             {}", hash.get_hex_le());
+    if Hash256::from(reversed).meets_target(&target){
+        println!("The block hash is below the target, the proof of work is valid.");
+    }else{
+        println!("The block hash is above the target, the proof of work is NOT valid.");
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::thread;
+use std::time::Instant;
+
+use clap::Args;
+
+use mysha::encoding::hex;
+use mysha::rand;
+use mysha::sha256::{sha256_with_rounds, Hash256, InputType, Sha256, TruncatedHash, ROUNDS};
+
+#[derive(Args, Debug)]
+pub struct CollisionArgs{
+    /// number of bits of the start of the hashes that must be the same
+    #[arg(short, long, default_value_t = 24, value_parser = clap::value_parser!(u32).range(1..=MAX_BITS as i64))]
+    bits: u32,
+
+    /// Runs only the first rounds of the 64 of every block. INSECURE, it isn't sha256
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=ROUNDS as i64))]
+    rounds: Option<u8>,
+
+    /// start of every message, followed by a counter. Random if not provided, so every search is different
+    #[arg(short, long)]
+    prefix: Option<String>,
+
+    /// number of threads hashing the messages
+    #[arg(short = 'j', long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    threads: u64,
+}
+
+/// Largest number of bits, about 2^20 hashes are kept in memory until a collision shows up.
+const MAX_BITS: u32 = 40;
+
+/// Messages hashed at once, split between the threads.
+const BATCH: u64 = 4096;
+
+/// The first bits of the hash, as a number.
+fn truncate(hash: &[u8; 32], bits: u32) -> u64{
+    // the bits are at most 40, so they always fit
+    TruncatedHash::new(hash, bits).unwrap().to_u64().unwrap()
+}
+
+/// Hash of the message, with every round, or only the first ones.
+fn hash(message: &str, rounds: usize) -> [u8; 32]{
+    if rounds == ROUNDS{
+        Sha256::digest(message.as_bytes())
+    }else{
+        sha256_with_rounds(message, InputType::Text, rounds).unwrap().get_bytes()
+    }
+}
+
+/// Hashes the messages of the counters from the first, every thread a part of the batch, in order.
+fn hash_batch(prefix: &str, first: u64, rounds: usize, threads: u64) -> Vec<[u8; 32]>{
+    let part = BATCH.div_ceil(threads);
+    thread::scope(|scope|{
+        let handles: Vec<_> = (0..threads).map(|t|{
+            let start = (first + t * part).min(first + BATCH);
+            let end = (start + part).min(first + BATCH);
+            scope.spawn(move || (start..end).map(|i| hash(&format!("{}{}", prefix, i), rounds)).collect::<Vec<_>>())
+        }).collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+/// Hashes messages until two of them have hashes that start with the same bits, which takes about 2^(bits/2) hashes
+/// by the birthday paradox, much less than the 2^bits of finding a message with a given hash.
+pub fn collision(args: CollisionArgs){
+    let rounds = args.rounds.map_or(ROUNDS, usize::from);
+    if rounds < ROUNDS{
+        eprintln!("Warning: only {} of the {} rounds, this isn't sha256 and it is insecure.", rounds, ROUNDS);
+    }
+    let prefix = args.prefix.unwrap_or_else(||{
+        let mut bytes = [0; 4];
+        rand::fill_random(&mut bytes);
+        format!("{}-", hex::encode(&bytes))
+    });
+    let name = if rounds == ROUNDS {String::from("sha256")} else {format!("sha256 with {} rounds", rounds)};
+    println!("Searching two messages whose {} start with the same {} bits, the messages are \"{}\" followed by a counter.",
+        name, args.bits, prefix);
+
+    let start = Instant::now();
+    // the counter of the message that gave every truncated hash seen
+    let mut seen: HashMap<u64, u64> = HashMap::new();
+    let mut first = 0;
+    let (earlier, later) = 'search: loop{
+        for (i, hash) in (first..).zip(hash_batch(&prefix, first, rounds, args.threads)){
+            if let Some(earlier) = seen.insert(truncate(&hash, args.bits), i){
+                break 'search (earlier, i);
+            }
+        }
+        first += BATCH;
+    };
+    let elapsed = start.elapsed();
+    let attempts = later + 1;
+
+    let messages = [format!("{}{}", prefix, earlier), format!("{}{}", prefix, later)];
+    let hashes: Vec<[u8; 32]> = messages.iter().map(|message| hash(message, rounds)).collect();
+    println!();
+    println!("message 1: {}", messages[0]);
+    println!("message 2: {}", messages[1]);
+    println!("hash 1:    {}", Hash256::from(hashes[0]));
+    println!("hash 2:    {}", Hash256::from(hashes[1]));
+    println!("common:    {:0width$b}", truncate(&hashes[0], args.bits), width = args.bits as usize);
+    println!();
+
+    let bound = 2_f64.powf(args.bits as f64 / 2.0);
+    // the expected number of hashes until the first collision is sqrt(pi / 2 * 2^bits)
+    let expected = (std::f64::consts::PI / 2.0).sqrt() * bound;
+    println!("Found after {} hashes, in {:.2?}.", attempts, elapsed);
+    println!("The birthday bound is 2^{} = {:.0}, and {:.0} hashes are needed on average, this search took {:.2} times the bound.",
+        args.bits as f64 / 2.0, bound, expected, attempts as f64 / bound);
+    println!("Finding a message with a given hash would take about 2^{} = {:.0} hashes.", args.bits, 2_f64.powi(args.bits as i32));
+}
@@ -0,0 +1,108 @@
+use std::fs::{self, File};
+use std::io::Write;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use mysha::encoding::hex;
+use mysha::rand;
+use mysha::sha256::{Hash256, Sha256};
+
+use crate::failure::Failure;
+use crate::Exit;
+
+/// Bytes of the random nonce, enough that the value can't be found by trying every nonce.
+const NONCE_SIZE: usize = 32;
+
+#[derive(Args, Debug)]
+pub struct CommitArgs{
+    /// value committed to, like a bid or a guess
+    value: String,
+
+    /// file the nonce and the value are written to, to be revealed later
+    #[arg(short, long, default_value_t = String::from("commitment.toml"))]
+    output: String,
+}
+
+#[derive(Args, Debug)]
+pub struct RevealArgs{
+    /// commitment published by commit, in hex
+    commitment: String,
+
+    /// file written by commit, with the nonce and the value
+    #[arg(long, default_value_t = String::from("commitment.toml"))]
+    opening: String,
+
+    /// value revealed instead of the one of the file, to see that no other value opens the commitment
+    #[arg(long)]
+    value: Option<String>,
+
+    /// nonce revealed instead of the one of the file, in hex
+    #[arg(long)]
+    nonce: Option<String>,
+}
+
+/// What commit keeps secret until the reveal, with the commitment it published.
+#[derive(Serialize, Deserialize)]
+struct OpeningFile{
+    commitment: String,
+    nonce: String,
+    value: String,
+}
+
+fn commit_to(nonce: &[u8], value: &str) -> Hash256{
+    let mut hasher = Sha256::new();
+    hasher.update(nonce);
+    hasher.update(value.as_bytes());
+    hasher.finalize()
+}
+
+/// Commits to the value with SHA256(nonce || value), printing the commitment and keeping the opening in a file.
+pub fn commit(args: CommitArgs){
+    let mut nonce = [0; NONCE_SIZE];
+    rand::fill_random(&mut nonce);
+    let commitment = commit_to(&nonce, &args.value);
+
+    let opening = OpeningFile{
+        commitment: commitment.to_string(),
+        nonce: hex::encode(&nonce),
+        value: args.value,
+    };
+    let content = toml::to_string(&opening).exit("Error while parsing to toml.");
+    File::create(&args.output).and_then(|mut file| file.write_all(content.as_bytes())).exit("Error while writing the opening.");
+
+    println!("nonce:      {}", opening.nonce);
+    println!("commitment: SHA256(nonce || {:?})", opening.value);
+    println!("            {}", commitment);
+    println!();
+    println!("Publish the commitment: it hides the value, which can't be found by hashing guesses without the random nonce.");
+    println!("The nonce and the value are in {}, keep them secret until the reveal.", args.output);
+}
+
+/// Checks that the revealed nonce and value hash to the published commitment.
+pub fn reveal(args: RevealArgs){
+    let commitment = Hash256::from_hex(&args.commitment.to_lowercase(), false).exit("Invalid commitment.");
+    // the file is only read for what isn't given
+    let opening = match (args.value, args.nonce){
+        (Some(value), Some(nonce)) => OpeningFile{commitment: args.commitment, nonce, value},
+        (value, nonce) => {
+            let content = fs::read_to_string(&args.opening).exit("Error while reading the opening.");
+            let opening = toml::from_str::<OpeningFile>(&content).exit("Error while parsing the opening.");
+            OpeningFile{value: value.unwrap_or(opening.value), nonce: nonce.unwrap_or(opening.nonce), ..opening}
+        },
+    };
+    let (value, nonce) = (opening.value, hex::decode(&opening.nonce).exit("Invalid hex nonce."));
+    let opened = commit_to(&nonce, &value);
+    println!("nonce:      {}", hex::encode(&nonce));
+    println!("value:      {:?}", value);
+    println!("SHA256(nonce || value):");
+    println!("            {}", opened);
+    println!("commitment: {}", commitment);
+    println!();
+    if opened == commitment{
+        println!("The reveal IS valid: this value was committed to, and no other value opens the commitment.");
+    }else{
+        println!("The reveal is NOT valid: finding another nonce and value with the same hash would break sha256.");
+        Failure::Verification.exit();
+    }
+}
@@ -0,0 +1,139 @@
+use clap::Args;
+use num_bigint::BigUint;
+
+use mysha::ecc::{cryptanalysis::*, Curve, KeyPair, Point, PubKey};
+use mysha::rand;
+use mysha::sha256::Sha256;
+
+use crate::ecc_cli::read_private_key;
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct InvalidCurveArgs{
+    /// ecc toml file with the private key of the victim, on a curve whose p has at most 20 bits, a random key of a random curve by default
+    #[arg(short, long)]
+    key: Option<String>,
+
+    /// bits of p of the random curve, from 4 to 20
+    #[arg(short, long, default_value_t = 16, conflicts_with = "key")]
+    bits: u32,
+
+    /// largest order of the points sent to the victim, larger orders need fewer points but more guesses each
+    #[arg(long, default_value_t = 100)]
+    max_order: u64,
+}
+
+/// A point as (x, y).
+fn show(point: &Point) -> String{
+    match point.get_xy(){
+        Some((x, y)) => format!("({}, {})", x, y),
+        None => String::from("infinity"),
+    }
+}
+
+/// The key ECDH gives with the point: sha256 of the x of the private key times the point, on the curve the point is on.
+///
+/// The formulas of addition and doubling don't use b, so this is what the victim computes with the formulas of its own curve
+/// when it doesn't check that the point is on it.
+fn ecdh_key(curve: &Curve, found: &InvalidCurvePoint, scalar: &BigUint) -> [u8; 32]{
+    let invalid = Curve::new(curve.get_a(), found.b, curve.get_p().clone(), BigUint::from(found.order), found.point.clone())
+        .exit("Invalid point.");
+    let shared = invalid.multiply(&found.point, scalar.clone()).exit("Error while multiplying.");
+    Sha256::digest(&shared.get_x().map(|x| x.to_bytes_be()).unwrap_or_default())
+}
+
+/// The number modulo the product of the moduli that has every remainder, by the Chinese remainder theorem, for prime moduli.
+fn crt(remainders: &[(u64, u64)]) -> BigUint{
+    let product = remainders.iter().fold(BigUint::from(1_u8), |product, (_, modulo)| product * modulo);
+    let mut result = BigUint::from(0_u8);
+    for (remainder, modulo) in remainders{
+        let others = &product / modulo;
+        // the modulo is prime, so the inverse is a power of modulo - 2 by Fermat's little theorem
+        let inverse = (&others % modulo).modpow(&BigUint::from(modulo - 2), &BigUint::from(*modulo));
+        result += others * inverse * remainder;
+    }
+    result % product
+}
+
+/// Recovers the private key of a victim that doesn't check the points of ECDH, with points of small order on invalid curves.
+pub fn invalid_curve(args: InvalidCurveArgs){
+    let private = match args.key{
+        Some(path) => read_private_key(&path),
+        None => {
+            let curve = Curve::random_small(args.bits).exit("Can't generate the curve.");
+            KeyPair::new(rand::random_scalar(&curve), curve).exit("Error while generating the key.").private()
+        },
+    };
+    let curve = private.get_curve().clone();
+    let public = KeyPair::from_private(&private).exit("Invalid private key.").public();
+    let mut points = invalid_curve_points(&curve, args.max_order).exit("The demo needs a curve whose p has at most 20 bits.");
+    points.sort_by_key(|found| found.order);
+    let product = points.iter().fold(BigUint::from(1_u8), |product, found| product * found.order);
+    if product <= *curve.get_n(){
+        None.exit(&format!("The points of order up to {} aren't enough to cover n, try a larger --max-order.", args.max_order))
+    }
+
+    println!("victim");
+    println!("   curve:      y² = x³ + {}x + {} mod {}, n = {}", curve.get_a(), curve.get_b(), curve.get_p(), curve.get_n());
+    println!("   private d:  {}, secret", private.get_private());
+    println!("   public:     {}", show(public.get_public()));
+    println!();
+
+    println!("1. the attacker finds points of small prime order on the curves y² = x³ + {}x + b' mod {} with other b',", curve.get_a(), curve.get_p());
+    println!("   the formulas of addition and doubling only use a and p, so they work on these curves too");
+    for found in &points{
+        println!("   b' = {:<8} point {} of order {}", format!("{}:", found.b), show(&found.point), found.order);
+    }
+    println!("   the product of the orders, {}, is larger than n", product);
+    println!();
+
+    println!("2. the victim does ECDH with every point without checking it, and uses sha256 of the x of dQ as the key,");
+    println!("   the attacker tries the multiples of the point until one gives the same key, at most its order");
+    let mut guesses = 0;
+    let mut remainders = Vec::new();
+    for found in &points{
+        let key = ecdh_key(&curve, found, private.get_private());
+        let remainder = (0..found.order).find(|k| ecdh_key(&curve, found, &BigUint::from(*k)) == key).unwrap();
+        guesses += remainder + 1;
+        // 0 and the remainders mod 2 are their own negative
+        let sign = if remainder == 0 || found.order == 2 {""} else {"±"};
+        println!("   order {:<6} d = {}{} mod {}, in {} guess{}", format!("{}:", found.order), sign, remainder, found.order,
+            remainder + 1, if remainder == 0 {""} else {"es"});
+        remainders.push((remainder, found.order));
+    }
+    println!();
+
+    // x is the same for dQ and -dQ, so every remainder can be either sign
+    let ambiguous: Vec<usize> = (0..remainders.len()).filter(|i| remainders[*i].0 != 0 && remainders[*i].1 != 2).collect();
+    println!("3. the x of dQ is the same for -dQ, so {} of the remainders have two signs, and the Chinese remainder theorem", ambiguous.len());
+    println!("   joins them in {} candidates modulo {}, checked against the public key", 1_u64 << ambiguous.len(), product);
+    let mut recovered = None;
+    for signs in 0..1_u64 << ambiguous.len(){
+        let mut signed = remainders.clone();
+        for (bit, i) in ambiguous.iter().enumerate(){
+            if signs >> bit & 1 == 1{
+                signed[*i].0 = signed[*i].1 - signed[*i].0;
+            }
+        }
+        guesses += 1;
+        let candidate = crt(&signed);
+        if candidate < *curve.get_n() && curve.multiply_generator(candidate.clone()).exit("Error while multiplying.") == *public.get_public(){
+            recovered = Some(candidate);
+            break;
+        }
+    }
+    let recovered = recovered.exit_as(Failure::Internal, "   the private key was NOT recovered");
+    if recovered != *private.get_private(){
+        None.exit_as(Failure::Internal, "   the recovered key is NOT the private key")
+    }
+    println!("   d:          {}, the private key, in {} guesses, instead of about n, {}", recovered, guesses, curve.get_n());
+    println!();
+
+    println!("4. checking that the points are on the curve, as PubKey::new and every operation of ecc::Curve do, stops the attack");
+    let rejected = points.iter().filter(|found| PubKey::new(found.point.clone(), curve.clone()).is_err()).count();
+    println!("   {} of the {} points rejected: {}", rejected, points.len(), PubKey::new(points[0].point.clone(), curve.clone()).unwrap_err());
+    println!();
+    println!("Real curves are too big to count the points of other curves one x at a time, but their orders are computed once");
+    println!("with Schoof's algorithm, and a victim that doesn't check its points loses its key the same way.");
+}
@@ -0,0 +1,42 @@
+use clap::Args;
+
+use mysha::ecc::KeyPair;
+use mysha::encoding::hex;
+use mysha::vrf;
+
+use crate::ecc_cli::read_private_key;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct LotteryArgs{
+    /// ecc toml files with the private keys of the participants, on secp256k1
+    #[arg(required = true)]
+    keys: Vec<String>,
+
+    /// text of the round, every round has a new draw
+    #[arg(short, long, default_value_t = String::from("round 1"))]
+    round: String,
+}
+
+/// Every participant proves the VRF output of the round with their key, everyone else verifies it with their public key,
+/// and the lowest output wins, so no one can choose their number, or claim one they didn't get.
+pub fn lottery(args: LotteryArgs){
+    println!("round: {:?}", args.round);
+    let mut draws = Vec::new();
+    for path in &args.keys{
+        let private = read_private_key(path);
+        let public = KeyPair::from_private(&private).exit("Invalid private key.").public();
+
+        let proof = vrf::prove(&private, args.round.as_bytes()).exit(&format!("Can't prove the draw of {}.", path));
+        let output = vrf::verify(&public, args.round.as_bytes(), &proof).exit(&format!("The proof of {} isn't valid.", path));
+        println!();
+        println!("{}", path);
+        println!("  proof:  {}", hex::encode(&proof.to_bytes()));
+        println!("  output: {} (verified with the public key)", output);
+        draws.push((output, path));
+    }
+
+    let (output, winner) = draws.iter().min_by_key(|(output, _)| output.get_bytes()).unwrap();
+    println!();
+    println!("winner: {}, with the lowest output {}", winner, output);
+}
@@ -0,0 +1,209 @@
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+
+use mysha::encoding::hex;
+use mysha::sha256::Sha256;
+
+use crate::encode_cli::get_text;
+use crate::sha256_cli::animation::{self, printf, leave};
+use crate::sha256_cli::terminal::{Clear, Color, MoveTo, Style, SetStyle, ResetStyle};
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct MineArgs{
+    /// message to be mined, read from stdin if not provided
+    message: Option<String>,
+
+    /// number of leading zero bits the hash must have
+    #[arg(short, long, default_value_t = 20)]
+    difficulty: u32,
+
+    /// number of threads trying nonces
+    #[arg(short = 'j', long, default_value_t = 1)]
+    threads: u64,
+
+    /// shows the nonces being tried, with their hashes compared to the target, in one thread
+    #[arg(short, long, conflicts_with = "threads")]
+    animation: bool,
+}
+
+/// Time between frames of the animation.
+const FRAME: Duration = Duration::from_millis(50);
+
+/// Result of a miner, the nonce found, if any, and the attempts made.
+struct Mined{
+    nonce: Option<u64>,
+    attempts: u64,
+}
+
+/// Number of zero bits at the start of the hash.
+pub(crate) fn leading_zero_bits(hash: &[u8]) -> u32{
+    let mut bits = 0;
+    for byte in hash{
+        bits += byte.leading_zeros();
+        if *byte != 0{
+            break;
+        }
+    }
+    bits
+}
+
+/// Double sha256 of the message followed by the nonce in decimal, continuing from the hasher that already has the message.
+pub(crate) fn double_hash(message: &Sha256, nonce: u64) -> [u8; 32]{
+    let mut hasher = message.clone();
+    hasher.update(nonce.to_string().as_bytes());
+    Sha256::digest(&hasher.finalize_bytes())
+}
+
+/// Tries the nonces from the first, stepping by the step, until one gives a hash with the difficulty, or another miner finds one.
+fn mine_nonces(message: &Sha256, difficulty: u32, first: u64, step: u64, found: &AtomicBool) -> Mined{
+    let mut attempts = 0;
+    let mut nonce = first;
+    while !found.load(Ordering::Relaxed){
+        attempts += 1;
+        if leading_zero_bits(&double_hash(message, nonce)) >= difficulty{
+            found.store(true, Ordering::Relaxed);
+            return Mined{nonce: Some(nonce), attempts};
+        }
+        nonce += step;
+    }
+    Mined{nonce: None, attempts}
+}
+
+/// The first bits of the hash, the ones the difficulty is about colored green if they are zero and red otherwise.
+fn colored_bits(hash: &[u8], difficulty: u32, shown: usize) -> String{
+    let bits: String = hash.iter().map(|byte| format!("{:08b}", byte)).collect();
+    let mut colored = String::new();
+    for (i, bit) in bits[..shown].chars().enumerate(){
+        if i < difficulty as usize{
+            let color = if bit == '0' {Color::Green} else {Color::Red};
+            colored += &format!("{}{}{}", SetStyle(Style::color(color)), bit, ResetStyle);
+        }else{
+            colored.push(bit);
+        }
+    }
+    colored
+}
+
+/// Draws a frame of the animation, with the last nonce tried and its hash.
+fn draw_frame(message: &str, difficulty: u32, nonce: u64, hash: &[u8], attempts: u64, best: (u32, u64), elapsed: Duration){
+    let shown = (difficulty as usize + 16).min(256);
+    let zeros = leading_zero_bits(hash);
+    let mut frame = format!("{}{}", Clear::All, MoveTo(0, 0));
+    frame += &format!("mining \"{}\" with {} leading zero bits\n\n", message, difficulty);
+    frame += &format!("nonce:     {}\n", nonce);
+    frame += &format!("input:     {}{}\n", message, nonce);
+    frame += &format!("hash:      {}\n\n", hex::encode(hash));
+    frame += &format!("bits:      {}...\n", colored_bits(hash, difficulty, shown));
+    frame += &format!("target:    {}{}...\n", "0".repeat(difficulty as usize), "-".repeat(shown - difficulty as usize));
+    if zeros >= difficulty{
+        frame += &format!("           {}{} zero bits, the hash is below the target{}\n\n", SetStyle(Style::color(Color::Green)), zeros, ResetStyle);
+    }else{
+        frame += &format!("           {} zero bits, the hash is above the target\n\n", zeros);
+    }
+    frame += &format!("best:      {} zero bits, nonce {}\n", best.0, best.1);
+    frame += &format!("attempts:  {}\n", attempts);
+    frame += &format!("hashrate:  {}\n", format_rate(attempts as f64 / elapsed.as_secs_f64().max(1e-9)));
+    printf(frame);
+}
+
+/// Tries the nonces in order, drawing the last one tried every frame, until one gives a hash with the difficulty.
+///
+/// The animation screen is left by the caller, after the last frame was shown for a while.
+fn mine_animated(message: &str, hasher: &Sha256, difficulty: u32) -> Mined{
+    ctrlc::set_handler(||{
+        leave();
+        std::process::exit(0);
+    }).expect("Error initializing program");
+    animation::enter(io::stdin().is_terminal(), None);
+
+    let start = Instant::now();
+    let mut best = (0, 0);
+    let mut nonce = 0;
+    loop{
+        let frame = Instant::now();
+        let mut hash;
+        loop{
+            hash = double_hash(hasher, nonce);
+            let zeros = leading_zero_bits(&hash);
+            if zeros > best.0 || nonce == 0{
+                best = (zeros, nonce);
+            }
+            if zeros >= difficulty{
+                break;
+            }
+            // checking the time on every nonce would slow down the mining
+            if nonce % 256 == 255 && frame.elapsed() >= FRAME{
+                break;
+            }
+            nonce += 1;
+        }
+        draw_frame(message, difficulty, nonce, &hash, nonce + 1, best, start.elapsed());
+        if leading_zero_bits(&hash) >= difficulty{
+            break;
+        }
+        nonce += 1;
+    }
+    Mined{nonce: Some(nonce), attempts: nonce + 1}
+}
+
+/// Rate formatted with a metric prefix.
+pub(crate) fn format_rate(rate: f64) -> String{
+    let prefixes = ["", "k", "M", "G", "T"];
+    let mut rate = rate;
+    let mut prefix = 0;
+    while rate >= 1000.0 && prefix < prefixes.len() - 1{
+        rate /= 1000.0;
+        prefix += 1;
+    }
+    format!("{:.2} {}H/s", rate, prefixes[prefix])
+}
+
+pub fn mine(args: MineArgs){
+    let message = get_text(args.message);
+    if args.difficulty > 256{
+        Err::<(), &str>("a hash has 256 bits.").exit("Invalid difficulty.");
+    }
+    let threads = args.threads.max(1);
+    println!("mining \"{}\" with {} leading zero bits, {} attempts expected on average, with {} thread{}",
+        message, args.difficulty, 2_f64.powi(args.difficulty as i32), threads, if threads == 1 {""} else {"s"});
+
+    // the blocks of the message are the same for every nonce, so they are hashed only once
+    let mut hasher = Sha256::new();
+    hasher.update(message.as_bytes());
+
+    let found = AtomicBool::new(false);
+    let start = Instant::now();
+    let results: Vec<Mined> = if args.animation{
+        vec![mine_animated(&message, &hasher, args.difficulty)]
+    }else{
+        thread::scope(|scope|{
+            let miners: Vec<_> = (0..threads).map(|first|{
+                let (hasher, found) = (&hasher, &found);
+                scope.spawn(move || mine_nonces(hasher, args.difficulty, first, threads, found))
+            }).collect();
+            miners.into_iter().map(|miner| miner.join().unwrap()).collect()
+        })
+    };
+    let elapsed = start.elapsed().max(Duration::from_nanos(1));
+    if args.animation{
+        // the last frame stays for a while, so the hash found can be seen below the target
+        thread::sleep(Duration::from_secs(2));
+        leave();
+    }
+
+    let nonce = results.iter().find_map(|mined| mined.nonce).unwrap();
+    let attempts: u64 = results.iter().map(|mined| mined.attempts).sum();
+    let hash = double_hash(&hasher, nonce);
+    println!("\nnonce:     {}", nonce);
+    println!("mined:     {}{}", message, nonce);
+    println!("hash:      {}", hex::encode(&hash));
+    println!("zero bits: {}", leading_zero_bits(&hash));
+    println!("attempts:  {}", attempts);
+    println!("time:      {:.3} s", elapsed.as_secs_f64());
+    println!("hashrate:  {}", format_rate(attempts as f64 / elapsed.as_secs_f64()));
+}
@@ -0,0 +1,97 @@
+use clap::{Args, Subcommand};
+
+mod avalanche;
+use avalanche::*;
+mod birthday;
+use birthday::*;
+mod blind_sign;
+use blind_sign::*;
+mod block_header;
+use block_header::*;
+mod collision;
+use collision::*;
+mod commitment;
+use commitment::*;
+mod invalid_curve;
+use invalid_curve::*;
+mod lottery;
+use lottery::*;
+mod mine;
+use mine::*;
+pub(crate) use mine::format_rate;
+mod nonce_reuse;
+use nonce_reuse::*;
+mod preimage;
+use preimage::*;
+pub(crate) use preimage::format_duration;
+mod rainbow;
+use rainbow::*;
+mod sign_tx;
+use sign_tx::*;
+mod stats;
+use stats::*;
+mod timing;
+use timing::*;
+
+#[derive(Args, Debug)]
+pub struct DemoArgs{
+    #[command(subcommand)]
+    subcommand: SubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommand{
+    /// Hashes a bitcoin block header, showing its serialization and why block hashes are shown reversed
+    BlockHeader(BlockHeaderArgs),
+    /// Finds a nonce that makes the double sha256 of a message start with zero bits, like bitcoin's proof of work
+    Mine(MineArgs),
+    /// Draws a winner with a verifiable random function, whose output every participant can check but no one can choose
+    Lottery(LotteryArgs),
+    /// Walks through a blind Schnorr signature, where the signer signs a message without seeing it
+    BlindSign(BlindSignArgs),
+    /// Commits to a value with the sha256 of a random nonce and the value, without revealing it
+    Commit(CommitArgs),
+    /// Reveals a committed value, checking that it opens the commitment
+    Reveal(RevealArgs),
+    /// Hashes two messages that differ in a few bits, and shows how many bits of the hashes differ
+    Avalanche(AvalancheArgs),
+    /// Searches two messages whose hashes start with the same bits, in about 2^(bits/2) hashes by the birthday paradox
+    Collision(CollisionArgs),
+    /// Hashes many messages and shows how uniform the bits of the hashes are, with chi-square statistics and histograms
+    Stats(StatsArgs),
+    /// Measures the hashes needed to find a collision of truncated hashes many times, and plots them against the birthday paradox
+    Birthday(BirthdayArgs),
+    /// Tries every message of a mask, like pass?d?d, until one hashes to the target, showing what a preimage search costs
+    Preimage(PreimageArgs),
+    /// Builds a rainbow table of the passwords of a mask and finds passwords with it, trading time for memory
+    Rainbow(RainbowArgs),
+    /// Builds and signs a bitcoin transaction spending a P2PKH or P2WPKH output, showing the sighash, signature and raw transaction
+    SignTx(SignTxArgs),
+    /// Signs two messages with the same nonce and recovers the private key from the signatures, showing the algebra
+    NonceReuse(NonceReuseArgs),
+    /// Recovers a private key from ECDH with points of small order on other curves, when the points aren't checked
+    InvalidCurve(InvalidCurveArgs),
+    /// Times the multiplication of the generator by scalars with more and more ones, with double and add and with the Montgomery ladder
+    Timing(TimingArgs),
+}
+
+pub fn demo(args: DemoArgs){
+    match args.subcommand{
+        SubCommand::BlockHeader(args) => block_header(args),
+        SubCommand::Mine(args) => mine(args),
+        SubCommand::Lottery(args) => lottery(args),
+        SubCommand::BlindSign(args) => blind_sign(args),
+        SubCommand::Commit(args) => commit(args),
+        SubCommand::Reveal(args) => reveal(args),
+        SubCommand::Avalanche(args) => avalanche(args),
+        SubCommand::Collision(args) => collision(args),
+        SubCommand::Stats(args) => stats(args),
+        SubCommand::Birthday(args) => birthday(args),
+        SubCommand::Preimage(args) => preimage(args),
+        SubCommand::Rainbow(args) => rainbow(args),
+        SubCommand::SignTx(args) => sign_tx(args),
+        SubCommand::NonceReuse(args) => nonce_reuse(args),
+        SubCommand::InvalidCurve(args) => invalid_curve(args),
+        SubCommand::Timing(args) => timing(args),
+    }
+}
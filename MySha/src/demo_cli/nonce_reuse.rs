@@ -0,0 +1,157 @@
+use clap::Args;
+use num_bigint::BigUint;
+
+use mysha::ecc::{cryptanalysis::*, Curve, KeyPair, PrivKey};
+use mysha::rand;
+use mysha::sha256::Sha256;
+
+use crate::ecc_cli::read_private_key;
+use crate::sha256_cli::animation::{printf, wait};
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct NonceReuseArgs{
+    /// first message signed
+    #[arg(default_value = "first message")]
+    first: String,
+
+    /// second message signed, with the same nonce
+    #[arg(default_value = "second message")]
+    second: String,
+
+    /// ecc toml file with the private key of the signer, a random key of secp256k1 by default
+    #[arg(short, long)]
+    key: Option<String>,
+
+    /// nonce used for both signatures, in decimal, a random one by default
+    #[arg(long)]
+    nonce: Option<String>,
+
+    /// shows the steps one at a time, and types out the numbers the attacker computes
+    #[arg(short, long)]
+    animation: bool,
+}
+
+/// Time between the digits typed out in the animation, in milliseconds.
+const DIGIT: u64 = 15;
+/// Time between the steps of the animation, in milliseconds.
+const STEP: u64 = 1500;
+
+/// Prints a line, and a pause after it with the animation.
+fn line(animated: bool, text: &str){
+    printf(format!("{}\n", text));
+    if animated{
+        wait(false, STEP / 3);
+    }
+}
+
+/// Prints a number computed by the attacker, typing it out digit by digit with the animation.
+fn computed(animated: bool, label: &str, value: &BigUint){
+    printf(label);
+    let digits = format!("{:x}", value);
+    if animated{
+        for digit in digits.chars(){
+            printf(digit);
+            wait(false, DIGIT);
+        }
+    }else{
+        printf(digits);
+    }
+    printf("\n");
+}
+
+/// Ends a step, with a longer pause with the animation.
+fn end_step(animated: bool){
+    printf("\n");
+    if animated{
+        wait(false, STEP);
+    }
+}
+
+/// The message read as a number, like the signatures read its sha256.
+fn hash_of(message: &str) -> BigUint{
+    BigUint::from_bytes_be(&Sha256::digest(message.as_bytes()))
+}
+
+/// Signs two messages with the same nonce, and recovers the private key from the signatures, showing every step of the algebra.
+pub fn nonce_reuse(args: NonceReuseArgs){
+    if args.first == args.second{
+        None.exit("The messages must be different, the same message signed twice with the same nonce gives the same signature.")
+    }
+    let private = match args.key{
+        Some(path) => read_private_key(&path),
+        None => KeyPair::new(rand::random_scalar(&Curve::secp256k1()), Curve::secp256k1()).exit("Error while generating the key.").private(),
+    };
+    let curve = private.get_curve().clone();
+    let n = curve.get_n().clone();
+    let nonce = match args.nonce{
+        Some(nonce) => nonce.parse::<BigUint>().ok().exit("Invalid nonce."),
+        None => rand::random_scalar(&curve),
+    };
+    let public = KeyPair::from_private(&private).exit("Invalid private key.").public();
+    let first = sign_with_nonce(&private, args.first.as_bytes(), &nonce).exit("Invalid nonce, it must be from 1 to n - 1.");
+    let second = sign_with_nonce(&private, args.second.as_bytes(), &nonce).exit("Invalid nonce, it must be from 1 to n - 1.");
+    let animated = args.animation;
+
+    line(animated, "signer");
+    line(animated, &format!("   private d:  {:x}, secret", private.get_private()));
+    line(animated, &format!("   public, x:  {:x}", public.get_public().get_x().exit("Invalid public key.")));
+    line(animated, &format!("   order n:    {:x}", n));
+    end_step(animated);
+
+    line(animated, "1. the signer signs two messages with the same nonce k, s = k⁻¹ (z + r d) mod n, where R = kG and r is its x");
+    line(animated, &format!("   k:          {:x}, should have been random and new for every signature", nonce));
+    line(animated, &format!("   z1:         {:x}, sha256 of {:?}", hash_of(&args.first), args.first));
+    line(animated, &format!("   z2:         {:x}, sha256 of {:?}", hash_of(&args.second), args.second));
+    line(animated, &format!("   r1:         {:x}", first.get_r()));
+    line(animated, &format!("   s1:         {:x}", first.get_s()));
+    line(animated, &format!("   r2:         {:x}, the same as r1", second.get_r()));
+    line(animated, &format!("   s2:         {:x}", second.get_s()));
+    end_step(animated);
+
+    // the attacker only uses the public signatures, messages and public key from here
+    let (r, s1, s2) = (first.get_r(), first.get_s(), second.get_s());
+    let (z1, z2) = (hash_of(&args.first) % &n, hash_of(&args.second) % &n);
+    // n is prime, so the inverse of a is a^(n - 2) by Fermat's little theorem
+    let inverse = |a: &BigUint| a.modpow(&(&n - 2_u8), &n);
+
+    line(animated, "2. the attacker sees two signatures with the same r, so the same R and the same nonce,");
+    line(animated, "   two equations with only two unknowns, k and d:");
+    line(animated, "   s1 = k⁻¹ (z1 + r d) mod n");
+    line(animated, "   s2 = k⁻¹ (z2 + r d) mod n");
+    end_step(animated);
+
+    line(animated, "3. subtracting them, r d cancels out: s1 - s2 = k⁻¹ (z1 - z2), so k = (z1 - z2) (s1 - s2)⁻¹ mod n");
+    let hash_difference = (&z1 + &n - &z2) % &n;
+    let signature_difference = (s1 + &n - s2) % &n;
+    computed(animated, "   z1 - z2:          ", &hash_difference);
+    computed(animated, "   s1 - s2:          ", &signature_difference);
+    computed(animated, "   (s1 - s2)⁻¹:      ", &inverse(&signature_difference));
+    let recovered_nonce = hash_difference * inverse(&signature_difference) % &n;
+    computed(animated, "   k:                ", &recovered_nonce);
+    end_step(animated);
+
+    line(animated, "4. with k, the first equation gives d: s1 k = z1 + r d, so d = (s1 k - z1) r⁻¹ mod n");
+    let numerator = (s1 * &recovered_nonce % &n + &n - &z1) % &n;
+    computed(animated, "   s1 k - z1:        ", &numerator);
+    computed(animated, "   r⁻¹:              ", &inverse(r));
+    let recovered = numerator * inverse(r) % &n;
+    computed(animated, "   d:                ", &recovered);
+    end_step(animated);
+
+    line(animated, "5. the attacker checks d against the public key, and ecc::cryptanalysis::recover_from_reused_nonce does the same");
+    let from_library = recover_from_reused_nonce(&first, &second, args.first.as_bytes(), args.second.as_bytes())
+        .exit("Error while recovering the key.");
+    let recovered = PrivKey::new(recovered, curve).exit("The recovered key is invalid.");
+    if recovered != private || from_library != private{
+        None.exit_as(Failure::Internal, "   the recovered key is NOT the private key")
+    }
+    line(animated, &format!("   dG, x:      {:x}, the public key of the signer", KeyPair::from_private(&recovered)
+        .exit("Invalid private key.").public().get_public().get_x().exit("Invalid public key.")));
+    line(animated, &format!("   recovered:  {:x}, the private key, from two public signatures", recovered.get_private()));
+    end_step(animated);
+
+    println!("Anyone with the key can now sign anything. Signers must draw a new secret nonce for every signature,");
+    println!("or derive it from the key and the message, like RFC 6979, so different messages never share it.");
+}
@@ -0,0 +1,141 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+
+use mysha::sha256::{Hash256, Sha256};
+
+use super::mine::format_rate;
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct PreimageArgs{
+    /// sha256 whose message is searched, in hex
+    #[arg(short, long)]
+    target: String,
+
+    /// Characters of the message, every ?l a lowercase letter, ?u an uppercase letter, ?d a digit, ?s a symbol,
+    /// ?h a lowercase hex digit, ?H an uppercase one, ?a any printable character, ?? a question mark, and any other character itself
+    #[arg(short, long)]
+    mask: String,
+
+    /// number of threads trying candidates
+    #[arg(short = 'j', long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    threads: u64,
+}
+
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = " !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// Result of a thread, the candidate found, if any, and the attempts made.
+struct Searched{
+    found: Option<u64>,
+    attempts: u64,
+}
+
+/// The characters every position of the mask can have.
+pub(crate) fn parse_mask(mask: &str) -> Result<Vec<Vec<char>>, String>{
+    let mut positions = Vec::new();
+    let mut chars = mask.chars();
+    while let Some(c) = chars.next(){
+        if c != '?'{
+            positions.push(vec![c]);
+            continue;
+        }
+        let charset = match chars.next(){
+            Some('l') => LOWER.to_owned(),
+            Some('u') => UPPER.to_owned(),
+            Some('d') => DIGITS.to_owned(),
+            Some('s') => SYMBOLS.to_owned(),
+            Some('h') => String::from("0123456789abcdef"),
+            Some('H') => String::from("0123456789ABCDEF"),
+            Some('a') => [LOWER, UPPER, DIGITS, SYMBOLS].concat(),
+            Some('?') => String::from("?"),
+            Some(other) => return Err(format!("unknown charset ?{}.", other)),
+            None => return Err(String::from("the mask ends with a ?, write ?? for a question mark.")),
+        };
+        positions.push(charset.chars().collect());
+    }
+    Ok(positions)
+}
+
+/// The candidate of the index, the last position changing the fastest, like counting.
+pub(crate) fn candidate(positions: &[Vec<char>], mut index: u64) -> String{
+    let mut chars = Vec::with_capacity(positions.len());
+    for charset in positions.iter().rev(){
+        let size = charset.len() as u64;
+        chars.push(charset[(index % size) as usize]);
+        index /= size;
+    }
+    chars.iter().rev().collect()
+}
+
+/// Tries the candidates from the first, stepping by the step, until one hashes to the target, or another thread finds one.
+fn search(positions: &[Vec<char>], target: &[u8; 32], keyspace: u64, first: u64, step: u64, found: &AtomicBool) -> Searched{
+    let mut attempts = 0;
+    let mut index = first;
+    while index < keyspace && !found.load(Ordering::Relaxed){
+        attempts += 1;
+        if Sha256::digest(candidate(positions, index).as_bytes()) == *target{
+            found.store(true, Ordering::Relaxed);
+            return Searched{found: Some(index), attempts};
+        }
+        index += step;
+    }
+    Searched{found: None, attempts}
+}
+
+/// A duration of the seconds in the largest unit that fits.
+pub(crate) fn format_duration(seconds: f64) -> String{
+    let units = [("years", 365.25 * 24.0 * 3600.0), ("days", 24.0 * 3600.0), ("hours", 3600.0), ("minutes", 60.0)];
+    match units.iter().find(|(_, size)| seconds >= *size){
+        Some((unit, size)) if seconds / size >= 1e6 => format!("{:.1e} {}", seconds / size, unit),
+        Some((unit, size)) => format!("{:.1} {}", seconds / size, unit),
+        None => format!("{:.1} seconds", seconds),
+    }
+}
+
+/// Tries every message of the mask until one hashes to the target, showing how the cost grows with the characters searched:
+/// every position multiplies it by the size of its charset, so only short or guessable messages can be found.
+pub fn preimage(args: PreimageArgs){
+    let target = Hash256::from_hex(&args.target.to_lowercase(), false).exit("Invalid target.").get_bytes();
+    let positions = parse_mask(&args.mask).exit("Invalid mask.");
+    let keyspace = positions.iter().try_fold(1_u64, |product, charset| product.checked_mul(charset.len() as u64))
+        .exit("The mask has more than 2^64 candidates, far too many to try.");
+    println!("searching the message of {} among the {} candidates of \"{}\", with {} thread{}",
+        Hash256::from(target), keyspace, args.mask, args.threads, if args.threads == 1 {""} else {"s"});
+
+    let found = AtomicBool::new(false);
+    let start = Instant::now();
+    let results: Vec<Searched> = thread::scope(|scope|{
+        let searchers: Vec<_> = (0..args.threads).map(|first|{
+            let (positions, target, found) = (&positions, &target, &found);
+            scope.spawn(move || search(positions, target, keyspace, first, args.threads, found))
+        }).collect();
+        searchers.into_iter().map(|searcher| searcher.join().unwrap()).collect()
+    });
+    let elapsed = start.elapsed().max(Duration::from_nanos(1));
+    let attempts: u64 = results.iter().map(|searched| searched.attempts).sum();
+    let rate = attempts as f64 / elapsed.as_secs_f64();
+
+    println!();
+    match results.iter().find_map(|searched| searched.found){
+        Some(index) => println!("found:     {}", candidate(&positions, index)),
+        None => println!("not found, no candidate of the mask hashes to the target"),
+    }
+    println!("attempts:  {} of {}, {:.1}%", attempts, keyspace, 100.0 * attempts as f64 / keyspace as f64);
+    println!("time:      {:.3} s", elapsed.as_secs_f64());
+    println!("hashrate:  {}", format_rate(rate));
+    println!();
+    println!("At this rate trying every candidate of the mask takes {}, and {} with one more ?a character.",
+        format_duration(keyspace as f64 / rate), format_duration(keyspace as f64 * 95.0 / rate));
+    println!("A random 32 byte message, 2^256 candidates, would take {}.", format_duration(2_f64.powi(256) / rate));
+
+    if results.iter().all(|searched| searched.found.is_none()){
+        Failure::Verification.exit();
+    }
+}
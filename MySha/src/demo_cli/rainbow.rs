@@ -0,0 +1,176 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::time::Instant;
+
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use mysha::rand;
+use mysha::sha256::{Hash256, Sha256};
+
+use super::preimage::{candidate, parse_mask};
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct RainbowArgs{
+    #[command(subcommand)]
+    subcommand: RainbowCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum RainbowCommand{
+    /// Builds a table of chains over the passwords of a mask
+    Build(BuildArgs),
+    /// Finds the password of a hash with a table
+    Lookup(LookupArgs),
+}
+
+#[derive(Args, Debug)]
+struct BuildArgs{
+    /// passwords of the table, with the charsets of demo preimage
+    #[arg(short, long, default_value_t = String::from("?l?l?l?l"))]
+    mask: String,
+
+    /// number of chains, the rows kept in the table
+    #[arg(short, long, default_value_t = 4000, value_parser = clap::value_parser!(u64).range(1..))]
+    chains: u64,
+
+    /// number of passwords of every chain, only the first and the last are kept
+    #[arg(short, long, default_value_t = 150, value_parser = clap::value_parser!(u64).range(1..))]
+    length: u64,
+
+    /// file the table is written to
+    #[arg(short, long, default_value_t = String::from("rainbow.toml"))]
+    output: String,
+}
+
+#[derive(Args, Debug)]
+struct LookupArgs{
+    /// sha256 of the password, in hex
+    hash: String,
+
+    /// file of the table, written by build
+    #[arg(short, long, default_value_t = String::from("rainbow.toml"))]
+    table: String,
+}
+
+/// The first and the last password of every chain, as their indexes among the passwords of the mask.
+#[derive(Serialize, Deserialize)]
+struct Table{
+    mask: String,
+    length: u64,
+    chains: Vec<(u64, u64)>,
+}
+
+/// Reduction function of the column, from a hash back to the index of a password.
+///
+/// Every column has its own, so two chains that reach the same password in different columns don't merge from there on,
+/// which is what makes the table a rainbow table.
+fn reduce(hash: &[u8; 32], column: u64, keyspace: u64) -> u64{
+    u64::from_be_bytes(hash[..8].try_into().unwrap()).wrapping_add(column) % keyspace
+}
+
+/// Hashes and reduces the password of the column until the end of the chain, returning the last password.
+fn walk(positions: &[Vec<char>], start: u64, from: u64, to: u64, keyspace: u64) -> u64{
+    (from..to).fold(start, |index, column| reduce(&Sha256::digest(candidate(positions, index).as_bytes()), column, keyspace))
+}
+
+fn keyspace_of(positions: &[Vec<char>]) -> u64{
+    positions.iter().try_fold(1_u64, |product, charset| product.checked_mul(charset.len() as u64))
+        .exit("The mask has more than 2^64 passwords, far too many for a table.")
+}
+
+fn build(args: BuildArgs){
+    let positions = parse_mask(&args.mask).exit("Invalid mask.");
+    let keyspace = keyspace_of(&positions);
+    println!("building {} chains of {} passwords over the {} passwords of \"{}\"", args.chains, args.length, keyspace, args.mask);
+
+    let start = Instant::now();
+    let mut covered = HashSet::new();
+    let chains: Vec<(u64, u64)> = (0..args.chains).map(|_|{
+        let mut bytes = [0; 8];
+        rand::fill_random(&mut bytes);
+        let first = u64::from_be_bytes(bytes) % keyspace;
+        let mut index = first;
+        for column in 0..args.length{
+            covered.insert(index);
+            index = reduce(&Sha256::digest(candidate(&positions, index).as_bytes()), column, keyspace);
+        }
+        (first, index)
+    }).collect();
+    let elapsed = start.elapsed();
+    let ends = chains.iter().map(|(_, end)| end).collect::<HashSet<_>>().len();
+
+    let table = Table{mask: args.mask, length: args.length, chains};
+    let content = toml::to_string(&table).exit("Error while parsing to toml.");
+    File::create(&args.output).and_then(|mut file| file.write_all(content.as_bytes())).exit("Error while writing the table.");
+
+    let password_size: usize = positions.len();
+    println!();
+    println!("hashes:     {}, in {:.3} s", args.chains * args.length, elapsed.as_secs_f64());
+    println!("covered:    {} passwords, {:.1}% of the mask", covered.len(), 100.0 * covered.len() as f64 / keyspace as f64);
+    println!("merged:     {} chains end like another one", args.chains as usize - ends);
+    println!("table:      {} bytes, the first and the last password of every chain, in {}", 16 * args.chains, args.output);
+    println!();
+    println!("A full table of every password and its hash would take {} bytes, and a lookup would be instant.",
+        keyspace * (32 + password_size as u64));
+    println!("This one is {:.0} times smaller, but a lookup hashes about {} times, the length squared over two, and more for every false alarm:",
+        keyspace as f64 * (32 + password_size) as f64 / (16 * table.chains.len()) as f64, table.length * (table.length + 1) / 2);
+    println!("longer chains trade more time for less memory.");
+}
+
+fn lookup(args: LookupArgs){
+    let target = Hash256::from_hex(&args.hash.to_lowercase(), false).exit("Invalid hash.").get_bytes();
+    let content = fs::read_to_string(&args.table).exit("Error while reading the table.");
+    let table = toml::from_str::<Table>(&content).exit("Error while parsing the table.");
+    let positions = parse_mask(&table.mask).exit("Invalid mask of the table.");
+    let keyspace = keyspace_of(&positions);
+
+    let mut starts: HashMap<u64, Vec<u64>> = HashMap::new();
+    for (first, last) in &table.chains{
+        starts.entry(*last).or_default().push(*first);
+    }
+
+    let start = Instant::now();
+    let mut hashes = 0;
+    let mut false_alarms = 0;
+    let mut found = None;
+    // the hash may be in any column, the last ones are the cheapest to try
+    'columns: for column in (0..table.length).rev(){
+        let end = walk(&positions, reduce(&target, column, keyspace), column + 1, table.length, keyspace);
+        hashes += table.length - column - 1;
+        for first in starts.get(&end).into_iter().flatten(){
+            // the chain ends the same, it has the password if it has the hash, and not if it only merged after it
+            let index = walk(&positions, *first, 0, column, keyspace);
+            hashes += column + 1;
+            if Sha256::digest(candidate(&positions, index).as_bytes()) == target{
+                found = Some(index);
+                break 'columns;
+            }
+            false_alarms += 1;
+        }
+    }
+    let elapsed = start.elapsed();
+
+    match found{
+        Some(index) => println!("found:        {}", candidate(&positions, index)),
+        None => println!("not found, the password isn't in the chains of the table"),
+    }
+    println!("hashes:       {}, in {:.3} s", hashes, elapsed.as_secs_f64());
+    println!("false alarms: {}, chains that ended the same without having the hash", false_alarms);
+    println!("brute force:  {} hashes on average, half of the {} passwords of \"{}\"", keyspace / 2, keyspace, table.mask);
+
+    if found.is_none(){
+        Failure::Verification.exit();
+    }
+}
+
+/// Builds rainbow tables and looks hashes up in them, trading the time of a brute force for the memory of the table.
+pub fn rainbow(args: RainbowArgs){
+    match args.subcommand{
+        RainbowCommand::Build(args) => build(args),
+        RainbowCommand::Lookup(args) => lookup(args),
+    }
+}
@@ -0,0 +1,204 @@
+use clap::{Args, ValueEnum};
+
+use mysha::ecc::{bitcoin::*, Curve, KeyPair};
+use mysha::encoding::{base58, bech32, hex};
+use mysha::rand;
+
+use crate::ecc_cli::read_private_key;
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct SignTxArgs{
+    /// ecc toml file with the private key of the spent output, a random key of secp256k1 by default
+    #[arg(short, long)]
+    key: Option<String>,
+
+    /// type of the spent output
+    #[arg(short, long, value_enum, default_value_t = OutputType::P2wpkh)]
+    r#type: OutputType,
+
+    /// id of the transaction of the spent output, as explorers show it, a random one by default
+    #[arg(long)]
+    txid: Option<String>,
+
+    /// index of the spent output in its transaction
+    #[arg(long, default_value_t = 0)]
+    vout: u32,
+
+    /// amount of the spent output, in satoshis
+    #[arg(long, default_value_t = 100_000)]
+    value: u64,
+
+    /// fee left to the miners, in satoshis, the rest of the value is sent
+    #[arg(long, default_value_t = 1_000)]
+    fee: u64,
+
+    /// address the value is sent to, the address of the key by default
+    #[arg(long)]
+    to: Option<String>,
+
+    /// Uses the addresses of the main network instead of the ones of testnet
+    #[arg(long)]
+    mainnet: bool,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+enum OutputType{
+    /// Pay to public key hash, signed in the script of the input, with the legacy sighash
+    P2pkh,
+    /// Pay to witness public key hash, signed in the witness, with the sighash of BIP-143
+    P2wpkh,
+}
+
+/// The script of an address of the network: P2PKH and P2SH in base58check, and segwit in bech32.
+fn script_of_address(address: &str, mainnet: bool) -> Vec<u8>{
+    let hrp = if mainnet {"bc"} else {"tb"};
+    if address.to_lowercase().starts_with(&format!("{}1", hrp)){
+        let (version, program) = bech32::decode_segwit(hrp, address).exit("Invalid segwit address.");
+        // OP_0 for version 0, and OP_1 to OP_16 for the others
+        let op = if version == 0 {0} else {0x50 + version};
+        return [&[op, program.len() as u8], &program[..]].concat();
+    }
+    let data = base58::decode_check(address).unwrap_or_default();
+    match (data.first(), data.len(), mainnet){
+        (Some(0x00), 21, true) | (Some(0x6f), 21, false) => p2pkh_script(data[1..].try_into().unwrap()),
+        (Some(0x05), 21, true) | (Some(0xc4), 21, false) => [&[0xa9, 0x14], &data[1..], &[0x87]].concat(),
+        _ => None.exit(&format!("{} isn't a P2PKH, P2SH or segwit address of {}.", address, if mainnet {"mainnet"} else {"testnet"})),
+    }
+}
+
+/// Push of the data in a script, its length in one byte followed by it, for data of up to 75 bytes.
+fn push(data: &[u8]) -> Vec<u8>{
+    [&[data.len() as u8], data].concat()
+}
+
+/// The DER signature with spaces between its parts: the sequence, the integers r and s, and the sighash type.
+fn show_der(signature: &[u8]) -> String{
+    let r_length = signature[3] as usize;
+    let (r, rest) = signature[4..].split_at(r_length);
+    let (s, sighash_type) = rest[2..].split_at(rest[1] as usize);
+    format!("{} {} | {} {} {} | {} {} {} | {}", hex::encode(&signature[..1]), hex::encode(&signature[1..2]),
+        hex::encode(&signature[2..3]), hex::encode(&signature[3..4]), hex::encode(r),
+        hex::encode(&rest[..1]), hex::encode(&rest[1..2]), hex::encode(s), hex::encode(sighash_type))
+}
+
+/// Builds a transaction spending one output of the key, and signs it step by step, printing every part of the raw transaction.
+pub fn sign_tx(args: SignTxArgs){
+    if args.fee >= args.value{
+        None.exit("The fee must be lower than the value of the spent output.")
+    }
+    let private = match args.key{
+        Some(path) => read_private_key(&path),
+        None => KeyPair::new(rand::random_scalar(&Curve::secp256k1()), Curve::secp256k1()).exit("Error while generating the key.").private(),
+    };
+    let key_pair = KeyPair::from_private(&private).exit("Invalid private key.");
+    let public = compressed_public_key(&key_pair.public()).exit("The key must be of secp256k1.");
+    let key_hash = hash160(&public);
+    let (script_pubkey, address) = match args.r#type{
+        OutputType::P2pkh => {
+            let version = if args.mainnet {0x00} else {0x6f};
+            (p2pkh_script(&key_hash), base58::encode_check(&[&[version], &key_hash[..]].concat()))
+        },
+        OutputType::P2wpkh => {
+            let hrp = if args.mainnet {"bc"} else {"tb"};
+            (p2wpkh_script(&key_hash), bech32::encode_segwit(hrp, 0, &key_hash).unwrap())
+        },
+    };
+    let to = args.to.as_deref().unwrap_or(&address);
+    let destination = script_of_address(to, args.mainnet);
+    let txid = match &args.txid{
+        Some(txid) => {
+            let mut bytes: [u8; 32] = hex::decode(txid).exit("Invalid txid.").try_into().ok().exit("The txid must have 32 bytes.");
+            bytes.reverse();
+            bytes
+        },
+        None => {
+            let mut bytes = [0; 32];
+            rand::fill_random(&mut bytes);
+            bytes
+        },
+    };
+
+    println!("key");
+    println!("   private:    {}", private.to_hex());
+    println!("   public:     {}, compressed, 02 or 03 for the parity of y, and x", hex::encode(&public));
+    println!("   hash160:    {}, ripemd160 of the sha256 of the public key", hex::encode(&key_hash));
+    println!("   address:    {}", address);
+    println!();
+
+    println!("1. the output spent, {} satoshis locked to the hash of the key", args.value);
+    println!("   outpoint:   {}:{}, the txid in a transaction is reversed: {}", hex::encode(&txid.iter().rev().copied().collect::<Vec<u8>>()),
+        args.vout, hex::encode(&txid));
+    match args.r#type{
+        OutputType::P2pkh => println!("   script:     {}, OP_DUP OP_HASH160 <hash160> OP_EQUALVERIFY OP_CHECKSIG", hex::encode(&script_pubkey)),
+        OutputType::P2wpkh => println!("   script:     {}, OP_0 <hash160>, a version 0 witness program", hex::encode(&script_pubkey)),
+    }
+    println!();
+
+    let mut transaction = Transaction{
+        version: 2,
+        inputs: vec![TxIn{txid, vout: args.vout, script_sig: Vec::new(), sequence: 0xffffffff, witness: Vec::new()}],
+        outputs: vec![TxOut{value: args.value - args.fee, script_pubkey: destination}],
+        lock_time: 0,
+    };
+    println!("2. the unsigned transaction, sending {} satoshis to {} and leaving {} to the miners", args.value - args.fee, to, args.fee);
+    println!("   {}", hex::encode(&transaction.to_bytes()));
+    println!("   version 02000000, 1 input with an empty script, 1 output of the value and its script, and lock time 00000000");
+    println!();
+
+    let sighash = match args.r#type{
+        OutputType::P2pkh => {
+            println!("3. the legacy sighash: the transaction with the script of the spent output as the script of the input,");
+            println!("   followed by the type SIGHASH_ALL in 4 bytes, 01000000, hashed with sha256 twice");
+            transaction.legacy_sighash(0, &script_pubkey, SIGHASH_ALL).unwrap()
+        },
+        OutputType::P2wpkh => {
+            println!("3. the sighash of BIP-143: the version, the hashes of the outpoints, sequences and outputs, the outpoint,");
+            println!("   the script code {}, the value and sequence of the input, the lock time and SIGHASH_ALL, hashed with sha256 twice",
+                hex::encode(&p2pkh_script(&key_hash)));
+            transaction.segwit_sighash(0, &p2pkh_script(&key_hash), args.value, SIGHASH_ALL).unwrap()
+        },
+    };
+    println!("   sighash:    {}", sighash);
+    println!();
+
+    let signature = key_pair.sign_prehashed(&sighash).exit("Error while signing.");
+    let encoded = encode_signature(&signature, SIGHASH_ALL).exit("Error while encoding the signature.");
+    println!("4. the ECDSA signature of the sighash, with s replaced by n - s if it is the higher one, or nodes don't relay it");
+    println!("   DER:        {}", show_der(&encoded));
+    println!("               sequence and length | integer r | integer s | SIGHASH_ALL");
+    println!();
+
+    match args.r#type{
+        OutputType::P2pkh => {
+            transaction.inputs[0].script_sig = [push(&encoded), push(&public)].concat();
+            println!("5. the script of the input: <signature> <public key>, the script of the output then checks");
+            println!("   that the public key has the hash160, and the signature with it");
+            println!("   script:     {}", hex::encode(&transaction.inputs[0].script_sig));
+        },
+        OutputType::P2wpkh => {
+            transaction.inputs[0].witness = vec![encoded, public];
+            println!("5. the witness of the input: <signature> <public key>, the script of the input stays empty,");
+            println!("   so the txid doesn't change when the transaction is signed");
+            for item in &transaction.inputs[0].witness{
+                println!("   item:       {}", hex::encode(item));
+            }
+        },
+    }
+    println!();
+
+    let raw = transaction.to_bytes();
+    println!("6. the signed transaction, {} bytes", raw.len());
+    println!("   {}", hex::encode(&raw));
+    println!("   txid:       {}", transaction.txid());
+    if args.r#type == OutputType::P2wpkh{
+        println!("   with the marker 00 and the flag 01 after the version, and the witness before the lock time");
+    }
+    if !signature.verify_prehashed(&sighash).exit("Error while verifying."){
+        None.exit_as(Failure::Internal, "the signature is NOT valid")
+    }
+    println!();
+    let network = if args.mainnet {""} else {" -testnet"};
+    println!("If the output exists and is locked to the key, `bitcoin-cli{} sendrawtransaction <hex>` broadcasts it.", network);
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::fs;
+
+use clap::Args;
+
+use mysha::sha256::Sha256;
+
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct StatsArgs{
+    /// number of counter values hashed, 0, 1, 2, ... in decimal
+    #[arg(short = 'n', long, default_value_t = 100_000, value_parser = clap::value_parser!(u64).range(1..))]
+    count: u64,
+
+    /// hashes every line of the file instead of counter values
+    #[arg(short, long, conflicts_with = "count")]
+    file: Option<String>,
+
+    /// prints the frequency of ones of every bit
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+/// Bits of a hash.
+const BITS: usize = 256;
+
+/// Width of the longest bar of the histograms.
+const BAR: usize = 50;
+
+/// Counts of ones of every bit position, and of every byte value, of the hashes.
+struct Counts{
+    hashes: u64,
+    ones: [u64; BITS],
+    bytes: [u64; 256],
+}
+
+impl Counts{
+    fn new() -> Counts{
+        Counts{hashes: 0, ones: [0; BITS], bytes: [0; 256]}
+    }
+
+    fn add(&mut self, hash: &[u8; 32]){
+        self.hashes += 1;
+        for (i, byte) in hash.iter().enumerate(){
+            self.bytes[*byte as usize] += 1;
+            for bit in 0..8{
+                self.ones[8 * i + bit] += (byte >> (7 - bit) & 1) as u64;
+            }
+        }
+    }
+
+    /// Deviation of the ones of every bit from half the hashes, in standard deviations of a fair coin.
+    fn scores(&self) -> Vec<f64>{
+        let n = self.hashes as f64;
+        self.ones.iter().map(|&ones| (ones as f64 - n / 2.0) / (n / 4.0).sqrt()).collect()
+    }
+}
+
+/// Chi-square of the counts against the same expected count for all of them.
+fn chi_square(counts: &[u64], expected: f64) -> f64{
+    counts.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum()
+}
+
+/// Prints the statistic next to its expected value, the degrees of freedom, and the standard deviation of a chi-square, sqrt(2k).
+fn print_chi_square(name: &str, value: f64, freedom: usize){
+    let deviation = (2.0 * freedom as f64).sqrt();
+    let distance = (value - freedom as f64) / deviation;
+    let verdict = if distance.abs() <= 3.0 {"consistent with a uniform output"} else {"NOT consistent with a uniform output"};
+    println!("chi-square of the {}: {:.1}, expected {} ± {:.1}, {:+.2} standard deviations, {}",
+        name, value, freedom, deviation, distance, verdict);
+}
+
+fn bar(count: u64, max: u64) -> String{
+    "#".repeat((count as f64 / max.max(1) as f64 * BAR as f64).round() as usize)
+}
+
+/// Hashes many messages and shows how often every bit of the hashes is one, which is half the time, independently, for a good hash,
+/// with the chi-square statistics of the bits and of the byte values, and histograms of both.
+pub fn stats(args: StatsArgs){
+    let mut counts = Counts::new();
+    match &args.file{
+        Some(file) => {
+            let text = fs::read_to_string(file).exit("Error while reading the file.");
+            for line in text.lines(){
+                counts.add(&Sha256::digest(line.as_bytes()));
+            }
+            if counts.hashes == 0{
+                None.exit("The file has no lines to hash.")
+            }
+            println!("Hashed the {} lines of {}.", counts.hashes, file);
+            let distinct = text.lines().collect::<HashSet<_>>().len();
+            if distinct < counts.hashes as usize{
+                println!("Only {} of them are different, and a repeated line gives the same hash again, which skews the statistics.", distinct);
+            }
+        },
+        None => {
+            for i in 0..args.count{
+                counts.add(&Sha256::digest(i.to_string().as_bytes()));
+            }
+            println!("Hashed the numbers from 0 to {}.", args.count - 1);
+        },
+    }
+    let n = counts.hashes as f64;
+
+    let scores = counts.scores();
+    let frequency = |bit: usize| 100.0 * counts.ones[bit] as f64 / n;
+    if args.verbose{
+        println!();
+        println!("Frequency of ones of every bit, from the most significant bit of the first byte:");
+        for row in 0..BITS / 8{
+            let line: Vec<String> = (8 * row..8 * row + 8).map(|bit| format!("{:6.2}%", frequency(bit))).collect();
+            println!("  {:3}  {}", 8 * row, line.join(" "));
+        }
+    }
+    let (lowest, highest) = (0..BITS).fold((0, 0), |(low, high), bit|{
+        (if scores[bit] < scores[low] {bit} else {low}, if scores[bit] > scores[high] {bit} else {high})
+    });
+    println!();
+    println!("Every bit should be one half of the time, give or take {:.2}% with this many hashes.", 100.0 * (0.25 / n).sqrt());
+    println!("lowest:  bit {:3}, {:.3}%", lowest, frequency(lowest));
+    println!("highest: bit {:3}, {:.3}%", highest, frequency(highest));
+
+    // a deviation of every bit from a fair coin is a normal of mean 0 and deviation 1, their squares add up to a chi-square
+    let bits_chi: f64 = scores.iter().map(|score| score * score).sum();
+    println!();
+    print_chi_square("bits", bits_chi, BITS);
+
+    println!();
+    println!("Histogram of the deviations of the bits from half, in standard deviations, a bell centered at 0:");
+    let mut bins = [0_u64; 14];
+    for score in &scores{
+        bins[((score + 3.5) * 2.0).floor().clamp(0.0, 13.0) as usize] += 1;
+    }
+    let max = *bins.iter().max().unwrap();
+    for (i, count) in bins.iter().enumerate(){
+        let label = match i{
+            0 => String::from("      < -3.0"),
+            13 => String::from("     >= +3.0"),
+            _ => format!("{:+.1} .. {:+.1}", i as f64 / 2.0 - 3.5, i as f64 / 2.0 - 3.0),
+        };
+        println!("  {}  {:3} {}", label, count, bar(*count, max));
+    }
+
+    let expected = n * 32.0 / 256.0;
+    println!();
+    print_chi_square("byte values", chi_square(&counts.bytes, expected), 255);
+
+    println!();
+    println!("Histogram of the byte values of the hashes, in groups of 16, all about the same:");
+    let groups: Vec<u64> = counts.bytes.chunks(16).map(|group| group.iter().sum()).collect();
+    let max = *groups.iter().max().unwrap();
+    for (i, count) in groups.iter().enumerate(){
+        println!("  {:02x}..{:02x}  {:+6.2}% {}", 16 * i, 16 * i + 15, 100.0 * (*count as f64 / (16.0 * expected) - 1.0), bar(*count, max));
+    }
+}
@@ -0,0 +1,129 @@
+use std::time::Instant;
+
+use clap::Args;
+use num_bigint::BigUint;
+
+use mysha::ecc::{Curve, CURVE_NAMES};
+use mysha::rand;
+
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct TimingArgs{
+    /// number of scalars measured for every number of ones
+    #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u64).range(1..))]
+    samples: u64,
+
+    /// named curve the generator is multiplied on
+    #[arg(short, long, default_value = "secp256k1")]
+    curve: String,
+}
+
+/// Width of the longest bar of the table.
+const BAR: usize = 30;
+
+/// Fractions, in sixteenths, of the bits of the scalars that are one in every row of the table.
+const WEIGHTS: [u64; 5] = [1, 4, 8, 12, 15];
+
+/// Random number below the limit.
+fn random_below(limit: u64) -> u64{
+    let mut bytes = [0; 8];
+    rand::fill_random(&mut bytes);
+    u64::from_be_bytes(bytes) % limit
+}
+
+/// A random scalar of that many bits, with its top bit and ones - 1 other random bits set, so all have the same size.
+fn scalar_with_ones(bits: u64, ones: u64) -> BigUint{
+    let mut positions: Vec<u64> = (0..bits - 1).collect();
+    let mut scalar = BigUint::from(0_u8);
+    scalar.set_bit(bits - 1, true);
+    for i in 0..ones as usize - 1{
+        let chosen = i + random_below((positions.len() - i) as u64) as usize;
+        positions.swap(i, chosen);
+        scalar.set_bit(positions[i], true);
+    }
+    scalar
+}
+
+/// Time of the multiplication, in milliseconds.
+fn time(multiply: impl FnOnce()) -> f64{
+    let start = Instant::now();
+    multiply();
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+fn median(times: &mut [f64]) -> f64{
+    times.sort_by(|a, b| a.total_cmp(b));
+    times[times.len() / 2]
+}
+
+/// Slope of the least squares line of the times over the ones, and their correlation.
+fn fit(points: &[(f64, f64)]) -> (f64, f64){
+    let count = points.len() as f64;
+    let (mean_x, mean_y) = (points.iter().map(|p| p.0).sum::<f64>() / count, points.iter().map(|p| p.1).sum::<f64>() / count);
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance_x: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let variance_y: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+    (covariance / variance_x, covariance / (variance_x * variance_y).sqrt().max(f64::MIN_POSITIVE))
+}
+
+fn bar(time: f64, max: f64) -> String{
+    format!("{:<width$}", "#".repeat((time / max * BAR as f64).round() as usize), width = BAR)
+}
+
+/// Prints what the times of one implementation tell about the scalar.
+fn verdict(name: &str, points: &[(f64, f64)]){
+    let (slope, correlation) = fit(points);
+    let leak = if correlation.abs() > 0.5 {"the time tells how many bits of the secret scalar are one"} else {"no visible leak"};
+    println!("{:<16}{:+.4} ms for every bit that is one, correlation {:+.2}, {}", format!("{}:", name), slope, correlation, leak);
+}
+
+/// Measures double and add and the Montgomery ladder with scalars of the same size and more and more bits that are one,
+/// showing that the time of double and add grows with the ones, and the time of the ladder doesn't.
+pub fn timing(args: TimingArgs){
+    let curve = Curve::from_name(&args.curve)
+        .exit(&format!("Unknown curve {:?}, the named curves are {}.", args.curve, CURVE_NAMES.join(", ")));
+    let g = curve.get_g();
+    // below n, so both multiply the same scalar
+    let bits = curve.get_n().bits() - 1;
+    println!("timing Curve::multiply, double and add, against Curve::multiply_ladder, on {}, with {} scalars of {} bits for every number of ones",
+        args.curve, args.samples, bits);
+    println!();
+
+    // the first multiplications are slower, while the caches and the allocator warm up
+    for _ in 0..3{
+        curve.multiply(g, scalar_with_ones(bits, bits / 2)).exit("Error while multiplying.");
+        curve.multiply_ladder(g, scalar_with_ones(bits, bits / 2)).exit("Error while multiplying.");
+    }
+
+    let start = Instant::now();
+    let mut rows = Vec::new();
+    let (mut naive_points, mut ladder_points) = (Vec::new(), Vec::new());
+    for weight in WEIGHTS{
+        let ones = (bits * weight / 16).max(1);
+        let (mut naive, mut ladder) = (Vec::new(), Vec::new());
+        for _ in 0..args.samples{
+            let scalar = scalar_with_ones(bits, ones);
+            // one after the other, so both see the same load of the machine
+            naive.push(time(|| {curve.multiply(g, scalar.clone()).exit("Error while multiplying.");}));
+            ladder.push(time(|| {curve.multiply_ladder(g, scalar.clone()).exit("Error while multiplying.");}));
+        }
+        naive_points.extend(naive.iter().map(|time| (ones as f64, *time)));
+        ladder_points.extend(ladder.iter().map(|time| (ones as f64, *time)));
+        rows.push((ones, median(&mut naive), median(&mut ladder)));
+    }
+
+    let max = rows.iter().map(|(_, naive, ladder)| naive.max(*ladder)).fold(f64::MIN_POSITIVE, f64::max);
+    println!("median times");
+    println!("{:>6}   {:<width$}   ladder", "ones", "double and add", width = BAR + 12);
+    for (ones, naive, ladder) in &rows{
+        let row = format!("{:>6}   {:>8.3} ms {}   {:>8.3} ms {}", ones, naive, bar(*naive, max), ladder, bar(*ladder, max));
+        println!("{}", row.trim_end());
+    }
+    println!();
+    verdict("double and add", &naive_points);
+    verdict("ladder", &ladder_points);
+    println!();
+    println!("measured in {:.1} s. Double and add only adds for the ones, so anyone who can time it, even over a network,", start.elapsed().as_secs_f64());
+    println!("learns about the private key. The ladder does an addition and a doubling for every bit, whatever they are.");
+}
@@ -0,0 +1,64 @@
+//! Module with the [Digest] trait, for code that works with any hash function.
+//!
+//! Signing and other algorithms that only need the bytes of a hash take a `D: Digest` type parameter,
+//! so they aren't tied to sha256, and work with new hash functions as soon as they implement the trait.
+//!
+//! # Examples
+//! ```
+//! use mysha::digest::Digest;
+//! use mysha::sha256::Sha256;
+//!
+//! fn double_hash<D: Digest>(data: &[u8]) -> Vec<u8>{
+//!     D::hash(&D::hash(data))
+//! }
+//!
+//! assert_eq!(double_hash::<Sha256>(b"hello").len(), Sha256::SIZE);
+//! ```
+//!
+//! [git_blob_id] is such a function, the ids of files of git, which are SHA-1 or sha256 hashes depending on the repository.
+
+/// A hash function that can be fed data in parts.
+pub trait Digest{
+    /// Size of the hash in bytes.
+    const SIZE: usize;
+
+    /// Creates a hasher with no data.
+    fn new() -> Self;
+
+    /// Adds the data to the message being hashed.
+    fn update(&mut self, data: &[u8]);
+
+    /// Pads the message and returns the bytes of the hash.
+    fn finish(self) -> Vec<u8>;
+
+    /// Hashes the data in one call.
+    fn hash(data: &[u8]) -> Vec<u8> where Self: Sized{
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finish()
+    }
+}
+
+/// Object id git gives to a file with the content, the hash of a `blob <length>\0` header followed by the content,
+/// like `git hash-object`.
+///
+/// Repositories with the default object format use [Sha1][crate::sha1::Sha1], and the ones made with `--object-format=sha256`
+/// use [Sha256][crate::sha256::Sha256], so the id of the same file is different in each.
+///
+/// # Examples
+/// ```
+/// use mysha::digest::git_blob_id;
+/// use mysha::encoding::hex;
+/// use mysha::sha1::Sha1;
+/// use mysha::sha256::Sha256;
+///
+/// // echo hello | git hash-object --stdin
+/// assert_eq!(hex::encode(&git_blob_id::<Sha1>(b"hello\n")), "ce013625030ba8dba906f756967f9e9ca394464a");
+/// assert_eq!(git_blob_id::<Sha256>(b"hello\n").len(), 32);
+/// ```
+pub fn git_blob_id<D: Digest>(content: &[u8]) -> Vec<u8>{
+    let mut hasher = D::new();
+    hasher.update(format!("blob {}\0", content.len()).as_bytes());
+    hasher.update(content);
+    hasher.finish()
+}
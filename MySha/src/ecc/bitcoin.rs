@@ -0,0 +1,431 @@
+//! Sighashes of Bitcoin transactions, the hashes that are signed to spend their inputs.
+//!
+//! An input is spent by signing a hash of the transaction, with the script of the output it spends in place of its script.
+//! The sighash type, the last byte of the signature, chooses which parts of the transaction are covered:
+//! [SIGHASH_ALL] covers every input and output, [SIGHASH_NONE] no output, [SIGHASH_SINGLE] only the output of the same index,
+//! and with [SIGHASH_ANYONECANPAY] only the input being signed.
+//!
+//! - [Transaction::legacy_sighash] is the original algorithm, for P2PKH and the other inputs without a witness;
+//! - [Transaction::segwit_sighash] is the one of [BIP-143], for P2WPKH and P2WSH inputs, which also covers the value of the spent output,
+//!   so a signer doesn't need the previous transactions to know the fee.
+//!
+//! Both return the [Hash256] to sign with [sign_prehashed][super::KeyPair::sign_prehashed], and [encode_signature] gives the bytes that go
+//! into the script or the witness. Scripts are given already serialized, so [OP_CODESEPARATOR] isn't handled.
+//!
+//! # Examples
+//! The native P2WPKH example of BIP-143:
+//! ```
+//! use mysha::ecc::{*, bitcoin::*};
+//! use mysha::encoding::hex;
+//!
+//! # fn main() -> Result<(), mysha::MyshaError>{
+//! let unsigned = hex::decode("0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffff\
+//!     ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a914\
+//!     8280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000")?;
+//! let transaction = Transaction::from_bytes(&unsigned)?;
+//!
+//! // the script code of P2WPKH is the P2PKH script of the hash of the key
+//! let script_code = hex::decode("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac")?;
+//! let sighash = transaction.segwit_sighash(1, &script_code, 600_000_000, SIGHASH_ALL)?;
+//! assert_eq!(sighash.get_hex(), "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670");
+//!
+//! let key_pair = KeyPair::new(1234_u32, Curve::secp256k1())?;
+//! let signature = key_pair.sign_prehashed(&sighash)?;
+//! assert!(signature.verify_prehashed(&sighash)?);
+//! assert_eq!(*encode_signature(&signature, SIGHASH_ALL)?.last().unwrap(), 0x01);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [BIP-143]: https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki
+//! [OP_CODESEPARATOR]: https://en.bitcoin.it/wiki/OP_CODESEPARATOR
+
+use num_bigint::BigUint;
+
+use super::{Curve, EccError, PubKey, Signature};
+use crate::encoding::hex;
+use crate::ripemd160::Ripemd160;
+use crate::sha256::{Hash256, Sha256};
+
+/// Signs every input and output.
+pub const SIGHASH_ALL: u32 = 0x01;
+/// Signs every input and no output, anyone can choose where the coins go.
+pub const SIGHASH_NONE: u32 = 0x02;
+/// Signs every input and only the output with the index of the input signed.
+pub const SIGHASH_SINGLE: u32 = 0x03;
+/// Added to the other types, signs only the input being signed, so others can add inputs.
+pub const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// An input of a transaction, the output it spends and the data that unlocks it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn{
+    /// Id of the transaction of the spent output, in the order of its bytes in transactions, the reverse of how explorers show it.
+    pub txid: [u8; 32],
+    /// Index of the spent output in its transaction.
+    pub vout: u32,
+    /// Script unlocking the output, with the signature and the public key for P2PKH, empty for segwit inputs.
+    pub script_sig: Vec<u8>,
+    /// Sequence number, `0xffffffff` unless the lock time or replace by fee are used.
+    pub sequence: u32,
+    /// Items of the witness, the signature and the public key for P2WPKH, empty for legacy inputs.
+    pub witness: Vec<Vec<u8>>,
+}
+
+/// An output of a transaction, an amount and the script that locks it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut{
+    /// Amount in satoshis.
+    pub value: u64,
+    /// Script locking the amount, like `OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG` for P2PKH.
+    pub script_pubkey: Vec<u8>,
+}
+
+/// A Bitcoin transaction, read from and written to the bytes of the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction{
+    /// Version, 1 or 2.
+    pub version: u32,
+    /// Inputs, the outputs of previous transactions spent.
+    pub inputs: Vec<TxIn>,
+    /// Outputs, the amounts sent.
+    pub outputs: Vec<TxOut>,
+    /// Block height or time before which the transaction can't be mined, 0 for none.
+    pub lock_time: u32,
+}
+
+/// Double sha256, the hash of Bitcoin.
+fn hash256(data: &[u8]) -> [u8; 32]{
+    Sha256::digest(&Sha256::digest(data))
+}
+
+/// The ripemd160 of the sha256 of the data, the 20 byte hash of public keys and scripts in addresses.
+///
+/// # Examples
+/// ```
+/// use mysha::ecc::{*, bitcoin::*};
+/// use mysha::encoding::{bech32, hex};
+///
+/// # fn main() -> Result<(), mysha::MyshaError>{
+/// let public = KeyPair::new(1_u32, Curve::secp256k1())?.public();
+/// let key_hash = hash160(&compressed_public_key(&public)?);
+///
+/// assert_eq!(hex::encode(&key_hash), "751e76e8199196d454941c45d1b3a323f1433bd6");
+/// assert_eq!(bech32::encode_segwit("bc", 0, &key_hash)?, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+/// assert_eq!(hex::encode(&p2wpkh_script(&key_hash)), "0014751e76e8199196d454941c45d1b3a323f1433bd6");
+/// # Ok(())
+/// # }
+/// ```
+pub fn hash160(data: &[u8]) -> [u8; 20]{
+    Ripemd160::digest(&Sha256::digest(data))
+}
+
+/// The 33 bytes of the public key in the compressed SEC1 encoding, 02 or 03 for the parity of y, followed by x.
+///
+/// # Errors
+///
+/// This can fail with [UnsupportedCurve][EccError::UnsupportedCurve] if the key isn't of secp256k1.
+pub fn compressed_public_key(public: &PubKey) -> Result<Vec<u8>, EccError>{
+    if *public.get_curve() != Curve::secp256k1(){
+        return Err(EccError::UnsupportedCurve);
+    }
+    let (x, y) = public.get_public().get_xy().ok_or(EccError::PublicKeyOnInfinity)?;
+    let mut bytes = vec![0; 33];
+    bytes[0] = if y.bit(0) {0x03} else {0x02};
+    let x = x.to_bytes_be();
+    bytes[33 - x.len()..].copy_from_slice(&x);
+    Ok(bytes)
+}
+
+/// Script of a P2PKH output, `OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG`, spent with a signature and the public key
+/// of the hash in the script of the input. It is also the script code of a P2WPKH input.
+pub fn p2pkh_script(key_hash: &[u8; 20]) -> Vec<u8>{
+    [&[0x76, 0xa9, 0x14], &key_hash[..], &[0x88, 0xac]].concat()
+}
+
+/// Script of a P2WPKH output, `OP_0 <hash>`, spent with a signature and the public key of the hash in the witness.
+pub fn p2wpkh_script(key_hash: &[u8; 20]) -> Vec<u8>{
+    [&[0x00, 0x14], &key_hash[..]].concat()
+}
+
+/// Writes a length as a compact size, in 1, 3, 5 or 9 bytes.
+fn write_compact_size(bytes: &mut Vec<u8>, n: usize){
+    match n{
+        0..=0xfc => bytes.push(n as u8),
+        0xfd..=0xffff => {
+            bytes.push(0xfd);
+            bytes.extend_from_slice(&(n as u16).to_le_bytes());
+        },
+        0x10000..=0xffffffff => {
+            bytes.push(0xfe);
+            bytes.extend_from_slice(&(n as u32).to_le_bytes());
+        },
+        _ => {
+            bytes.push(0xff);
+            bytes.extend_from_slice(&(n as u64).to_le_bytes());
+        },
+    }
+}
+
+/// Writes the script, or any bytes, after their length.
+fn write_bytes(bytes: &mut Vec<u8>, data: &[u8]){
+    write_compact_size(bytes, data.len());
+    bytes.extend_from_slice(data);
+}
+
+fn write_output(bytes: &mut Vec<u8>, output: &TxOut){
+    bytes.extend_from_slice(&output.value.to_le_bytes());
+    write_bytes(bytes, &output.script_pubkey);
+}
+
+/// Reads the fields of a transaction in order.
+struct Reader<'a>{
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a>{
+    fn take(&mut self, n: usize) -> Result<&'a [u8], EccError>{
+        if n > self.bytes.len(){
+            return Err(EccError::InvalidTransaction);
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32, EccError>{
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, EccError>{
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn compact_size(&mut self) -> Result<usize, EccError>{
+        let n = match self.take(1)?[0]{
+            0xfd => u16::from_le_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            0xfe => self.u32()? as u64,
+            0xff => self.u64()?,
+            n => n as u64,
+        };
+        // no count or length can be larger than what is left, which also keeps huge values from allocating
+        if n > self.bytes.len() as u64{
+            return Err(EccError::InvalidTransaction);
+        }
+        Ok(n as usize)
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>, EccError>{
+        let length = self.compact_size()?;
+        Ok(self.take(length)?.to_vec())
+    }
+}
+
+impl Transaction{
+    /// Reads a transaction from its bytes, with or without the witnesses of [BIP-144].
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [InvalidTransaction][EccError::InvalidTransaction] if the bytes end before the transaction,
+    /// or continue after it.
+    ///
+    /// [BIP-144]: https://github.com/bitcoin/bips/blob/master/bip-0144.mediawiki
+    pub fn from_bytes(bytes: &[u8]) -> Result<Transaction, EccError>{
+        let mut reader = Reader{bytes};
+        let version = reader.u32()?;
+        // a transaction with witnesses has a zero where the number of inputs was, and a one, which old nodes read as no inputs
+        let segwit = reader.bytes.starts_with(&[0, 1]);
+        if segwit{
+            reader.take(2)?;
+        }
+
+        let mut inputs = (0..reader.compact_size()?).map(|_| Ok(TxIn{
+            txid: reader.take(32)?.try_into().unwrap(),
+            vout: reader.u32()?,
+            script_sig: reader.bytes()?,
+            sequence: reader.u32()?,
+            witness: Vec::new(),
+        })).collect::<Result<Vec<TxIn>, EccError>>()?;
+        let outputs = (0..reader.compact_size()?).map(|_| Ok(TxOut{
+            value: reader.u64()?,
+            script_pubkey: reader.bytes()?,
+        })).collect::<Result<Vec<TxOut>, EccError>>()?;
+        if segwit{
+            for input in &mut inputs{
+                input.witness = (0..reader.compact_size()?).map(|_| reader.bytes()).collect::<Result<_, _>>()?;
+            }
+        }
+        let lock_time = reader.u32()?;
+        if !reader.bytes.is_empty(){
+            return Err(EccError::InvalidTransaction);
+        }
+        Ok(Transaction{version, inputs, outputs, lock_time})
+    }
+
+    /// The bytes of the transaction, with the witnesses if any input has one, the raw transaction that is broadcast.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        self.serialize(self.inputs.iter().any(|input| !input.witness.is_empty()))
+    }
+
+    fn serialize(&self, witness: bool) -> Vec<u8>{
+        let mut bytes = self.version.to_le_bytes().to_vec();
+        if witness{
+            bytes.extend_from_slice(&[0, 1]);
+        }
+        write_compact_size(&mut bytes, self.inputs.len());
+        for input in &self.inputs{
+            bytes.extend_from_slice(&input.txid);
+            bytes.extend_from_slice(&input.vout.to_le_bytes());
+            write_bytes(&mut bytes, &input.script_sig);
+            bytes.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+        write_compact_size(&mut bytes, self.outputs.len());
+        for output in &self.outputs{
+            write_output(&mut bytes, output);
+        }
+        if witness{
+            for input in &self.inputs{
+                write_compact_size(&mut bytes, input.witness.len());
+                for item in &input.witness{
+                    write_bytes(&mut bytes, item);
+                }
+            }
+        }
+        bytes.extend_from_slice(&self.lock_time.to_le_bytes());
+        bytes
+    }
+
+    /// Id of the transaction in hex, as explorers show it: the reversed double sha256 of the bytes without the witnesses,
+    /// so signing a segwit input doesn't change it.
+    pub fn txid(&self) -> String{
+        let mut hash = hash256(&self.serialize(false));
+        hash.reverse();
+        hex::encode(&hash)
+    }
+
+    /// Sighash of an input without a witness, like P2PKH, with the original algorithm.
+    ///
+    /// The transaction is copied without the scripts of the inputs, the script code, the script of the spent output,
+    /// takes the place of the one of the input signed, the inputs and outputs the type doesn't cover are removed,
+    /// and the double sha256 of the copy followed by the type, in 4 bytes, is the sighash.
+    ///
+    /// With [SIGHASH_SINGLE] and no output of the index of the input, the sighash is the number one,
+    /// a bug of the first versions of Bitcoin kept for compatibility, and a signature of it can be reused for any transaction.
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [InvalidTransaction][EccError::InvalidTransaction] if the transaction doesn't have the input.
+    pub fn legacy_sighash(&self, input: usize, script_code: &[u8], sighash_type: u32) -> Result<Hash256, EccError>{
+        if input >= self.inputs.len(){
+            return Err(EccError::InvalidTransaction);
+        }
+        let base = sighash_type & 0x1f;
+        if base == SIGHASH_SINGLE && input >= self.outputs.len(){
+            let mut one = [0; 32];
+            one[0] = 1;
+            return Ok(Hash256::from(one));
+        }
+
+        let mut copy = self.clone();
+        for (i, other) in copy.inputs.iter_mut().enumerate(){
+            other.script_sig = if i == input {script_code.to_vec()} else {Vec::new()};
+            other.witness = Vec::new();
+            // with none and single the other inputs can be replaced, so their sequences aren't signed
+            if i != input && (base == SIGHASH_NONE || base == SIGHASH_SINGLE){
+                other.sequence = 0;
+            }
+        }
+        match base{
+            SIGHASH_NONE => copy.outputs.clear(),
+            SIGHASH_SINGLE => {
+                copy.outputs.truncate(input + 1);
+                for output in &mut copy.outputs[..input]{
+                    *output = TxOut{value: u64::MAX, script_pubkey: Vec::new()};
+                }
+            },
+            _ => {},
+        }
+        if sighash_type & SIGHASH_ANYONECANPAY != 0{
+            copy.inputs = vec![copy.inputs.swap_remove(input)];
+        }
+
+        let mut bytes = copy.serialize(false);
+        bytes.extend_from_slice(&sighash_type.to_le_bytes());
+        Ok(Hash256::from(hash256(&bytes)))
+    }
+
+    /// Sighash of a segwit version 0 input, like P2WPKH, with the algorithm of BIP-143.
+    ///
+    /// The hashes of all the outpoints, sequences and outputs are computed once for every input, instead of hashing
+    /// the whole transaction again, and the value of the spent output is signed, which the legacy sighash didn't.
+    /// For P2WPKH the script code is the P2PKH script of the hash of the key, `76a914 <hash> 88ac`.
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [InvalidTransaction][EccError::InvalidTransaction] if the transaction doesn't have the input.
+    pub fn segwit_sighash(&self, input: usize, script_code: &[u8], value: u64, sighash_type: u32) -> Result<Hash256, EccError>{
+        let signed = self.inputs.get(input).ok_or(EccError::InvalidTransaction)?;
+        let base = sighash_type & 0x1f;
+        let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+
+        let hash_prevouts = if anyone_can_pay {[0; 32]} else {
+            hash256(&self.inputs.iter().flat_map(|input| input.txid.into_iter().chain(input.vout.to_le_bytes())).collect::<Vec<u8>>())
+        };
+        let hash_sequence = if anyone_can_pay || base == SIGHASH_NONE || base == SIGHASH_SINGLE {[0; 32]} else {
+            hash256(&self.inputs.iter().flat_map(|input| input.sequence.to_le_bytes()).collect::<Vec<u8>>())
+        };
+        let hash_outputs = match (base, self.outputs.get(input)){
+            (SIGHASH_NONE, _) | (SIGHASH_SINGLE, None) => [0; 32],
+            (SIGHASH_SINGLE, Some(output)) => {
+                let mut bytes = Vec::new();
+                write_output(&mut bytes, output);
+                hash256(&bytes)
+            },
+            _ => {
+                let mut bytes = Vec::new();
+                for output in &self.outputs{
+                    write_output(&mut bytes, output);
+                }
+                hash256(&bytes)
+            },
+        };
+
+        let mut bytes = self.version.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&hash_prevouts);
+        bytes.extend_from_slice(&hash_sequence);
+        bytes.extend_from_slice(&signed.txid);
+        bytes.extend_from_slice(&signed.vout.to_le_bytes());
+        write_bytes(&mut bytes, script_code);
+        bytes.extend_from_slice(&value.to_le_bytes());
+        bytes.extend_from_slice(&signed.sequence.to_le_bytes());
+        bytes.extend_from_slice(&hash_outputs);
+        bytes.extend_from_slice(&self.lock_time.to_le_bytes());
+        bytes.extend_from_slice(&sighash_type.to_le_bytes());
+        Ok(Hash256::from(hash256(&bytes)))
+    }
+}
+
+/// The signature as it goes into a script or a witness: its DER encoding followed by the sighash type, in one byte.
+///
+/// s is replaced by n - s if it is the higher of the two, since nodes only relay signatures with the lower one, [BIP-146],
+/// so a third party can't change the signature, and the id of a legacy transaction, by negating s.
+///
+/// # Errors
+///
+/// This can fail with [UnsupportedCurve][EccError::UnsupportedCurve] if the signature isn't of secp256k1.
+///
+/// [BIP-146]: https://github.com/bitcoin/bips/blob/master/bip-0146.mediawiki
+pub fn encode_signature(signature: &Signature, sighash_type: u32) -> Result<Vec<u8>, EccError>{
+    let curve = signature.get_curve();
+    if *curve != Curve::secp256k1(){
+        return Err(EccError::UnsupportedCurve);
+    }
+    let n = curve.get_n();
+    let mut bytes = if *signature.get_s() > n >> 1{
+        let s: BigUint = n - signature.get_s();
+        Signature::new(signature.get_r().clone(), s, curve.clone(), signature.get_public().clone())?.to_der()
+    }else{
+        signature.to_der()
+    };
+    bytes.push(sighash_type as u8);
+    Ok(bytes)
+}
@@ -0,0 +1,219 @@
+//! Blind Schnorr signatures, where the signer signs a message without ever seeing it.
+//!
+//! The user blinds the message before sending it, and unblinds the answer into a normal Schnorr signature,
+//! that the signer can't link to the session it came from. It is how e-cash and anonymous credentials are issued:
+//! the bank signs a coin, but doesn't know which coin it signed when it is spent.
+//!
+//! With the private key x of the signer, its public key P = xG, and a message m, the protocol is:
+//! 1. the signer picks a random nonce k and sends R = kG, [Signer::new] and [Signer::commitment];
+//! 2. the user picks random α and β, computes R' = R + αG + βP and c' = H(R' || P || m),
+//!    and sends the blinded challenge c = c' + β, [Blinding::new] and [Blinding::challenge];
+//! 3. the signer answers s = k + cx, [Signer::sign];
+//! 4. the user checks that sG = R + cP, and unblinds it as s' = s + α, [Blinding::unblind].
+//!
+//! The signature is (R', s'), and it is valid because s'G = R + αG + cP = R + αG + βP + c'P = R' + c'P,
+//! which is what [BlindSignature::verify] checks. The signer only saw R, c and s, which are unrelated to R', c' and s',
+//! since α and β are random.
+//!
+//! The hash is the [tagged hash][crate::sha256::tagged_hash] `"mysha/blind-schnorr"` of the coordinates of R' and P, and the message.
+//!
+//! # Security
+//!
+//! A nonce must only be used once, so [Signer::sign] takes the signer by value. Blind Schnorr signatures are also
+//! broken by the [ROS attack] when the signer has many sessions open at once: a user with about 256 sessions open on secp256k1
+//! can get one more valid signature than the sessions. A signer must finish every session before starting the next one.
+//!
+//! # Examples
+//! ```
+//! use mysha::ecc::{*, blind::*};
+//!
+//! # fn main() -> Result<(), EccError>{
+//! let key_pair = KeyPair::new(1234_u32, Curve::secp256k1())?;
+//!
+//! // the signer commits to a nonce
+//! let signer = Signer::new(&key_pair.private())?;
+//! // the user blinds the message with it, and only sends the challenge
+//! let blinding = Blinding::new(&key_pair.public(), signer.commitment(), b"coin 42")?;
+//! // the signer signs the challenge, without knowing the message
+//! let s = signer.sign(blinding.challenge());
+//! // the user unblinds the answer
+//! let signature = blinding.unblind(&s)?;
+//!
+//! assert!(signature.verify(&key_pair.public(), b"coin 42"));
+//! assert!(!signature.verify(&key_pair.public(), b"coin 43"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [ROS attack]: https://eprint.iacr.org/2020/945
+
+use num_bigint::BigUint;
+
+use super::{EccError, Point, PrivKey, PubKey};
+use crate::rand;
+use crate::sha256::tagged_hash;
+
+/// Tag of the hash of the challenge.
+const TAG: &str = "mysha/blind-schnorr";
+
+/// The challenge c' = H(R' || P || m), as a number modulo n.
+fn challenge(nonce_point: &Point, public: &PubKey, message: &[u8]) -> Result<BigUint, EccError>{
+    let curve = public.get_curve();
+    // R' as a public key is its padded coordinates, and the point at infinity is rejected
+    let nonce_point = PubKey::new(nonce_point.clone(), curve.clone())?;
+    let hash = tagged_hash(TAG, &[nonce_point.to_bytes(), public.to_bytes(), message.to_vec()].concat());
+    Ok(BigUint::from_bytes_be(&hash.get_bytes()) % curve.get_n())
+}
+
+/// The side of the signer, a session with a nonce.
+///
+/// Created by [new][Signer::new], and used once by [sign][Signer::sign].
+#[derive(Debug)]
+pub struct Signer{
+    private: PrivKey,
+    nonce: BigUint,
+    commitment: Point,
+}
+
+impl Signer{
+    /// Starts a session with a random nonce k.
+    ///
+    /// # Errors
+    ///
+    /// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+    ///
+    /// [wrong]: crate::ecc::Curve#problematic-curves
+    pub fn new(private: &PrivKey) -> Result<Signer, EccError>{
+        let nonce = rand::random_scalar(private.get_curve());
+        let commitment = private.get_curve().multiply_generator(nonce.clone())?;
+        Ok(Signer{
+            private: private.clone(),
+            nonce,
+            commitment,
+        })
+    }
+
+    /// Returns R = kG, the commitment to the nonce, that is sent to the user.
+    pub fn commitment(&self) -> &Point{
+        &self.commitment
+    }
+
+    /// Signs the blinded challenge c from the user, returning s = k + cx modulo n.
+    ///
+    /// The signer is consumed, so the nonce can't sign a second challenge, which would reveal the private key.
+    pub fn sign(self, challenge: &BigUint) -> BigUint{
+        (self.nonce + challenge * self.private.get_private()) % self.private.get_curve().get_n()
+    }
+}
+
+/// The side of the user, the blinded message of a session.
+///
+/// Created by [new][Blinding::new], and turned into a signature by [unblind][Blinding::unblind].
+#[derive(Debug)]
+pub struct Blinding{
+    public: PubKey,
+    commitment: Point,
+    alpha: BigUint,
+    nonce_point: Point,
+    unblinded_challenge: BigUint,
+    blinded_challenge: BigUint,
+}
+
+impl Blinding{
+    /// Blinds the message with random α and β, for the commitment R of the signer with the public key.
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [NotOnCurve][EccError::NotOnCurve] if the commitment isn't a point of the curve of the key,
+    /// and with [PublicKeyOnInfinity][EccError::PublicKeyOnInfinity] if it is the point at infinity,
+    /// or if R' happens to be, which is as likely as guessing the private key.
+    pub fn new(public: &PubKey, commitment: &Point, message: &[u8]) -> Result<Blinding, EccError>{
+        let curve = public.get_curve();
+        if !curve.is_on_curve(commitment){
+            return Err(EccError::NotOnCurve);
+        }
+        if commitment.get_xy().is_none(){
+            return Err(EccError::PublicKeyOnInfinity);
+        }
+        let alpha = rand::random_scalar(curve);
+        let beta = rand::random_scalar(curve);
+
+        // R' = R + αG + βP
+        let blinded = curve.add(&curve.multiply_generator(alpha.clone())?, &curve.multiply(public.get_public(), beta.clone())?)?;
+        let nonce_point = curve.add(commitment, &blinded)?;
+        let challenge = challenge(&nonce_point, public, message)?;
+        let blinded_challenge = (&challenge + beta) % curve.get_n();
+
+        Ok(Blinding{
+            public: public.clone(),
+            commitment: commitment.clone(),
+            alpha,
+            nonce_point,
+            unblinded_challenge: challenge,
+            blinded_challenge,
+        })
+    }
+
+    /// Returns the blinded challenge c = c' + β, that is sent to the signer.
+    pub fn challenge(&self) -> &BigUint{
+        &self.blinded_challenge
+    }
+
+    /// Returns the challenge c' of the signature, that the signer never sees.
+    pub fn unblinded_challenge(&self) -> &BigUint{
+        &self.unblinded_challenge
+    }
+
+    /// Checks the answer s of the signer, sG = R + cP, and unblinds it into the signature (R', s + α).
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [InvalidSignature][EccError::InvalidSignature] if the answer isn't the one of the challenge,
+    /// which would also give an invalid signature.
+    pub fn unblind(self, s: &BigUint) -> Result<BlindSignature, EccError>{
+        let curve = self.public.get_curve();
+        let n = curve.get_n();
+        let expected = curve.add(&self.commitment, &curve.multiply(self.public.get_public(), self.blinded_challenge.clone())?)?;
+        if s >= n || curve.multiply_generator(s.clone())? != expected{
+            return Err(EccError::InvalidSignature);
+        }
+        Ok(BlindSignature{
+            nonce_point: self.nonce_point,
+            s: (s + self.alpha) % n,
+        })
+    }
+}
+
+/// A signature from the blind protocol, a Schnorr signature (R', s') of the message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlindSignature{
+    nonce_point: Point,
+    s: BigUint,
+}
+
+impl BlindSignature{
+    /// Returns the nonce point R' of the signature
+    pub fn get_r(&self) -> &Point{
+        &self.nonce_point
+    }
+
+    /// Returns the value of s' of the signature
+    pub fn get_s(&self) -> &BigUint{
+        &self.s
+    }
+
+    /// Checks that the message was signed by the key, s'G = R' + c'P.
+    pub fn verify(&self, public: &PubKey, message: &[u8]) -> bool{
+        let curve = public.get_curve();
+        if &self.s >= curve.get_n() || !curve.is_on_curve(&self.nonce_point){
+            return false;
+        }
+        let Ok(challenge) = challenge(&self.nonce_point, public, message) else{
+            return false;
+        };
+        let expected = curve.multiply(public.get_public(), challenge).and_then(|cp| curve.add(&self.nonce_point, &cp));
+        let actual = curve.multiply_generator(self.s.clone());
+        matches!((actual, expected), (Ok(actual), Ok(expected)) if actual == expected)
+    }
+}
+
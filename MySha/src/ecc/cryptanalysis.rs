@@ -0,0 +1,245 @@
+//! Attacks on ECDSA signatures made the wrong way, to show why the rules of the nonce matter.
+//!
+//! A signature is `s = k⁻¹ (z + r d) mod n`, with z the hash, d the private key and k the nonce, whose point kG gives r.
+//! The equation is linear in k and d, so the nonce must be secret, random and never used twice:
+//! - knowing the nonce of a signature gives the key, `d = (s k - z) / r`;
+//! - two signatures with the same nonce, which share r, give two equations in the same two unknowns,
+//!   whose difference is `s1 - s2 = k⁻¹ (z1 - z2)`, so `k = (z1 - z2) / (s1 - s2)`, and the key follows from either.
+//!
+//! That is how the signing key of the PlayStation 3 was recovered in 2010, and how bitcoins were stolen from wallets
+//! whose random generator repeated. The signatures of this crate draw a new random nonce every time,
+//! [sign_with_nonce] is only here to reproduce the mistake.
+//!
+//! The formulas of addition and doubling use a and p, but never b, so an implementation that doesn't check that the points
+//! it receives are on its curve computes on whatever curve y² = x³ + ax + b' the point is on. [invalid_curve_points] finds points
+//! of small prime orders on such curves: ECDH of the private key with a point of order q only has q possible results,
+//! which give the key modulo q, and the Chinese remainder theorem joins enough of them into the whole key.
+//! [PubKey::new][super::PubKey::new] and the operations of [Curve] reject those points.
+//!
+//! # Examples
+//! ```
+//! use mysha::ecc::{*, cryptanalysis::*};
+//! use num_bigint::BigUint;
+//!
+//! # fn main() -> Result<(), EccError>{
+//! let private = PrivKey::new(123456789_u32, Curve::secp256k1())?;
+//! let nonce = BigUint::from(42_u32);
+//!
+//! let first = sign_with_nonce(&private, b"first message", &nonce)?;
+//! let second = sign_with_nonce(&private, b"second message", &nonce)?;
+//! assert_eq!(first.get_r(), second.get_r());
+//!
+//! // anyone with both signatures and messages gets the key
+//! assert_eq!(recover_from_reused_nonce(&first, &second, b"first message", b"second message")?, private);
+//! assert_eq!(recover_nonce(&first, &second, b"first message", b"second message")?, nonce);
+//! # Ok(())
+//! # }
+//! ```
+
+use num_bigint::{BigInt, BigUint};
+
+use super::{hash_number, in_range, sign_hash_with_nonce, Curve, EccError, Point, PrivKey, Signature};
+use super::ecc_math::{get_mod, mod_inv, ToInt};
+use super::security::{count_points, factor};
+use super::toy::{pow_mod, random_below};
+use crate::sha256::Sha256;
+
+/// Curves with a larger p are rejected by [invalid_curve_points], their points are counted one x at a time.
+const MAX_P: u64 = 1 << 20;
+/// Random points tried on an invalid curve for one of a given order, before giving up on it.
+const POINT_TRIES: usize = 16;
+
+/// Signs the sha256 of the message with the nonce given instead of a random one, INSECURE.
+///
+/// The signature is the same as [PrivKey::sign_digest] with [Sha256] would give if it drew that nonce,
+/// so it verifies like any other, but whoever knows the nonce, or sees it used twice, can recover the private key.
+///
+/// # Errors
+///
+/// This can fail with [InvalidSignature][EccError::InvalidSignature] if the nonce isn't from 1 to n - 1,
+/// and like [PrivKey::sign_digest] if there is something wrong with the curve.
+pub fn sign_with_nonce(private: &PrivKey, message: &[u8], nonce: &BigUint) -> Result<Signature, EccError>{
+    let curve = private.get_curve();
+    if !in_range(nonce, curve.get_n()){
+        return Err(EccError::InvalidSignature);
+    }
+    let (r, s, _) = sign_hash_with_nonce(private.get_private(), curve, hash_number::<Sha256>(message), nonce, None)?;
+    Ok(Signature{
+        r,
+        s,
+        curve: curve.clone(),
+        public: curve.multiply_generator(private.get_private().to_int())?,
+    })
+}
+
+/// Solves the nonce and the private key of two signatures of the same key with the same nonce,
+/// keeping only a key whose public key is the one of the signatures.
+fn solve(first: &Signature, second: &Signature, first_message: &[u8], second_message: &[u8]) -> Result<(BigUint, BigUint), EccError>{
+    if first.curve != second.curve || first.public != second.public || first.r != second.r{
+        return Err(EccError::NotRecoverable);
+    }
+    let curve = &first.curve;
+    let n = curve.get_n().to_int();
+    let (z1, z2) = (hash_number::<Sha256>(first_message), hash_number::<Sha256>(second_message));
+    let (r, s1) = (first.r.to_int(), first.s.to_int());
+
+    // signers that keep the lower s negate it, and the nonce with it, so the second nonce is k or -k
+    for s2 in [second.s.to_int(), &n - second.s.to_int()]{
+        let difference = get_mod(&(&s1 - &s2), &n)?;
+        // the same hash signed twice with the same nonce gives the same signature, and no second equation
+        if difference == BigInt::from(0_u8){
+            continue;
+        }
+        let nonce = get_mod(&((&z1 - &z2) * mod_inv(&difference, &n)?), &n)?;
+        let private = get_mod(&((&s1 * &nonce - &z1) * mod_inv(&r, &n)?), &n)?;
+        if private != BigInt::from(0_u8) && curve.multiply_generator(private.clone())? == first.public{
+            return Ok((nonce.to_uint(), private.to_uint()));
+        }
+    }
+    Err(EccError::NotRecoverable)
+}
+
+/// Recovers the nonce shared by two signatures of the same key, of the sha256 of different messages.
+///
+/// It is the nonce of the first signature, or its negative modulo n if its s was replaced by n - s.
+///
+/// # Errors
+///
+/// Same as [recover_from_reused_nonce].
+pub fn recover_nonce(first: &Signature, second: &Signature, first_message: &[u8], second_message: &[u8]) -> Result<BigUint, EccError>{
+    Ok(solve(first, second, first_message, second_message)?.0)
+}
+
+/// Recovers the private key of two signatures of the same key made with the same nonce, of the sha256 of different messages.
+///
+/// Signatures that share a nonce are easy to spot, they have the same r. The key found is checked against the public key
+/// of the signatures, so a wrong message gives an error rather than a wrong key.
+///
+/// # Errors
+///
+/// This can fail with [NotRecoverable][EccError::NotRecoverable] if the signatures have different r, curves or public keys,
+/// if they are of the same hash, or if the messages aren't the ones signed.
+pub fn recover_from_reused_nonce(first: &Signature, second: &Signature, first_message: &[u8], second_message: &[u8]) -> Result<PrivKey, EccError>{
+    let (_, private) = solve(first, second, first_message, second_message)?;
+    PrivKey::new(private, first.curve.clone())
+}
+
+/// A point of small prime order on an invalid curve, with the a and p of the attacked curve but another b.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCurvePoint{
+    /// b of the invalid curve y² = x³ + ax + b.
+    pub b: i32,
+    /// The point, which is on the invalid curve and not on the attacked one.
+    pub point: Point,
+    /// Prime order of the point.
+    pub order: u64,
+}
+
+/// Square root modulo the prime p, with Tonelli-Shanks, if the number has one.
+fn sqrt_mod(number: u64, p: u64) -> Option<u64>{
+    if number == 0 || p == 2{
+        return Some(number);
+    }
+    if pow_mod(number, (p - 1) / 2, p) != 1{
+        return None;
+    }
+    // p - 1 = q 2^s with q odd, and z a number without a square root
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|z| pow_mod(*z, (p - 1) / 2, p) == p - 1)?;
+
+    let (mut m, mut c, mut t, mut root) = (s, pow_mod(z, q, p), pow_mod(number, q, p), pow_mod(number, q.div_ceil(2), p));
+    while t != 1{
+        let mut i = 0;
+        let mut square = t;
+        while square != 1{
+            square = square * square % p;
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        (m, c) = (i, b * b % p);
+        t = t * c % p;
+        root = root * b % p;
+    }
+    Some(root)
+}
+
+/// A random point of the curve with that number of points, multiplied into the subgroup of the order, if one is found.
+fn point_of_order(a: i32, b: i32, p: u64, points: u64, order: u64) -> Result<Option<Point>, EccError>{
+    let a_mod = (a as i64).rem_euclid(p as i64) as u64;
+    for _ in 0..POINT_TRIES{
+        let x = random_below(p);
+        let Some(y) = sqrt_mod((pow_mod(x, 3, p) + a_mod * x + b as u64) % p, p) else{
+            continue;
+        };
+        // every point times the number of points is the point at infinity, so that is a valid order for the curve
+        let invalid = Curve::new(a, b, p, points, Point::point(x, y))?;
+        let point = invalid.multiply(invalid.get_g(), points / order)?;
+        if point != Point::PointAtInfinity{
+            return Ok(Some(point));
+        }
+    }
+    Ok(None)
+}
+
+/// Finds points of different prime orders, up to the maximum, on the curves with the a and p of the curve and every other b,
+/// until the product of their orders is larger than n, so the results of ECDH with them give a whole private key.
+///
+/// Points are only found if the maximum is large enough, with fewer points the product may stay below n.
+///
+/// # Examples
+/// ```
+/// use mysha::ecc::{*, cryptanalysis::*};
+/// use num_bigint::BigUint;
+///
+/// # fn main() -> Result<(), EccError>{
+/// let curve = Curve::random_small(16)?;
+/// let points = invalid_curve_points(&curve, 1000)?;
+///
+/// let product = points.iter().fold(BigUint::from(1_u8), |product, found| product * found.order);
+/// assert!(product > *curve.get_n());
+/// for found in points{
+///     // a public key must be on the curve, so the point is rejected
+///     assert!(PubKey::new(found.point, curve.clone()).is_err());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This fails with [InvalidBits][EccError::InvalidBits] if p has more than 20 bits.
+pub fn invalid_curve_points(curve: &Curve, max_order: u64) -> Result<Vec<InvalidCurvePoint>, EccError>{
+    let p = match u64::try_from(curve.get_p()){
+        Ok(p) if p < MAX_P => p,
+        _ => return Err(EccError::InvalidBits),
+    };
+    let a = curve.get_a();
+    let a_cubed = (a as i64).rem_euclid(p as i64).pow(3) % p as i64;
+    let mut found: Vec<InvalidCurvePoint> = Vec::new();
+    let mut product = BigUint::from(1_u8);
+    // p has at most 20 bits, so every b fits in an i32
+    for b in 0..p as i32{
+        if product > *curve.get_n(){
+            break;
+        }
+        // 4a³ + 27b² = 0 is a singular curve
+        if (b as i64 - curve.get_b() as i64).rem_euclid(p as i64) == 0 || (4 * a_cubed + 27 * (b as i64).pow(2)) % p as i64 == 0{
+            continue;
+        }
+        let points = count_points(a, b, p);
+        let mut orders = factor(&BigUint::from(points));
+        orders.dedup();
+        for order in orders{
+            let order = u64::try_from(&order).unwrap();
+            if order > max_order || found.iter().any(|point| point.order == order){
+                continue;
+            }
+            if let Some(point) = point_of_order(a, b, p, points, order)?{
+                found.push(InvalidCurvePoint{b, point, order});
+                product *= order;
+            }
+        }
+    }
+    Ok(found)
+}
@@ -1,7 +1,11 @@
 use std::fmt;
-use num_bigint::{BigInt, BigUint, ToBigInt};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
+use num_bigint::{BigInt, BigUint};
 use num_traits::Num;
 
+use super::trace::{Event, Observer};
+
 /// The error type implemented for this module, with all possible errors that can occur in ecc operations.
 #[derive(Debug)]
 pub enum EccError{
@@ -36,6 +40,22 @@ pub enum EccError{
     NotPrime,
     /// Happens when the signature provided isn't valid
     InvalidSignature,
+    /// Happens when a [random small curve][Curve::random_small] is asked with a size that isn't from 4 to 20 bits
+    InvalidBits,
+    /// Happens when a curve has no equivalent in the model it is converted to, like short Weierstrass curves without a point of order 2
+    /// in the Montgomery model, or when the parameters of the equivalent don't fit the types of the model
+    NotConvertible,
+    /// Happens when the bytes of a coordinate don't have the size of the numbers modulo p of the curve
+    InvalidLength,
+    /// Happens when an operation that only exists for secp256k1, like [ethereum][crate::ecc::ethereum] signatures, is used with another curve
+    UnsupportedCurve,
+    /// Happens when a curve is asked [by name][Curve::from_name] with a name that isn't one of [CURVE_NAMES][super::CURVE_NAMES]
+    UnknownCurve,
+    /// Happens when a [bitcoin][crate::ecc::bitcoin] transaction can't be read from its bytes, or doesn't have the input that is signed
+    InvalidTransaction,
+    /// Happens when the private key can't be [recovered][crate::ecc::cryptanalysis] from signatures,
+    /// because they don't share their nonce, or are of the same hash
+    NotRecoverable,
 }
 
 impl fmt::Display for EccError{
@@ -51,6 +71,13 @@ impl fmt::Display for EccError{
             EccError::InvalidOrderN => write!(f, "Invalid order of curve, parameter n,"),
             EccError::NotPrime => write!(f, "Modulo p and the order n of the curve must be prime"),
             EccError::InvalidSignature => write!(f, "Invalid signature."),
+            EccError::InvalidBits => write!(f, "Small curves must have from 4 to 20 bits."),
+            EccError::NotConvertible => write!(f, "The curve has no equivalent in the other model."),
+            EccError::InvalidLength => write!(f, "Coordinate bytes must have the size of the modulo p of the curve."),
+            EccError::UnsupportedCurve => write!(f, "Operation only supported on the secp256k1 curve."),
+            EccError::UnknownCurve => write!(f, "There is no curve with that name."),
+            EccError::InvalidTransaction => write!(f, "Invalid bitcoin transaction."),
+            EccError::NotRecoverable => write!(f, "The private key can't be recovered from the signatures."),
         }
     }
 }
@@ -62,6 +89,35 @@ pub fn get_mod(x: &BigInt, p: &BigInt) -> Result<BigInt, EccError>{
     Ok(((x % p) + p) % p) // Rust % is the remainder not mod
 }
 
+/// Conversions of the numbers of the formulas that can't fail, instead of the [Option] of [ToBigInt][num_bigint::ToBigInt] and [ToBigUint][num_bigint::ToBigUint],
+/// so a malformed input can't reach an unwrap.
+pub(crate) trait ToInt{
+    /// The number as a [BigInt].
+    fn to_int(&self) -> BigInt;
+    /// The number as a [BigUint], for numbers reduced modulo p with [get_mod], which are never negative.
+    fn to_uint(&self) -> BigUint;
+}
+
+impl ToInt for BigUint{
+    fn to_int(&self) -> BigInt{
+        BigInt::from(self.clone())
+    }
+
+    fn to_uint(&self) -> BigUint{
+        self.clone()
+    }
+}
+
+impl ToInt for BigInt{
+    fn to_int(&self) -> BigInt{
+        self.clone()
+    }
+
+    fn to_uint(&self) -> BigUint{
+        self.magnitude().clone()
+    }
+}
+
 pub fn mod_inv(a0: &BigInt, p: &BigInt) -> Result<BigInt, EccError>{
     if a0 == &BigInt::from(0){
         return Err(EccError::DivisionByZero);
@@ -102,7 +158,7 @@ pub fn mod_inv(a0: &BigInt, p: &BigInt) -> Result<BigInt, EccError>{
 /// println!("{:?}", point);
 /// ```
 /// [identity element]: https://en.wikipedia.org/wiki/Identity_element
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum Point{
     Point{
         x: BigUint,
@@ -128,6 +184,7 @@ impl Point{
     /// let p = Point::point(x, y);
     /// println!("{:?}", p);
     /// ```
+    #[allow(clippy::self_named_constructors)]
     pub fn point<T: Into<BigUint>>(x: T, y:T) -> Point{
         Point::Point { x: x.into(), y: y.into() }
     }
@@ -193,10 +250,10 @@ impl Point{
         }
     }
 
-    fn point_neg<T: Into<BigInt>>(&self, prime: T) -> Result<Point, EccError>{
+    pub(super) fn point_neg<T: Into<BigInt>>(&self, prime: T) -> Result<Point, EccError>{
         let prime: BigInt = prime.into();
         match self{
-            Point::Point{x, y} => Ok(Point::Point{x: x.clone(), y: (get_mod(&-y.to_bigint().unwrap(), &prime)?).to_biguint().unwrap()}),
+            Point::Point{x, y} => Ok(Point::Point{x: x.clone(), y: (get_mod(&-y.to_int(), &prime)?).to_uint()}),
             Point::PointAtInfinity => Ok(Point::PointAtInfinity),
         }
     }
@@ -218,7 +275,7 @@ impl Point{
 /// - the parameter "p" defines the field of the elliptic curve, so the elliptic curve is (mod p);
 /// - the parameter "g" is the curves Generator point, it is a [Point] that is used to start the ecc operations.
 /// - the parameter "n" is the order of the subgroup generated by the curve and the generator point, 
-/// it is a result of the other parameters, but it needs to be calculated and provided.
+///   it is a result of the other parameters, but it needs to be calculated and provided.
 /// 
 /// # Problematic curves
 /// It is worth pointing out, that when creating a curve it is possible to create a problematic curve, despite the verifications made to ensure the curve is valid.
@@ -228,16 +285,58 @@ impl Point{
 /// Problematic curves aren't fit for cryptography, and can cause an [NotPrime][EccError::NotPrime] when doing operations with them, to fix that 
 /// make sure your curve has prime parameters n and p, and it is a valid curve.
 /// 
+/// # Cost
+/// 
+/// The parameters are shared behind an [Arc], so cloning a curve only increments a counter, and [secp256k1][Curve::secp256k1]
+/// is only parsed the first time it's called, later calls return a clone of that one.
+/// 
 /// 
 /// [secp256k1]: https://www.secg.org/sec2-v2.pdf#Recommended%20Parameters%20secp256k1
 /// [elliptic curve]: https://en.wikipedia.org/wiki/Elliptic_curve
-#[derive(Debug, Clone)]
-pub struct Curve{
+#[derive(Clone)]
+pub struct Curve(Arc<Parameters>);
+
+/// The parameters of a [Curve], shared by its clones.
+struct Parameters{
     a: i32,
     b: i32,
     p: BigUint,
     n: BigUint,
     g: Point,
+    /// `2^i g` for every bit of n, filled by [precompute_generator][Curve::precompute_generator].
+    generator_table: OnceLock<Vec<Point>>,
+}
+
+// curves are equal when their parameters are, whether the generator table was built or not
+impl PartialEq for Curve{
+    fn eq(&self, other: &Curve) -> bool{
+        let (a, b) = (&self.0, &other.0);
+        Arc::ptr_eq(a, b) || (a.a == b.a && a.b == b.b && a.p == b.p && a.n == b.n && a.g == b.g)
+    }
+}
+
+impl Eq for Curve{}
+
+impl Hash for Curve{
+    fn hash<H: Hasher>(&self, state: &mut H){
+        self.0.a.hash(state);
+        self.0.b.hash(state);
+        self.0.p.hash(state);
+        self.0.n.hash(state);
+        self.0.g.hash(state);
+    }
+}
+
+impl fmt::Debug for Curve{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        f.debug_struct("Curve")
+            .field("a", &self.0.a)
+            .field("b", &self.0.b)
+            .field("p", &self.0.p)
+            .field("n", &self.0.n)
+            .field("g", &self.0.g)
+            .finish()
+    }
 }
 
 impl Curve{
@@ -264,7 +363,7 @@ impl Curve{
             return Err(EccError::GeneratorOnInfinity);
         }
         
-        if get_mod(&BigInt::from(4 * a.pow(3) + 27 * b.pow(2)), &p.to_bigint().unwrap())? == BigInt::from(0){
+        if get_mod(&(4 * BigInt::from(a).pow(3) + 27 * BigInt::from(b).pow(2)), &p.to_int())? == BigInt::from(0){
             return Err(EccError::SingularCurve);
         }
 
@@ -272,18 +371,19 @@ impl Curve{
             return Err(EccError::InvalidOrderN);
         }
 
-        let curve = Curve{
+        let curve = Curve(Arc::new(Parameters{
             a,
             b,
             p,
             n,
             g,
-        };
+            generator_table: OnceLock::new(),
+        }));
 
-        if curve.multiply(&curve.g, curve.n.to_bigint().unwrap())? != Point::PointAtInfinity{
+        if curve.multiply(curve.get_g(), curve.get_n().to_int())? != Point::PointAtInfinity{
             return Err(EccError::InvalidOrderN)
         }
-        if ! curve.is_on_curve(&curve.g){
+        if ! curve.is_on_curve(curve.get_g()){
             return Err(EccError::GeneratorNotOnCurve);
         }
 
@@ -292,34 +392,40 @@ impl Curve{
 
     /// Returns the value of the [parameter](#parameters) "a"
     pub fn get_a(&self) -> i32{
-        self.a
+        self.0.a
     }
 
     /// Returns the value of the [parameter](#parameters) "b"
     pub fn get_b(&self) -> i32{
-        self.b
+        self.0.b
     }
 
     /// Returns the value of the [parameter](#parameters) "p"
     pub fn get_p(&self) -> &BigUint{
-        &self.p
+        &self.0.p
     }
 
     /// Returns the value of the [parameter](#parameters) "n"
     pub fn get_n(&self) -> &BigUint{
-        &self.n
+        &self.0.n
     }
 
     /// Returns the [generator point](#parameters)
     pub fn get_g(&self) -> &Point{
-        &self.g
+        &self.0.g
+    }
+
+    /// Returns the number of bytes needed for a number modulo p, the size of each coordinate in byte encodings
+    pub fn coordinate_size(&self) -> usize{
+        self.0.p.bits().div_ceil(8) as usize
     }
 
     /// Returns a [Curve] with the [secp256k1] specs
     /// 
     /// [secp256k1]: https://www.secg.org/sec2-v2.pdf#Recommended%20Parameters%20secp256k1
     pub fn secp256k1() -> Curve{
-        Curve{
+        static SECP256K1: OnceLock<Curve> = OnceLock::new();
+        SECP256K1.get_or_init(|| Curve(Arc::new(Parameters{
             a: 0,
             b: 7,
             p: BigUint::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap(),
@@ -328,7 +434,8 @@ impl Curve{
                 x: BigUint::from_str_radix("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap(),
                 y: BigUint::from_str_radix("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap(),
             },
-        }
+            generator_table: OnceLock::new(),
+        }))).clone()
     }
 
     /// Returns a [bool] value that indicates wether the point provided is on the curve
@@ -347,10 +454,10 @@ impl Curve{
     pub fn is_on_curve(&self, p: &Point) -> bool{
         match p{
             Point::Point{x, y} => {
-                let x = x.to_bigint().unwrap();
-                let y = y.to_bigint().unwrap();
-                let prime = self.p.to_bigint().unwrap();
-                (y.pow(2) - x.pow(3) -  &x * self.a - self.b) % prime == BigInt::from(0)
+                let x = x.to_int();
+                let y = y.to_int();
+                let prime = self.0.p.to_int();
+                (y.pow(2) - x.pow(3) -  &x * self.0.a - self.0.b) % prime == BigInt::from(0)
             },
             Point::PointAtInfinity => true,
         }
@@ -376,42 +483,75 @@ impl Curve{
     /// 
     /// [something wrong]: #problematic-curves
     pub fn add(&self, p: &Point, q: &Point) -> Result<Point, EccError>{
+        self.add_observed(p, q, None)
+    }
+
+    /// Adds two [points][Point] like [add][Curve::add], giving the [Event::Add] to the observer,
+    /// or the [Event::Double] if the points are equal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::{*, trace::Event};
+    /// # fn main() -> Result<(), EccError>{
+    /// # let c = Curve::new(2, 3, 97_u32, 50_u32, Point::point(0_u32, 10_u32))?;
+    /// let mut slope = None;
+    /// c.add_traced(&Point::point(17_u32, 10_u32), &Point::point(95_u32, 31_u32), &mut |event: &Event| {
+    ///     if let Event::Add{slope: s, ..} = event{
+    ///         slope = s.clone();
+    ///     }
+    /// })?;
+    /// // (10 - 31) / (17 - 95) mod 97
+    /// assert_eq!(slope, Some(4_u32.into()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// Same as [add][Curve::add].
+    pub fn add_traced(&self, p: &Point, q: &Point, observer: &mut impl Observer) -> Result<Point, EccError>{
+        self.add_observed(p, q, Some(observer))
+    }
+
+    pub(super) fn add_observed(&self, p: &Point, q: &Point, observer: Option<&mut (dyn Observer + '_)>) -> Result<Point, EccError>{
         if !(self.is_on_curve(p) && self.is_on_curve(q)){
             return Err(EccError::NotOnCurve)
         }
         
         if p == q{
-            return self.double(p);
+            return self.double_observed(p, observer);
         }
-        match p{
+        let (result, slope) = match p{
             Point::Point{x: px, y: py} => {
-                let px = px.to_bigint().unwrap();
-                let py = py.to_bigint().unwrap();
+                let px = px.to_int();
+                let py = py.to_int();
                 match q{
                     Point::Point{x: qx, y: qy} => {
-                        let prime = self.p.to_bigint().unwrap();
-                        let qx = qx.to_bigint().unwrap();
-                        let qy = qy.to_bigint().unwrap();
+                        let prime = self.0.p.to_int();
+                        let qx = qx.to_int();
+                        let qy = qy.to_int();
                         if px == qx{
-                            return Ok(Point::PointAtInfinity);
-                        }
-
-                        let slope = get_mod(&((&py - &qy) * mod_inv(&(&px - &qx), &prime)?), &prime)?;
+                            (Point::PointAtInfinity, None)
+                        }else{
+                            let slope = get_mod(&((&py - &qy) * mod_inv(&(&px - &qx), &prime)?), &prime)?;
 
-                        let x = get_mod(&(slope.pow(2) - &px - &qx), &prime)?;
+                            let x = get_mod(&(slope.pow(2) - &px - &qx), &prime)?;
 
-                        let y = get_mod(&(&slope * (&px - &x) - &py), &prime)?;
+                            let y = get_mod(&(&slope * (&px - &x) - &py), &prime)?;
 
-                        Ok(Point::Point{
-                            x: x.try_into().unwrap(),
-                            y: y.try_into().unwrap(),
-                        })
+                            (Point::Point{
+                                x: x.to_uint(),
+                                y: y.to_uint(),
+                            }, Some(slope))
+                        }
                     },
-                    Point::PointAtInfinity => Ok(p.clone()),
+                    Point::PointAtInfinity => (p.clone(), None),
                 }
             },
-            Point::PointAtInfinity => Ok(q.clone()),
+            Point::PointAtInfinity => (q.clone(), None),
+        };
+        if let Some(observer) = observer{
+            observer.observe(&Event::Add{p: p.clone(), q: q.clone(), slope: slope.map(|slope| slope.to_uint()), result: result.clone()});
         }
+        Ok(result)
     }
 
     /// Doubles a [Point] on the [Curve]
@@ -434,29 +574,45 @@ impl Curve{
     /// 
     /// [problem]: #problematic-curves
     pub fn double(&self, p: &Point) -> Result<Point, EccError>{
+        self.double_observed(p, None)
+    }
+
+    /// Doubles a [Point] like [double][Curve::double], giving the [Event::Double] to the observer.
+    ///
+    /// # Errors
+    /// Same as [double][Curve::double].
+    pub fn double_traced(&self, p: &Point, observer: &mut impl Observer) -> Result<Point, EccError>{
+        self.double_observed(p, Some(observer))
+    }
+
+    pub(super) fn double_observed(&self, p: &Point, observer: Option<&mut (dyn Observer + '_)>) -> Result<Point, EccError>{
         if !self.is_on_curve(p){
             return Err(EccError::NotOnCurve);
         }
 
-        match p{
+        let (result, slope) = match p{
             Point::Point{x, y} => {
-                let x = x.to_bigint().unwrap();
-                let y = y.to_bigint().unwrap();
+                let x = x.to_int();
+                let y = y.to_int();
                 if y == BigInt::from(0){
-                    return Ok(Point::PointAtInfinity);
+                    (Point::PointAtInfinity, None)
+                }else{
+                    let prime = self.0.p.to_int();
+                    let slope = get_mod(&((x.pow(2) * 3 + self.0.a) * mod_inv(&(2 * &y), &prime)?), &prime)?;
+                    let x1 = get_mod(&(slope.pow(2) - 2 * &x), &prime)?;
+                    let y1 = get_mod(&(&slope * (&x - &x1) - &y), &prime)?;
+                    (Point::Point {
+                        x: x1.to_uint(),
+                        y: y1.to_uint(),
+                    }, Some(slope))
                 }
-                let prime = self.p.to_bigint().unwrap();
-                let slope = get_mod(&((x.pow(2) * 3 + self.a) * mod_inv(&(2 * &y), &prime)?), &prime)?;
-                let x1 = get_mod(&(slope.pow(2) - 2 * &x), &prime)?;
-                let y1 = get_mod(&(&slope * (&x - &x1) - &y), &prime)?;
-                Ok(Point::Point {
-                    x: x1.try_into().unwrap(),
-                    y: y1.try_into().unwrap(),
-                })
             },
-            Point::PointAtInfinity => Ok(Point::PointAtInfinity),
+            Point::PointAtInfinity => (Point::PointAtInfinity, None),
+        };
+        if let Some(observer) = observer{
+            observer.observe(&Event::Double{p: p.clone(), slope: slope.map(|slope| slope.to_uint()), result: result.clone()});
         }
-        
+        Ok(result)
     }
 
     /// Multiples a [Point] with a scalar number, on the [Curve]
@@ -480,26 +636,168 @@ impl Curve{
     /// This can fail if the Point provided isn't on the curve, or if there is a [problem] with the curve. 
     /// 
     /// [problem]: #problematic-curves
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
     pub fn multiply<T: Into<BigInt>>(&self, p: &Point, k: T) -> Result<Point, EccError>{
-        let k: BigInt = k.into();
-        if &k == &BigInt::from(0){
+        self.multiply_observed(p, k.into(), None)
+    }
+
+    /// Multiplies a [Point] like [multiply][Curve::multiply], giving the observer an [Event::Bit] for every bit of the scalar
+    /// after the top one, followed by the [Event::Double] and, if the bit is one, the [Event::Add] it leads to.
+    ///
+    /// See the [trace][super::trace] module for an example.
+    ///
+    /// # Errors
+    /// Same as [multiply][Curve::multiply].
+    pub fn multiply_traced<T: Into<BigInt>>(&self, p: &Point, k: T, observer: &mut impl Observer) -> Result<Point, EccError>{
+        self.multiply_observed(p, k.into(), Some(observer))
+    }
+
+    pub(super) fn multiply_observed(&self, p: &Point, k: BigInt, mut observer: Option<&mut (dyn Observer + '_)>) -> Result<Point, EccError>{
+        // only the size of the scalar, that can be a private key or a nonce
+        #[cfg(feature = "tracing")]
+        tracing::debug!(bits = k.bits(), "double and add");
+        if k == BigInt::from(0){
             return Ok(Point::PointAtInfinity);
         }
 
         let mut p = p.clone();
         let mut bits = format!("{:b}", k);
-        if &k < &BigInt::from(0){
-            p = p.point_neg( self.p.to_bigint().unwrap())?;
+        if k < BigInt::from(0){
+            p = p.point_neg( self.0.p.to_int())?;
             bits = format!("{:b}", -k);
         }
         let mut current = p.clone();
-        for i in bits[1..].chars(){
-            current = self.double(&current)?;
+        for (index, i) in bits[1..].chars().enumerate().map(|(index, i)| ((bits.len() - 2 - index) as u64, i)){
+            if let Some(observer) = observer.as_deref_mut(){
+                observer.observe(&Event::Bit{index, one: i == '1'});
+            }
+            current = self.double_observed(&current, observer.as_deref_mut())?;
             if i == '1'{
-                current = self.add(&current, &p)?;
+                current = self.add_observed(&current, &p, observer.as_deref_mut())?;
             }
         }
         Ok(current)
     }
 
+    /// Multiplies a [Point] by a scalar with the Montgomery ladder, doing the same operations for every scalar.
+    ///
+    /// [multiply][Curve::multiply] only adds for the bits that are one, so its time tells how many ones the scalar has,
+    /// and which ones with finer measurements. The ladder keeps two points that differ by p and does one addition and one doubling
+    /// for every bit, and the scalar is first made k mod n plus n or 2n, so every scalar has the bits of n plus one.
+    /// The big numbers of the coordinates still take different times for different values, so it is only closer to constant time.
+    ///
+    /// The result is the same as [multiply][Curve::multiply] for points of the subgroup of the generator,
+    /// which is every point on curves of cofactor 1, like secp256k1.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let curve = Curve::secp256k1();
+    /// assert_eq!(curve.multiply_ladder(curve.get_g(), 1234)?, curve.multiply(curve.get_g(), 1234)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// Same as [multiply][Curve::multiply].
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn multiply_ladder<T: Into<BigInt>>(&self, p: &Point, k: T) -> Result<Point, EccError>{
+        if !self.is_on_curve(p){
+            return Err(EccError::NotOnCurve);
+        }
+        let n = self.0.n.to_int();
+        let mut k = get_mod(&k.into(), &n)? + &n;
+        if k.bits() <= n.bits(){
+            k += &n;
+        }
+        let k = k.to_uint();
+
+        // the top bit is always one, so the ladder starts at p and 2p, never at the point at infinity
+        let (mut low, mut high) = (p.clone(), self.double(p)?);
+        for i in (0..k.bits() - 1).rev(){
+            if k.bit(i){
+                low = self.add(&low, &high)?;
+                high = self.double(&high)?;
+            }else{
+                high = self.add(&low, &high)?;
+                low = self.double(&low)?;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Builds a table of the generator multiplied by every power of 2 below n, used by [multiply_generator][Curve::multiply_generator].
+    ///
+    /// The table is shared by every clone of the curve, including the ones inside keys and signatures, and every later call of
+    /// [secp256k1][Curve::secp256k1], so it only has to be built once, and after that multiplying the generator takes only additions, about half the work of [multiply][Curve::multiply].
+    /// It is worth it when many keys are created or many messages are signed with the same curve. Calling it again does nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let curve = Curve::secp256k1();
+    /// curve.precompute_generator()?;
+    ///
+    /// // the key pair holds a clone of the curve, which uses the same table
+    /// let key_pair = KeyPair::new(1234_u32, curve.clone())?;
+    /// assert_eq!(*key_pair.get_public(), curve.multiply(curve.get_g(), 1234)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// This can fail if there is a [problem] with the curve.
+    ///
+    /// [problem]: #problematic-curves
+    pub fn precompute_generator(&self) -> Result<(), EccError>{
+        if self.0.generator_table.get().is_some(){
+            return Ok(());
+        }
+        let mut table = vec![self.0.g.clone()];
+        for _ in 1..self.0.n.bits(){
+            table.push(self.double(table.last().unwrap())?);
+        }
+        // another thread may have built it meanwhile, and both tables are the same
+        let _ = self.0.generator_table.set(table);
+        Ok(())
+    }
+
+    /// Multiplies the generator by a scalar number, the same as [multiply][Curve::multiply] with the generator.
+    ///
+    /// If [precompute_generator][Curve::precompute_generator] was called, the scalar is reduced modulo n and the
+    /// multiples of the table for its bits are added, otherwise it falls back to double and add.
+    ///
+    /// # Errors
+    /// Same as [multiply][Curve::multiply].
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn multiply_generator<T: Into<BigInt>>(&self, k: T) -> Result<Point, EccError>{
+        self.multiply_generator_observed(k.into(), None)
+    }
+
+    /// Multiplies the generator like [multiply_generator][Curve::multiply_generator], giving the observer the events of
+    /// [multiply_traced][Curve::multiply_traced], or only an [Event::Add] for every multiple of the table that is added.
+    ///
+    /// # Errors
+    /// Same as [multiply][Curve::multiply].
+    pub fn multiply_generator_traced<T: Into<BigInt>>(&self, k: T, observer: &mut impl Observer) -> Result<Point, EccError>{
+        self.multiply_generator_observed(k.into(), Some(observer))
+    }
+
+    pub(super) fn multiply_generator_observed(&self, k: BigInt, mut observer: Option<&mut (dyn Observer + '_)>) -> Result<Point, EccError>{
+        let Some(table) = self.0.generator_table.get() else {
+            return self.multiply_observed(&self.0.g, k, observer);
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(multiples = table.len(), "adding the multiples of the precomputed table");
+        // n g is the point at infinity, so only k mod n matters
+        let k = get_mod(&k, &self.0.n.to_int())?.to_uint();
+        let mut result = Point::PointAtInfinity;
+        for (i, multiple) in table.iter().enumerate(){
+            if k.bit(i as u64){
+                result = self.add_observed(&result, multiple, observer.as_deref_mut())?;
+            }
+        }
+        Ok(result)
+    }
+
 }
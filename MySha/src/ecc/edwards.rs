@@ -0,0 +1,188 @@
+use std::sync::OnceLock;
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::Num;
+
+use super::ecc_math::{get_mod, mod_inv, ToInt};
+use super::{EccError, Point};
+
+/// Twisted Edwards curve type
+///
+/// An elliptic curve in the twisted Edwards model, ax&#178; + y&#178; = 1 + dx&#178;y&#178; (mod p), the model of [Ed25519].
+///
+/// The identity is the point (0, 1), [neutral][EdwardsCurve::neutral], not the [point at infinity][Point::PointAtInfinity],
+/// which isn't on these curves. The same formula adds and doubles, and when a is a square and d isn't, like in Ed25519,
+/// it works for every pair of points, so there are no special cases to branch on.
+///
+/// # Examples
+/// ```
+/// # use mysha::ecc::*;
+/// # fn main() -> Result<(), EccError>{
+/// let curve = EdwardsCurve::ed25519();
+///
+/// let point = curve.multiply(curve.get_g(), 1234)?;
+/// assert!(curve.is_on_curve(&point));
+/// assert_eq!(curve.add(&point, &EdwardsCurve::neutral())?, point);
+/// assert_eq!(curve.multiply(curve.get_g(), curve.get_n().clone())?, EdwardsCurve::neutral());
+/// # Ok(())
+/// # }
+/// ```
+/// [Ed25519]: https://datatracker.ietf.org/doc/html/rfc8032#section-5.1
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EdwardsCurve{
+    a: BigUint,
+    d: BigUint,
+    p: BigUint,
+    n: BigUint,
+    g: Point,
+}
+
+impl EdwardsCurve{
+    /// Creates a new [EdwardsCurve], with a and d, which can be negative, and are reduced modulo p.
+    ///
+    /// # Errors
+    /// This fails with [SingularCurve][EccError::SingularCurve] if a or d are 0 modulo p, or a = d,
+    /// and like [Curve::new][super::Curve::new] if the generator isn't on the curve or n times it isn't the neutral point.
+    pub fn new<S: Into<BigInt>, T: Into<BigUint>>(a: S, d: S, p: T, n: T, g: Point) -> Result<EdwardsCurve, EccError>{
+        let p: BigUint = p.into();
+        let prime = p.to_int();
+        let a = get_mod(&a.into(), &prime)?.to_uint();
+        let d = get_mod(&d.into(), &prime)?.to_uint();
+
+        if a == BigUint::from(0_u8) || d == BigUint::from(0_u8) || a == d{
+            return Err(EccError::SingularCurve);
+        }
+
+        let curve = EdwardsCurve{a, d, p, n: n.into(), g};
+        if !curve.is_on_curve(&curve.g){
+            return Err(EccError::GeneratorNotOnCurve);
+        }
+        if curve.n == BigUint::from(0_u8) || curve.multiply(&curve.g, curve.n.to_int())? != EdwardsCurve::neutral(){
+            return Err(EccError::InvalidOrderN);
+        }
+        Ok(curve)
+    }
+
+    /// Returns the curve of [Ed25519], -x&#178; + y&#178; = 1 - (121665/121666)x&#178;y&#178; (mod 2&#178;&#8309;&#8309; - 19), with its base point.
+    ///
+    /// It is birationally equivalent to [Curve25519][super::MontgomeryCurve::curve25519], and has the same order n.
+    ///
+    /// [Ed25519]: https://datatracker.ietf.org/doc/html/rfc8032#section-5.1
+    pub fn ed25519() -> EdwardsCurve{
+        static ED25519: OnceLock<EdwardsCurve> = OnceLock::new();
+        ED25519.get_or_init(|| {
+            let p: BigUint = (BigUint::from(1_u8) << 255) - 19_u8;
+            EdwardsCurve{
+                a: &p - 1_u8,
+                d: BigUint::from_str_radix("37095705934669439343138083508754565189542113879843219016388785533085940283555", 10).unwrap(),
+                n: (BigUint::from(1_u8) << 252) + BigUint::from_str_radix("27742317777372353535851937790883648493", 10).unwrap(),
+                g: Point::Point{
+                    x: BigUint::from_str_radix("15112221349535400772501151409588531511454012693041857206046113283949847762202", 10).unwrap(),
+                    y: BigUint::from_str_radix("46316835694926478169428394003475163141307993866256225615783033603165251855960", 10).unwrap(),
+                },
+                p,
+            }
+        }).clone()
+    }
+
+    /// The identity of the group, (0, 1).
+    pub fn neutral() -> Point{
+        Point::point(0_u8, 1_u8)
+    }
+
+    /// Returns the parameter a, modulo p.
+    pub fn get_a(&self) -> &BigUint{
+        &self.a
+    }
+
+    /// Returns the parameter d, modulo p.
+    pub fn get_d(&self) -> &BigUint{
+        &self.d
+    }
+
+    /// Returns the modulo p.
+    pub fn get_p(&self) -> &BigUint{
+        &self.p
+    }
+
+    /// Returns the order n of the generator.
+    pub fn get_n(&self) -> &BigUint{
+        &self.n
+    }
+
+    /// Returns the generator point.
+    pub fn get_g(&self) -> &Point{
+        &self.g
+    }
+
+    /// Returns a [bool] value that indicates wether the point provided is on the curve, the point at infinity never is.
+    pub fn is_on_curve(&self, p: &Point) -> bool{
+        match p{
+            Point::Point{x, y} => {
+                let (x, y) = (x.to_int(), y.to_int());
+                let (a, d) = (self.a.to_int(), self.d.to_int());
+                let (x2, y2) = (x.pow(2), y.pow(2));
+                (a * &x2 + &y2 - 1 - d * x2 * y2) % self.p.to_int() == BigInt::from(0)
+            },
+            Point::PointAtInfinity => false,
+        }
+    }
+
+    /// Adds two [points][Point] on the curve, with x = (x&#8321;y&#8322; + y&#8321;x&#8322;) / (1 + dx&#8321;x&#8322;y&#8321;y&#8322;)
+    /// and y = (y&#8321;y&#8322; - ax&#8321;x&#8322;) / (1 - dx&#8321;x&#8322;y&#8321;y&#8322;).
+    ///
+    /// # Errors
+    /// This can fail if the points provided aren't on the curve, or with [DivisionByZero][EccError::DivisionByZero]
+    /// on curves where the formula isn't complete.
+    pub fn add(&self, p: &Point, q: &Point) -> Result<Point, EccError>{
+        let (Point::Point{x: px, y: py}, Point::Point{x: qx, y: qy}) = (p, q) else {
+            return Err(EccError::NotOnCurve);
+        };
+        if !(self.is_on_curve(p) && self.is_on_curve(q)){
+            return Err(EccError::NotOnCurve);
+        }
+        let prime = self.p.to_int();
+        let (px, py) = (px.to_int(), py.to_int());
+        let (qx, qy) = (qx.to_int(), qy.to_int());
+        let (a, d) = (self.a.to_int(), self.d.to_int());
+
+        let t = get_mod(&(d * &px * &qx * &py * &qy), &prime)?;
+        let x = get_mod(&((&px * &qy + &py * &qx) * mod_inv(&get_mod(&(1 + &t), &prime)?, &prime)?), &prime)?;
+        let y = get_mod(&((&py * &qy - a * &px * &qx) * mod_inv(&get_mod(&(1 - &t), &prime)?, &prime)?), &prime)?;
+        Ok(Point::Point{x: x.to_uint(), y: y.to_uint()})
+    }
+
+    /// Doubles a [Point] on the curve, with the same formula as [add][EdwardsCurve::add].
+    ///
+    /// # Errors
+    /// Same as [add][EdwardsCurve::add].
+    pub fn double(&self, p: &Point) -> Result<Point, EccError>{
+        self.add(p, p)
+    }
+
+    /// Multiples a [Point] with a scalar number, with double and add, like [Curve::multiply][super::Curve::multiply].
+    ///
+    /// # Errors
+    /// Same as [add][EdwardsCurve::add].
+    pub fn multiply<T: Into<BigInt>>(&self, p: &Point, k: T) -> Result<Point, EccError>{
+        let k: BigInt = k.into();
+        let mut p = p.clone();
+        if k < BigInt::from(0){
+            // the negative of (x, y) is (-x, y)
+            let Point::Point{x, y} = &p else {
+                return Err(EccError::NotOnCurve);
+            };
+            let x = get_mod(&-x.to_int(), &self.p.to_int())?.to_uint();
+            p = Point::Point{x, y: y.clone()};
+        }
+        let k = k.magnitude();
+        let mut result = EdwardsCurve::neutral();
+        for i in (0..k.bits()).rev(){
+            result = self.double(&result)?;
+            if k.bit(i){
+                result = self.add(&result, &p)?;
+            }
+        }
+        Ok(result)
+    }
+}
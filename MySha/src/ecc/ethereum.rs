@@ -0,0 +1,233 @@
+//! Signatures of messages in the convention of Ethereum, checked by `personal_sign`, `ecrecover` and other web3 tools.
+//!
+//! The message is prefixed with `"\x19Ethereum Signed Message:\n"` and its length in decimal, so a signed message can
+//! never be a valid transaction, and hashed with [Keccak-256][crate::keccak]. The hash is signed with secp256k1,
+//! and the signature carries a recovery id `v`, which tells which of the points with x = r was the nonce point.
+//! With it the public key can be recovered from the signature and the message alone, so the signature is checked against
+//! an [address], the last 20 bytes of the hash of the public key, instead of the key itself.
+//!
+//! Signatures are encoded in 65 bytes, r and s as 32 bytes big endian numbers followed by v, which is 27 or 28.
+//! s is always the lower of its two possible values, as required by [EIP-2].
+//!
+//! # Examples
+//! ```
+//! use mysha::ecc::{*, ethereum::*};
+//!
+//! # fn main() -> Result<(), EccError>{
+//! let key_pair = KeyPair::new(1_u32, Curve::secp256k1())?;
+//! let public_key = PubKey::new(key_pair.get_public().clone(), Curve::secp256k1())?;
+//! assert_eq!(checksum_address(&address(&public_key)), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+//!
+//! let signature = key_pair.sign_ethereum(b"hello")?;
+//! let bytes = signature.to_bytes();
+//!
+//! let received = EthSignature::from_bytes(&bytes)?;
+//! assert!(received.verify(b"hello", &address(&public_key)));
+//! assert!(!received.verify(b"hallo", &address(&public_key)));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+
+use num_bigint::BigUint;
+
+use super::{hash_number, sign_hash_recoverable, Curve, EccError, KeyPair, Point, PrivKey, PubKey};
+use super::ecc_math::{get_mod, mod_inv, ToInt};
+use crate::encoding::hex;
+use crate::keccak::Keccak256;
+
+/// Prefix of every signed message, before its length.
+pub const MESSAGE_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+/// The prefixed message, which is what gets hashed and signed.
+fn prefixed(message: &[u8]) -> Vec<u8>{
+    let mut data = format!("{}{}", MESSAGE_PREFIX, message.len()).into_bytes();
+    data.extend_from_slice(message);
+    data
+}
+
+/// Keccak-256 hash of the prefixed message, the hash that is signed.
+pub fn message_hash(message: &[u8]) -> [u8; 32]{
+    Keccak256::digest(&prefixed(message))
+}
+
+/// Ethereum address of the public key, the last 20 bytes of the hash of its 64 bytes coordinates.
+pub fn address(public: &PubKey) -> [u8; 20]{
+    Keccak256::digest(&public.to_bytes())[12..].try_into().unwrap()
+}
+
+/// Hexadecimal address with the mixed case checksum of [EIP-55], the way wallets show it.
+///
+/// A letter is upper case when the matching hex digit of the hash of the lower case address is 8 or more.
+///
+/// [EIP-55]: https://eips.ethereum.org/EIPS/eip-55
+pub fn checksum_address(address: &[u8; 20]) -> String{
+    let lower = hex::encode(address);
+    let hash = Keccak256::digest(lower.as_bytes());
+    let checksummed: String = lower.chars().enumerate().map(|(i, c)|{
+        let nibble = hash[i / 2] >> (4 * (1 - i % 2)) & 0xf;
+        if nibble >= 8 {c.to_ascii_uppercase()} else {c}
+    }).collect();
+    format!("0x{}", checksummed)
+}
+
+/// Signature with recovery id, in the Ethereum convention.
+///
+/// Created by [KeyPair::sign_ethereum], [PrivKey::sign_ethereum] or [from_bytes][EthSignature::from_bytes].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EthSignature{
+    r: BigUint,
+    s: BigUint,
+    /// Recovery id, 0 or 1, the parity of the y of the nonce point.
+    recovery_id: u8,
+}
+
+impl EthSignature{
+    /// Returns the value of r of the signature
+    pub fn get_r(&self) -> &BigUint{
+        &self.r
+    }
+
+    /// Returns the value of s of the signature
+    pub fn get_s(&self) -> &BigUint{
+        &self.s
+    }
+
+    /// Returns v, 27 if the y of the nonce point is even and 28 if it is odd
+    pub fn get_v(&self) -> u8{
+        27 + self.recovery_id
+    }
+
+    /// Returns the 65 bytes of r, s and v.
+    pub fn to_bytes(&self) -> [u8; 65]{
+        let mut bytes = [0; 65];
+        let (r, s) = (self.r.to_bytes_be(), self.s.to_bytes_be());
+        bytes[32 - r.len()..32].copy_from_slice(&r);
+        bytes[64 - s.len()..64].copy_from_slice(&s);
+        bytes[64] = self.get_v();
+        bytes
+    }
+
+    /// Reads a signature from its 65 bytes, with v as 27 or 28, or as 0 or 1 like some tools write it.
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [InvalidLength][EccError::InvalidLength] if there aren't 65 bytes,
+    /// and with [InvalidSignature][EccError::InvalidSignature] if v isn't one of those values, or r or s aren't from 1 to n - 1.
+    pub fn from_bytes(bytes: &[u8]) -> Result<EthSignature, EccError>{
+        if bytes.len() != 65{
+            return Err(EccError::InvalidLength);
+        }
+        let recovery_id = match bytes[64]{
+            0 | 27 => 0,
+            1 | 28 => 1,
+            _ => return Err(EccError::InvalidSignature),
+        };
+        let r = BigUint::from_bytes_be(&bytes[..32]);
+        let s = BigUint::from_bytes_be(&bytes[32..64]);
+        let n = Curve::secp256k1().get_n().clone();
+        if r == BigUint::from(0_u8) || s == BigUint::from(0_u8) || r >= n || s >= n{
+            return Err(EccError::InvalidSignature);
+        }
+        Ok(EthSignature{
+            r,
+            s,
+            recovery_id,
+        })
+    }
+
+    /// Recovers the public key that signed the message, like `ecrecover`.
+    ///
+    /// The nonce point R is the point with x = r and the parity of v, and the key is `r⁻¹ (s R - z G)`, where z is the hash.
+    /// Any valid looking signature recovers some key, so the key must be compared with the expected one,
+    /// which [verify][EthSignature::verify] does with addresses.
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [InvalidSignature][EccError::InvalidSignature] if there is no point with x = r.
+    pub fn recover(&self, message: &[u8]) -> Result<PubKey, EccError>{
+        let curve = Curve::secp256k1();
+        let n = curve.get_n().to_int();
+        let p = curve.get_p();
+
+        // y² = x³ + 7, and since p = 3 mod 4 the square root is a power
+        let x = &self.r;
+        let alpha = (x.pow(3) + 7_u32) % p;
+        let beta = alpha.modpow(&((p + 1_u32) / 4_u32), p);
+        if beta.modpow(&2_u32.into(), p) != alpha{
+            return Err(EccError::InvalidSignature);
+        }
+        let y = if beta.bit(0) == (self.recovery_id == 1) {beta} else {p - beta};
+        let nonce_point = Point::point(x.clone(), y);
+
+        let hash = hash_number::<Keccak256>(&prefixed(message));
+        let r_inverse = mod_inv(&self.r.to_int(), &n)?;
+        let u1 = get_mod(&(-hash * &r_inverse), &n)?;
+        let u2 = get_mod(&(self.s.to_int() * &r_inverse), &n)?;
+        let public = curve.add(&curve.multiply_generator(u1)?, &curve.multiply(&nonce_point, u2)?)?;
+
+        PubKey::new(public, curve).map_err(|_| EccError::InvalidSignature)
+    }
+
+    /// Recovers the address that signed the message.
+    ///
+    /// # Errors
+    ///
+    /// Same as [recover][EthSignature::recover].
+    pub fn recover_address(&self, message: &[u8]) -> Result<[u8; 20], EccError>{
+        Ok(address(&self.recover(message)?))
+    }
+
+    /// Checks that the message was signed by the key of the address.
+    pub fn verify(&self, message: &[u8], address: &[u8; 20]) -> bool{
+        self.recover_address(message).is_ok_and(|recovered| recovered == *address)
+    }
+}
+
+/// Signs the hash of the prefixed message with the private key of secp256k1.
+fn sign(private: &BigUint, curve: &Curve, message: &[u8]) -> Result<EthSignature, EccError>{
+    if *curve != Curve::secp256k1(){
+        return Err(EccError::UnsupportedCurve);
+    }
+    let n = curve.get_n();
+    loop{
+        let (r, mut s, mut recovery_id) = sign_hash_recoverable(private, curve, hash_number::<Keccak256>(&prefixed(message)))?;
+        // v can't tell when x was reduced modulo n, which happens with probability 2^-128, so another nonce is used
+        if recovery_id > 1{
+            continue;
+        }
+        // s and n - s are both valid, the lower one is used, and the nonce point becomes its negative
+        if s > n >> 1{
+            s = n - s;
+            recovery_id ^= 1;
+        }
+        return Ok(EthSignature{
+            r,
+            s,
+            recovery_id,
+        });
+    }
+}
+
+impl PrivKey{
+    /// Signs the message in the Ethereum convention, with `personal_sign`'s prefix and Keccak-256.
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [UnsupportedCurve][EccError::UnsupportedCurve] if the key isn't of secp256k1.
+    pub fn sign_ethereum(&self, message: &[u8]) -> Result<EthSignature, EccError>{
+        sign(&self.private, &self.curve, message)
+    }
+}
+
+impl KeyPair{
+    /// Signs the message in the Ethereum convention, with `personal_sign`'s prefix and Keccak-256.
+    ///
+    /// # Errors
+    ///
+    /// Same as [PrivKey::sign_ethereum].
+    pub fn sign_ethereum(&self, message: &[u8]) -> Result<EthSignature, EccError>{
+        sign(&self.private, &self.curve, message)
+    }
+}
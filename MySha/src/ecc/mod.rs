@@ -5,19 +5,19 @@
 //! It provides types and methods that enable you to use ecc, such as creating [private keys][PrivKey], [public keys][PubKey],
 //! [signing][PrivKey::sign], and performing ecc math, such as point [doubling][Curve::double], [adding][Curve::add] and [multiplying][Curve::multiply].
 //! 
+//! Keys and signatures use the short Weierstrass [Curve], and the curves of the Montgomery and twisted Edwards models,
+//! like Curve25519 and Ed25519, have their own types, [MontgomeryCurve] and [EdwardsCurve], with their own formulas.
+//! 
 //! # Examples
 //! - Simple generating key-pair and signing:
 //! ```
 //! use mysha::ecc::*;
 //! use mysha::sha256::{sha256, InputType};
-//! use rand::{self, SeedableRng};
-//! use num_bigint::{RandBigInt, BigUint};
+//! use mysha::rand;
 //! 
 //! let curve = Curve::secp256k1();
 //! 
-//! let mut rng = rand::rngs::StdRng::from_entropy(); // check if your rng is cryptographically secure
-//! 
-//! let private_key = rng.gen_biguint_range(&BigUint::from(1_u8), curve.get_n()); // needs to be BigInt, this makes code more complex
+//! let private_key = rand::random_scalar(&curve);
 //! 
 //! let key_pair = KeyPair::new(private_key, curve).unwrap();
 //! 
@@ -32,16 +32,36 @@
 //! [asymetric cryptography]: https://en.wikipedia.org/wiki/Public-key_cryptography
 //! [elliptic curves]: https://en.wikipedia.org/wiki/Elliptic_curve
 
-use num_bigint::{BigUint, ToBigInt, RandBigInt, BigInt};
-use rand::{self, SeedableRng};
+use std::fmt;
+
+use num_bigint::{BigUint, BigInt, Sign};
+use num_traits::Num;
 
+pub mod bitcoin;
+pub mod blind;
+pub mod cryptanalysis;
+mod der;
 mod ecc_math;
+mod edwards;
+pub mod ethereum;
+mod models;
+mod montgomery;
+mod named;
+mod security;
+mod ssh;
+mod toy;
+pub mod trace;
+pub mod zkp;
 
 pub use ecc_math::{Curve, EccError, Point};
+pub use edwards::EdwardsCurve;
+pub use montgomery::MontgomeryCurve;
+pub use named::CURVE_NAMES;
+pub use security::{Finding, SecurityReport};
 
-use crate::{sha256::{sha256, InputType}, MyshaError};
+use crate::{digest::Digest, sha256::{sha256, Hash256, InputType}, MyshaError};
 
-use self::ecc_math::{get_mod, mod_inv};
+use self::ecc_math::{get_mod, mod_inv, ToInt};
 
 
 /// Key Pair type 
@@ -51,7 +71,26 @@ use self::ecc_math::{get_mod, mod_inv};
 /// To create a KeyPair, refer to the [new][KeyPair::new] and [from_private][KeyPair::from_private] methods.
 /// Since the fields are private, these methods are the only way to create a KeyPair.
 /// This ensures that the KeyPair type always holds valid values.
-#[derive(Debug)]
+///
+/// Like [PubKey], [PrivKey] and [Signature], it can be cloned, compared and stored in hashed collections.
+/// Two values are equal when all their numbers and their curves are.
+///
+/// # Examples
+/// ```
+/// # use mysha::ecc::*;
+/// use std::collections::HashSet;
+///
+/// # fn main() -> Result<(), EccError>{
+/// let key_pair = KeyPair::new(1234_u32, Curve::secp256k1())?;
+/// let same = KeyPair::new(1234_u32, Curve::secp256k1())?;
+/// assert_eq!(key_pair, same);
+///
+/// let keys: HashSet<KeyPair> = [key_pair.clone(), same, KeyPair::new(5678_u32, Curve::secp256k1())?].into();
+/// assert_eq!(keys.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyPair{
     private: BigUint,
     public: Point,
@@ -85,7 +124,7 @@ impl KeyPair{
         if private == BigUint::from(0_u8) || &private >= curve.get_n(){
             return Err(EccError::InvalidPrivateKey);
         }
-        let public = curve.multiply(curve.get_g(), private.to_bigint().unwrap())?;
+        let public = curve.multiply_generator(private.to_int())?;
         Ok(KeyPair{
             private,
             public,
@@ -102,7 +141,7 @@ impl KeyPair{
     /// 
     /// [problematic]: Curve#problematic-curves
     pub fn from_private(private: &PrivKey) -> Result<KeyPair, EccError>{
-        let public = private.curve.multiply(private.curve.get_g(), private.private.to_bigint().unwrap())?;
+        let public = private.curve.multiply_generator(private.private.to_int())?;
         Ok(KeyPair{
             private: private.private.clone(),
             public,
@@ -171,18 +210,82 @@ impl KeyPair{
     /// [wrong]: Curve#problematic-curves
     pub fn sign(&self, message: &str, input_type: InputType) -> Result<Signature, MyshaError>{
         let hash = sha256(message, input_type)?;
-        let mut rng = rand::rngs::StdRng::from_entropy();
-        let curve = self.get_curve();
-        let n = curve.get_n().to_bigint().unwrap();
-        let random_nonce = rng.gen_bigint_range(&BigInt::from(1_u8), &n);
-        
-        let r = get_mod(&curve.multiply(curve.get_g(), random_nonce.clone())?.get_x().unwrap().to_bigint().unwrap(), &n)?;
-        let s = get_mod(&(mod_inv(&random_nonce, &n)? * (BigInt::from(&hash) + self.get_private().to_bigint().unwrap() * &r)), &n)?;
+        let (r, s) = sign_hash(self.get_private(), self.get_curve(), BigInt::from(&hash))?;
 
         Ok(Signature{
-            r: r.to_biguint().unwrap(),
-            s: s.to_biguint().unwrap(),
-            curve: curve.clone(),
+            r,
+            s,
+            curve: self.get_curve().clone(),
+            public: self.get_public().clone(),
+        })
+    }
+
+    /// Signs the data hashed with any hash function that implements [Digest].
+    ///
+    /// The whole hash is read as a big endian number, like in [sign][KeyPair::sign], so signing with [Sha256][crate::sha256::Sha256]
+    /// gives signatures that [verify][Signature::verify] checks for the same data as text.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::{MyshaError, ecc::*};
+    /// use mysha::sha256::{InputType, Sha256};
+    ///
+    /// # fn main() -> Result<(), MyshaError>{
+    /// let key_pair = KeyPair::new(1001001_u32, Curve::secp256k1())?;
+    /// let sig = key_pair.sign_digest::<Sha256>(b"hello")?;
+    ///
+    /// assert!(sig.verify_digest::<Sha256>(b"hello")?);
+    /// assert!(sig.verify("hello", InputType::Text)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+    ///
+    /// [wrong]: Curve#problematic-curves
+    pub fn sign_digest<D: Digest>(&self, data: &[u8]) -> Result<Signature, EccError>{
+        self.sign_number(hash_number::<D>(data))
+    }
+
+    /// Signs a hash that was already computed, without hashing it again.
+    ///
+    /// It is for protocols that define their own hash of what is signed, like the sighashes of [bitcoin] transactions,
+    /// which are a double sha256 of parts of the transaction. The hash is read as a big endian number, like in [sign][KeyPair::sign].
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::{MyshaError, ecc::*};
+    /// use mysha::sha256::{sha256, InputType};
+    ///
+    /// # fn main() -> Result<(), MyshaError>{
+    /// let key_pair = KeyPair::new(1001001_u32, Curve::secp256k1())?;
+    /// let hash = sha256("hello", InputType::Text)?;
+    /// let sig = key_pair.sign_prehashed(&hash)?;
+    ///
+    /// assert!(sig.verify_prehashed(&hash)?);
+    /// assert!(sig.verify("hello", InputType::Text)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+    ///
+    /// [wrong]: Curve#problematic-curves
+    pub fn sign_prehashed(&self, hash: &Hash256) -> Result<Signature, EccError>{
+        self.sign_number(BigInt::from(hash))
+    }
+
+    fn sign_number(&self, hash: BigInt) -> Result<Signature, EccError>{
+        let (r, s) = sign_hash(self.get_private(), self.get_curve(), hash)?;
+
+        Ok(Signature{
+            r,
+            s,
+            curve: self.get_curve().clone(),
             public: self.get_public().clone(),
         })
     }
@@ -194,7 +297,7 @@ impl KeyPair{
 /// 
 /// To create a PubKey, refer to the [new][PubKey::new()] method,
 /// since its fields are private to ensure that it is a valid public key.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PubKey{
     /// Public Key
     public: Point,
@@ -243,6 +346,57 @@ impl PubKey{
     pub fn get_curve(&self) -> &Curve{
         &self.curve
     }
+
+    /// Creates a [PubKey] from the big endian bytes of its coordinates
+    ///
+    /// Each coordinate must have exactly [coordinate_size][Curve::coordinate_size] bytes, 32 for secp256k1,
+    /// padded with zeros on the left, the same format returned by [to_bytes][PubKey::to_bytes].
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let key_pair = KeyPair::new(1234_u32, Curve::secp256k1())?;
+    /// let bytes = PubKey::new(key_pair.get_public().clone(), Curve::secp256k1())?.to_bytes();
+    /// assert_eq!(bytes.len(), 64);
+    ///
+    /// let public_key = PubKey::from_affine_bytes(&bytes[..32], &bytes[32..], Curve::secp256k1())?;
+    /// assert_eq!(public_key.get_public(), key_pair.get_public());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    ///
+    /// This can fail with [InvalidLength][EccError::InvalidLength] if a coordinate doesn't have the right size,
+    /// with [NotOnCurve][EccError::NotOnCurve] if it isn't below p, and like [new][PubKey::new()] if the point can't be a valid public key.
+    pub fn from_affine_bytes(x: &[u8], y: &[u8], curve: Curve) -> Result<PubKey, EccError>{
+        let size = curve.coordinate_size();
+        if x.len() != size || y.len() != size{
+            return Err(EccError::InvalidLength);
+        }
+        let (x, y) = (BigUint::from_bytes_be(x), BigUint::from_bytes_be(y));
+        // the curve equation is checked modulo p, so x + p would pass it
+        if &x >= curve.get_p() || &y >= curve.get_p(){
+            return Err(EccError::NotOnCurve);
+        }
+        PubKey::new(Point::point(x, y), curve)
+    }
+
+    /// Returns the coordinates x and y of the public key, one after the other, as big endian numbers
+    /// padded to [coordinate_size][Curve::coordinate_size] bytes each, 64 bytes in total for secp256k1
+    pub fn to_bytes(&self) -> Vec<u8>{
+        let size = self.curve.coordinate_size();
+        let mut bytes = Vec::with_capacity(2 * size);
+        // a public key is never the point at infinity
+        if let Some((x, y)) = self.public.get_xy(){
+            for coordinate in [x, y]{
+                let number = coordinate.to_bytes_be();
+                bytes.extend(vec![0; size - number.len()]);
+                bytes.extend(number);
+            }
+        }
+        bytes
+    }
 }
 
 
@@ -252,7 +406,7 @@ impl PubKey{
 /// 
 /// To create a PrivKey, refer to [new][PrivKey::new()] method,
 /// since its field are private, to ensure it is a valid private key.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PrivKey{
     /// Private Key
     private: BigUint,
@@ -302,6 +456,38 @@ impl PrivKey{
         &self.private
     }
 
+    /// Creates a [PrivKey] from a hexadecimal number, upper or lower case, with or without leading zeros
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let hex = "00000000000000000000000000000000000000000000000000000000000004d2";
+    /// let private_key = PrivKey::from_hex(hex, &Curve::secp256k1())?;
+    ///
+    /// assert_eq!(*private_key.get_private(), 1234_u32.into());
+    /// assert_eq!(private_key.to_hex(), hex);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    ///
+    /// This can fail if the text isn't a hexadecimal number, or like [new][PrivKey::new()] if the number can't be a valid private key.
+    pub fn from_hex(hex: &str, curve: &Curve) -> Result<PrivKey, EccError>{
+        if hex.is_empty() || ! hex.chars().all(|c| c.is_ascii_hexdigit()){
+            return Err(EccError::InvalidPrivateKey);
+        }
+        let private = BigUint::from_str_radix(hex, 16).map_err(|_| EccError::InvalidPrivateKey)?;
+        PrivKey::new(private, curve.clone())
+    }
+
+    /// Returns the private key as lower case hexadecimal, padded with zeros to the size of the order n of the curve,
+    /// 64 characters for secp256k1
+    pub fn to_hex(&self) -> String{
+        let width = self.curve.get_n().bits().div_ceil(8) as usize * 2;
+        format!("{:0width$x}", self.private)
+    }
+
     /// Signs a message using the [PrivKey].
     /// 
     /// Creates a Signature for a message.
@@ -332,18 +518,82 @@ impl PrivKey{
     /// [wrong]: Curve#problematic-curves
     pub fn sign(&self, message: &str, input_type: InputType) -> Result<Signature, MyshaError>{
         let hash = sha256(message, input_type)?;
-        let mut rng = rand::rngs::StdRng::from_entropy();
+        Ok(self.sign_number(BigInt::from(&hash))?)
+    }
+
+    /// Signs a message like [sign][PrivKey::sign], giving the observer every step, see the [trace] module:
+    /// the [hash][trace::Event::Hash], the events of the multiplication of the generator by the nonce,
+    /// the [nonce][trace::Event::Nonce] and [s][trace::Event::Signed].
+    ///
+    /// The events include the nonce, which reveals the private key along with the signature.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::{ecc::{*, trace::Event}, MyshaError};
+    /// use mysha::sha256::InputType;
+    ///
+    /// # fn main() -> Result<(), MyshaError>{
+    /// let private_key = PrivKey::new(1001001_u32, Curve::secp256k1())?;
+    /// let mut nonce = None;
+    /// let sig = private_key.sign_traced("73", InputType::Decimal, &mut |event: &Event| {
+    ///     if let Event::Nonce{k, ..} = event{
+    ///         nonce = Some(k.clone());
+    ///     }
+    /// })?;
+    ///
+    /// assert!(nonce.is_some());
+    /// assert!(sig.verify("73", InputType::Decimal)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [sign][PrivKey::sign].
+    pub fn sign_traced(&self, message: &str, input_type: InputType, observer: &mut impl trace::Observer) -> Result<Signature, MyshaError>{
+        let hash = sha256(message, input_type)?;
         let curve = self.get_curve();
-        let n = curve.get_n().to_bigint().unwrap();
-        let random_nonce = rng.gen_bigint_range(&BigInt::from(1_u8), &n);
-        let r = get_mod(&curve.multiply(curve.get_g(), random_nonce.clone())?.get_x().unwrap().to_bigint().unwrap(), &n)?;
-        let s = get_mod(&(mod_inv(&random_nonce, &n)? * (BigInt::from(&hash) + self.get_private().to_bigint().unwrap() * &r)), &n)?;
+        let nonce = crate::rand::random_scalar(curve);
+        let (r, s, _) = sign_hash_with_nonce(self.get_private(), curve, BigInt::from(&hash), &nonce, Some(observer))?;
 
-        let public = curve.multiply(curve.get_g(), self.get_private().to_bigint().unwrap())?;
+        Ok(Signature{
+            r,
+            s,
+            curve: curve.clone(),
+            public: curve.multiply_generator(self.get_private().to_int())?,
+        })
+    }
+
+    /// Signs the data hashed with any hash function that implements [Digest], see [KeyPair::sign_digest].
+    ///
+    /// # Errors
+    ///
+    /// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+    ///
+    /// [wrong]: Curve#problematic-curves
+    pub fn sign_digest<D: Digest>(&self, data: &[u8]) -> Result<Signature, EccError>{
+        self.sign_number(hash_number::<D>(data))
+    }
+
+    /// Signs a hash that was already computed, without hashing it again, see [KeyPair::sign_prehashed].
+    ///
+    /// # Errors
+    ///
+    /// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+    ///
+    /// [wrong]: Curve#problematic-curves
+    pub fn sign_prehashed(&self, hash: &Hash256) -> Result<Signature, EccError>{
+        self.sign_number(BigInt::from(hash))
+    }
+
+    fn sign_number(&self, hash: BigInt) -> Result<Signature, EccError>{
+        let curve = self.get_curve();
+        let (r, s) = sign_hash(self.get_private(), curve, hash)?;
+        let public = curve.multiply_generator(self.get_private().to_int())?;
 
         Ok(Signature{
-            r: r.to_biguint().unwrap(),
-            s: s.to_biguint().unwrap(),
+            r,
+            s,
             curve: curve.clone(),
             public,
         })
@@ -358,7 +608,7 @@ impl PrivKey{
 /// 
 /// The Signature is made by the "r" and "s" values that are the actual signature values,
 /// the curve that it was used to sign, and the public key that signed it, that can be used to verify its validity.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Signature{
     r: BigUint,
     s: BigUint,
@@ -391,18 +641,28 @@ impl Signature{
     /// };
     /// 
     /// let signature = Signature::new(r, s, curve, public);
+    /// assert!(signature.is_ok());
     /// ```
-    pub fn new<T: Into<BigUint>>(r: T, s: T, curve: Curve, public: Point) -> Signature{
+    ///
+    /// # Errors
+    ///
+    /// Returns [EccError::InvalidSignature] if r or s aren't from 1 to n - 1, the values a signature can have.
+    /// Zero would make s have no inverse, and values of n or more are equal to smaller ones, so different values would verify.
+    pub fn new<T: Into<BigUint>>(r: T, s: T, curve: Curve, public: Point) -> Result<Signature, EccError>{
 
         let r: BigUint = r.into();
         let s: BigUint = s.into();
 
-        Signature{
+        if ! in_range(&r, curve.get_n()) || ! in_range(&s, curve.get_n()){
+            return Err(EccError::InvalidSignature);
+        }
+
+        Ok(Signature{
             r,
             s,
             curve,
             public,
-        }
+        })
     }
     /// Returns the r part of the signature
     pub fn get_r(&self) -> &BigUint{
@@ -444,7 +704,7 @@ impl Signature{
     /// #     x: BigUint::from_str_radix("397a5ba468b33496b3b8ff5a31b4ff5aabbd35873d3a496598102c26ae950d7b", 16).unwrap(),
     /// #     y: BigUint::from_str_radix("a46f8ffcbce897893819dfa9b8ca60b6672e0768588687280d6088ed1f01862d", 16).unwrap(),
     /// # };
-    /// # let signature = Signature::new(r, s, curve, public);
+    /// # let signature = Signature::new(r, s, curve, public)?;
     /// assert!(signature.verify("Hello, World!", InputType::Text)?);
     /// # Ok(())
     /// # }
@@ -458,11 +718,147 @@ impl Signature{
     /// [wrong]: Curve#problematic-curves
     pub fn verify(&self, message: &str, input_type: InputType) -> Result<bool, MyshaError>{
         let hash = sha256(message, input_type)?;
-        let r = self.r.to_bigint().unwrap();
-        let s = self.s.to_bigint().unwrap();
-        let n = self.curve.get_n().to_bigint().unwrap();
+        Ok(self.verify_number(BigInt::from(&hash))?)
+    }
+
+    /// Verifies if the signature is valid for the data hashed with any hash function that implements [Digest],
+    /// the counterpart of [KeyPair::sign_digest].
+    ///
+    /// # Errors
+    ///
+    /// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+    ///
+    /// [wrong]: Curve#problematic-curves
+    pub fn verify_digest<D: Digest>(&self, data: &[u8]) -> Result<bool, EccError>{
+        self.verify_number(hash_number::<D>(data))
+    }
+
+    /// Verifies if the signature is valid for a hash that was already computed, the counterpart of [KeyPair::sign_prehashed].
+    ///
+    /// # Errors
+    ///
+    /// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+    ///
+    /// [wrong]: Curve#problematic-curves
+    pub fn verify_prehashed(&self, hash: &Hash256) -> Result<bool, EccError>{
+        self.verify_number(BigInt::from(hash))
+    }
+
+    /// Verifies the signature like [verify][Signature::verify], but tells why it isn't valid.
+    ///
+    /// Every check of the verification is done in order, the first one that fails is returned as a [Verification],
+    /// so it is easy to tell an invalid public key from a signature of a different message.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::{ecc::*, MyshaError};
+    /// use mysha::sha256::InputType;
+    ///
+    /// # fn main() -> Result<(), MyshaError>{
+    /// let key_pair = KeyPair::new(1001001_u32, Curve::secp256k1())?;
+    /// let sig = key_pair.sign("hello", InputType::Text)?;
+    ///
+    /// assert_eq!(sig.verify_detailed("hello", InputType::Text)?, Verification::Valid);
+    /// assert!(matches!(sig.verify_detailed("hallo", InputType::Text)?, Verification::XMismatch{..}));
+    ///
+    /// let other = KeyPair::new(1234_u32, Curve::secp256k1())?;
+    /// let wrong_key = Signature::new(sig.get_r().clone(), sig.get_s().clone(), Curve::secp256k1(), other.get_public().clone())?;
+    /// assert!(!wrong_key.verify_detailed("hello", InputType::Text)?.is_valid());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [verify][Signature::verify].
+    pub fn verify_detailed(&self, message: &str, input_type: InputType) -> Result<Verification, MyshaError>{
+        let hash = sha256(message, input_type)?;
+        Ok(self.check(BigInt::from(&hash), None)?)
+    }
+
+    /// Verifies the signature like [verify_detailed][Signature::verify_detailed], giving the observer every step
+    /// after the checks of range, see the [trace] module: the [hash][trace::Event::Hash], the [scalars][trace::Event::Scalars],
+    /// the events of both multiplications and the addition, and the [recomputed point][trace::Event::Recomputed].
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::{ecc::{*, trace::Event}, MyshaError};
+    /// use mysha::sha256::InputType;
+    ///
+    /// # fn main() -> Result<(), MyshaError>{
+    /// let key_pair = KeyPair::new(1001001_u32, Curve::secp256k1())?;
+    /// let sig = key_pair.sign("hello", InputType::Text)?;
+    ///
+    /// let mut recomputed = None;
+    /// let verification = sig.verify_traced("hello", InputType::Text, &mut |event: &Event| {
+    ///     if let Event::Recomputed{point} = event{
+    ///         recomputed = point.get_x().cloned();
+    ///     }
+    /// })?;
+    /// assert_eq!(verification, Verification::Valid);
+    /// assert_eq!(recomputed.as_ref(), Some(sig.get_r()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [verify][Signature::verify].
+    pub fn verify_traced(&self, message: &str, input_type: InputType, observer: &mut impl trace::Observer) -> Result<Verification, MyshaError>{
+        let hash = sha256(message, input_type)?;
+        Ok(self.check(BigInt::from(&hash), Some(observer))?)
+    }
+
+    fn check(&self, hash: BigInt, mut observer: Option<&mut (dyn trace::Observer + '_)>) -> Result<Verification, EccError>{
+        let n = self.curve.get_n();
+        if ! in_range(&self.r, n){
+            return Ok(Verification::ROutOfRange);
+        }
+        if ! in_range(&self.s, n){
+            return Ok(Verification::SOutOfRange);
+        }
+        if self.public == Point::PointAtInfinity{
+            return Ok(Verification::PublicKeyAtInfinity);
+        }
+        if ! self.curve.is_on_curve(&self.public){
+            return Ok(Verification::PublicKeyNotOnCurve);
+        }
+
+        let n = n.to_int();
+        let w = get_mod(&mod_inv(&self.s.to_int(), &n)?, &n)?;
+        let u1 = get_mod(&(&hash * &w), &n)?;
+        let u2 = get_mod(&(self.r.to_int() * &w), &n)?;
+        if let Some(observer) = observer.as_deref_mut(){
+            observer.observe(&trace::Event::Hash{z: hash.to_uint()});
+            observer.observe(&trace::Event::Scalars{w: w.to_uint(), u1: u1.to_uint(), u2: u2.to_uint()});
+        }
+        let point1 = self.curve.multiply_generator_observed(u1, observer.as_deref_mut())?;
+        let point2 = self.curve.multiply_observed(&self.public, u2, observer.as_deref_mut())?;
+        let point = self.curve.add_observed(&point1, &point2, observer.as_deref_mut())?;
+        if let Some(observer) = observer{
+            observer.observe(&trace::Event::Recomputed{point: point.clone()});
+        }
+        let x = match point.get_x(){
+            Some(x) => x % self.curve.get_n(),
+            None => return Ok(Verification::PointAtInfinity),
+        };
+        if x != self.r{
+            return Ok(Verification::XMismatch{computed: x});
+        }
+        Ok(Verification::Valid)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "verify", level = "debug", skip_all, ret))]
+    fn verify_number(&self, hash: BigInt) -> Result<bool, EccError>{
+        // rejected before any point math, so out of range values never reach the inverse of s
+        if ! in_range(&self.r, self.curve.get_n()) || ! in_range(&self.s, self.curve.get_n()){
+            return Ok(false);
+        }
+        let r = self.r.to_int();
+        let s = self.s.to_int();
+        let n = self.curve.get_n().to_int();
         
-        let point1 = self.curve.multiply(self.curve.get_g(), BigInt::from(&hash) * mod_inv(&s, &n)?)?;
+        let point1 = self.curve.multiply_generator(hash * mod_inv(&s, &n)?)?;
 
         let point2 = self.curve.multiply(&self.public, mod_inv(&s, &n)? * &r)?;
 
@@ -472,3 +868,97 @@ impl Signature{
     }
 
 }
+
+/// Result of [Signature::verify_detailed], the first check of the verification that failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification{
+    /// Every check passed, the signature is valid.
+    Valid,
+    /// r isn't from 1 to n - 1, so it can't come from a signature.
+    ROutOfRange,
+    /// s isn't from 1 to n - 1, so it can't come from a signature, and it may not have an inverse.
+    SOutOfRange,
+    /// The public key is the point at infinity, which every signature would be valid for.
+    PublicKeyAtInfinity,
+    /// The public key isn't on the curve of the signature, usually the wrong curve.
+    PublicKeyNotOnCurve,
+    /// `hash / s G + r / s public` is the point at infinity, so it has no x to compare to r.
+    PointAtInfinity,
+    /// The x of `hash / s G + r / s public`, modulo n, isn't r.
+    /// The usual failure for a different message, public key or hash function.
+    XMismatch{
+        /// The x computed, that should have been r.
+        computed: BigUint,
+    },
+}
+
+impl Verification{
+    /// If the signature is valid.
+    pub fn is_valid(&self) -> bool{
+        *self == Verification::Valid
+    }
+}
+
+impl fmt::Display for Verification{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            Verification::Valid => write!(f, "the signature is valid"),
+            Verification::ROutOfRange => write!(f, "r is not from 1 to n - 1"),
+            Verification::SOutOfRange => write!(f, "s is not from 1 to n - 1"),
+            Verification::PublicKeyAtInfinity => write!(f, "the public key is the point at infinity"),
+            Verification::PublicKeyNotOnCurve => write!(f, "the public key is not on the curve"),
+            Verification::PointAtInfinity => write!(f, "the point recomputed from the hash and the public key is the point at infinity"),
+            Verification::XMismatch{computed} => write!(f, "the x recomputed from the hash and the public key is {:x}, not r, \
+                the message, the public key or the hash function is different from the signed one", computed),
+        }
+    }
+}
+
+/// If the value is from 1 to n - 1, like r and s of signatures.
+fn in_range(x: &BigUint, n: &BigUint) -> bool{
+    *x != BigUint::from(0_u8) && x < n
+}
+
+/// The hash of the data as a big endian number.
+fn hash_number<D: Digest>(data: &[u8]) -> BigInt{
+    BigInt::from_bytes_be(Sign::Plus, &D::hash(data))
+}
+
+/// Signs the hash, read as a number, with a random nonce, returning r and s.
+fn sign_hash(private: &BigUint, curve: &Curve, hash: BigInt) -> Result<(BigUint, BigUint), EccError>{
+    let (r, s, _) = sign_hash_recoverable(private, curve, hash)?;
+    Ok((r, s))
+}
+
+/// Signs like [sign_hash], also returning the recovery id: 1 if the y of the nonce point is odd, plus 2 if its x was reduced modulo n.
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "sign", level = "debug", skip_all))]
+fn sign_hash_recoverable(private: &BigUint, curve: &Curve, hash: BigInt) -> Result<(BigUint, BigUint, u8), EccError>{
+    sign_hash_with_nonce(private, curve, hash, &crate::rand::random_scalar(curve), None)
+}
+
+/// Signs like [sign_hash_recoverable] with the nonce given, which must never be known or used twice, see [cryptanalysis],
+/// giving the steps to the observer, if any.
+fn sign_hash_with_nonce(private: &BigUint, curve: &Curve, hash: BigInt, nonce: &BigUint, mut observer: Option<&mut (dyn trace::Observer + '_)>)
+    -> Result<(BigUint, BigUint, u8), EccError>{
+    let n = curve.get_n().to_int();
+    if let Some(observer) = observer.as_deref_mut(){
+        observer.observe(&trace::Event::Hash{z: hash.to_uint()});
+    }
+
+    // the nonce point is only the point at infinity if n isn't the prime order of the generator
+    let point = curve.multiply_generator_observed(nonce.to_int(), observer.as_deref_mut())?;
+    let (x, y) = point.get_xy().map(|(x, y)| (x.clone(), y.clone())).ok_or(EccError::NotPrime)?;
+    let r = get_mod(&x.to_int(), &n)?;
+    let nonce_inverse = get_mod(&mod_inv(&nonce.to_int(), &n)?, &n)?;
+    let sum = get_mod(&(hash + private.to_int() * &r), &n)?;
+    let s = get_mod(&(&nonce_inverse * &sum), &n)?;
+    let recovery_id = y.bit(0) as u8 + if x.to_int() >= n {2} else {0};
+    #[cfg(feature = "tracing")]
+    tracing::debug!(recovery_id, "signed");
+    if let Some(observer) = observer{
+        observer.observe(&trace::Event::Nonce{k: nonce.clone(), point, r: r.to_uint()});
+        observer.observe(&trace::Event::Signed{k_inverse: nonce_inverse.to_uint(), sum: sum.to_uint(), s: s.to_uint()});
+    }
+
+    Ok((r.to_uint(), s.to_uint(), recovery_id))
+}
@@ -0,0 +1,388 @@
+//! Conversions between the short Weierstrass, Montgomery and twisted Edwards models of the same curve.
+//!
+//! Every Montgomery curve By² = x³ + Ax² + x is birationally equivalent to the twisted Edwards curve with
+//! a = (A + 2) / B and d = (A - 2) / B, and to a short Weierstrass curve. A short Weierstrass curve only has a Montgomery model
+//! when it has a point of order 2, (&#945;, 0), so the number of its points must be even.
+//!
+//! The curves convert with the maps of their points, which keep the group operations, so multiplying a point and then mapping it
+//! gives the same as mapping it and then multiplying it.
+
+use num_bigint::{BigInt, BigUint};
+
+use super::ecc_math::{get_mod, mod_inv, ToInt};
+use super::{Curve, EccError, EdwardsCurve, MontgomeryCurve, Point};
+
+/// Numbers modulo p, for the formulas of the maps.
+struct Field<'a>{
+    p: &'a BigUint,
+}
+
+impl Field<'_>{
+    fn reduce(&self, n: &BigInt) -> BigUint{
+        get_mod(n, &self.p.to_int()).unwrap().to_uint()
+    }
+
+    fn div(&self, a: &BigInt, b: &BigInt) -> Result<BigUint, EccError>{
+        let prime = self.p.to_int();
+        let b = get_mod(b, &prime)?;
+        Ok(self.reduce(&(a * mod_inv(&b, &prime)?)))
+    }
+
+    /// A square root with Tonelli-Shanks, the smaller of the two, or [None] if there isn't one.
+    fn sqrt(&self, n: &BigUint) -> Option<BigUint>{
+        let p = self.p;
+        let n = n % p;
+        let one = BigUint::from(1_u8);
+        if n == BigUint::from(0_u8){
+            return Some(n);
+        }
+        let half = (p - 1_u8) >> 1;
+        if n.modpow(&half, p) != one{
+            return None;
+        }
+        // p - 1 = q 2^s, with q odd
+        let s = (p - 1_u8).trailing_zeros().unwrap();
+        let q = (p - 1_u8) >> s;
+        let mut z = BigUint::from(2_u8);
+        while z.modpow(&half, p) == one{
+            z += 1_u8;
+        }
+        let (mut m, mut c, mut t) = (s, z.modpow(&q, p), n.modpow(&q, p));
+        let mut r = n.modpow(&((&q + 1_u8) >> 1), p);
+        while t != one{
+            let mut i = 0;
+            let mut t2 = t.clone();
+            while t2 != one{
+                t2 = &t2 * &t2 % p;
+                i += 1;
+            }
+            let b = c.modpow(&(BigUint::from(1_u8) << (m - i - 1)), p);
+            r = r * &b % p;
+            c = &b * &b % p;
+            t = t * &c % p;
+            m = i;
+        }
+        Some(r.clone().min(p - r))
+    }
+
+    /// The roots of x³ + ax + b, from the smallest, with Cantor-Zassenhaus.
+    fn cubic_roots(&self, a: &BigUint, b: &BigUint) -> Vec<BigUint>{
+        let cubic = vec![b.clone(), a.clone(), BigUint::from(0_u8), BigUint::from(1_u8)];
+        // the roots are the roots of gcd(x^p - x, f), which has a factor x - r for each of them
+        let x = vec![BigUint::from(0_u8), BigUint::from(1_u8)];
+        let xp = self.poly_pow(&x, self.p, &cubic);
+        let linear = self.poly_gcd(cubic.clone(), self.poly_sub(&xp, &x));
+        let mut roots = self.split(linear);
+        roots.sort();
+        roots
+    }
+
+    fn split(&self, f: Vec<BigUint>) -> Vec<BigUint>{
+        match f.len(){
+            0 | 1 => Vec::new(),
+            2 => vec![self.reduce(&-(self.div(&f[0].to_int(), &f[1].to_int()).unwrap().to_int()))],
+            _ => {
+                // (x + δ)^((p - 1) / 2) - 1 has the roots r where r + δ is a square, about half of them
+                let half = (self.p - 1_u8) >> 1;
+                let mut delta = BigUint::from(0_u8);
+                loop{
+                    let w = self.poly_pow(&[delta.clone(), BigUint::from(1_u8)], &half, &f);
+                    let g = self.poly_gcd(f.clone(), self.poly_sub(&w, &[BigUint::from(1_u8)]));
+                    if g.len() > 1 && g.len() < f.len(){
+                        let quotient = self.poly_div(&f, &g).0;
+                        return [self.split(g), self.split(quotient)].concat();
+                    }
+                    delta += 1_u8;
+                }
+            },
+        }
+    }
+
+    fn trim(mut f: Vec<BigUint>) -> Vec<BigUint>{
+        while f.last() == Some(&BigUint::from(0_u8)){
+            f.pop();
+        }
+        f
+    }
+
+    fn poly_sub(&self, f: &[BigUint], g: &[BigUint]) -> Vec<BigUint>{
+        let zero = BigUint::from(0_u8);
+        let result = (0..f.len().max(g.len()))
+            .map(|i| (f.get(i).unwrap_or(&zero) + self.p - g.get(i).unwrap_or(&zero) % self.p) % self.p)
+            .collect();
+        Field::trim(result)
+    }
+
+    /// Quotient and remainder of f / g.
+    fn poly_div(&self, f: &[BigUint], g: &[BigUint]) -> (Vec<BigUint>, Vec<BigUint>){
+        let mut remainder = Field::trim(f.to_vec());
+        if remainder.len() < g.len(){
+            return (Vec::new(), remainder);
+        }
+        let mut quotient = vec![BigUint::from(0_u8); remainder.len() - g.len() + 1];
+        let lead = self.div(&BigInt::from(1), &g.last().unwrap().to_int()).unwrap();
+        while remainder.len() >= g.len(){
+            let shift = remainder.len() - g.len();
+            let factor = remainder.last().unwrap() * &lead % self.p;
+            for (i, coefficient) in g.iter().enumerate(){
+                remainder[i + shift] = (&remainder[i + shift] + self.p - &factor * coefficient % self.p) % self.p;
+            }
+            quotient[shift] = factor;
+            remainder = Field::trim(remainder);
+        }
+        (Field::trim(quotient), remainder)
+    }
+
+    fn poly_gcd(&self, mut f: Vec<BigUint>, mut g: Vec<BigUint>) -> Vec<BigUint>{
+        while !g.is_empty(){
+            let remainder = self.poly_div(&f, &g).1;
+            (f, g) = (g, remainder);
+        }
+        f
+    }
+
+    /// base^exponent modulo the polynomial.
+    fn poly_pow(&self, base: &[BigUint], exponent: &BigUint, modulus: &[BigUint]) -> Vec<BigUint>{
+        let mul = |f: &[BigUint], g: &[BigUint]| {
+            let mut product = vec![BigUint::from(0_u8); (f.len() + g.len()).saturating_sub(1)];
+            for (i, a) in f.iter().enumerate(){
+                for (j, b) in g.iter().enumerate(){
+                    product[i + j] = (&product[i + j] + a * b) % self.p;
+                }
+            }
+            self.poly_div(&product, modulus).1
+        };
+        let mut result = vec![BigUint::from(1_u8)];
+        let base = self.poly_div(base, modulus).1;
+        for i in (0..exponent.bits()).rev(){
+            result = mul(&result, &result);
+            if exponent.bit(i){
+                result = mul(&result, &base);
+            }
+        }
+        result
+    }
+}
+
+/// a or b of a short Weierstrass curve as the i32 of [Curve], the number modulo p closest to zero.
+fn small_parameter(n: &BigUint, p: &BigUint) -> Result<i32, EccError>{
+    let signed = if n > &(p >> 1) {-((p - n).to_int())} else {n.to_int()};
+    i32::try_from(signed).map_err(|_| EccError::NotConvertible)
+}
+
+impl MontgomeryCurve{
+    /// Converts the curve to the twisted Edwards model, with a = (A + 2) / B and d = (A - 2) / B.
+    ///
+    /// For Curve25519 the result is Ed25519 with x scaled by a square root of -486664, since Ed25519 chose a = -1.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let montgomery = MontgomeryCurve::curve25519();
+    /// let edwards = montgomery.to_edwards()?;
+    ///
+    /// let point = montgomery.multiply(montgomery.get_g(), 1234)?;
+    /// let mapped = montgomery.edwards_point(&point)?;
+    /// assert_eq!(mapped, edwards.multiply(edwards.get_g(), 1234)?);
+    /// assert_eq!(edwards.montgomery_point(&mapped)?, point);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// This can fail like [EdwardsCurve::new], and with [DivisionByZero][EccError::DivisionByZero] if the generator has no image.
+    pub fn to_edwards(&self) -> Result<EdwardsCurve, EccError>{
+        let field = Field{p: self.get_p()};
+        let (a, b) = (self.get_a().to_int(), self.get_b().to_int());
+        let edwards_a = field.div(&(&a + 2), &b)?;
+        let edwards_d = field.div(&(&a - 2), &b)?;
+        EdwardsCurve::new(edwards_a, edwards_d, self.get_p().clone(), self.get_n().clone(), self.edwards_point(self.get_g())?)
+    }
+
+    /// Maps a point to the [twisted Edwards model][MontgomeryCurve::to_edwards], (u, v) to (u / v, (u - 1) / (u + 1)).
+    ///
+    /// The point at infinity maps to (0, 1), and (0, 0) to (0, -1).
+    ///
+    /// # Errors
+    /// This fails with [NotOnCurve][EccError::NotOnCurve] if the point isn't on the curve, and with
+    /// [DivisionByZero][EccError::DivisionByZero] for the points that have no image, with v = 0 or u = -1.
+    pub fn edwards_point(&self, point: &Point) -> Result<Point, EccError>{
+        if !self.is_on_curve(point){
+            return Err(EccError::NotOnCurve);
+        }
+        let field = Field{p: self.get_p()};
+        match point{
+            Point::PointAtInfinity => Ok(EdwardsCurve::neutral()),
+            Point::Point{x, y} if *x == BigUint::from(0_u8) && *y == BigUint::from(0_u8) => Ok(Point::point(BigUint::from(0_u8), self.get_p() - 1_u8)),
+            Point::Point{x: u, y: v} => {
+                let (u, v) = (u.to_int(), v.to_int());
+                Ok(Point::Point{x: field.div(&u, &v)?, y: field.div(&(&u - 1), &(&u + 1))?})
+            },
+        }
+    }
+
+    /// The parameters a and b of the short Weierstrass model, (3 - A&#178;) / 3B&#178; and (2A&#179; - 9A) / 27B&#179;, modulo p.
+    ///
+    /// # Errors
+    /// This fails with [DivisionByZero][EccError::DivisionByZero] if p is 3.
+    pub fn weierstrass_parameters(&self) -> Result<(BigUint, BigUint), EccError>{
+        let field = Field{p: self.get_p()};
+        let (a, b) = (self.get_a().to_int(), self.get_b().to_int());
+        Ok((
+            field.div(&(3 - a.pow(2)), &(3 * b.pow(2)))?,
+            field.div(&(2 * a.pow(3) - 9 * &a), &(27 * b.pow(3)))?,
+        ))
+    }
+
+    /// Converts the curve to the short Weierstrass model.
+    ///
+    /// # Errors
+    /// [Curve] holds a and b as an [i32], so this fails with [NotConvertible][EccError::NotConvertible] if the
+    /// [parameters][MontgomeryCurve::weierstrass_parameters] don't fit it, as with Curve25519, and like [Curve::new].
+    pub fn to_weierstrass(&self) -> Result<Curve, EccError>{
+        let (a, b) = self.weierstrass_parameters()?;
+        let (a, b) = (small_parameter(&a, self.get_p())?, small_parameter(&b, self.get_p())?);
+        Curve::new(a, b, self.get_p().clone(), self.get_n().clone(), self.weierstrass_point(self.get_g())?)
+    }
+
+    /// Maps a point to the short Weierstrass model, (u, v) to (u / B + A / 3B, v / B).
+    ///
+    /// # Errors
+    /// This fails with [NotOnCurve][EccError::NotOnCurve] if the point isn't on the curve.
+    pub fn weierstrass_point(&self, point: &Point) -> Result<Point, EccError>{
+        if !self.is_on_curve(point){
+            return Err(EccError::NotOnCurve);
+        }
+        let field = Field{p: self.get_p()};
+        let (a, b) = (self.get_a().to_int(), self.get_b().to_int());
+        match point{
+            Point::PointAtInfinity => Ok(Point::PointAtInfinity),
+            Point::Point{x: u, y: v} => Ok(Point::Point{
+                x: field.div(&(3 * u.to_int() + a), &(3 * &b))?,
+                y: field.div(&v.to_int(), &b)?,
+            }),
+        }
+    }
+}
+
+impl EdwardsCurve{
+    /// Converts the curve to the Montgomery model, with A = 2(a + d) / (a - d) and B = 4 / (a - d).
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let edwards = EdwardsCurve::ed25519();
+    /// let montgomery = edwards.to_montgomery()?;
+    ///
+    /// // the base point of Ed25519 is the one of x = 9 of Curve25519
+    /// assert_eq!(montgomery.get_a(), MontgomeryCurve::curve25519().get_a());
+    /// assert_eq!(montgomery.get_g().get_x(), MontgomeryCurve::curve25519().get_g().get_x());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// This can fail like [MontgomeryCurve::new].
+    pub fn to_montgomery(&self) -> Result<MontgomeryCurve, EccError>{
+        let field = Field{p: self.get_p()};
+        let (a, d) = (self.get_a().to_int(), self.get_d().to_int());
+        let montgomery_a = field.div(&(2 * (&a + &d)), &(&a - &d))?;
+        let montgomery_b = field.div(&BigInt::from(4), &(&a - &d))?;
+        MontgomeryCurve::new(montgomery_a, montgomery_b, self.get_p().clone(), self.get_n().clone(), self.montgomery_point(self.get_g())?)
+    }
+
+    /// Maps a point to the [Montgomery model][EdwardsCurve::to_montgomery], (x, y) to ((1 + y) / (1 - y), (1 + y) / (1 - y)x).
+    ///
+    /// The neutral point (0, 1) maps to the point at infinity, and (0, -1) to (0, 0).
+    ///
+    /// # Errors
+    /// This fails with [NotOnCurve][EccError::NotOnCurve] if the point isn't on the curve.
+    pub fn montgomery_point(&self, point: &Point) -> Result<Point, EccError>{
+        if !self.is_on_curve(point){
+            return Err(EccError::NotOnCurve);
+        }
+        let field = Field{p: self.get_p()};
+        let Point::Point{x, y} = point else {unreachable!("the point at infinity isn't on twisted Edwards curves")};
+        if *point == EdwardsCurve::neutral(){
+            return Ok(Point::PointAtInfinity);
+        }
+        if *x == BigUint::from(0_u8){
+            return Ok(Point::point(0_u8, 0_u8));
+        }
+        let (x, y) = (x.to_int(), y.to_int());
+        let u = field.div(&(1 + &y), &(1 - &y))?.to_int();
+        Ok(Point::Point{x: u.to_uint(), y: field.div(&u, &x)?})
+    }
+}
+
+impl Curve{
+    /// The point of order 2 (&#945;, 0) and the scale s = 1 / &#8730;(3&#945;&#178; + a) of the Montgomery model,
+    /// from the smallest &#945; that has one, and the smallest square root.
+    fn montgomery_root(&self) -> Result<(BigUint, BigUint), EccError>{
+        let field = Field{p: self.get_p()};
+        if *self.get_p() <= BigUint::from(3_u8){
+            return Err(EccError::NotConvertible);
+        }
+        let a = field.reduce(&BigInt::from(self.get_a()));
+        let b = field.reduce(&BigInt::from(self.get_b()));
+        for alpha in field.cubic_roots(&a, &b){
+            let square = field.reduce(&(3 * alpha.to_int().pow(2) + a.to_int()));
+            if let Some(root) = field.sqrt(&square){
+                let s = field.div(&BigInt::from(1), &root.to_int())?;
+                return Ok((alpha, s));
+            }
+        }
+        Err(EccError::NotConvertible)
+    }
+
+    /// Converts the curve to the Montgomery model, with A = 3&#945;s and B = s, where (&#945;, 0) is a point of order 2,
+    /// and s = 1 / &#8730;(3&#945;&#178; + a).
+    ///
+    /// When there is more than one choice of &#945;, the smallest that works is used, so converting a Montgomery curve to this model
+    /// and back may give another Montgomery curve, equivalent to the first.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// // y² = x³ + 2x (mod 97) has 116 points, and three of order 2, of x 0, 17 and 80
+    /// let curve = Curve::new(2, 0, 97_u32, 29_u32, Point::point(16_u32, 32_u32))?;
+    /// let montgomery = curve.to_montgomery()?;
+    ///
+    /// let point = curve.multiply(curve.get_g(), 7)?;
+    /// assert_eq!(curve.montgomery_point(&point)?, montgomery.multiply(montgomery.get_g(), 7)?);
+    /// assert_eq!(montgomery.to_weierstrass()?.get_a(), 2);
+    ///
+    /// // secp256k1 has an odd number of points, so no point of order 2
+    /// assert!(Curve::secp256k1().to_montgomery().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// This fails with [NotConvertible][EccError::NotConvertible] if the curve has no point of order 2 with 3&#945;&#178; + a a square.
+    pub fn to_montgomery(&self) -> Result<MontgomeryCurve, EccError>{
+        let (alpha, s) = self.montgomery_root()?;
+        let field = Field{p: self.get_p()};
+        let a = field.reduce(&(3 * alpha.to_int() * s.to_int()));
+        MontgomeryCurve::new(a.to_int(), s.to_int(), self.get_p().clone(), self.get_n().clone(), self.montgomery_point(self.get_g())?)
+    }
+
+    /// Maps a point to the [Montgomery model][Curve::to_montgomery], (x, y) to (s(x - &#945;), sy).
+    ///
+    /// # Errors
+    /// This fails with [NotOnCurve][EccError::NotOnCurve] if the point isn't on the curve, and like [to_montgomery][Curve::to_montgomery].
+    pub fn montgomery_point(&self, point: &Point) -> Result<Point, EccError>{
+        if !self.is_on_curve(point){
+            return Err(EccError::NotOnCurve);
+        }
+        let (alpha, s) = self.montgomery_root()?;
+        let field = Field{p: self.get_p()};
+        match point{
+            Point::PointAtInfinity => Ok(Point::PointAtInfinity),
+            Point::Point{x, y} => Ok(Point::Point{
+                x: field.reduce(&(s.to_int() * (x.to_int() - alpha.to_int()))),
+                y: field.reduce(&(s * y).to_int()),
+            }),
+        }
+    }
+}
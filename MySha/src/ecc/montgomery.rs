@@ -0,0 +1,249 @@
+use std::sync::OnceLock;
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::Num;
+
+use super::ecc_math::{get_mod, mod_inv, ToInt};
+use super::{EccError, Point};
+
+/// Montgomery curve type
+///
+/// An elliptic curve in the Montgomery model, By&#178; = x&#179; + Ax&#178; + x (mod p), the model of [Curve25519].
+/// It has the same [Point] type and the same point at infinity as the short Weierstrass [Curve][super::Curve],
+/// but its own formulas, and a [ladder][MontgomeryCurve::ladder] that multiplies with only the x coordinates.
+///
+/// # Examples
+/// ```
+/// # use mysha::ecc::*;
+/// # fn main() -> Result<(), EccError>{
+/// let curve = MontgomeryCurve::curve25519();
+///
+/// let point = curve.multiply(curve.get_g(), 1234)?;
+/// assert!(curve.is_on_curve(&point));
+/// assert_eq!(curve.ladder(curve.get_g().get_x().unwrap(), 1234_u32), point.get_x().cloned());
+/// # Ok(())
+/// # }
+/// ```
+/// [Curve25519]: https://datatracker.ietf.org/doc/html/rfc7748
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MontgomeryCurve{
+    a: BigUint,
+    b: BigUint,
+    p: BigUint,
+    n: BigUint,
+    g: Point,
+}
+
+impl MontgomeryCurve{
+    /// Creates a new [MontgomeryCurve], with A and B, which can be negative, and are reduced modulo p.
+    ///
+    /// # Errors
+    /// This fails with [SingularCurve][EccError::SingularCurve] if B(A&#178; - 4) is 0 modulo p,
+    /// and like [Curve::new][super::Curve::new] if the generator isn't on the curve or n times it isn't the point at infinity.
+    pub fn new<S: Into<BigInt>, T: Into<BigUint>>(a: S, b: S, p: T, n: T, g: Point) -> Result<MontgomeryCurve, EccError>{
+        let p: BigUint = p.into();
+        let prime = p.to_int();
+        let a = get_mod(&a.into(), &prime)?;
+        let b = get_mod(&b.into(), &prime)?;
+
+        if g == Point::PointAtInfinity{
+            return Err(EccError::GeneratorOnInfinity);
+        }
+        if get_mod(&(&b * (a.pow(2) - 4)), &prime)? == BigInt::from(0){
+            return Err(EccError::SingularCurve);
+        }
+
+        let curve = MontgomeryCurve{
+            a: a.to_uint(),
+            b: b.to_uint(),
+            p,
+            n: n.into(),
+            g,
+        };
+        if !curve.is_on_curve(&curve.g){
+            return Err(EccError::GeneratorNotOnCurve);
+        }
+        if curve.n == BigUint::from(0_u8) || curve.multiply(&curve.g, curve.n.to_int())? != Point::PointAtInfinity{
+            return Err(EccError::InvalidOrderN);
+        }
+        Ok(curve)
+    }
+
+    /// Returns [Curve25519], y&#178; = x&#179; + 486662x&#178; + x (mod 2&#178;&#8309;&#8309; - 19), with the generator of x = 9.
+    ///
+    /// Its group has 8n points, n is the order of the generator.
+    ///
+    /// [Curve25519]: https://datatracker.ietf.org/doc/html/rfc7748#section-4.1
+    pub fn curve25519() -> MontgomeryCurve{
+        static CURVE25519: OnceLock<MontgomeryCurve> = OnceLock::new();
+        CURVE25519.get_or_init(|| MontgomeryCurve{
+            a: BigUint::from(486662_u32),
+            b: BigUint::from(1_u8),
+            p: (BigUint::from(1_u8) << 255) - 19_u8,
+            n: (BigUint::from(1_u8) << 252) + BigUint::from_str_radix("27742317777372353535851937790883648493", 10).unwrap(),
+            g: Point::Point{
+                x: BigUint::from(9_u8),
+                y: BigUint::from_str_radix("14781619447589544791020593568409986887264606134616475288964881837755586237401", 10).unwrap(),
+            },
+        }).clone()
+    }
+
+    /// Returns the parameter A, modulo p.
+    pub fn get_a(&self) -> &BigUint{
+        &self.a
+    }
+
+    /// Returns the parameter B, modulo p.
+    pub fn get_b(&self) -> &BigUint{
+        &self.b
+    }
+
+    /// Returns the modulo p.
+    pub fn get_p(&self) -> &BigUint{
+        &self.p
+    }
+
+    /// Returns the order n of the generator.
+    pub fn get_n(&self) -> &BigUint{
+        &self.n
+    }
+
+    /// Returns the generator point.
+    pub fn get_g(&self) -> &Point{
+        &self.g
+    }
+
+    /// Returns a [bool] value that indicates wether the point provided is on the curve.
+    pub fn is_on_curve(&self, p: &Point) -> bool{
+        match p{
+            Point::Point{x, y} => {
+                let (x, y) = (x.to_int(), y.to_int());
+                let (a, b) = (self.a.to_int(), self.b.to_int());
+                (b * y.pow(2) - x.pow(3) - a * x.pow(2) - x) % self.p.to_int() == BigInt::from(0)
+            },
+            Point::PointAtInfinity => true,
+        }
+    }
+
+    /// Adds two [points][Point] on the curve.
+    ///
+    /// The slope is the same as in the short Weierstrass model, but x = B&#955;&#178; - A - x&#8321; - x&#8322;.
+    ///
+    /// # Errors
+    /// This can fail if the points provided aren't on the curve.
+    pub fn add(&self, p: &Point, q: &Point) -> Result<Point, EccError>{
+        if !(self.is_on_curve(p) && self.is_on_curve(q)){
+            return Err(EccError::NotOnCurve);
+        }
+        if p == q{
+            return self.double(p);
+        }
+        match (p, q){
+            (Point::PointAtInfinity, _) => Ok(q.clone()),
+            (_, Point::PointAtInfinity) => Ok(p.clone()),
+            (Point::Point{x: px, y: py}, Point::Point{x: qx, y: qy}) => {
+                if px == qx{
+                    return Ok(Point::PointAtInfinity);
+                }
+                let prime = self.p.to_int();
+                let (px, py) = (px.to_int(), py.to_int());
+                let (qx, qy) = (qx.to_int(), qy.to_int());
+                let slope = get_mod(&((&qy - &py) * mod_inv(&(&qx - &px), &prime)?), &prime)?;
+                self.third_point(&slope, &px, &py, &qx)
+            },
+        }
+    }
+
+    /// Doubles a [Point] on the curve, with the slope (3x&#178; + 2Ax + 1) / 2By.
+    ///
+    /// # Errors
+    /// This can fail if the point provided isn't on the curve.
+    pub fn double(&self, p: &Point) -> Result<Point, EccError>{
+        if !self.is_on_curve(p){
+            return Err(EccError::NotOnCurve);
+        }
+        match p{
+            Point::Point{x, y} => {
+                if *y == BigUint::from(0_u8){
+                    return Ok(Point::PointAtInfinity);
+                }
+                let prime = self.p.to_int();
+                let (x, y) = (x.to_int(), y.to_int());
+                let (a, b) = (self.a.to_int(), self.b.to_int());
+                let slope = get_mod(&((3 * x.pow(2) + 2 * a * &x + 1) * mod_inv(&(2 * b * &y), &prime)?), &prime)?;
+                self.third_point(&slope, &x, &y, &x)
+            },
+            Point::PointAtInfinity => Ok(Point::PointAtInfinity),
+        }
+    }
+
+    /// The sum of the points of x px and qx, on the line of the slope through (px, py): the third point where it crosses the curve, mirrored.
+    fn third_point(&self, slope: &BigInt, px: &BigInt, py: &BigInt, qx: &BigInt) -> Result<Point, EccError>{
+        let prime = self.p.to_int();
+        let (a, b) = (self.a.to_int(), self.b.to_int());
+        let x = get_mod(&(b * slope.pow(2) - a - px - qx), &prime)?;
+        let y = get_mod(&(slope * (px - &x) - py), &prime)?;
+        Ok(Point::Point{x: x.to_uint(), y: y.to_uint()})
+    }
+
+    /// Multiples a [Point] with a scalar number, with double and add, like [Curve::multiply][super::Curve::multiply].
+    ///
+    /// # Errors
+    /// This can fail if the point provided isn't on the curve.
+    pub fn multiply<T: Into<BigInt>>(&self, p: &Point, k: T) -> Result<Point, EccError>{
+        let k: BigInt = k.into();
+        let mut p = p.clone();
+        if k < BigInt::from(0){
+            p = p.point_neg(self.p.to_int())?;
+        }
+        let k = k.magnitude();
+        let mut result = Point::PointAtInfinity;
+        for i in (0..k.bits()).rev(){
+            result = self.double(&result)?;
+            if k.bit(i){
+                result = self.add(&result, &p)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Multiplies the point of that x coordinate by k, with the Montgomery ladder, returning the x coordinate of the result,
+    /// or [None] if it is the point at infinity.
+    ///
+    /// Only x is used, in projective coordinates, so there is a single inversion at the end, and every bit of k takes
+    /// the same steps, which is why X25519 uses it. The x doesn't need to be of a point of the curve, without y the ladder
+    /// can't tell, and x of a point of the twist gives x of the multiple on the twist.
+    pub fn ladder<T: Into<BigUint>>(&self, x: &BigUint, k: T) -> Option<BigUint>{
+        let k: BigUint = k.into();
+        let prime = self.p.to_int();
+        let reduce = |n: BigInt| get_mod(&n, &prime).unwrap();
+        // (A + 2) / 4, for the doubling
+        let a24 = reduce((self.a.to_int() + 2) * mod_inv(&BigInt::from(4), &prime).ok()?);
+        let x1 = x.to_int();
+
+        // (x2 : z2) is k' P and (x3 : z3) is (k' + 1) P, for the bits k' of k read so far
+        let (mut x2, mut z2) = (BigInt::from(1), BigInt::from(0));
+        let (mut x3, mut z3) = (x1.clone(), BigInt::from(1));
+        for i in (0..k.bits()).rev(){
+            if k.bit(i){
+                (x2, x3) = (x3, x2);
+                (z2, z3) = (z3, z2);
+            }
+            let (a, b) = (reduce(&x2 + &z2), reduce(&x2 - &z2));
+            let (c, d) = (reduce(&x3 + &z3), reduce(&x3 - &z3));
+            let (aa, bb) = (reduce(a.pow(2)), reduce(b.pow(2)));
+            let e = reduce(&aa - &bb);
+            let (da, cb) = (reduce(d * &a), reduce(c * &b));
+            x3 = reduce((&da + &cb).pow(2));
+            z3 = reduce(&x1 * (&da - &cb).pow(2));
+            x2 = reduce(&aa * &bb);
+            z2 = reduce(&e * (&bb + &a24 * &e));
+            if k.bit(i){
+                (x2, x3) = (x3, x2);
+                (z2, z3) = (z3, z2);
+            }
+        }
+        let inverse = mod_inv(&z2, &prime).ok()?;
+        Some(reduce(x2 * inverse).to_uint())
+    }
+}
@@ -0,0 +1,81 @@
+//! Curves known by name, so files and commands can refer to them instead of writing every parameter.
+
+use std::sync::OnceLock;
+
+use num_bigint::BigUint;
+use num_traits::Num;
+
+use super::{Curve, EccError, Point};
+
+/// Names accepted by [Curve::from_name], the SEC 2 Koblitz curves, whose a and b fit the parameters of [Curve].
+pub const CURVE_NAMES: [&str; 3] = ["secp256k1", "secp224k1", "secp192k1"];
+
+fn hex(n: &str) -> BigUint{
+    BigUint::from_str_radix(n, 16).unwrap()
+}
+
+impl Curve{
+    /// Returns a [Curve] with the [secp224k1] specs
+    ///
+    /// [secp224k1]: https://www.secg.org/sec2-v2.pdf#Recommended%20Parameters%20secp224k1
+    pub fn secp224k1() -> Curve{
+        static SECP224K1: OnceLock<Curve> = OnceLock::new();
+        SECP224K1.get_or_init(|| Curve::new(
+            0,
+            5,
+            hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFE56D"),
+            hex("010000000000000000000000000001DCE8D2EC6184CAF0A971769FB1F7"),
+            Point::Point{
+                x: hex("A1455B334DF099DF30FC28A169A467E9E47075A90F7E650EB6B7A45C"),
+                y: hex("7E089FED7FBA344282CAFBD6F7E319F7C0B0BD59E2CA4BDB556D61A5"),
+            },
+        ).unwrap()).clone()
+    }
+
+    /// Returns a [Curve] with the [secp192k1] specs
+    ///
+    /// [secp192k1]: https://www.secg.org/sec2-v2.pdf#Recommended%20Parameters%20secp192k1
+    pub fn secp192k1() -> Curve{
+        static SECP192K1: OnceLock<Curve> = OnceLock::new();
+        SECP192K1.get_or_init(|| Curve::new(
+            0,
+            3,
+            hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFEE37"),
+            hex("FFFFFFFFFFFFFFFFFFFFFFFE26F2FC170F69466A74DEFD8D"),
+            Point::Point{
+                x: hex("DB4FF10EC057E9AE26B07D0280B7F4341DA5D1B1EAE06C7D"),
+                y: hex("9B2F2F6D9C5628A7844163D015BE86344082AA88D95E2F9D"),
+            },
+        ).unwrap()).clone()
+    }
+
+    /// Returns the curve of the name, one of [CURVE_NAMES].
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let curve = Curve::from_name("secp256k1")?;
+    /// assert_eq!(curve, Curve::secp256k1());
+    /// assert_eq!(curve.name(), Some("secp256k1"));
+    ///
+    /// assert!(matches!(Curve::from_name("secp256r1"), Err(EccError::UnknownCurve)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// This fails with [UnknownCurve][EccError::UnknownCurve] if there is no curve with that name.
+    pub fn from_name(name: &str) -> Result<Curve, EccError>{
+        match name{
+            "secp256k1" => Ok(Curve::secp256k1()),
+            "secp224k1" => Ok(Curve::secp224k1()),
+            "secp192k1" => Ok(Curve::secp192k1()),
+            _ => Err(EccError::UnknownCurve),
+        }
+    }
+
+    /// Returns the name of the curve, if it is one of [CURVE_NAMES].
+    pub fn name(&self) -> Option<&'static str>{
+        CURVE_NAMES.into_iter().find(|name| Curve::from_name(name).is_ok_and(|curve| curve == *self))
+    }
+}
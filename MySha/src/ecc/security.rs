@@ -0,0 +1,308 @@
+//! Checks of the known weaknesses of elliptic curves, for [Curve::security_report].
+//!
+//! [Curve::new] only checks that the curve is valid, the report checks that it is fit for cryptography:
+//! the parameters must be prime, the order must not be p, where Smart's attack solves logarithms in polynomial time,
+//! and n must not divide p^k - 1 for small k, where the MOV attack moves logarithms to the field of p^k.
+//!
+//! It also checks the quadratic twist, the curve y² = x³ + ax + b over the other half of the x coordinates.
+//! Formulas that only use x, or don't check that points are on the curve, compute on the twist when given one of its points,
+//! so an attacker that sends such points solves logarithms on the twist, one small factor of its order at a time.
+//! Points are always checked by this crate, but a weak twist tells that other implementations of the curve must check them too.
+
+use std::fmt;
+
+use num_bigint::BigUint;
+
+use super::Curve;
+use crate::rand::fill_random;
+
+/// Largest embedding degree checked, pairing friendly curves use up to 12 or so.
+const MAX_EMBEDDING_DEGREE: u32 = 20;
+/// Curves with a smaller p have their points counted, so the cofactor is exact.
+const COUNT_POINTS_LIMIT: u64 = 1 << 20;
+/// Security below this is considered weak, 112 bits is the minimum recommended by NIST.
+const MIN_SECURITY_BITS: u32 = 112;
+
+/// Weaknesses found by [Curve::security_report].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding{
+    /// The modulo p isn't prime, so the coordinates aren't a field.
+    PNotPrime,
+    /// The order n isn't prime, so logarithms can be solved in its factors with Pohlig-Hellman.
+    NNotPrime,
+    /// The curve has p points, and logarithms can be solved in polynomial time with Smart's attack.
+    Anomalous,
+    /// n divides p^k - 1 for the embedding degree k, and logarithms are easier in the field of p^k with the MOV attack.
+    SmallEmbeddingDegree(u32),
+    /// The estimated security, in bits, is below 112.
+    WeakSecurity(u32),
+}
+
+impl fmt::Display for Finding{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            Finding::PNotPrime => write!(f, "The modulo p isn't prime."),
+            Finding::NNotPrime => write!(f, "The order n isn't prime, so it is open to Pohlig-Hellman."),
+            Finding::Anomalous => write!(f, "The curve is anomalous, it has p points, so it is open to Smart's attack."),
+            Finding::SmallEmbeddingDegree(k) => write!(f, "The embedding degree is {}, so it is open to the MOV attack.", k),
+            Finding::WeakSecurity(bits) => write!(f, "The estimated security of {} bits is below {}.", bits, MIN_SECURITY_BITS),
+        }
+    }
+}
+
+/// The result of [Curve::security_report], with what was checked about the curve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityReport{
+    /// Whether p is prime, tested with Miller-Rabin.
+    pub p_prime: bool,
+    /// Whether n is prime, tested with Miller-Rabin.
+    pub n_prime: bool,
+    /// The number of points of the curve divided by n, when it can be known.
+    ///
+    /// For small p the points are counted, otherwise it is the only one possible by Hasse's bound,
+    /// which needs n bigger than 4&#8730;p.
+    pub cofactor: Option<BigUint>,
+    /// Whether the curve has p points.
+    pub anomalous: bool,
+    /// The smallest k where n divides p^k - 1, if it is at most 20.
+    pub embedding_degree: Option<u32>,
+    /// Estimated cost of the best attack, in bits, the smallest of Pollard's rho in the largest factor of n,
+    /// and the number field sieve in the field of the embedding degree.
+    pub security_bits: u32,
+    /// The number of points of the quadratic twist, 2p + 2 minus the points of the curve, when the cofactor is known.
+    pub twist_order: Option<BigUint>,
+    /// The factors of the twist order below 2^16, in order, followed by the part left, which may not be prime.
+    pub twist_factors: Option<Vec<BigUint>>,
+    /// Estimated cost of Pollard's rho in the largest factor of the twist order, in bits.
+    pub twist_security_bits: Option<u32>,
+}
+
+impl SecurityReport{
+    /// The weaknesses found, empty if the curve is fit for cryptography.
+    pub fn findings(&self) -> Vec<Finding>{
+        let mut findings = Vec::new();
+        if !self.p_prime{
+            findings.push(Finding::PNotPrime);
+        }
+        if !self.n_prime{
+            findings.push(Finding::NNotPrime);
+        }
+        if self.anomalous{
+            findings.push(Finding::Anomalous);
+        }
+        if let Some(k) = self.embedding_degree{
+            findings.push(Finding::SmallEmbeddingDegree(k));
+        }
+        if self.security_bits < MIN_SECURITY_BITS{
+            findings.push(Finding::WeakSecurity(self.security_bits));
+        }
+        findings
+    }
+
+    /// Whether there aren't any [findings][SecurityReport::findings].
+    ///
+    /// The twist isn't part of it, see [weak_twist][SecurityReport::weak_twist].
+    pub fn is_secure(&self) -> bool{
+        self.findings().is_empty()
+    }
+
+    /// Whether the estimated security of the twist is below 112 bits.
+    ///
+    /// The curve is still safe to use when every point is checked to be on it, as this crate does,
+    /// but implementations that skip the check are open to twist attacks.
+    pub fn weak_twist(&self) -> bool{
+        self.twist_security_bits.is_some_and(|bits| bits < MIN_SECURITY_BITS)
+    }
+}
+
+impl Curve{
+    /// Checks the curve for the known weaknesses of elliptic curves, the ones that [new][Curve::new] can't check.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// use num_bigint::BigUint;
+    /// # fn main() -> Result<(), EccError>{
+    /// let report = Curve::secp256k1().security_report();
+    /// assert!(report.p_prime && report.n_prime);
+    /// assert_eq!(report.cofactor, Some(BigUint::from(1_u8)));
+    /// assert_eq!(report.security_bits, 128);
+    /// assert!(report.is_secure());
+    ///
+    /// // the twist of secp256k1 has an order with small factors, and a prime of 220 bits
+    /// assert_eq!(report.twist_security_bits, Some(110));
+    /// assert!(report.weak_twist());
+    ///
+    /// // 100 points, and 97^4 - 1 is a multiple of 5
+    /// let report = Curve::new(2, 3, 97_u32, 5_u32, Point::point(3_u32, 6_u32))?.security_report();
+    /// assert_eq!(report.cofactor, Some(BigUint::from(20_u8)));
+    /// assert_eq!(report.embedding_degree, Some(4));
+    /// assert!(!report.is_secure());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn security_report(&self) -> SecurityReport{
+        let (p, n) = (self.get_p(), self.get_n());
+        let p_prime = is_probable_prime(p);
+        let n_prime = is_probable_prime(n);
+
+        let cofactor = match u64::try_from(p){
+            Ok(small) if small < COUNT_POINTS_LIMIT => {
+                let points = BigUint::from(count_points(self.get_a(), self.get_b(), small));
+                (&points % n == BigUint::from(0_u8)).then(|| points / n)
+            },
+            // Hasse's bound: the number of points is within 2√p of p + 1, so only one multiple of n fits
+            _ if n * n > p * 16_u8 => Some((p + 1_u8 + n / 2_u8) / n),
+            _ => None,
+        };
+        let anomalous = match &cofactor{
+            Some(h) => h * n == *p,
+            None => n == p,
+        };
+        let embedding_degree = if anomalous {None} else {embedding_degree(p, n)};
+
+        let security_bits = if anomalous{
+            0
+        }else{
+            let rho = factor(n).iter().max().map_or(0, |largest| largest.bits() as u32 / 2);
+            match embedding_degree{
+                Some(k) => rho.min(number_field_sieve_bits(k as u64 * p.bits())),
+                None => rho,
+            }
+        };
+
+        // the points of the curve and of its twist add up to 2p + 2
+        let twist_order = cofactor.as_ref()
+            .map(|h| h * n)
+            .filter(|points| *points <= (p + 1_u8) * 2_u8)
+            .map(|points| (p + 1_u8) * 2_u8 - points);
+        let twist_factors = twist_order.as_ref().map(factor);
+        let twist_security_bits = twist_factors.as_ref().map(|factors| {
+            factors.iter().max().map_or(0, |largest| largest.bits() as u32 / 2)
+        });
+
+        SecurityReport{
+            p_prime, n_prime, cofactor, anomalous, embedding_degree, security_bits,
+            twist_order, twist_factors, twist_security_bits,
+        }
+    }
+}
+
+/// Miller-Rabin with the first primes as bases, and random ones, so curves can't be made to pass it.
+pub(super) fn is_probable_prime(n: &BigUint) -> bool{
+    const SMALL_PRIMES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    const RANDOM_BASES: usize = 8;
+
+    if *n < BigUint::from(2_u8){
+        return false;
+    }
+    for prime in SMALL_PRIMES{
+        if *n == BigUint::from(prime){
+            return true;
+        }
+        if n % prime == BigUint::from(0_u8){
+            return false;
+        }
+    }
+
+    let one = BigUint::from(1_u8);
+    let n_minus_one = n - 1_u8;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> s;
+
+    let random_bases = (0..RANDOM_BASES).map(|_| {
+        let mut bytes = vec![0; n.bits().div_ceil(8) as usize + 8];
+        fill_random(&mut bytes);
+        // from 2 to n - 2
+        BigUint::from_bytes_be(&bytes) % (n - 3_u8) + 2_u8
+    });
+    let mut bases = SMALL_PRIMES.iter().map(|prime| BigUint::from(*prime)).chain(random_bases);
+    bases.all(|base| {
+        let mut x = base.modpow(&d, n);
+        if x == one || x == n_minus_one{
+            return true;
+        }
+        for _ in 1..s{
+            x = x.modpow(&BigUint::from(2_u8), n);
+            if x == n_minus_one{
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Number of points of y² = x³ + ax + b (mod p), with the point at infinity, checking every x with Euler's criterion.
+pub(super) fn count_points(a: i32, b: i32, p: u64) -> u64{
+    let pow = |mut base: u64, mut exponent: u64| {
+        let mut result = 1;
+        base %= p;
+        while exponent > 0{
+            if exponent & 1 == 1{
+                result = result * base % p;
+            }
+            base = base * base % p;
+            exponent >>= 1;
+        }
+        result
+    };
+    let (a, b) = ((a as i64).rem_euclid(p as i64) as u64, (b as i64).rem_euclid(p as i64) as u64);
+    let mut points = 1;
+    for x in 0..p{
+        let rhs = (x * x % p * x + a * x + b) % p;
+        points += if rhs == 0{
+            1
+        }else if pow(rhs, (p - 1) / 2) == 1{
+            2
+        }else{
+            0
+        };
+    }
+    points
+}
+
+/// The smallest k up to [MAX_EMBEDDING_DEGREE] where p^k = 1 (mod n).
+fn embedding_degree(p: &BigUint, n: &BigUint) -> Option<u32>{
+    let one = BigUint::from(1_u8);
+    if *n <= one{
+        return None;
+    }
+    let mut power = p % n;
+    for k in 1..=MAX_EMBEDDING_DEGREE{
+        if power == one{
+            return Some(k);
+        }
+        power = power * p % n;
+    }
+    None
+}
+
+/// Divides n by the primes below 2^16, returning them, and the part left if it isn't 1.
+///
+/// The part left is prime when it is smaller than 2^32, otherwise it may not be.
+pub(super) fn factor(n: &BigUint) -> Vec<BigUint>{
+    let mut n = n.clone();
+    let mut factors = Vec::new();
+    for factor in 2_u32..1 << 16{
+        if n < BigUint::from(factor) * factor{
+            break;
+        }
+        while &n % factor == BigUint::from(0_u8){
+            n /= factor;
+            factors.push(BigUint::from(factor));
+        }
+    }
+    if n > BigUint::from(1_u8){
+        factors.push(n);
+    }
+    factors
+}
+
+/// Estimated security, in bits, of logarithms in a field of that many bits, by the running time of the number field sieve.
+///
+/// It gives about 80 bits for 1024 bits, and 128 for 3072 bits, like the NIST estimates.
+fn number_field_sieve_bits(field_bits: u64) -> u32{
+    let ln = field_bits as f64 * std::f64::consts::LN_2;
+    let cost = 1.923 * ln.cbrt() * ln.ln().powf(2.0 / 3.0) / std::f64::consts::LN_2 - 4.69;
+    cost.max(0.0) as u32
+}
@@ -0,0 +1,100 @@
+//! Random small curves, with numbers small enough to follow the operations by hand.
+
+use num_bigint::BigUint;
+
+use super::{Curve, EccError, Point};
+use super::security::{count_points, factor, is_probable_prime};
+use crate::rand::fill_random;
+
+/// Sizes of p accepted by [Curve::random_small], the points are counted one x at a time, so bigger ones are slow.
+const BITS: std::ops::RangeInclusive<u32> = 4..=20;
+/// Largest cofactor accepted, so most points of the curve are in the subgroup of the generator.
+const MAX_COFACTOR: u64 = 4;
+
+pub(super) fn random_below(limit: u64) -> u64{
+    let mut bytes = [0; 8];
+    fill_random(&mut bytes);
+    u64::from_be_bytes(bytes) % limit
+}
+
+pub(super) fn pow_mod(mut base: u64, mut exponent: u64, p: u64) -> u64{
+    let mut result = 1;
+    base %= p;
+    while exponent > 0{
+        if exponent & 1 == 1{
+            result = result * base % p;
+        }
+        base = base * base % p;
+        exponent >>= 1;
+    }
+    result
+}
+
+/// A random prime of that many bits, that is 3 mod 4, so square roots are a single power.
+fn random_prime(bits: u32) -> u64{
+    loop{
+        let p = random_below(1 << (bits - 1)) | 1 << (bits - 1) | 3;
+        if is_probable_prime(&BigUint::from(p)){
+            return p;
+        }
+    }
+}
+
+impl Curve{
+    /// Generates a random curve where p has that many bits, from 4 to 20, for exercises where the numbers of secp256k1 are too big to follow.
+    ///
+    /// The points of the curve are counted, and n is their largest prime factor, with a cofactor of at most 4,
+    /// so the generator has prime order n, as in real curves. Anomalous curves, which have p points, are skipped.
+    ///
+    /// These curves are broken in an instant, they are only for learning.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::ecc::*;
+    /// # fn main() -> Result<(), EccError>{
+    /// let curve = Curve::random_small(16)?;
+    /// assert_eq!(curve.get_p().bits(), 16);
+    ///
+    /// let report = curve.security_report();
+    /// assert!(report.p_prime && report.n_prime);
+    /// assert_eq!(curve.multiply(curve.get_g(), curve.get_n().clone())?, Point::PointAtInfinity);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// # Errors
+    /// This fails with [InvalidBits][EccError::InvalidBits] if bits isn't from 4 to 20.
+    pub fn random_small(bits: u32) -> Result<Curve, EccError>{
+        if !BITS.contains(&bits){
+            return Err(EccError::InvalidBits);
+        }
+        loop{
+            let p = random_prime(bits);
+            let (a, b) = (random_below(p), random_below(p));
+            // 4a³ + 27b² = 0 is a singular curve
+            if (4 * pow_mod(a, 3, p) + 27 * pow_mod(b, 2, p)).is_multiple_of(p){
+                continue;
+            }
+            let points = count_points(a as i32, b as i32, p);
+            let n = factor(&BigUint::from(points)).pop().unwrap();
+            let cofactor = points / u64::try_from(&n).unwrap();
+            if cofactor > MAX_COFACTOR || points == p || n < BigUint::from(5_u8){
+                continue;
+            }
+
+            // a random point of the curve, times the cofactor, is in the subgroup of order n
+            let x = random_below(p);
+            let rhs = (pow_mod(x, 3, p) + a * x + b) % p;
+            let y = pow_mod(rhs, (p + 1) / 4, p);
+            if y * y % p != rhs{
+                continue;
+            }
+            // every point times the number of points is the point at infinity, so that is a valid order for now
+            let curve = Curve::new(a as i32, b as i32, p, points, Point::point(x, y))?;
+            let g = curve.multiply(curve.get_g(), cofactor)?;
+            if g == Point::PointAtInfinity{
+                continue;
+            }
+            return Curve::new(a as i32, b as i32, BigUint::from(p), n, g);
+        }
+    }
+}
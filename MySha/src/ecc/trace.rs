@@ -0,0 +1,99 @@
+//! Events of the elliptic curve math, to follow every step of an operation without copying it.
+//!
+//! [add_traced][super::Curve::add_traced], [double_traced][super::Curve::double_traced], [multiply_traced][super::Curve::multiply_traced],
+//! [sign_traced][super::PrivKey::sign_traced] and [verify_traced][super::Signature::verify_traced]
+//! do the same as their untraced versions, and give every intermediate value to an [Observer] as an [Event]:
+//! the slopes and results of the additions and doublings, the bits that double and add reads, and the numbers of the
+//! signature equations. Any closure that takes an `&Event` is an observer.
+//!
+//! The events of signing include the nonce, which gives the private key to anyone who also has the signature,
+//! see [cryptanalysis][super::cryptanalysis], so they must be kept as secret as the key.
+//!
+//! # Examples
+//! ```
+//! use mysha::ecc::{*, trace::Event};
+//!
+//! # fn main() -> Result<(), EccError>{
+//! let curve = Curve::new(2, 3, 97_u32, 50_u32, Point::point(0_u32, 10_u32))?;
+//! let mut events = Vec::new();
+//! let point = curve.multiply_traced(curve.get_g(), 5, &mut |event: &Event| events.push(event.clone()))?;
+//!
+//! // 5 is 101 in binary: after the top bit, double for 0, then double and add for 1
+//! assert!(matches!(events[0], Event::Bit{index: 1, one: false}));
+//! assert!(matches!(events[1], Event::Double{..}));
+//! assert!(matches!(events[2], Event::Bit{index: 0, one: true}));
+//! assert!(matches!(events[3], Event::Double{..}));
+//! assert!(matches!(events[4], Event::Add{..}));
+//! assert_eq!(events.len(), 5);
+//! assert_eq!(point, curve.multiply(curve.get_g(), 5)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use num_bigint::BigUint;
+
+use super::Point;
+
+/// A step of the elliptic curve math, given to an [Observer].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event{
+    /// p + q, with p different from q, and the slope of the line through them,
+    /// none if one of them is the point at infinity or they are opposite, so the line is vertical.
+    Add{
+        p: Point,
+        q: Point,
+        slope: Option<BigUint>,
+        result: Point,
+    },
+    /// 2p, and the slope of the tangent at p, none if p is the point at infinity or its y is 0, so the tangent is vertical.
+    Double{
+        p: Point,
+        slope: Option<BigUint>,
+        result: Point,
+    },
+    /// Double and add read a bit of the scalar, from the one below the top bit down to bit 0:
+    /// the point is doubled, and then the base point is added if the bit is one.
+    Bit{
+        index: u64,
+        one: bool,
+    },
+    /// The hash that is signed or verified, read as the number z.
+    Hash{
+        z: BigUint,
+    },
+    /// Signing drew the nonce k, whose point R = kG gives r, its x mod n.
+    Nonce{
+        k: BigUint,
+        point: Point,
+        r: BigUint,
+    },
+    /// Signing computed s = k⁻¹ (z + r d) mod n, from the inverse of the nonce and the sum.
+    Signed{
+        k_inverse: BigUint,
+        sum: BigUint,
+        s: BigUint,
+    },
+    /// Verifying computed w = s⁻¹ mod n, u1 = z w mod n and u2 = r w mod n.
+    Scalars{
+        w: BigUint,
+        u1: BigUint,
+        u2: BigUint,
+    },
+    /// Verifying computed X = u1 G + u2 Q, the signature is valid if its x mod n is r.
+    Recomputed{
+        point: Point,
+    },
+}
+
+/// Receives the [events][Event] of a traced operation, in the order they happen.
+///
+/// It is implemented for every closure that takes an `&Event`.
+pub trait Observer{
+    fn observe(&mut self, event: &Event);
+}
+
+impl<F: FnMut(&Event)> Observer for F{
+    fn observe(&mut self, event: &Event){
+        self(event)
+    }
+}
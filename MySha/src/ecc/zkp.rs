@@ -0,0 +1,162 @@
+//! Zero knowledge proofs that the prover knows the private key of a public key, without revealing anything about it.
+//!
+//! This is the [Schnorr identification protocol], made non-interactive with the [Fiat-Shamir] heuristic:
+//! with the private key x and the public key P = xG, the prover picks a random nonce k and computes
+//! 1. the commitment R = kG;
+//! 2. the challenge c = H(G || P || R || context), which the verifier would have picked at random if they talked;
+//! 3. the response s = k + cx modulo n.
+//!
+//! The proof is (R, s), and the verifier checks that sG = R + cP. Only someone who knows x can answer a challenge
+//! that depends on R, and since k is random, s reveals nothing about x.
+//!
+//! H is the [tagged hash][crate::sha256::tagged_hash] `"mysha/schnorr-proof"` of the coordinates of the points, and the context.
+//! The context binds the proof to its use: a verifier that gives a fresh context, like a random challenge or the time,
+//! can't be fooled by a proof that was made for someone else.
+//!
+//! # Examples
+//! ```
+//! use mysha::ecc::{*, zkp};
+//!
+//! # fn main() -> Result<(), EccError>{
+//! let key_pair = KeyPair::new(1234_u32, Curve::secp256k1())?;
+//!
+//! let proof = zkp::prove(&key_pair.private(), b"login 2024-06-01")?;
+//! assert!(zkp::verify(&key_pair.public(), &proof, b"login 2024-06-01"));
+//!
+//! // the proof only works for its context, and its key
+//! assert!(!zkp::verify(&key_pair.public(), &proof, b"login 2024-06-02"));
+//! let someone_else = KeyPair::new(5678_u32, Curve::secp256k1())?;
+//! assert!(!zkp::verify(&someone_else.public(), &proof, b"login 2024-06-01"));
+//!
+//! // the proof is sent as bytes
+//! let bytes = proof.to_bytes();
+//! assert_eq!(zkp::Proof::from_bytes(&bytes, Curve::secp256k1())?, proof);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Schnorr identification protocol]: https://en.wikipedia.org/wiki/Proof_of_knowledge#Schnorr_protocol
+//! [Fiat-Shamir]: https://en.wikipedia.org/wiki/Fiat%E2%80%93Shamir_heuristic
+
+use num_bigint::BigUint;
+
+use super::{Curve, EccError, Point, PrivKey, PubKey};
+use crate::rand;
+use crate::sha256::tagged_hash;
+
+/// Tag of the hash of the challenge.
+const TAG: &str = "mysha/schnorr-proof";
+
+/// Coordinates of the point, padded to the size of the numbers modulo p, or the error of the point at infinity.
+fn point_bytes(point: &Point, curve: &Curve) -> Result<Vec<u8>, EccError>{
+    Ok(PubKey::new(point.clone(), curve.clone())?.to_bytes())
+}
+
+/// Bytes of the order n of the curve, the size of s.
+fn scalar_size(curve: &Curve) -> usize{
+    curve.get_n().bits().div_ceil(8) as usize
+}
+
+/// The challenge c = H(G || P || R || context), as a number modulo n.
+fn challenge(public: &PubKey, commitment: &Point, context: &[u8]) -> Result<BigUint, EccError>{
+    let curve = public.get_curve();
+    let data = [point_bytes(curve.get_g(), curve)?, public.to_bytes(), point_bytes(commitment, curve)?, context.to_vec()].concat();
+    Ok(BigUint::from_bytes_be(&tagged_hash(TAG, &data).get_bytes()) % curve.get_n())
+}
+
+/// A proof of knowledge of a private key, from [prove].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Proof{
+    commitment: Point,
+    s: BigUint,
+    curve: Curve,
+}
+
+impl Proof{
+    /// Returns the commitment R of the proof
+    pub fn get_r(&self) -> &Point{
+        &self.commitment
+    }
+
+    /// Returns the response s of the proof
+    pub fn get_s(&self) -> &BigUint{
+        &self.s
+    }
+
+    /// Returns the curve of the proof
+    pub fn get_curve(&self) -> &Curve{
+        &self.curve
+    }
+
+    /// Encodes the proof as the coordinates x and y of R, padded to [coordinate_size][Curve::coordinate_size] bytes each,
+    /// followed by s, padded to the bytes of n, 96 bytes in total for secp256k1.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        // R is checked to be on the curve when the proof is created or read, so it is never the point at infinity
+        let mut bytes = point_bytes(&self.commitment, &self.curve).unwrap_or_default();
+        let s = self.s.to_bytes_be();
+        bytes.extend(vec![0; scalar_size(&self.curve) - s.len()]);
+        bytes.extend(s);
+        bytes
+    }
+
+    /// Reads a proof of the curve encoded by [to_bytes][Proof::to_bytes].
+    ///
+    /// # Errors
+    ///
+    /// This can fail with [InvalidLength][EccError::InvalidLength] if the bytes don't have the size of a proof of the curve,
+    /// like [from_affine_bytes][PubKey::from_affine_bytes] if R isn't a point of the curve,
+    /// and with [InvalidSignature][EccError::InvalidSignature] if s isn't below n.
+    pub fn from_bytes(bytes: &[u8], curve: Curve) -> Result<Proof, EccError>{
+        let size = curve.coordinate_size();
+        if bytes.len() != 2 * size + scalar_size(&curve){
+            return Err(EccError::InvalidLength);
+        }
+        let (x, rest) = bytes.split_at(size);
+        let (y, s) = rest.split_at(size);
+        let commitment = PubKey::from_affine_bytes(x, y, curve.clone())?.get_public().clone();
+        let s = BigUint::from_bytes_be(s);
+        if &s >= curve.get_n(){
+            return Err(EccError::InvalidSignature);
+        }
+        Ok(Proof{
+            commitment,
+            s,
+            curve,
+        })
+    }
+}
+
+/// Proves the knowledge of the private key, for the context.
+///
+/// # Errors
+///
+/// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+///
+/// [wrong]: crate::ecc::Curve#problematic-curves
+pub fn prove(private: &PrivKey, context: &[u8]) -> Result<Proof, EccError>{
+    let curve = private.get_curve();
+    let public = PubKey::new(curve.multiply_generator(private.get_private().clone())?, curve.clone())?;
+    let nonce = rand::random_scalar(curve);
+    let commitment = curve.multiply_generator(nonce.clone())?;
+    let challenge = challenge(&public, &commitment, context)?;
+    let s = (nonce + challenge * private.get_private()) % curve.get_n();
+    Ok(Proof{
+        commitment,
+        s,
+        curve: curve.clone(),
+    })
+}
+
+/// Checks that the proof was made for the context by someone who knows the private key of the public key, sG = R + cP.
+pub fn verify(public: &PubKey, proof: &Proof, context: &[u8]) -> bool{
+    let curve = public.get_curve();
+    if proof.curve != *curve || &proof.s >= curve.get_n(){
+        return false;
+    }
+    let Ok(challenge) = challenge(public, &proof.commitment, context) else{
+        return false;
+    };
+    let expected = curve.multiply(public.get_public(), challenge).and_then(|cp| curve.add(&proof.commitment, &cp));
+    let actual = curve.multiply_generator(proof.s.clone());
+    matches!((actual, expected), (Ok(actual), Ok(expected)) if actual == expected)
+}
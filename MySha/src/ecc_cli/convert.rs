@@ -0,0 +1,182 @@
+use std::{fs, io::Write};
+
+use clap::ValueEnum;
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+
+use mysha::ecc::{Curve, KeyPair, PrivKey, PubKey};
+use mysha::encoding::{base64::{self, Alphabet}, pem};
+use crate::Exit;
+
+use super::output::{from_toml, Artifact, OutputTomlFile};
+
+/// Formats of keys and signatures that can be converted.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format{
+    /// The toml files written by this tool
+    Toml,
+    /// The same fields as the toml files, in json
+    Json,
+    /// PEM text, `PUBLIC KEY` or `EC PRIVATE KEY`, like OpenSSL
+    Pem,
+    /// Binary DER, the content of PEM
+    Der,
+    /// JSON web key, as used by JWT libraries
+    Jwk,
+    /// OpenSSH, `OPENSSH PRIVATE KEY` files and `authorized_keys` lines, as ecdsa-sha2-1.3.132.0.10 keys, which ssh itself doesn't accept yet
+    Ssh,
+}
+
+/// Reads a file in the format, strict rejects unknown fields of toml files.
+pub fn read(path: &str, format: Format, strict: bool) -> Artifact{
+    match format{
+        Format::Toml => from_toml(path, strict).into_artifact(),
+        Format::Json => {
+            let text = fs::read_to_string(path).exit("Error while reading the file.");
+            serde_json::from_str::<OutputTomlFile>(&text).exit("Error while parsing the json.").into_artifact()
+        },
+        Format::Pem => {
+            let text = fs::read_to_string(path).exit("Error while reading the file.");
+            match pem::decode(&text).exit("Invalid PEM file.").0.as_str(){
+                "PUBLIC KEY" => Artifact::PubKey(PubKey::from_pem(&text).exit("Invalid public key.")),
+                "EC PRIVATE KEY" => Artifact::KeyPair(KeyPair::from_pem(&text).exit("Invalid private key.")),
+                label => None.exit(&format!("Unsupported PEM label {:?}, only PUBLIC KEY and EC PRIVATE KEY can be read.", label)),
+            }
+        },
+        Format::Der => {
+            let der = fs::read(path).exit("Error while reading the file.");
+            if let Ok(kp) = KeyPair::from_der(&der){
+                Artifact::KeyPair(kp)
+            }else{
+                Artifact::PubKey(PubKey::from_der(&der).exit("The DER file isn't a secp256k1 public or private key."))
+            }
+        },
+        Format::Jwk => {
+            let text = fs::read_to_string(path).exit("Error while reading the file.");
+            read_jwk(&serde_json::from_str(&text).exit("Error while parsing the json."))
+        },
+        Format::Ssh => {
+            let text = fs::read_to_string(path).exit("Error while reading the file.");
+            if text.trim_start().starts_with("-----BEGIN "){
+                Artifact::KeyPair(KeyPair::from_openssh(&text).exit("Invalid OpenSSH private key."))
+            }else{
+                Artifact::PubKey(PubKey::from_openssh(&text).exit("Invalid OpenSSH public key."))
+            }
+        },
+    }
+}
+
+fn jwk_bytes(jwk: &Value, field: &str) -> Vec<u8>{
+    let text = jwk[field].as_str().exit(&format!("The JWK needs the field {:?}.", field));
+    base64::decode(text, Alphabet::UrlSafe).exit(&format!("Invalid base64 in the field {:?}.", field))
+}
+
+fn read_jwk(jwk: &Value) -> Artifact{
+    if jwk["kty"] != "EC" || jwk["crv"] != "secp256k1"{
+        None.exit("Only JWKs with kty EC and crv secp256k1 can be read.")
+    }
+    let public = PubKey::from_affine_bytes(&jwk_bytes(jwk, "x"), &jwk_bytes(jwk, "y"), Curve::secp256k1()).exit("Invalid public key.");
+    if jwk.get("d").is_none(){
+        return Artifact::PubKey(public);
+    }
+    let private = PrivKey::new(BigUint::from_bytes_be(&jwk_bytes(jwk, "d")), Curve::secp256k1()).exit("Invalid private key.");
+    let kp = KeyPair::from_private(&private).exit("Invalid private key.");
+    if kp.get_public() != public.get_public(){
+        None.exit("The public key doesn't match the private key.")
+    }
+    Artifact::KeyPair(kp)
+}
+
+/// Coordinate or private key as a JWK field, base64 of 32 bytes.
+fn jwk_field(n: &BigUint) -> String{
+    let mut bytes = vec![0; 32];
+    let number = n.to_bytes_be();
+    bytes[32 - number.len()..].copy_from_slice(&number);
+    base64::encode(&bytes, Alphabet::UrlSafe)
+}
+
+fn to_jwk(public: &PubKey, private: Option<&BigUint>) -> Value{
+    if *public.get_curve() != Curve::secp256k1(){
+        None.exit("JWKs can only hold secp256k1 keys.")
+    }
+    let (x, y) = public.get_public().get_xy().unwrap();
+    let mut jwk = json!({
+        "kty": "EC",
+        "crv": "secp256k1",
+        "x": jwk_field(x),
+        "y": jwk_field(y),
+    });
+    if let Some(d) = private{
+        jwk["d"] = Value::from(jwk_field(d));
+    }
+    jwk
+}
+
+/// Only toml and json hold curves and signatures, the other formats only hold secp256k1 keys.
+fn check_format(artifact: &Artifact, format: Format){
+    if matches!(format, Format::Toml | Format::Json){
+        return;
+    }
+    match artifact{
+        Artifact::Curve(_) => None.exit("Curves can only be converted between toml and json."),
+        Artifact::Signature(_) => None.exit("Signatures can only be converted between toml and json, since DER signatures don't hold the curve and public key."),
+        _ => (),
+    }
+}
+
+/// Writes the artifact in the format, as bytes, since DER is binary.
+pub fn write(artifact: Artifact, format: Format, hex: bool, le: bool) -> Vec<u8>{
+    check_format(&artifact, format);
+    if let Format::Toml | Format::Json = format{
+        let output = match &artifact{
+            Artifact::Curve(c) => OutputTomlFile::from_curve(c, hex, le),
+            Artifact::KeyPair(kp) => OutputTomlFile::from_key_pair(kp, hex, le),
+            Artifact::PubKey(p) => OutputTomlFile::from_public(p, hex, le),
+            Artifact::PrivKey(p) => OutputTomlFile::from_private(p, hex, le),
+            Artifact::Signature(sig) => OutputTomlFile::from_sig(sig, hex, le),
+        };
+        let text = if format == Format::Toml{
+            toml::to_string(&output).exit("Error while parsing to toml.")
+        }else{
+            serde_json::to_string_pretty(&output).exit("Error while parsing to json.") + "\n"
+        };
+        return text.into_bytes();
+    }
+
+    // a private key alone is written with its public key, which the formats expect
+    let artifact = match artifact{
+        Artifact::PrivKey(private) => Artifact::KeyPair(KeyPair::from_private(&private).exit("Invalid private key.")),
+        artifact => artifact,
+    };
+    match (artifact, format){
+        (Artifact::KeyPair(kp), Format::Pem) => kp.to_pem().exit("Can't write the key.").into_bytes(),
+        (Artifact::KeyPair(kp), Format::Der) => kp.to_der().exit("Can't write the key."),
+        (Artifact::KeyPair(kp), Format::Jwk) => {
+            let public = PubKey::new(kp.get_public().clone(), kp.get_curve().clone()).exit("Invalid public key.");
+            (serde_json::to_string_pretty(&to_jwk(&public, Some(kp.get_private()))).unwrap() + "\n").into_bytes()
+        },
+        (Artifact::PubKey(p), Format::Pem) => p.to_pem().exit("Can't write the key.").into_bytes(),
+        (Artifact::PubKey(p), Format::Der) => p.to_der().exit("Can't write the key."),
+        (Artifact::PubKey(p), Format::Jwk) => {
+            (serde_json::to_string_pretty(&to_jwk(&p, None)).unwrap() + "\n").into_bytes()
+        },
+        (Artifact::KeyPair(kp), Format::Ssh) => kp.to_openssh("").exit("Can't write the key.").into_bytes(),
+        (Artifact::PubKey(p), Format::Ssh) => (p.to_openssh("").exit("Can't write the key.") + "\n").into_bytes(),
+        _ => unreachable!("checked by check_format"),
+    }
+}
+
+/// Writes the converted file, or prints it, refusing to replace an existing file unless asked to.
+pub fn output(bytes: &[u8], path: Option<&str>, overwrite: bool){
+    match path{
+        Some(path) => {
+            let mut file = if overwrite{
+                fs::File::create(path)
+            }else{
+                fs::File::options().write(true).create_new(true).open(path)
+            }.exit("Error while creating file.");
+            file.write_all(bytes).exit("Error while writing to the file.");
+        },
+        None => std::io::stdout().write_all(bytes).exit("Error while writing the output."),
+    }
+}
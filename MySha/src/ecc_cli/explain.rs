@@ -0,0 +1,93 @@
+//! Numbered steps of signing and verifying, with every number of the equations, for `sign --explain` and `verify --explain`.
+//!
+//! The steps of signing print the nonce, which gives the private key to anyone who also has the signature.
+
+use mysha::ecc::{trace::Event, Point, PrivKey, Signature, Verification};
+use mysha::sha256::InputType;
+
+use super::show_number;
+use crate::Exit;
+
+fn show_point(point: &Point, hex: bool, le: bool) -> String{
+    match point.get_xy(){
+        Some((x, y)) => format!("({}, {})", show_number(x, hex, le), show_number(y, hex, le)),
+        None => String::from("the point at infinity"),
+    }
+}
+
+/// Signs the message with [PrivKey::sign_traced], printing every step from its events.
+pub fn sign(private: &PrivKey, message: &str, input_type: InputType, hex: bool, le: bool) -> Signature{
+    println!("Signing {:?}, with the private key d and the generator G of order n", message);
+    println!("   n = {}", show_number(private.get_curve().get_n(), hex, le));
+    let signature = private.sign_traced(message, input_type, &mut |event: &Event| match event{
+        Event::Hash{z} => {
+            println!("1. z = sha256 of the message, read as a big endian number");
+            println!("   z = {}", show_number(z, hex, le));
+        },
+        Event::Nonce{k, point, r} => {
+            println!("2. k = a random nonce from 1 to n - 1, secret, and new for every signature");
+            println!("   k = {}", show_number(k, hex, le));
+            println!("   Warning: with k and the signature, anyone gets the private key, d = (s k - z) r⁻¹ mod n,");
+            println!("   as `mysha demo nonce-reuse` shows, so this output must be kept as secret as the key and never shared");
+            println!("3. R = kG");
+            println!("   R = {}", show_point(point, hex, le));
+            println!("4. r = x of R mod n");
+            println!("   r = {}", show_number(r, hex, le));
+        },
+        Event::Signed{k_inverse, sum, s} => {
+            println!("5. s = k⁻¹ (z + r d) mod n");
+            println!("   k⁻¹ mod n     = {}", show_number(k_inverse, hex, le));
+            println!("   z + r d mod n = {}", show_number(sum, hex, le));
+            println!("   s             = {}", show_number(s, hex, le));
+        },
+        _ => (),
+    }).exit("Error while signing.");
+    println!("6. the signature is (r, s), with the public key Q = dG to verify it");
+    println!();
+    signature
+}
+
+/// Prints the steps of [Signature::verify_traced] from its events, up to the first check that fails.
+pub fn verify(signature: &Signature, message: &str, input_type: InputType, hex: bool, le: bool){
+    let n = signature.get_curve().get_n();
+    let r = signature.get_r();
+    println!("Verifying the signature (r, s) of {:?}, with the public key Q and the generator G of order n", message);
+    println!("   n = {}", show_number(n, hex, le));
+    println!("   r = {}", show_number(r, hex, le));
+    println!("   s = {}", show_number(signature.get_s(), hex, le));
+    println!("   Q = {}", show_point(signature.get_public(), hex, le));
+
+    println!("1. r and s must be from 1 to n - 1, and Q a point of the curve other than the point at infinity");
+    let verification = signature.verify_traced(message, input_type, &mut |event: &Event| match event{
+        Event::Hash{z} => {
+            println!("   they are");
+            println!("2. z = sha256 of the message, read as a big endian number");
+            println!("   z = {}", show_number(z, hex, le));
+        },
+        Event::Scalars{w, u1, u2} => {
+            println!("3. w = s⁻¹ mod n");
+            println!("   w = {}", show_number(w, hex, le));
+            println!("4. u1 = z w mod n and u2 = r w mod n");
+            println!("   u1 = {}", show_number(u1, hex, le));
+            println!("   u2 = {}", show_number(u2, hex, le));
+        },
+        Event::Recomputed{point} => {
+            println!("5. X = u1 G + u2 Q, which is R = kG if s = k⁻¹ (z + r d), since u1 + u2 d = (z + r d) w = k");
+            println!("   X = {}", show_point(point, hex, le));
+            println!("6. the signature is valid if x of X mod n = r");
+            match point.get_x(){
+                Some(x) => {
+                    let x = x % n;
+                    println!("   x of X mod n = {}", show_number(&x, hex, le));
+                    println!("   r            = {}, {}", show_number(r, hex, le), if x == *r {"equal"} else {"different"});
+                },
+                None => println!("   X is the point at infinity, which has no x"),
+            }
+        },
+        _ => (),
+    }).exit("Error while hashing message");
+    if matches!(verification, Verification::ROutOfRange | Verification::SOutOfRange | Verification::PublicKeyAtInfinity | Verification::PublicKeyNotOnCurve){
+        println!("   they aren't, so the signature is rejected");
+    }
+    println!();
+}
@@ -1,16 +1,23 @@
 use clap::{Args, Subcommand};
-use num_bigint::{BigUint, RandBigInt};
+use num_bigint::{BigInt, BigUint};
 use num_traits::{Num, ToBytes};
-use std::{num::ParseIntError, str::FromStr};
-use rand::{self, SeedableRng};
+use std::{io::{self, BufRead, IsTerminal, Read}, str::FromStr};
 
-use mysha::ecc::{self, Curve, KeyPair, Point, PubKey, PrivKey, Signature};
-use mysha::sha256;
+use mysha::ecc::{self, ethereum::{self, EthSignature}, zkp, Curve, KeyPair, Point, PubKey, PrivKey, Signature};
+use mysha::sha256::{InputType, Sha256};
+use mysha::encoding::{hex, randomart};
+use mysha::rand;
+use crate::failure::Failure;
 use crate::Exit;
 use crate::sha256_cli;
+use crate::config::{self, Config};
+use crate::qr;
+use crate::timing;
 
+mod convert;
+mod explain;
 mod output;
-use self::output::{from_toml, to_toml, OutputTomlFile};
+use self::output::{from_toml, to_toml, to_toml_or_json, Artifact, MultiSignatureFile, OutputTomlFile};
 
 
 #[derive(Args, Debug)]
@@ -26,17 +33,45 @@ pub struct ECCArgs{
     #[arg(long)]
     overwrite: bool,
 
-    /// path to toml file with curve specs. Defaults to secp256k1. Structure avaiable with new curve command.
+    /// path to toml file with curve specs. Defaults to secp256k1, or the curve in the config file. Structure avaiable with new curve command.
     #[arg(short, long)]
     curve: Option<String>,
 
-    /// Displays output as hex
+    /// Rejects unknown fields in the toml files read, instead of ignoring them
     #[arg(long)]
+    strict: bool,
+
+    /// Displays output as hex
+    #[arg(long, overrides_with = "no_hex")]
     hex: bool,
 
+    /// Displays output as decimal, if hex is set in the config file
+    #[arg(long, overrides_with = "hex")]
+    no_hex: bool,
+
     /// Displays output as little endian. Needs to have hex enabled.
-    #[arg(short, long)]
+    #[arg(short, long, overrides_with = "no_little_endian")]
     little_endian: bool,
+
+    /// Displays output as big endian, if little endian is set in the config file
+    #[arg(long, overrides_with = "little_endian")]
+    no_little_endian: bool,
+
+    /// Prints the public key or signature as a qr code
+    #[arg(long)]
+    qr: bool,
+
+    /// Writes the qr code of the public key or signature to a file, as svg if it ends with .svg and png if not
+    #[arg(long, value_name = "FILE")]
+    qr_file: Option<String>,
+
+    /// Prints to stderr how long key generation, every signature and verification took
+    #[arg(long)]
+    time: bool,
+
+    /// Prints the randomart of the fingerprint of the public key generated or inspected, like ssh-keygen, to compare keys at a glance
+    #[arg(long)]
+    randomart: bool,
 } 
 
 #[derive(Debug, Subcommand)]
@@ -49,6 +84,41 @@ enum SubCommand{
     Verify(VerifyArgs),
     /// Create new toml objects
     New(NewArgs),
+    /// Detect what a toml file holds and print it, with the values derived from it
+    Inspect(InspectArgs),
+    /// Convert keys and signatures between toml and other formats, printing the result or writing it to the output file
+    Convert(ConvertArgs),
+    /// Print the parameters of the curve, secp256k1 or the one of --curve, and the checks they pass
+    CurveInfo(CurveInfoArgs),
+    /// Prove knowing the private key of a public key, with a zero knowledge proof that reveals nothing about it
+    ProveOwnership(ProveOwnershipArgs),
+    /// Verify a proof of ownership made by prove-ownership, against the public key
+    VerifyOwnership(VerifyOwnershipArgs),
+}
+
+#[derive(Args, Debug)]
+struct ConvertArgs{
+    /// File to be converted
+    file: String,
+    /// Format of the file
+    #[arg(long, default_value_t = convert::Format::Toml, value_enum)]
+    from: convert::Format,
+    /// Format to convert to
+    #[arg(long, value_enum)]
+    to: convert::Format,
+}
+
+#[derive(Args, Debug)]
+struct CurveInfoArgs{
+    /// Also checks the curve for known weaknesses: prime parameters, cofactor, anomalous curves and small embedding degrees
+    #[arg(long)]
+    check: bool,
+}
+
+#[derive(Args, Debug)]
+struct InspectArgs{
+    /// toml file with a curve, key pair, public key, private key or signature
+    file: String,
 }
 
 #[derive(Args, Debug)]
@@ -104,6 +174,14 @@ struct CurveArgs{
     /// treats curve parameters as little endian. Needs to have hex enabled.
     #[arg(short, long)]
     little_endian: bool,
+
+    /// generates a random small curve, with a prime order generator, instead of using the parameters
+    #[arg(long, conflicts_with_all = ["a", "b", "p", "n", "x", "y"])]
+    random: bool,
+
+    /// size in bits of p of the random curve, from 4 to 20
+    #[arg(long, requires = "random", default_value_t = 16)]
+    bits: u32,
 }
 
 #[derive(Args, Debug)]
@@ -203,14 +281,29 @@ struct GenArgs{
 
 #[derive(Args, Debug)]
 struct SignArgs{
-    /// Message to be signed
-    message: String,
+    /// Messages to be signed, one signature each. Read from the lines of stdin passed by | when there are none
+    messages: Vec<String>,
+    /// File listing messages to be signed, one per line, skipping blank lines and lines starting with #. Can be repeated
+    #[arg(long, value_name = "FILE", visible_alias = "messages-file")]
+    input_file: Vec<String>,
+    /// Turn off separate by lines on stdin passed by |, signing all of it as one message
+    #[arg(short, long)]
+    separate_off: bool,
+    /// Writes every signature to the single file of --output, as json if it ends with .json and toml if not, instead of a numbered file each
+    #[arg(long)]
+    combined: bool,
     /// Private Key or key pair file
     #[arg(short, long)]
     private: String,
     /// Message type
     #[arg(short, long, default_value_t = sha256_cli::Type::Text, value_enum)]
     r#type: sha256_cli::Type,
+    /// Signs like Ethereum's personal_sign, with Keccak-256, printing the 65 bytes signature and the address in hex. Needs a secp256k1 key
+    #[arg(long)]
+    ethereum: bool,
+    /// Prints the numbered steps of every signature before it: the hash, the nonce, and the computation of r and s
+    #[arg(long, conflicts_with = "ethereum")]
+    explain: bool,
 }
 
 #[derive(Args, Debug)]
@@ -223,30 +316,277 @@ struct VerifyArgs{
     /// message type
     #[arg(short, long, default_value_t = sha256_cli::Type::Text, value_enum)]
     r#type: sha256_cli::Type,
+    /// Doesn't print anything, the result is only given by the exit status
+    #[arg(short, long)]
+    quiet: bool,
+    /// Prints only `valid` or `invalid`, to be parsed by scripts
+    #[arg(long, conflicts_with = "quiet")]
+    porcelain: bool,
+    /// Verifies an Ethereum signature, given in hex as <SIGNATURE> instead of a file, against the address
+    #[arg(long, value_name = "ADDRESS")]
+    ethereum: Option<String>,
+    /// Prints the numbered steps of the verification before the result, with the numbers of its equation
+    #[arg(long, conflicts_with_all = ["ethereum", "quiet", "porcelain"])]
+    explain: bool,
 }
 
-fn get_biguint(n: &str, hex: bool, le: bool) -> BigUint{
-    if hex{
-        if le{
-            if n.len() % 2 != 0{
-                eprintln!("Error while parsing large integers: you are not able to use little endian, since you did not provide a whole number of bytes.");
-                std::process::exit(1);
-            }
-            (0..n.len()).step_by(2).map(|b| u8::from_str_radix(&n[b..b + 2], 16)).collect::<Result<Vec<u8>, ParseIntError>>().map(|b| BigUint::from_bytes_le(&b)).ok()
+#[derive(Args, Debug)]
+struct ProveOwnershipArgs{
+    /// Private Key or key pair file
+    #[arg(short, long)]
+    private: String,
+    /// Text the proof is bound to, like a challenge given by the verifier, which must be the same to verify it
+    #[arg(long, default_value_t = String::new())]
+    context: String,
+}
+
+#[derive(Args, Debug)]
+struct VerifyOwnershipArgs{
+    /// Proof in hex, as printed by prove-ownership
+    proof: String,
+    /// Public key, key pair or private key file
+    #[arg(short, long)]
+    public: String,
+    /// Text the proof was bound to
+    #[arg(long, default_value_t = String::new())]
+    context: String,
+    /// Doesn't print anything, the result is only given by the exit status
+    #[arg(short, long)]
+    quiet: bool,
+    /// Prints only `valid` or `invalid`, to be parsed by scripts
+    #[arg(long, conflicts_with = "quiet")]
+    porcelain: bool,
+}
+
+fn get_biguint(n: &str, is_hex: bool, le: bool) -> BigUint{
+    match (is_hex, le){
+        (true, true) => BigUint::from_bytes_le(&hex::decode(n).exit("Error while parsing large integers.")),
+        (true, false) => BigUint::from_str_radix(n, 16).ok().exit("Error while parsing large integers."),
+        (false, _) => BigUint::from_str(n).ok().exit("Error while parsing large integers."),
+    }
+}
+
+/// Hex of the number in little endian byte order.
+fn get_hex_le(n: &BigUint) -> String{
+    hex::encode(&n.to_le_bytes())
+}
+
+/// Hex of the number, padded with zeros to the given number of bytes.
+fn get_hex_padded(n: &BigUint, bytes: usize) -> String{
+    format!("{:0width$x}", n, width = bytes * 2)
+}
+
+/// Public key in the uncompressed SEC1 format, 04 followed by x and y, in hex.
+fn get_public_sec1(public: &Point, curve: &Curve) -> String{
+    let bytes = curve.get_p().bits().div_ceil(8) as usize;
+    format!("04{}{}", get_hex_padded(public.get_x().unwrap(), bytes), get_hex_padded(public.get_y().unwrap(), bytes))
+}
+
+/// Signature in the compact format, r followed by s, in hex.
+fn get_signature_compact(sig: &Signature) -> String{
+    let bytes = sig.get_curve().get_n().bits().div_ceil(8) as usize;
+    get_hex_padded(sig.get_r(), bytes) + &get_hex_padded(sig.get_s(), bytes)
+}
+
+/// Bytes of a message for Ethereum signatures, the text itself or the decoded hex.
+fn ethereum_message(message: &str, input_type: InputType) -> Vec<u8>{
+    match input_type{
+        InputType::Text => message.as_bytes().to_vec(),
+        InputType::Hex => hex::decode(message.trim_start_matches("0x")).exit("Invalid hex message."),
+        _ => None.exit("Ethereum messages must be text or hex."),
+    }
+}
+
+/// Number in the output format, decimal or hex, big or little endian.
+fn show_number(n: &BigUint, hex: bool, le: bool) -> String{
+    match (hex, le){
+        (true, true) => get_hex_le(n),
+        (true, false) => format!("{:x}", n),
+        (false, _) => n.to_string(),
+    }
+}
+
+/// Name of the curve, if it is a known one.
+fn curve_name(curve: &Curve) -> String{
+    match curve.name(){
+        Some(name) => name.to_string(),
+        None => format!("a custom curve of {} bits", curve.get_p().bits()),
+    }
+}
+
+/// Fingerprint of a public key, the first 8 bytes of the sha256 of its uncompressed SEC1 encoding.
+fn fingerprint(public: &Point, curve: &Curve) -> String{
+    let sec1 = hex::decode(&get_public_sec1(public, curve)).unwrap();
+    let digest = hex::encode(&Sha256::digest(&sec1)[..8]);
+    digest.as_bytes().chunks(2).map(|pair| std::str::from_utf8(pair).unwrap()).collect::<Vec<_>>().join(":")
+}
+
+/// Randomart of the sha256 of the uncompressed SEC1 encoding of the public key, the hash the fingerprint is the start of.
+fn get_randomart(public: &Point, curve: &Curve) -> String{
+    let sec1 = hex::decode(&get_public_sec1(public, curve)).unwrap();
+    let title = curve.name().map_or(format!("custom {}", curve.get_p().bits()), |name| name.to_string());
+    randomart::draw(&Sha256::digest(&sec1), &title, "SHA256")
+}
+
+fn show_randomart(public: &Point, curve: &Curve, print: bool){
+    if print{
+        println!("{}", get_randomart(public, curve));
+    }
+}
+
+fn print_curve(curve: &Curve, hex: bool, le: bool){
+    let (x, y) = curve.get_g().get_xy().unwrap();
+    println!("Curve: {}", curve_name(curve));
+    println!("    y² = x³ + {}x + {} (mod p)", curve.get_a(), curve.get_b());
+    println!("    p: {}", show_number(curve.get_p(), hex, le));
+    println!("    n: {}", show_number(curve.get_n(), hex, le));
+    println!("    generator x: {}", show_number(x, hex, le));
+    println!("    generator y: {}", show_number(y, hex, le));
+}
+
+fn print_public(title: &str, public: &Point, curve: &Curve, hex: bool, le: bool, randomart: bool){
+    let (x, y) = public.get_xy().unwrap();
+    println!("{}:", title);
+    println!("    x: {}", show_number(x, hex, le));
+    println!("    y: {}", show_number(y, hex, le));
+    println!("    SEC1: {}", get_public_sec1(public, curve));
+    println!("    Fingerprint: {}", fingerprint(public, curve));
+    show_randomart(public, curve, randomart);
+}
+
+/// The discriminant -16 (4a³ + 27b²) mod p, zero for singular curves.
+fn discriminant(curve: &Curve) -> BigUint{
+    let (a, b) = (BigInt::from(curve.get_a()), BigInt::from(curve.get_b()));
+    let p = BigInt::from(curve.get_p().clone());
+    let discriminant: BigInt = -16 * (4 * a.pow(3) + 27 * b.pow(2));
+    (((discriminant % &p) + &p) % &p).to_biguint().unwrap()
+}
+
+fn print_curve_info(curve: &Curve){
+    let (x, y) = curve.get_g().get_xy().unwrap();
+    let discriminant = discriminant(curve);
+    let order = curve.multiply(curve.get_g(), BigInt::from(curve.get_n().clone())).exit("Invalid curve.");
+
+    println!("Curve: {}", curve_name(curve));
+    println!("Equation: y² = x³ + {}x + {} (mod p)", curve.get_a(), curve.get_b());
+    println!("p ({} bits):", curve.get_p().bits());
+    println!("    hex: {:x}", curve.get_p());
+    println!("    decimal: {}", curve.get_p());
+    println!("n ({} bits):", curve.get_n().bits());
+    println!("    hex: {:x}", curve.get_n());
+    println!("    decimal: {}", curve.get_n());
+    println!("Generator:");
+    println!("    x hex: {:x}", x);
+    println!("    x decimal: {}", x);
+    println!("    y hex: {:x}", y);
+    println!("    y decimal: {}", y);
+    println!("Discriminant: {} ({})", discriminant, if discriminant == BigUint::from(0_u8) {"singular"} else {"not singular"});
+    println!("n * G is infinity: {}", if order == Point::PointAtInfinity {"yes"} else {"no"});
+    println!("Generator on the curve: {}", if curve.is_on_curve(curve.get_g()) {"yes"} else {"no"});
+}
+
+fn print_security_report(curve: &Curve){
+    let report = curve.security_report();
+    let yes_no = |b: bool| if b {"yes"} else {"no"};
+
+    println!("Security:");
+    println!("    p prime: {}", yes_no(report.p_prime));
+    println!("    n prime: {}", yes_no(report.n_prime));
+    match &report.cofactor{
+        Some(h) => println!("    Cofactor: {}", h),
+        None => println!("    Cofactor: unknown, n is too small to find it for this p"),
+    }
+    println!("    Anomalous: {}", yes_no(report.anomalous));
+    match report.embedding_degree{
+        Some(k) => println!("    Embedding degree: {}", k),
+        None => println!("    Embedding degree: more than 20"),
+    }
+    println!("    Estimated security: {} bits", report.security_bits);
+    match (&report.twist_order, &report.twist_factors, report.twist_security_bits){
+        (Some(order), Some(factors), Some(bits)) => {
+            println!("    Twist order: {}", order);
+            println!("    Twist factors: {}", factors.iter().map(|f| f.to_string()).collect::<Vec<_>>().join(" * "));
+            println!("    Twist security: {} bits", bits);
+        },
+        _ => println!("    Twist: unknown, the cofactor is needed to find its order"),
+    }
+    let findings = report.findings();
+    if findings.is_empty(){
+        println!("No weaknesses found.");
+    }else{
+        println!("Weaknesses:");
+        for finding in findings{
+            println!("    - {}", finding);
+        }
+    }
+    if report.weak_twist(){
+        println!("Warning: the twist is weak.");
+        println!("    Points that aren't on the curve may be on its twist, where the logarithm is easier, one small factor at a time.");
+        println!("    mysha checks every point, but other implementations of this curve must check them too, or be open to twist attacks.");
+    }
+}
+
+/// Prints the curve, when it is a custom one, since known ones are already named.
+fn print_custom_curve(curve: &Curve, hex: bool, le: bool){
+    if *curve != Curve::secp256k1(){
+        print_curve(curve, hex, le);
+    }
+}
+
+/// Messages to sign: the arguments and the lines of the input files, or else stdin passed by |, a message per line,
+/// or all of it with --separate-off.
+fn sign_messages(args: &SignArgs) -> Vec<String>{
+    let mut messages = args.messages.clone();
+    messages.extend(sha256_cli::read_input_files(&args.input_file));
+    if messages.is_empty() && ! io::stdin().is_terminal(){
+        if args.separate_off{
+            let mut message = String::new();
+            io::stdin().read_to_string(&mut message).exit("Error while geting stdin passed.");
+            messages.push(message);
         }else{
-            BigUint::from_str_radix(n, 16).ok()
+            for line in io::stdin().lock().lines(){
+                messages.push(line.exit("Error while geting stdin passed."));
+            }
         }
-    }else{
-        BigUint::from_str(n).ok()
-    }.exit("Error while parsing large integers.")
+    }
+    if messages.is_empty(){
+        None.exit("A message to sign is needed, as an argument, in --input-file, or passed by | to stdin.")
+    }
+    messages
+}
+
+/// Prints the qr code of the value, and writes it to a file, if asked to.
+fn show_qr(value: &str, print: bool, file: Option<&str>){
+    if print{
+        qr::print(value);
+    }
+    if let Some(path) = file{
+        qr::write(value, path);
+    }
 }
 
-pub fn key_pair(args: ECCArgs){
-    let hex = args.hex;
-    let le = args.little_endian;
-    let curve = if let Some(path) = &args.curve{
-        let input = from_toml(path);
-        input.to_curve()
+/// Reads the private key of an ecc toml file, for the commands that sign with it.
+pub(crate) fn read_private_key(path: &str) -> PrivKey{
+    from_toml(path, false).into_priv_key()
+}
+
+/// Reads the public key of an ecc toml file, derived from the private key if the file only has it.
+pub(crate) fn read_public_key(path: &str) -> PubKey{
+    match from_toml(path, false).into_artifact(){
+        Artifact::KeyPair(kp) => kp.public(),
+        Artifact::PrivKey(private) => KeyPair::from_private(&private).exit("Invalid private key.").public(),
+        Artifact::PubKey(public) => public,
+        Artifact::Signature(sig) => PubKey::new(sig.get_public().clone(), sig.get_curve().clone()).exit("Invalid public key."),
+        Artifact::Curve(_) => None.exit(&format!("{} has no key, only the curve.", path)),
+    }
+}
+
+pub fn key_pair(args: ECCArgs, config: &Config){
+    let hex = config::flag(args.hex, args.no_hex, config.ecc.hex);
+    let le = config::flag(args.little_endian, args.no_little_endian, config.ecc.little_endian);
+    let curve = if let Some(path) = args.curve.as_ref().or(config.ecc.curve.as_ref()){
+        let input = from_toml(path, args.strict);
+        input.into_curve()
     }else{
         ecc::Curve::secp256k1()
     };
@@ -254,6 +594,10 @@ pub fn key_pair(args: ECCArgs){
     match args.subcommand{
         SubCommand::New(sub_args) => {
             let output = match sub_args.object{
+                Objects::Curve(specs) if specs.random => {
+                    let curve = Curve::random_small(specs.bits).exit("Can't generate the curve.");
+                    OutputTomlFile::from_curve(&curve, hex, le)
+                },
                 Objects::Curve(specs) => {
                     let mut a = curve.get_a();
                     let mut b = curve.get_b();
@@ -322,7 +666,7 @@ pub fn key_pair(args: ECCArgs){
                     let r = get_biguint(&specs.r, specs.hex, specs.little_endian);
                     let s = get_biguint(&specs.s, specs.hex, specs.little_endian);
                     let public = Point::Point { x, y };
-                    let sig = Signature::new(r, s, curve, public);
+                    let sig = Signature::new(r, s, curve, public).exit("Signature r and s must be from 1 to n - 1.");
                     OutputTomlFile::from_sig(&sig, hex, le)
                 },
             };
@@ -332,19 +676,65 @@ pub fn key_pair(args: ECCArgs){
                 println!("{}", toml::to_string(&output).exit("Error while parsing to toml."));
             }
         },
+        SubCommand::CurveInfo(sub_args) => {
+            print_curve_info(&curve);
+            if sub_args.check{
+                print_security_report(&curve);
+            }
+        },
+        SubCommand::Convert(sub_args) => {
+            let artifact = convert::read(&sub_args.file, sub_args.from, args.strict);
+            let bytes = convert::write(artifact, sub_args.to, hex, le);
+            convert::output(&bytes, args.output.as_deref(), args.overwrite);
+        },
+        SubCommand::Inspect(sub_args) => {
+            match from_toml(&sub_args.file, args.strict).into_artifact(){
+                Artifact::Curve(c) => print_curve(&c, hex, le),
+                Artifact::KeyPair(kp) => {
+                    println!("Key pair on {}", curve_name(kp.get_curve()));
+                    print_custom_curve(kp.get_curve(), hex, le);
+                    println!("Private key: {}", show_number(kp.get_private(), hex, le));
+                    print_public("Public key", kp.get_public(), kp.get_curve(), hex, le, args.randomart);
+                },
+                Artifact::PrivKey(private) => {
+                    let kp = KeyPair::from_private(&private).exit("Invalid private key.");
+                    println!("Private key on {}", curve_name(private.get_curve()));
+                    print_custom_curve(private.get_curve(), hex, le);
+                    println!("Private key: {}", show_number(private.get_private(), hex, le));
+                    print_public("Derived public key", kp.get_public(), kp.get_curve(), hex, le, args.randomart);
+                },
+                Artifact::PubKey(public) => {
+                    println!("Public key on {}", curve_name(public.get_curve()));
+                    print_custom_curve(public.get_curve(), hex, le);
+                    print_public("Public key", public.get_public(), public.get_curve(), hex, le, args.randomart);
+                },
+                Artifact::Signature(sig) => {
+                    println!("Signature on {}", curve_name(sig.get_curve()));
+                    print_custom_curve(sig.get_curve(), hex, le);
+                    println!("r: {}", show_number(sig.get_r(), hex, le));
+                    println!("s: {}", show_number(sig.get_s(), hex, le));
+                    println!("Compact: {}", get_signature_compact(&sig));
+                    if sig.get_s() > &(sig.get_curve().get_n() >> 1){
+                        println!("s is high, n - s would also be valid, some tools like Bitcoin reject it");
+                    }else{
+                        println!("s is low, the form required by Bitcoin and Ethereum");
+                    }
+                    print_public("Signer public key", sig.get_public(), sig.get_curve(), hex, le, args.randomart);
+                },
+            }
+        },
         SubCommand::Generate(sub_args) => {
             let private = sub_args.private.unwrap_or(String::from("random"));
             if private.to_lowercase() == "random" {
-                let mut rng = rand::rngs::StdRng::from_entropy();
-                let private = rng.gen_biguint_range(&BigUint::from(1_u8), curve.get_n());
-                let kp = KeyPair::new(private, curve).exit("Encountered");
+                let private = rand::random_scalar(&curve);
+                let kp = timing::time(args.time, "key generation", None, || KeyPair::new(private, curve)).exit("Encountered");
                 if let Some(filename) = args.output{
                     let output = OutputTomlFile::from_key_pair(&kp, hex, le);
                     to_toml(output, &filename, ! args.overwrite);
                 }else{
                     if hex{
                         if le{
-                            println!("private key: {}\nPublic Key: Point {{\n    x: {},\n    y: {},\n}}", &kp.get_private().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>(), &kp.get_public().get_x().unwrap().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>(), &kp.get_public().get_y().unwrap().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>());
+                            println!("private key: {}\nPublic Key: Point {{\n    x: {},\n    y: {},\n}}", get_hex_le(kp.get_private()), get_hex_le(kp.get_public().get_x().unwrap()), get_hex_le(kp.get_public().get_y().unwrap()));
                         }else{
                             println!("private key: {:x}\nPublic Key: Point {{\n    x: {:x},\n    y: {:x},\n}}", &kp.get_private(), &kp.get_public().get_x().unwrap(), &kp.get_public().get_y().unwrap());
                         }
@@ -352,16 +742,18 @@ pub fn key_pair(args: ECCArgs){
                         println!("private key: {}\nPublic Key: {:#?}",&kp.get_private(), &kp.get_public());
                     }
                 }
+                show_randomart(kp.get_public(), kp.get_curve(), args.randomart);
+                show_qr(&get_public_sec1(kp.get_public(), kp.get_curve()), args.qr, args.qr_file.as_deref());
             }else{
                 let multiplier = get_biguint(&private, sub_args.hex, sub_args.little_endian);
-                let kp = KeyPair::new(multiplier, curve).exit("Encoutered");
+                let kp = timing::time(args.time, "key generation", None, || KeyPair::new(multiplier, curve)).exit("Encoutered");
                 if let Some(filename) = args.output{
                     let output = OutputTomlFile::from_key_pair(&kp, sub_args.hex, sub_args.little_endian);
                     to_toml(output, &filename, ! args.overwrite)
                 }else{
                     if hex{
                         if le{
-                            println!("Public Key: Point {{\n    x: {},\n    y: {},\n}}", &kp.get_public().get_x().unwrap().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>(), &kp.get_public().get_y().unwrap().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>());
+                            println!("Public Key: Point {{\n    x: {},\n    y: {},\n}}", get_hex_le(kp.get_public().get_x().unwrap()), get_hex_le(kp.get_public().get_y().unwrap()));
                         }else{
                             println!("Public Key: Point {{\n    x: {:x},\n    y: {:x},\n}}", &kp.get_public().get_x().unwrap(), &kp.get_public().get_y().unwrap());
                         }
@@ -369,52 +761,124 @@ pub fn key_pair(args: ECCArgs){
                         println!("Public Key: {:#?}", &kp.get_public());
                     }
                 }
+                show_randomart(kp.get_public(), kp.get_curve(), args.randomart);
+                show_qr(&get_public_sec1(kp.get_public(), kp.get_curve()), args.qr, args.qr_file.as_deref());
             }
         },
         SubCommand::Sign(sub_args) => {
-            let private = from_toml(&sub_args.private);
-            let private = private.to_priv_key();
-            let t = match sub_args.r#type{
-                sha256_cli::Type::Text => sha256::InputType::Text,
-                sha256_cli::Type::Binary => sha256::InputType::Binary,
-                sha256_cli::Type::LeBinary => sha256::InputType::LeBinary,
-                sha256_cli::Type::File => sha256::InputType::File,
-                sha256_cli::Type::Hex => sha256::InputType::Hex,
-                sha256_cli::Type::LeHex => sha256::InputType::LeHex,
-                sha256_cli::Type::Decimal => sha256::InputType::Decimal,
-            };
-            let sig = private.sign(&sub_args.message, t).exit("Encountered");
-            if let Some(filename) = args.output{
-                let output = OutputTomlFile::from_sig(&sig, hex, le);
-                to_toml(output, &filename, false);
-            }else{
-                if hex{
-                    if le{
-                        println!("r: {}\ns: {}", sig.get_r().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>(), sig.get_s().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>())  
-                    }else{    
-                        println!("r: {:x}\ns: {:x}", sig.get_r(), sig.get_s());
+            let private = from_toml(&sub_args.private, args.strict);
+            let private = private.into_priv_key();
+            let messages = sign_messages(&sub_args);
+            let count = messages.len();
+            // with many messages, every output is numbered, and every file name gets the index of its message
+            let label = |i: usize| if count > 1 {println!("[{}]({})", i, messages[i]);};
+            let qr_file = |i: usize| args.qr_file.as_deref().map(|path| qr::get_name_indexed(path, i, count));
+            let time_label = |i: usize| if count > 1 {format!("signing [{}]({})", i, messages[i])} else {String::from("signing")};
+            if sub_args.ethereum{
+                let public = PubKey::new(KeyPair::from_private(&private).exit("Invalid private key.").get_public().clone(), private.get_curve().clone())
+                    .exit("Invalid public key.");
+                let address = ethereum::checksum_address(&ethereum::address(&public));
+                for (i, message) in messages.iter().enumerate(){
+                    let (t, message) = sub_args.r#type.input(message);
+                    let sig = timing::time(args.time, &time_label(i), None, || private.sign_ethereum(&ethereum_message(message, t)))
+                        .exit("Error while signing.");
+                    let signature = format!("0x{}", hex::encode(&sig.to_bytes()));
+                    label(i);
+                    println!("Signature: {}\nAddress: {}", signature, address);
+                    show_qr(&signature, args.qr, qr_file(i).as_deref());
+                }
+                return;
+            }
+            if sub_args.combined && args.output.is_none(){
+                None.exit("--combined needs --output, the file the signatures are written to.")
+            }
+            let sigs: Vec<Signature> = messages.iter().enumerate().map(|(i, message)| {
+                let (t, message) = sub_args.r#type.input(message);
+                if sub_args.explain{
+                    return explain::sign(&private, message, t, hex, le);
+                }
+                timing::time(args.time, &time_label(i), None, || private.sign(message, t)).exit("Encountered")
+            }).collect();
+            match &args.output{
+                Some(filename) if sub_args.combined => to_toml_or_json(MultiSignatureFile::from_sigs(&messages, &sigs, hex, le), filename),
+                Some(filename) => for (i, sig) in sigs.iter().enumerate(){
+                    to_toml(OutputTomlFile::from_sig(sig, hex, le), &qr::get_name_indexed(filename, i, count), false);
+                },
+                None => for (i, sig) in sigs.iter().enumerate(){
+                    label(i);
+                    println!("r: {}\ns: {}", show_number(sig.get_r(), hex, le), show_number(sig.get_s(), hex, le));
+                },
+            }
+            for (i, sig) in sigs.iter().enumerate(){
+                show_qr(&get_signature_compact(sig), args.qr, qr_file(i).as_deref());
+            }
+        },
+        SubCommand::Verify(sub_args) => {
+            if let Some(address) = &sub_args.ethereum{
+                let bytes = hex::decode(sub_args.signature.trim_start_matches("0x")).exit("Invalid hex signature.");
+                let signature = EthSignature::from_bytes(&bytes).exit("Invalid signature.");
+                let address = hex::decode(address.trim_start_matches("0x")).exit("Invalid hex address.");
+                let address: [u8; 20] = address.try_into().ok().exit("An address must have 20 bytes.");
+                let (t, message) = sub_args.r#type.input(&sub_args.message);
+                let recovered = timing::time(args.time, "verification", None, || signature.recover_address(&ethereum_message(message, t)));
+                let valid = recovered.as_ref().is_ok_and(|recovered| *recovered == address);
+                if sub_args.porcelain{
+                    println!("{}", if valid {"valid"} else {"invalid"});
+                }else if ! sub_args.quiet{
+                    match recovered{
+                        _ if valid => println!("Signature IS valid"),
+                        Ok(recovered) => println!("Signature is NOT valid, it recovers the address {}", ethereum::checksum_address(&recovered)),
+                        Err(e) => println!("Signature is NOT valid, {}", e),
                     }
+                }
+                if ! valid{
+                    Failure::Verification.exit();
+                }
+                return;
+            }
+            let signature = from_toml(&sub_args.signature, args.strict);
+            let signature = signature.into_sig();
+            let (t, message) = sub_args.r#type.input(&sub_args.message);
+            if sub_args.explain{
+                explain::verify(&signature, message, t, hex, le);
+            }
+            let verification = timing::time(args.time, "verification", None, || signature.verify_detailed(message, t)).exit("Error while hashing message");
+            let valid = verification.is_valid();
+            if sub_args.porcelain{
+                println!("{}", if valid {"valid"} else {"invalid"});
+            }else if ! sub_args.quiet{
+                if valid{
+                    println!("Signature IS valid");
                 }else{
-                    println!("r: {}\ns: {}", sig.get_r(), sig.get_s());
+                    println!("Signature is NOT valid, {}", verification);
                 }
             }
+            if ! valid{
+                Failure::Verification.exit();
+            }
         },
-        SubCommand::Verify(sub_args) => {
-            let signature = from_toml(&sub_args.signature);
-            let signature = signature.to_sig();
-            let t = match sub_args.r#type{
-                sha256_cli::Type::Text => sha256::InputType::Text,
-                sha256_cli::Type::Binary => sha256::InputType::Binary,
-                sha256_cli::Type::LeBinary => sha256::InputType::LeBinary,
-                sha256_cli::Type::File => sha256::InputType::File,
-                sha256_cli::Type::Hex => sha256::InputType::Hex,
-                sha256_cli::Type::LeHex => sha256::InputType::LeHex,
-                sha256_cli::Type::Decimal => sha256::InputType::Decimal,
-            };
-            if signature.verify(&sub_args.message, t).exit("Error while hashing message"){
-                println!("Signature IS valid");
-            }else{
-                println!("Signature is NOT valid");
+        SubCommand::ProveOwnership(sub_args) => {
+            let private = read_private_key(&sub_args.private);
+            let proof = timing::time(args.time, "proving", None, || zkp::prove(&private, sub_args.context.as_bytes()))
+                .exit("Error while proving.");
+            println!("Proof: {}", hex::encode(&proof.to_bytes()));
+        },
+        SubCommand::VerifyOwnership(sub_args) => {
+            let public = read_public_key(&sub_args.public);
+            let bytes = hex::decode(sub_args.proof.trim_start_matches("0x")).exit("Invalid hex proof.");
+            let proof = zkp::Proof::from_bytes(&bytes, public.get_curve().clone()).exit("Invalid proof.");
+            let valid = timing::time(args.time, "verification", None, || zkp::verify(&public, &proof, sub_args.context.as_bytes()));
+            if sub_args.porcelain{
+                println!("{}", if valid {"valid"} else {"invalid"});
+            }else if ! sub_args.quiet{
+                if valid{
+                    println!("Proof IS valid, the prover knows the private key");
+                }else{
+                    println!("Proof is NOT valid for the public key and context");
+                }
+            }
+            if ! valid{
+                Failure::Verification.exit();
             }
         },
     }
@@ -1,12 +1,14 @@
-use std::{fs::File, io::{Read, Write}};
+use std::{fmt, fs::File, io::{Read, Write}};
 
-use num_traits::ToBytes;
-use serde::{Serialize, Deserialize};
+use serde::{de::{self, MapAccess, Visitor}, Deserialize, Deserializer, Serialize};
 
 use crate::Exit;
-use mysha::ecc::{Curve, KeyPair, Point, PrivKey, Signature, PubKey};
+use mysha::ecc::{Curve, KeyPair, Point, PrivKey, Signature, PubKey, CURVE_NAMES};
 
-use super::get_biguint;
+use super::{get_biguint, show_number};
+
+/// Version of the files written. Files without it are of the first version, which always has the curve parameters.
+pub const VERSION: u32 = 2;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CurveToml{
@@ -18,6 +20,39 @@ pub struct CurveToml{
     pub y: String,
 }
 
+/// The curve of a file, its parameters, or a name of [CURVE_NAMES], like `curve = "secp256k1"`.
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum CurveField{
+    Named(String),
+    Parameters(CurveToml),
+}
+
+// written by hand, instead of untagged, so errors in the parameters still name the field
+impl<'de> Deserialize<'de> for CurveField{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CurveField, D::Error>{
+        struct CurveVisitor;
+
+        impl<'de> Visitor<'de> for CurveVisitor{
+            type Value = CurveField;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result{
+                write!(f, "a curve name or a table with a, b, p, n, x and y")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<CurveField, E>{
+                Ok(CurveField::Named(name.to_string()))
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, map: M) -> Result<CurveField, M::Error>{
+                CurveToml::deserialize(de::value::MapAccessDeserializer::new(map)).map(CurveField::Parameters)
+            }
+        }
+
+        deserializer.deserialize_any(CurveVisitor)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FlagsToml{
     pub hex: Option<bool>,
@@ -26,9 +61,10 @@ pub struct FlagsToml{
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct OutputTomlFile{
+    pub version: Option<u32>,
+    pub curve: CurveField,
     pub key_pair: Option<KeyPairToml>,
     pub signature: Option<SignatureToml>,
-    pub curve: CurveToml,
     pub flags: Option<FlagsToml>,
 }
 
@@ -44,396 +80,151 @@ pub struct SignatureToml{
     s: String,
 }
 
+impl CurveToml{
+    fn new(c: &Curve, hex: bool, le: bool) -> CurveToml{
+        let (x, y) = c.get_g().get_xy().unwrap();
+        CurveToml{
+            a: c.get_a(),
+            b: c.get_b(),
+            p: show_number(c.get_p(), hex, le),
+            n: show_number(c.get_n(), hex, le),
+            x: show_number(x, hex, le),
+            y: show_number(y, hex, le),
+        }
+    }
+}
+
+impl CurveField{
+    /// The name of the curve, if it has one, or its parameters.
+    fn new(c: &Curve, hex: bool, le: bool) -> CurveField{
+        match c.name(){
+            Some(name) => CurveField::Named(name.to_string()),
+            None => CurveField::Parameters(CurveToml::new(c, hex, le)),
+        }
+    }
+}
+
+impl FlagsToml{
+    fn new(hex: bool, le: bool) -> Option<FlagsToml>{
+        Some(FlagsToml{
+            hex: Some(hex),
+            little_endian: hex.then_some(le),
+        })
+    }
+}
+
+fn point_toml(p: &Point, hex: bool, le: bool) -> (String, String){
+    let (x, y) = p.get_xy().unwrap();
+    (show_number(x, hex, le), show_number(y, hex, le))
+}
+
 impl OutputTomlFile{
+    /// A curve file always has the parameters, even of named curves, so they can be read and changed.
     pub fn from_curve(c: &Curve, hex: bool, le: bool) -> OutputTomlFile{
-        let (x, y) = c.get_g().get_xy().unwrap();
-        if hex == true{
-            if le{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: c.get_a(),
-                        b: c.get_b(),
-                        p: c.get_p().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        n: c.get_n().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        x: x.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        y: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                    },
-                    key_pair: None,
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(true),
-                    }),
-                    signature: None,
-                }
-            }else{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: c.get_a(),
-                        b: c.get_b(),
-                        p: c.get_p().to_str_radix(16),
-                        n: c.get_n().to_str_radix(16),
-                        x: x.to_str_radix(16),
-                        y: y.to_str_radix(16),
-                    },
-                    key_pair: None,
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(false),
-                    }),
-                    signature: None,
-                }
-            }
-        }else{
-            OutputTomlFile{
-                curve: CurveToml{
-                    a: c.get_a(),
-                    b: c.get_b(),
-                    p: c.get_p().to_string(),
-                    n: c.get_n().to_string(),
-                    x: x.to_string(),
-                    y: y.to_string(),
-                },
-                key_pair: None,
-                flags: Some(FlagsToml{
-                    hex: Some(false),
-                    little_endian: None,
-                }),
-                signature: None,
-            }
+        OutputTomlFile{
+            version: Some(VERSION),
+            curve: CurveField::Parameters(CurveToml::new(c, hex, le)),
+            key_pair: None,
+            signature: None,
+            flags: FlagsToml::new(hex, le),
         }
     }
 
     pub fn from_key_pair(k: &KeyPair, hex: bool, le: bool) -> OutputTomlFile{
-        let (x, y) = k.get_curve().get_g().get_xy().unwrap();
-        if hex{
-            if le{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: k.get_curve().get_a(),
-                        b: k.get_curve().get_b(),
-                        p: k.get_curve().get_p().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        n: k.get_curve().get_n().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        x: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        y: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: Some((k.get_public().get_x().unwrap().to_str_radix(16), k.get_public().get_y().unwrap().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect())),
-                        private: Some(k.get_private().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect()),
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(true),
-                    }),
-                    signature: None,
-                }
-            }else{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: k.get_curve().get_a(),
-                        b: k.get_curve().get_b(),
-                        p: k.get_curve().get_p().to_str_radix(16),
-                        n: k.get_curve().get_n().to_str_radix(16),
-                        x: x.to_str_radix(16),
-                        y: y.to_str_radix(16),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: Some((k.get_public().get_x().unwrap().to_str_radix(16), k.get_public().get_y().unwrap().to_str_radix(16))),
-                        private: Some(k.get_private().to_str_radix(16)),
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(false),
-                    }),
-                    signature: None,
-                }
-            }
-        }else{
-            OutputTomlFile{
-                curve: CurveToml{
-                    a: k.get_curve().get_a(),
-                    b: k.get_curve().get_b(),
-                    p: k.get_curve().get_p().to_string(),
-                    n: k.get_curve().get_n().to_string(),
-                    x: x.to_string(),
-                    y: y.to_string(),
-                },
-                key_pair: Some(KeyPairToml{
-                    public: Some((k.get_public().get_x().unwrap().to_string(), k.get_public().get_y().unwrap().to_string())),
-                    private: Some(k.get_private().to_string()),
-                }),
-                flags: Some(FlagsToml{
-                    hex: Some(false),
-                    little_endian: None,
-                }),
-                signature: None,
-            }
+        OutputTomlFile{
+            version: Some(VERSION),
+            curve: CurveField::new(k.get_curve(), hex, le),
+            key_pair: Some(KeyPairToml{
+                public: Some(point_toml(k.get_public(), hex, le)),
+                private: Some(show_number(k.get_private(), hex, le)),
+            }),
+            signature: None,
+            flags: FlagsToml::new(hex, le),
         }
     }
 
     pub fn from_sig(sig: &Signature, hex: bool, le: bool) -> OutputTomlFile{
-        let (x, y) = sig.get_curve().get_g().get_xy().unwrap();
-        if hex{
-            if le{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: sig.get_curve().get_a(),
-                        b: sig.get_curve().get_b(),
-                        p: sig.get_curve().get_p().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        n: sig.get_curve().get_n().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        x: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        y: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: Some((sig.get_public().get_x().unwrap().to_str_radix(16), sig.get_public().get_y().unwrap().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect())),
-                        private: None,
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(true),
-                    }),
-                    signature: Some(SignatureToml{
-                        r: sig.get_r().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        s: sig.get_s().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                    }),
-                }
-            }else{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: sig.get_curve().get_a(),
-                        b: sig.get_curve().get_b(),
-                        p: sig.get_curve().get_p().to_str_radix(16),
-                        n: sig.get_curve().get_n().to_str_radix(16),
-                        x: x.to_str_radix(16),
-                        y: y.to_str_radix(16),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: Some((sig.get_public().get_x().unwrap().to_str_radix(16), sig.get_public().get_y().unwrap().to_str_radix(16))),
-                        private: None,
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(false),
-                    }),
-                    signature: Some(SignatureToml{
-                        r: sig.get_r().to_str_radix(16),
-                        s: sig.get_s().to_str_radix(16),
-                    }),
-                }
-            }
-        }else{
-            OutputTomlFile{
-                curve: CurveToml{
-                    a: sig.get_curve().get_a(),
-                    b: sig.get_curve().get_b(),
-                    p: sig.get_curve().get_p().to_string(),
-                    n: sig.get_curve().get_n().to_string(),
-                    x: x.to_string(),
-                    y: y.to_string(),
-                },
-                key_pair: Some(KeyPairToml{
-                    public: Some((sig.get_public().get_x().unwrap().to_string(), sig.get_public().get_y().unwrap().to_string())),
-                    private: None,
-                }),
-                flags: Some(FlagsToml{
-                    hex: Some(false),
-                    little_endian: None,
-                }),
-                signature: Some(SignatureToml{
-                    r: sig.get_r().to_string(),
-                    s: sig.get_s().to_string(),
-                }),
-            }
+        OutputTomlFile{
+            version: Some(VERSION),
+            curve: CurveField::new(sig.get_curve(), hex, le),
+            key_pair: Some(KeyPairToml{
+                public: Some(point_toml(sig.get_public(), hex, le)),
+                private: None,
+            }),
+            signature: Some(SignatureToml{
+                r: show_number(sig.get_r(), hex, le),
+                s: show_number(sig.get_s(), hex, le),
+            }),
+            flags: FlagsToml::new(hex, le),
         }
     }
 
     pub fn from_public(p: &PubKey, hex: bool, le: bool) -> OutputTomlFile{
-        let (x, y) = p.get_curve().get_g().get_xy().unwrap();
-        if hex{
-            if le{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: p.get_curve().get_a(),
-                        b: p.get_curve().get_b(),
-                        p: p.get_curve().get_p().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        n: p.get_curve().get_n().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        x: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        y: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: Some((p.get_public().get_x().unwrap().to_str_radix(16), p.get_public().get_y().unwrap().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect())),
-                        private: None,
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(true),
-                    }),
-                    signature: None,
-                }
-            }else{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: p.get_curve().get_a(),
-                        b: p.get_curve().get_b(),
-                        p: p.get_curve().get_p().to_str_radix(16),
-                        n: p.get_curve().get_n().to_str_radix(16),
-                        x: x.to_str_radix(16),
-                        y: y.to_str_radix(16),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: Some((p.get_public().get_x().unwrap().to_str_radix(16), p.get_public().get_y().unwrap().to_str_radix(16))),
-                        private: None,
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(false),
-                    }),
-                    signature: None,
-                }
-            }
-        }else{
-            OutputTomlFile{
-                curve: CurveToml{
-                    a: p.get_curve().get_a(),
-                    b: p.get_curve().get_b(),
-                    p: p.get_curve().get_p().to_string(),
-                    n: p.get_curve().get_n().to_string(),
-                    x: x.to_string(),
-                    y: y.to_string(),
-                },
-                key_pair: Some(KeyPairToml{
-                    public: Some((p.get_public().get_x().unwrap().to_string(), p.get_public().get_y().unwrap().to_string())),
-                    private: None,
-                }),
-                flags: Some(FlagsToml{
-                    hex: Some(false),
-                    little_endian: None,
-                }),
-                signature: None,
-            }
+        OutputTomlFile{
+            version: Some(VERSION),
+            curve: CurveField::new(p.get_curve(), hex, le),
+            key_pair: Some(KeyPairToml{
+                public: Some(point_toml(p.get_public(), hex, le)),
+                private: None,
+            }),
+            signature: None,
+            flags: FlagsToml::new(hex, le),
         }
     }
 
     pub fn from_private(p: &PrivKey, hex: bool, le: bool) -> OutputTomlFile{
-        let (x, y) = p.get_curve().get_g().get_xy().unwrap();
-        if hex{
-            if le{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: p.get_curve().get_a(),
-                        b: p.get_curve().get_b(),
-                        p: p.get_curve().get_p().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        n: p.get_curve().get_n().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        x: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                        y: y.to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: None,
-                        private: Some(p.get_private().to_le_bytes().iter().map(|b| format!("{:02x}", b)).collect()),
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(true),
-                    }),
-                    signature: None,
-                }
-            }else{
-                OutputTomlFile{
-                    curve: CurveToml{
-                        a: p.get_curve().get_a(),
-                        b: p.get_curve().get_b(),
-                        p: p.get_curve().get_p().to_str_radix(16),
-                        n: p.get_curve().get_n().to_str_radix(16),
-                        x: x.to_str_radix(16),
-                        y: y.to_str_radix(16),
-                    },
-                    key_pair: Some(KeyPairToml{
-                        public: None,
-                        private: Some(p.get_private().to_str_radix(16)),
-                    }),
-                    flags: Some(FlagsToml{
-                        hex: Some(true),
-                        little_endian: Some(false),
-                    }),
-                    signature: None,
-                }
-            }
-        }else{
-            OutputTomlFile{
-                curve: CurveToml{
-                    a: p.get_curve().get_a(),
-                    b: p.get_curve().get_b(),
-                    p: p.get_curve().get_p().to_string(),
-                    n: p.get_curve().get_n().to_string(),
-                    x: x.to_string(),
-                    y: y.to_string(),
-                },
-                key_pair: Some(KeyPairToml{
-                    public: None,
-                    private: Some(p.get_private().to_string()),
-                }),
-                flags: Some(FlagsToml{
-                    hex: Some(false),
-                    little_endian: None,
-                }),
-                signature: None,
-            }
+        OutputTomlFile{
+            version: Some(VERSION),
+            curve: CurveField::new(p.get_curve(), hex, le),
+            key_pair: Some(KeyPairToml{
+                public: None,
+                private: Some(show_number(p.get_private(), hex, le)),
+            }),
+            signature: None,
+            flags: FlagsToml::new(hex, le),
         }
     }
 
-    pub fn to_curve(self) -> Curve{
-        let (hex, le): (bool, bool) = match self.flags{
+    /// Whether the numbers of the file are hex, and little endian.
+    fn number_flags(&self) -> (bool, bool){
+        match &self.flags{
             Some(flag) => (flag.hex.unwrap_or(false), flag.little_endian.unwrap_or(false)),
             None => (false, false),
-        };
-        
-        Curve::new(
-            self.curve.a,
-            self.curve.b, 
-            get_biguint(&self.curve.p, hex, le), 
-            get_biguint(&self.curve.n, hex, le), 
-            Point::Point { 
-                x: get_biguint(&self.curve.x, hex, le), 
-                y: get_biguint(&self.curve.y, hex, le), 
-            }
-        ).exit("Invalid Curve parameters.")
+        }
     }
 
-    pub fn to_priv_key(self) -> PrivKey{
-        let (hex, le): (bool, bool) = match &self.flags{
-            Some(flag) => (flag.hex.unwrap_or(false), flag.little_endian.unwrap_or(false)),
-            None => (false, false),
-        };
+    /// The curve of the file, from its name or its parameters.
+    fn read_curve(&self) -> Curve{
+        let (hex, le) = self.number_flags();
+        match &self.curve{
+            CurveField::Named(name) => Curve::from_name(name).exit(&format!("Unknown curve {:?}, the named curves are {}.", name, CURVE_NAMES.join(", "))),
+            CurveField::Parameters(curve) => Curve::new(
+                curve.a,
+                curve.b,
+                get_biguint(&curve.p, hex, le),
+                get_biguint(&curve.n, hex, le),
+                Point::Point{
+                    x: get_biguint(&curve.x, hex, le),
+                    y: get_biguint(&curve.y, hex, le),
+                }
+            ).exit("Invalid Curve parameters."),
+        }
+    }
 
-        let curve = Curve::new(
-            self.curve.a,
-            self.curve.b, 
-            get_biguint(&self.curve.p, hex, le), 
-            get_biguint(&self.curve.n, hex, le), 
-            Point::Point { 
-                x: get_biguint(&self.curve.x, hex, le), 
-                y: get_biguint(&self.curve.y, hex, le), 
-            }
-        ).exit("Invalid Curve parameters.");
+    pub fn into_curve(self) -> Curve{
+        self.read_curve()
+    }
+
+    pub fn into_priv_key(self) -> PrivKey{
+        let (hex, le) = self.number_flags();
+        let curve = self.read_curve();
 
         PrivKey::new(get_biguint(&self.key_pair.exit("Private Key required for signing.").private.exit("Private Key required for signing."), hex, le), curve).unwrap()
     }
 
-    pub fn to_sig(self) -> Signature{
-        let(hex, le) = match &self.flags{
-            Some(flag) => (flag.hex.unwrap_or(false), flag.little_endian.unwrap_or(false)),
-            None => (false, false),
-        };
-
-        let curve = Curve::new(
-            self.curve.a,
-            self.curve.b,
-            get_biguint(&self.curve.p, hex, le),
-            get_biguint(&self.curve.n, hex, le),
-            Point::Point{
-                x: get_biguint(&self.curve.x, hex, le),
-                y: get_biguint(&self.curve.y, hex, le),
-            }
-        ).exit("Invalid Curve parameters");
+    pub fn into_sig(self) -> Signature{
+        let (hex, le) = self.number_flags();
+        let curve = self.read_curve();
 
         let sig = self.signature.exit("Signature field necessary.");
 
@@ -446,7 +237,90 @@ impl OutputTomlFile{
             y: get_biguint(&public.1, hex, le),
         };
 
-        Signature::new(r, s, curve, public_key)
+        Signature::new(r, s, curve, public_key).exit("Signature r and s must be from 1 to n - 1.")
+    }
+}
+
+/// Signatures of many messages with the same key, written by `sign --combined`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MultiSignatureFile{
+    pub version: Option<u32>,
+    pub curve: CurveField,
+    pub key_pair: KeyPairToml,
+    pub signatures: Vec<SignedMessageToml>,
+    pub flags: Option<FlagsToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SignedMessageToml{
+    pub message: String,
+    r: String,
+    s: String,
+}
+
+impl MultiSignatureFile{
+    /// The signatures, in the order of the messages, all made with the same key.
+    pub fn from_sigs(messages: &[String], sigs: &[Signature], hex: bool, le: bool) -> MultiSignatureFile{
+        let first = &sigs[0];
+        MultiSignatureFile{
+            version: Some(VERSION),
+            curve: CurveField::new(first.get_curve(), hex, le),
+            key_pair: KeyPairToml{
+                public: Some(point_toml(first.get_public(), hex, le)),
+                private: None,
+            },
+            signatures: messages.iter().zip(sigs).map(|(message, sig)| SignedMessageToml{
+                message: message.clone(),
+                r: show_number(sig.get_r(), hex, le),
+                s: show_number(sig.get_s(), hex, le),
+            }).collect(),
+            flags: FlagsToml::new(hex, le),
+        }
+    }
+}
+
+/// The object held by an ecc toml file.
+pub enum Artifact{
+    Curve(Curve),
+    KeyPair(KeyPair),
+    PubKey(PubKey),
+    PrivKey(PrivKey),
+    Signature(Signature),
+}
+
+impl OutputTomlFile{
+    /// Detects what the file holds from the fields it has: a signature, a key pair,
+    /// only one of the keys, or only the curve.
+    ///
+    /// A key pair whose public key doesn't match its private key is rejected.
+    pub fn into_artifact(self) -> Artifact{
+        let (hex, le) = self.number_flags();
+        if self.signature.is_some(){
+            return Artifact::Signature(self.into_sig());
+        }
+
+        let curve = self.read_curve();
+
+        let Some(key_pair) = self.key_pair else {
+            return Artifact::Curve(curve);
+        };
+        let public = key_pair.public.map(|(x, y)| Point::Point{
+            x: get_biguint(&x, hex, le),
+            y: get_biguint(&y, hex, le),
+        });
+        match (key_pair.private, public){
+            (Some(private), public) => {
+                let private = PrivKey::new(get_biguint(&private, hex, le), curve).exit("Invalid private key.");
+                let kp = KeyPair::from_private(&private).exit("Invalid private key.");
+                match public{
+                    None => Artifact::PrivKey(private),
+                    Some(public) if public == *kp.get_public() => Artifact::KeyPair(kp),
+                    Some(_) => None.exit("The public key doesn't match the private key."),
+                }
+            },
+            (None, Some(public)) => Artifact::PubKey(PubKey::new(public, curve).exit("Invalid public key.")),
+            (None, None) => Artifact::Curve(curve),
+        }
     }
 }
 
@@ -459,6 +333,16 @@ fn get_name_toml(filename: &str) -> String{
     
 }
 
+/// Writes to the file as json if its name ends with .json, and as toml if not.
+pub fn to_toml_or_json<T: Serialize>(t: T, path: &str){
+    if path.ends_with(".json"){
+        let content = serde_json::to_string_pretty(&t).exit("Error while parsing to json.");
+        std::fs::write(path, content).exit("Error while writing to the file.");
+    }else{
+        to_toml(t, path, false);
+    }
+}
+
 pub fn to_toml<T:Serialize>(t: T, path: &str, new: bool){
     let path = get_name_toml(path);
     let mut file;
@@ -471,10 +355,112 @@ pub fn to_toml<T:Serialize>(t: T, path: &str, new: bool){
     file.write_all(content.as_bytes()).exit("Error while writing to the file.");
 }
 
-pub fn from_toml(path: &str) -> OutputTomlFile{
+/// Fields of every section of an ecc toml file, for the strict mode.
+const FIELDS: [(&str, &[&str]); 5] = [
+    ("version", &[]),
+    ("curve", &["a", "b", "p", "n", "x", "y"]),
+    ("key_pair", &["public", "private"]),
+    ("signature", &["r", "s"]),
+    ("flags", &["hex", "little_endian"]),
+];
+
+/// Reads an ecc toml file, exiting with the field that can't be read and why.
+///
+/// Unknown fields are ignored, unless strict is set, then they are an error, so a typo like `privte` isn't silently dropped.
+pub fn from_toml(path: &str, strict: bool) -> OutputTomlFile{
     let path = get_name_toml(path);
-    let mut file = File::open(path).exit("Error while opening the file");
+    let mut file = File::open(&path).exit("Error while opening the file");
     let mut content = String::new();
     file.read_to_string(&mut content).exit("Error while reading the file.");
-    toml::from_str(&content).exit("Error while parsing to toml.")
+    let table: toml::Table = toml::from_str(&content).exit(&format!("Invalid toml in {}.", path));
+
+    if strict{
+        if let Err(field) = unknown_field(&table){
+            None.exit(&format!("Unknown field {} in {}, which isn't read in strict mode.", field, path))
+        }
+    }
+    if !table.contains_key("curve"){
+        None.exit(&format!("Missing curve in {}, every ecc file needs the curve, by name or in a [curve] section.", path))
+    }
+    let file = OutputTomlFile::deserialize(table).exit(&format!("Invalid field in {}.", path));
+    if let Err(e) = file.check_fields(){
+        None.exit(&format!("Can't read {}. {}.", path, e))
+    }
+    file
+}
+
+/// The first field of the table, in `section.field` form, that isn't one of an ecc file.
+fn unknown_field(table: &toml::Table) -> Result<(), String>{
+    for (section, value) in table{
+        let Some((_, fields)) = FIELDS.iter().find(|(name, _)| name == section) else {
+            return Err(if value.is_table() {format!("[{}]", section)} else {section.clone()});
+        };
+        if let Some(field) = value.as_table().and_then(|t| t.keys().find(|key| !fields.contains(&key.as_str()))){
+            return Err(format!("{}.{}", section, field));
+        }
+    }
+    Ok(())
+}
+
+impl OutputTomlFile{
+    /// Checks the version, the curve name, that the flags can be combined and that every number can be read with them,
+    /// so the errors name the field, instead of failing later on the first number that can't be read.
+    fn check_fields(&self) -> Result<(), String>{
+        match self.version{
+            Some(version) if version == 0 || version > VERSION => {
+                return Err(format!("Field version = {} isn't a version this tool reads, from 1 to {}", version, VERSION));
+            },
+            _ => (),
+        }
+        let (hex, le) = self.number_flags();
+        if le && !hex{
+            return Err(String::from("[flags] has little_endian = true without hex = true, decimal numbers have no byte order"));
+        }
+
+        let mut numbers = Vec::new();
+        match &self.curve{
+            CurveField::Named(name) if Curve::from_name(name).is_err() => {
+                return Err(format!("Field curve = {:?} isn't a named curve, the names are {}", name, CURVE_NAMES.join(", ")));
+            },
+            CurveField::Named(_) => (),
+            CurveField::Parameters(curve) => numbers.extend([
+                ("curve.p", &curve.p),
+                ("curve.n", &curve.n),
+                ("curve.x", &curve.x),
+                ("curve.y", &curve.y),
+            ]),
+        }
+        if let Some(key_pair) = &self.key_pair{
+            if let Some((x, y)) = &key_pair.public{
+                numbers.extend([("key_pair.public[0]", x), ("key_pair.public[1]", y)]);
+            }
+            if let Some(private) = &key_pair.private{
+                numbers.push(("key_pair.private", private));
+            }
+        }
+        if let Some(signature) = &self.signature{
+            numbers.extend([("signature.r", &signature.r), ("signature.s", &signature.s)]);
+        }
+
+        for (field, number) in numbers{
+            check_number(number, hex, le).map_err(|why| format!("Field {} = {:?} {}", field, number, why))?;
+        }
+        Ok(())
+    }
+}
+
+/// Why the number can't be read with the flags, if it can't.
+fn check_number(number: &str, hex: bool, le: bool) -> Result<(), String>{
+    if number.is_empty(){
+        return Err(String::from("is empty"));
+    }
+    let (radix, name) = if hex {(16, "hexadecimal")} else {(10, "decimal")};
+    if let Some(digit) = number.chars().find(|c| !c.is_digit(radix)){
+        let hint = if !hex && digit.is_ascii_hexdigit() {", set hex = true in [flags] for hexadecimal numbers"} else {""};
+        return Err(format!("has {:?}, which isn't a {} digit{}", digit, name, hint));
+    }
+    if le && !number.len().is_multiple_of(2){
+        return Err(String::from("has an odd number of digits, little endian hex is read as whole bytes"));
+    }
+    Ok(())
 }
@@ -0,0 +1,113 @@
+//! Module for [ECIES], encrypting messages to an [ecc](crate::ecc) public key, decrypted with its private key.
+//!
+//! Elliptic curves don't encrypt data themselves, so ECIES mixes them with symmetric encryption:
+//! 1. a random ephemeral key pair is generated for every message;
+//! 2. the ephemeral private key times the public key of the recipient is a point only the recipient can also compute,
+//!    as their private key times the ephemeral public key, which is ECDH;
+//! 3. [HKDF](crate::kdf::hkdf) derives a key from the x coordinate of that point, with the ephemeral public key as the salt;
+//! 4. the message is encrypted with [ChaCha20-Poly1305](crate::cipher::chacha20poly1305) and the key.
+//!
+//! The encrypted data is the ephemeral public key, as an uncompressed SEC1 point, followed by the encrypted message and its tag,
+//! so it is [OVERHEAD] bytes longer than the message on secp256k1.
+//! Every message has a new key, so the nonce is always zero, which would be insecure if a key was ever used twice.
+//!
+//! The sender can't decrypt what they encrypted, only the recipient, and ECIES doesn't say who the sender is,
+//! a message that must be authenticated also needs a [signature](crate::ecc::PrivKey::sign).
+//!
+//! # Examples
+//! ```
+//! use mysha::{ecc::{Curve, KeyPair}, ecies, MyshaError};
+//! # fn main() -> Result<(), MyshaError>{
+//! let recipient = KeyPair::new(1234_u32, Curve::secp256k1())?;
+//!
+//! let encrypted = ecies::encrypt(&recipient.public(), b"attack at dawn", &[])?;
+//! assert_eq!(encrypted.len(), 14 + ecies::OVERHEAD);
+//!
+//! assert_eq!(ecies::decrypt(&recipient.private(), &encrypted, &[])?, b"attack at dawn");
+//!
+//! let someone_else = KeyPair::new(5678_u32, Curve::secp256k1())?;
+//! assert!(ecies::decrypt(&someone_else.private(), &encrypted, &[]).is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [ECIES]: https://en.wikipedia.org/wiki/Integrated_Encryption_Scheme
+
+use num_bigint::BigUint;
+
+use crate::cipher::{chacha20poly1305, CipherError};
+use crate::ecc::{EccError, PrivKey, PubKey};
+use crate::kdf::hkdf;
+use crate::rand;
+use crate::MyshaError;
+
+/// Bytes added to the message on secp256k1, the 65 bytes of the ephemeral public key and the 16 bytes of the tag.
+pub const OVERHEAD: usize = 1 + 2 * 32 + chacha20poly1305::TAG_SIZE;
+
+/// Info of HKDF, so the key is different from keys derived for other uses from the same point.
+const INFO: &[u8] = b"mysha ecies chacha20-poly1305";
+/// The key is only used once, so the nonce doesn't need to change.
+const NONCE: [u8; chacha20poly1305::NONCE_SIZE] = [0; chacha20poly1305::NONCE_SIZE];
+
+/// Uncompressed SEC1 point, 04 followed by the coordinates.
+fn sec1(public: &PubKey) -> Vec<u8>{
+    [&[0x04], &public.to_bytes()[..]].concat()
+}
+
+/// Key of the shared point of ECDH, with the ephemeral public key.
+fn derive_key(private: &BigUint, public: &PubKey, ephemeral: &[u8]) -> Result<Vec<u8>, MyshaError>{
+    let curve = public.get_curve();
+    let shared = curve.multiply(public.get_public(), private.clone())?;
+    // the public key is in the group of order n, and the private key is below n, so this is never the point at infinity
+    let x = shared.get_x().ok_or(EccError::PublicKeyOnInfinity)?.to_bytes_be();
+    let mut secret = vec![0; curve.coordinate_size() - x.len()];
+    secret.extend(x);
+    Ok(hkdf::derive(&secret, ephemeral, INFO, chacha20poly1305::KEY_SIZE)?)
+}
+
+/// Encrypts the message to the public key, returning the ephemeral public key followed by the encrypted message and the tag.
+///
+/// The associated data is authenticated but isn't encrypted, nor part of the output, the same must be given to decrypt.
+///
+/// # Errors
+///
+/// This can only emit an [error][EccError] if there is something [wrong] with the curve.
+///
+/// [wrong]: crate::ecc::Curve#problematic-curves
+pub fn encrypt(public: &PubKey, message: &[u8], aad: &[u8]) -> Result<Vec<u8>, MyshaError>{
+    let curve = public.get_curve();
+    let ephemeral_private = rand::random_scalar(curve);
+    let ephemeral = sec1(&PubKey::new(curve.multiply_generator(ephemeral_private.clone())?, curve.clone())?);
+
+    let key = derive_key(&ephemeral_private, public, &ephemeral)?;
+    let mut encrypted = ephemeral;
+    encrypted.extend(chacha20poly1305::encrypt(&key, &NONCE, message, aad)?);
+    Ok(encrypted)
+}
+
+/// Decrypts data encrypted to the public key of the private key.
+///
+/// # Errors
+///
+/// This can fail with [InvalidLength][CipherError::InvalidLength] if the data is shorter than the ephemeral public key and the tag,
+/// with [NotOnCurve][EccError::NotOnCurve], or like [from_affine_bytes][PubKey::from_affine_bytes], if it doesn't start
+/// with a valid uncompressed point of the curve,
+/// and with [AuthenticationFailed][CipherError::AuthenticationFailed] if the private key or the associated data are wrong,
+/// or the data was changed, and then nothing is decrypted.
+pub fn decrypt(private: &PrivKey, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, MyshaError>{
+    let curve = private.get_curve();
+    let size = 1 + 2 * curve.coordinate_size();
+    if data.len() < size + chacha20poly1305::TAG_SIZE{
+        return Err(CipherError::InvalidLength.into());
+    }
+    let (ephemeral, encrypted) = data.split_at(size);
+    // compressed points, starting with 02 or 03, aren't used, so the length is always the same
+    if ephemeral[0] != 0x04{
+        return Err(EccError::NotOnCurve.into());
+    }
+    let (x, y) = ephemeral[1..].split_at(curve.coordinate_size());
+    let ephemeral_public = PubKey::from_affine_bytes(x, y, curve.clone())?;
+
+    let key = derive_key(private.get_private(), &ephemeral_public, ephemeral)?;
+    Ok(chacha20poly1305::decrypt(&key, &NONCE, encrypted, aad)?)
+}
@@ -0,0 +1,270 @@
+use clap::{Args, Subcommand, ValueEnum};
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+use mysha::encoding::{base58, base64::{self, Alphabet}, bech32::{self, Variant}, hex};
+
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct EncodeArgs{
+    #[command(subcommand)]
+    encoding: Encoding,
+}
+
+#[derive(Args, Debug)]
+pub struct DecodeArgs{
+    #[command(subcommand)]
+    encoding: Decoding,
+}
+
+#[derive(Debug, Subcommand)]
+enum Encoding{
+    /// base58, with the bitcoin alphabet
+    Base58(EncodeBase58Args),
+    /// base64, with the standard or the URL-safe alphabet
+    Base64(EncodeBase64Args),
+    /// bech32 or bech32m, and segwit addresses
+    Bech32(EncodeBech32Args),
+}
+
+#[derive(Debug, Subcommand)]
+enum Decoding{
+    /// base58, with the bitcoin alphabet
+    Base58(DecodeBase58Args),
+    /// base64, with the standard or the URL-safe alphabet
+    Base64(DecodeBase64Args),
+    /// bech32 or bech32m, and segwit addresses
+    Bech32(DecodeBech32Args),
+}
+
+#[derive(Args, Debug)]
+struct EncodeBase58Args{
+    /// data to be encoded, read from stdin if not provided
+    data: Option<String>,
+
+    /// data type
+    #[arg(short, long, default_value_t = Input::Text, value_enum)]
+    r#type: Input,
+
+    /// adds the 4 byte checksum of base58check
+    #[arg(short, long)]
+    check: bool,
+}
+
+#[derive(Args, Debug)]
+struct DecodeBase58Args{
+    /// text to be decoded, read from stdin if not provided
+    text: Option<String>,
+
+    /// how the decoded data is shown
+    #[arg(short, long, default_value_t = Output::Hex, value_enum)]
+    r#type: Output,
+
+    /// verifies and removes the 4 byte checksum of base58check
+    #[arg(short, long)]
+    check: bool,
+}
+
+#[derive(Args, Debug)]
+struct EncodeBase64Args{
+    /// data to be encoded, read from stdin if not provided
+    data: Option<String>,
+
+    /// data type
+    #[arg(short, long, default_value_t = Input::Text, value_enum)]
+    r#type: Input,
+
+    /// uses the URL-safe alphabet, without padding
+    #[arg(short, long)]
+    url: bool,
+}
+
+#[derive(Args, Debug)]
+struct DecodeBase64Args{
+    /// text to be decoded, read from stdin if not provided
+    text: Option<String>,
+
+    /// how the decoded data is shown
+    #[arg(short, long, default_value_t = Output::Hex, value_enum)]
+    r#type: Output,
+
+    /// uses the URL-safe alphabet
+    #[arg(short, long)]
+    url: bool,
+}
+
+#[derive(Args, Debug)]
+struct EncodeBech32Args{
+    /// data to be encoded, or witness program with --segwit, read from stdin if not provided
+    data: Option<String>,
+
+    /// human readable part, like bc for bitcoin addresses
+    #[arg(long)]
+    hrp: String,
+
+    /// data type
+    #[arg(short, long, default_value_t = Input::Text, value_enum)]
+    r#type: Input,
+
+    /// uses the bech32m checksum
+    #[arg(short = 'm', long)]
+    bech32m: bool,
+
+    /// encodes a segwit address with this witness version, the checksum is chosen by the version
+    #[arg(long, value_name = "VERSION", conflicts_with = "bech32m")]
+    segwit: Option<u8>,
+}
+
+#[derive(Args, Debug)]
+struct DecodeBech32Args{
+    /// text to be decoded, read from stdin if not provided
+    text: Option<String>,
+
+    /// human readable part expected, required with --segwit
+    #[arg(long, required_if_eq("segwit", "true"))]
+    hrp: Option<String>,
+
+    /// how the decoded data is shown
+    #[arg(short, long, default_value_t = Output::Hex, value_enum)]
+    r#type: Output,
+
+    /// decodes a segwit address, printing its witness version before the program
+    #[arg(long)]
+    segwit: bool,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub(crate) enum Input{
+    /// String
+    Text,
+    /// Hexadecimal bytes
+    Hex,
+    /// File with the data
+    File,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub(crate) enum Output{
+    /// String
+    Text,
+    /// Hexadecimal bytes
+    Hex,
+    /// Raw bytes, to be redirected to a file
+    Raw,
+}
+
+/// Reads the data argument, or stdin if it wasn't provided, as bytes of the input type.
+pub(crate) fn get_bytes(data: Option<String>, input: &Input) -> Vec<u8>{
+    let data = match data{
+        Some(data) => data,
+        None if *input == Input::File => Err::<String, &str>("no file provided.").exit("Error while reading the file."),
+        None => {
+            let mut data = Vec::new();
+            io::stdin().read_to_end(&mut data).exit("Error while reading stdin.");
+            if *input == Input::Text{
+                return data;
+            }
+            String::from_utf8(data).exit("Error while reading stdin.").trim().to_owned()
+        },
+    };
+    match input{
+        Input::Text => data.into_bytes(),
+        Input::Hex => hex::decode(&data).exit("Error while parsing hexadecimal value."),
+        Input::File => {
+            let mut bytes = Vec::new();
+            File::open(&data).and_then(|mut file| file.read_to_end(&mut bytes)).exit("Error while reading the file.");
+            bytes
+        },
+    }
+}
+
+/// Reads the text argument, or stdin if it wasn't provided.
+pub(crate) fn get_text(text: Option<String>) -> String{
+    match text{
+        Some(text) => text,
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).exit("Error while reading stdin.");
+            text.trim().to_owned()
+        },
+    }
+}
+
+pub(crate) fn print_bytes(bytes: &[u8], output: &Output){
+    match output{
+        Output::Text => println!("{}", String::from_utf8_lossy(bytes)),
+        Output::Hex => println!("{}", hex::encode(bytes)),
+        Output::Raw => io::stdout().write_all(bytes).exit("Error while writing to stdout."),
+    }
+}
+
+fn get_alphabet(url: bool) -> Alphabet{
+    if url{
+        Alphabet::UrlSafe
+    }else{
+        Alphabet::Standard
+    }
+}
+
+pub fn encode(args: EncodeArgs){
+    match args.encoding{
+        Encoding::Base58(args) => {
+            let bytes = get_bytes(args.data, &args.r#type);
+            if args.check{
+                println!("{}", base58::encode_check(&bytes));
+            }else{
+                println!("{}", base58::encode(&bytes));
+            }
+        },
+        Encoding::Base64(args) => {
+            let bytes = get_bytes(args.data, &args.r#type);
+            println!("{}", base64::encode(&bytes, get_alphabet(args.url)));
+        },
+        Encoding::Bech32(args) => {
+            let bytes = get_bytes(args.data, &args.r#type);
+            let text = match args.segwit{
+                Some(version) => bech32::encode_segwit(&args.hrp, version, &bytes),
+                None if args.bech32m => bech32::encode(&args.hrp, &bytes, Variant::Bech32m),
+                None => bech32::encode(&args.hrp, &bytes, Variant::Bech32),
+            };
+            println!("{}", text.exit("Error while encoding bech32."));
+        },
+    }
+}
+
+pub fn decode(args: DecodeArgs){
+    match args.encoding{
+        Decoding::Base58(args) => {
+            let text = get_text(args.text);
+            let bytes = if args.check{
+                base58::decode_check(&text)
+            }else{
+                base58::decode(&text)
+            }.exit("Error while decoding base58.");
+            print_bytes(&bytes, &args.r#type);
+        },
+        Decoding::Base64(args) => {
+            let text = get_text(args.text);
+            let bytes = base64::decode(&text, get_alphabet(args.url)).exit("Error while decoding base64.");
+            print_bytes(&bytes, &args.r#type);
+        },
+        Decoding::Bech32(args) => {
+            let text = get_text(args.text);
+            if args.segwit{
+                let hrp = args.hrp.unwrap_or_default();
+                let (version, program) = bech32::decode_segwit(&hrp, &text).exit("Error while decoding the segwit address.");
+                println!("witness version: {}", version);
+                print_bytes(&program, &args.r#type);
+                return;
+            }
+            let (hrp, bytes, _) = bech32::decode(&text).exit("Error while decoding bech32.");
+            if let Some(expected) = args.hrp{
+                if hrp != expected.to_lowercase(){
+                    Err::<(), String>(format!("expected {}, found {}.", expected, hrp)).exit("Error while decoding bech32. Invalid human readable part.");
+                }
+            }
+            print_bytes(&bytes, &args.r#type);
+        },
+    }
+}
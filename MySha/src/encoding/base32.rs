@@ -0,0 +1,71 @@
+//! [Base32] encoding, with the alphabet of RFC 4648.
+//!
+//! Base32 encodes every 5 bytes as 8 characters, with only uppercase letters and the digits 2 to 7,
+//! so it can be read aloud and typed without mixing up cases or `0` and `O`. It is the format of the secrets
+//! of authenticator apps, the keys of their one-time codes.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{base32, EncodingError};
+//! # fn main() -> Result<(), EncodingError>{
+//! assert_eq!(base32::encode(b"foobar"), "MZXW6YTBOI======");
+//!
+//! assert_eq!(base32::decode("MZXW6YTBOI======")?, b"foobar");
+//! assert_eq!(base32::decode("mzxw6ytboi")?, b"foobar");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Base32]: https://datatracker.ietf.org/doc/html/rfc4648#section-6
+
+use super::EncodingError;
+
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes the bytes as base32, padded with `=` to a multiple of 8 characters.
+pub fn encode(data: &[u8]) -> String{
+    let mut encoded = String::with_capacity(data.len().div_ceil(5) * 8);
+    for chunk in data.chunks(5){
+        let group = chunk.iter().enumerate().fold(0_u64, |group, (i, byte)| group | (*byte as u64) << (32 - 8 * i));
+        // every character has 5 bits, so n bytes need 8n / 5 characters, rounded up
+        for i in 0..(chunk.len() * 8).div_ceil(5){
+            encoded.push(ALPHABET[(group >> (35 - 5 * i) & 31) as usize] as char);
+        }
+    }
+    while !encoded.len().is_multiple_of(8){
+        encoded.push('=');
+    }
+    encoded
+}
+
+/// Decodes base32 text to bytes, in uppercase or lowercase.
+///
+/// The `=` padding is optional, but if present the text must be a multiple of 8 characters.
+///
+/// # Errors
+/// Returns [EncodingError::InvalidCharacter] if the text has a character that isn't in the alphabet,
+/// [EncodingError::InvalidLength] if the text doesn't have a valid number of characters or of `=`,
+/// and [EncodingError::InvalidPadding] if the bits left after the last byte aren't zeros.
+pub fn decode(text: &str) -> Result<Vec<u8>, EncodingError>{
+    let data = text.trim_end_matches('=');
+    let padding = text.len() - data.len();
+    // 1, 3 and 6 characters in the last group are never whole bytes
+    if (padding > 0 && !text.len().is_multiple_of(8)) || padding > 6 || matches!(data.len() % 8, 1 | 3 | 6){
+        return Err(EncodingError::InvalidLength);
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() * 5 / 8);
+    for chunk in data.as_bytes().chunks(8){
+        let mut group = 0_u64;
+        for (i, c) in chunk.iter().enumerate(){
+            let value = ALPHABET.iter().position(|a| *a == c.to_ascii_uppercase()).ok_or(EncodingError::InvalidCharacter)?;
+            group |= (value as u64) << (35 - 5 * i);
+        }
+        let bytes = chunk.len() * 5 / 8;
+        if group & (0xff_ffff_ffff >> (8 * bytes)) != 0{
+            return Err(EncodingError::InvalidPadding);
+        }
+        decoded.extend((0..bytes).map(|i| (group >> (32 - 8 * i)) as u8));
+    }
+    Ok(decoded)
+}
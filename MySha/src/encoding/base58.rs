@@ -0,0 +1,138 @@
+//! [Base58] and base58check encoding, with the bitcoin alphabet.
+//!
+//! Base58 is like base64 without the characters that look alike (0, O, I and l) and without symbols,
+//! so the text can be selected with a double click and retyped safely. Base58check adds a 4 byte checksum,
+//! the first bytes of the double sha256 of the data, so typos are detected when decoding.
+//! It is used for bitcoin addresses and WIF private keys.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{base58, EncodingError};
+//! # fn main() -> Result<(), EncodingError>{
+//! let address = base58::encode_check(&[0; 21]);
+//! assert_eq!(address, "1111111111111111111114oLvT2");
+//!
+//! assert_eq!(base58::decode_check(&address)?, [0; 21]);
+//! assert_eq!(base58::decode_check("1111111111111111111114oLvT3"), Err(EncodingError::InvalidChecksum));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Base58]: https://en.bitcoin.it/wiki/Base58Check_encoding
+
+use super::{hex, EncodingError};
+use crate::sha256::{sha256, InputType};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Encodes the bytes as base58.
+///
+/// Every leading zero byte is encoded as a leading `1`.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::base58;
+///
+/// assert_eq!(base58::encode(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]), "11233QC4");
+/// ```
+pub fn encode(data: &[u8]) -> String{
+    let zeros = data.iter().take_while(|byte| **byte == 0).count();
+
+    // digits in base 58, least significant first
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in &data[zeros..]{
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut(){
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0{
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut encoded = "1".repeat(zeros);
+    encoded.extend(digits.iter().rev().map(|digit| ALPHABET[*digit as usize] as char));
+    encoded
+}
+
+/// Decodes base58 text to bytes.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::{base58, EncodingError};
+/// # fn main() -> Result<(), EncodingError>{
+/// assert_eq!(base58::decode("11233QC4")?, [0, 0, 0x28, 0x7f, 0xb4, 0xcd]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [EncodingError::InvalidCharacter] if the text has a character that isn't in the base58 alphabet, like `0` or `l`.
+pub fn decode(text: &str) -> Result<Vec<u8>, EncodingError>{
+    let zeros = text.chars().take_while(|c| *c == '1').count();
+
+    // bytes, least significant first
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in text[zeros..].chars(){
+        let mut carry = ALPHABET.iter().position(|a| *a as char == c).ok_or(EncodingError::InvalidCharacter)? as u32;
+        for byte in bytes.iter_mut(){
+            carry += *byte as u32 * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0{
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut decoded = vec![0; zeros];
+    decoded.extend(bytes.iter().rev());
+    Ok(decoded)
+}
+
+/// First 4 bytes of the double sha256 of the data.
+fn checksum(data: &[u8]) -> Vec<u8>{
+    let hash = sha256(&hex::encode(data), InputType::Hex).unwrap();
+    let hash = sha256(hash.get_hex(), InputType::Hex).unwrap();
+    hex::decode(&hash.get_hex()[..8]).unwrap()
+}
+
+/// Encodes the bytes as base58check, the data followed by its 4 byte checksum.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::base58;
+///
+/// // version byte 0x80 followed by the private key 1, a WIF private key
+/// let mut wif = vec![0x80];
+/// wif.extend([0; 31]);
+/// wif.push(1);
+/// assert_eq!(base58::encode_check(&wif), "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf");
+/// ```
+pub fn encode_check(data: &[u8]) -> String{
+    let mut data = data.to_vec();
+    data.extend(checksum(&data));
+    encode(&data)
+}
+
+/// Decodes base58check text, returning the data without the checksum.
+///
+/// # Errors
+/// Returns [EncodingError::InvalidCharacter] if the text isn't valid base58,
+/// [EncodingError::InvalidLength] if it is too short to have a checksum,
+/// and [EncodingError::InvalidChecksum] if the checksum doesn't match the data.
+pub fn decode_check(text: &str) -> Result<Vec<u8>, EncodingError>{
+    let mut data = decode(text)?;
+    if data.len() < 4{
+        return Err(EncodingError::InvalidLength);
+    }
+    let check = data.split_off(data.len() - 4);
+    if check != checksum(&data){
+        return Err(EncodingError::InvalidChecksum);
+    }
+    Ok(data)
+}
@@ -0,0 +1,112 @@
+//! [Base64] encoding, with the standard and the URL-safe alphabets.
+//!
+//! Base64 encodes every 3 bytes as 4 characters, and is the usual text format of keys and signatures,
+//! like in PEM files and JSON web tokens. The URL-safe alphabet uses `-` and `_` instead of `+` and `/`,
+//! so the text can be part of URLs and file names, and is written without the `=` padding.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{base64::{self, Alphabet}, EncodingError};
+//! # fn main() -> Result<(), EncodingError>{
+//! assert_eq!(base64::encode(&[0xfb, 0xff], Alphabet::Standard), "+/8=");
+//! assert_eq!(base64::encode(&[0xfb, 0xff], Alphabet::UrlSafe), "-_8");
+//!
+//! assert_eq!(base64::decode("-_8", Alphabet::UrlSafe)?, [0xfb, 0xff]);
+//! assert_eq!(base64::decode("-_8", Alphabet::Standard), Err(EncodingError::InvalidCharacter));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Base64]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+
+use super::EncodingError;
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The characters used by the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet{
+    /// `+` and `/` as the last characters, padded with `=`
+    Standard,
+    /// `-` and `_` as the last characters, without padding
+    UrlSafe,
+}
+
+impl Alphabet{
+    fn characters(&self) -> &'static [u8; 64]{
+        match self{
+            Alphabet::Standard => STANDARD,
+            Alphabet::UrlSafe => URL_SAFE,
+        }
+    }
+}
+
+/// Encodes the bytes as base64, with the alphabet.
+///
+/// The standard alphabet pads the text with `=` to a multiple of 4 characters, the URL-safe alphabet doesn't.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::base64::{self, Alphabet};
+///
+/// assert_eq!(base64::encode(b"hello world", Alphabet::Standard), "aGVsbG8gd29ybGQ=");
+/// ```
+pub fn encode(data: &[u8], alphabet: Alphabet) -> String{
+    let characters = alphabet.characters();
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3){
+        let group = chunk.iter().enumerate().fold(0_u32, |group, (i, byte)| group | (*byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len(){
+            encoded.push(characters[(group >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+    if alphabet == Alphabet::Standard{
+        while !encoded.len().is_multiple_of(4){
+            encoded.push('=');
+        }
+    }
+    encoded
+}
+
+/// Decodes base64 text with the alphabet to bytes.
+///
+/// The `=` padding is optional for both alphabets, but if present the text must be a multiple of 4 characters.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::{base64::{self, Alphabet}, EncodingError};
+/// # fn main() -> Result<(), EncodingError>{
+/// assert_eq!(base64::decode("aGVsbG8gd29ybGQ=", Alphabet::Standard)?, b"hello world");
+/// assert_eq!(base64::decode("aGVsbG8gd29ybGQ", Alphabet::Standard)?, b"hello world");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [EncodingError::InvalidCharacter] if the text has a character that isn't in the alphabet,
+/// [EncodingError::InvalidLength] if the text doesn't have a valid number of characters or of `=`,
+/// and [EncodingError::InvalidPadding] if the bits left after the last byte aren't zeros.
+pub fn decode(text: &str, alphabet: Alphabet) -> Result<Vec<u8>, EncodingError>{
+    let characters = alphabet.characters();
+    let data = text.trim_end_matches('=');
+    let padding = text.len() - data.len();
+    if (padding > 0 && !text.len().is_multiple_of(4)) || padding > 2 || data.len() % 4 == 1{
+        return Err(EncodingError::InvalidLength);
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.as_bytes().chunks(4){
+        let mut group = 0_u32;
+        for (i, c) in chunk.iter().enumerate(){
+            let value = characters.iter().position(|a| a == c).ok_or(EncodingError::InvalidCharacter)?;
+            group |= (value as u32) << (18 - 6 * i);
+        }
+        let bytes = chunk.len() - 1;
+        if group & (0xffffff >> (8 * bytes)) != 0{
+            return Err(EncodingError::InvalidPadding);
+        }
+        decoded.extend((0..bytes).map(|i| (group >> (16 - 8 * i)) as u8));
+    }
+    Ok(decoded)
+}
@@ -0,0 +1,254 @@
+//! [Bech32] and [bech32m] encoding.
+//!
+//! Bech32 text is a human readable part (hrp), like `bc` for bitcoin addresses, the separator `1`,
+//! and the data in base32 followed by a 6 character checksum, that detects any typo of up to 4 characters.
+//! Bech32m only changes the checksum constant, fixing a weakness of bech32 when characters are added or removed before a final `p`.
+//! Segwit version 0 addresses use bech32, and taproot and later versions use bech32m, see [encode_segwit()].
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{bech32::{self, Variant}, EncodingError};
+//! # fn main() -> Result<(), EncodingError>{
+//! let text = bech32::encode("mysha", b"hello", Variant::Bech32m)?;
+//!
+//! assert_eq!(bech32::decode(&text)?, (String::from("mysha"), b"hello".to_vec(), Variant::Bech32m));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Bech32]: https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! [bech32m]: https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+
+use super::EncodingError;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+/// Longest text allowed, including the hrp and the checksum.
+const MAX_LENGTH: usize = 90;
+
+/// The checksum used by the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant{
+    /// Original checksum, used by segwit version 0 addresses
+    Bech32,
+    /// Fixed checksum, used by segwit version 1 (taproot) and later addresses
+    Bech32m,
+}
+
+impl Variant{
+    fn constant(&self) -> u32{
+        match self{
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+fn polymod(values: &[u8]) -> u32{
+    let mut checksum = 1_u32;
+    for value in values{
+        let top = checksum >> 25;
+        checksum = (checksum & 0x1ffffff) << 5 ^ *value as u32;
+        for (i, g) in GENERATOR.iter().enumerate(){
+            if (top >> i) & 1 == 1{
+                checksum ^= g;
+            }
+        }
+    }
+    checksum
+}
+
+/// The hrp expanded to be part of the checksum, high bits of every character, 0, and low bits of every character.
+fn hrp_expand(hrp: &str) -> Vec<u8>{
+    let mut values: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|c| c & 31));
+    values
+}
+
+fn create_checksum(hrp: &str, data: &[u8], variant: Variant) -> Vec<u8>{
+    let mut values = hrp_expand(hrp);
+    values.extend(data);
+    values.extend([0; 6]);
+    let checksum = polymod(&values) ^ variant.constant();
+    (0..6).map(|i| ((checksum >> (5 * (5 - i))) & 31) as u8).collect()
+}
+
+/// Regroups the bits of the values, from `from` bits per value to `to` bits per value.
+///
+/// If `pad` is true the last value is padded with zeros, if not, the bits left must be zeros and fewer than `from`.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, EncodingError>{
+    let mut acc = 0_u32;
+    let mut bits = 0;
+    let mut converted = Vec::new();
+    let max = (1 << to) - 1;
+    for value in data{
+        if (*value as u32) >> from != 0{
+            return Err(EncodingError::InvalidCharacter);
+        }
+        acc = acc << from | *value as u32;
+        bits += from;
+        while bits >= to{
+            bits -= to;
+            converted.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad{
+        if bits > 0{
+            converted.push(((acc << (to - bits)) & max) as u8);
+        }
+    }else if bits >= from || ((acc << (to - bits)) & max) != 0{
+        return Err(EncodingError::InvalidPadding);
+    }
+    Ok(converted)
+}
+
+fn validate_hrp(hrp: &str) -> Result<(), EncodingError>{
+    if hrp.is_empty() || hrp.len() > 83 || hrp.bytes().any(|c| !(33..=126).contains(&c)){
+        return Err(EncodingError::InvalidHrp);
+    }
+    Ok(())
+}
+
+/// Encodes 5 bit values with the hrp.
+fn encode_values(hrp: &str, values: &[u8], variant: Variant) -> Result<String, EncodingError>{
+    validate_hrp(hrp)?;
+    let hrp = hrp.to_lowercase();
+    if hrp.len() + 1 + values.len() + 6 > MAX_LENGTH{
+        return Err(EncodingError::InvalidLength);
+    }
+    let checksum = create_checksum(&hrp, values, variant);
+    let mut text = hrp + "1";
+    text.extend(values.iter().chain(&checksum).map(|value| CHARSET[*value as usize] as char));
+    Ok(text)
+}
+
+/// Decodes text to its hrp and 5 bit values, without the checksum.
+fn decode_values(text: &str) -> Result<(String, Vec<u8>, Variant), EncodingError>{
+    if text.len() > MAX_LENGTH{
+        return Err(EncodingError::InvalidLength);
+    }
+    if text.chars().any(|c| c.is_lowercase()) && text.chars().any(|c| c.is_uppercase()){
+        return Err(EncodingError::MixedCase);
+    }
+    let text = text.to_lowercase();
+    let separator = text.rfind('1').ok_or(EncodingError::InvalidLength)?;
+    let (hrp, data) = (&text[..separator], &text[separator + 1..]);
+    validate_hrp(hrp)?;
+    if data.len() < 6{
+        return Err(EncodingError::InvalidLength);
+    }
+    let values = data.bytes().map(|c| CHARSET.iter().position(|a| *a == c).map(|v| v as u8)).collect::<Option<Vec<u8>>>().ok_or(EncodingError::InvalidCharacter)?;
+
+    let mut check = hrp_expand(hrp);
+    check.extend(&values);
+    let variant = match polymod(&check){
+        c if c == Variant::Bech32.constant() => Variant::Bech32,
+        c if c == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err(EncodingError::InvalidChecksum),
+    };
+    Ok((hrp.to_owned(), values[..values.len() - 6].to_vec(), variant))
+}
+
+/// Encodes the bytes as bech32 or bech32m text, with the hrp.
+///
+/// The hrp is written in lowercase.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::{bech32::{self, Variant}, EncodingError};
+/// # fn main() -> Result<(), EncodingError>{
+/// assert_eq!(bech32::encode("a", &[], Variant::Bech32)?, "a12uel5l");
+/// assert_eq!(bech32::encode("a", &[], Variant::Bech32m)?, "a1lqfn3a");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [EncodingError::InvalidHrp] if the hrp is empty, longer than 83 characters, or has characters outside of the printable ascii,
+/// and [EncodingError::InvalidLength] if the text would be longer than 90 characters.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, EncodingError>{
+    encode_values(hrp, &convert_bits(data, 8, 5, true)?, variant)
+}
+
+/// Decodes bech32 or bech32m text to its hrp, bytes, and the variant of its checksum.
+///
+/// The hrp is returned in lowercase.
+///
+/// # Errors
+/// Returns [EncodingError::MixedCase] if the text has both lowercase and uppercase characters,
+/// [EncodingError::InvalidChecksum] if the checksum isn't valid for either variant,
+/// [EncodingError::InvalidPadding] if the data isn't a whole number of bytes,
+/// and [EncodingError::InvalidHrp], [EncodingError::InvalidCharacter] or [EncodingError::InvalidLength] if the text isn't bech32.
+pub fn decode(text: &str) -> Result<(String, Vec<u8>, Variant), EncodingError>{
+    let (hrp, values, variant) = decode_values(text)?;
+    Ok((hrp, convert_bits(&values, 5, 8, false)?, variant))
+}
+
+/// Encodes a segwit address, from its witness version and program.
+///
+/// Version 0 uses bech32 and later versions use bech32m.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::{bech32, EncodingError};
+/// # fn main() -> Result<(), EncodingError>{
+/// let program = [0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3, 0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6];
+///
+/// assert_eq!(bech32::encode_segwit("bc", 0, &program)?, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [EncodingError::InvalidProgram] if the version is over 16, or the program doesn't have a valid length for the version.
+pub fn encode_segwit(hrp: &str, version: u8, program: &[u8]) -> Result<String, EncodingError>{
+    validate_program(version, program)?;
+    let variant = if version == 0 {Variant::Bech32} else {Variant::Bech32m};
+    let mut values = vec![version];
+    values.extend(convert_bits(program, 8, 5, true)?);
+    encode_values(hrp, &values, variant)
+}
+
+/// Decodes a segwit address with the expected hrp, to its witness version and program.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::{bech32, EncodingError};
+/// # fn main() -> Result<(), EncodingError>{
+/// let (version, program) = bech32::decode_segwit("bc", "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0")?;
+///
+/// assert_eq!(version, 1);
+/// assert_eq!(program.len(), 32);
+///
+/// // bech32 checksum on a version 1 address
+/// assert_eq!(bech32::decode_segwit("bc", "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd"), Err(EncodingError::InvalidChecksum));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [EncodingError::InvalidHrp] if the hrp isn't the expected one,
+/// [EncodingError::InvalidChecksum] if the variant of the checksum isn't the one of the version,
+/// [EncodingError::InvalidProgram] if the version or the length of the program aren't valid,
+/// and the errors of [decode()] if the text isn't valid bech32.
+pub fn decode_segwit(hrp: &str, address: &str) -> Result<(u8, Vec<u8>), EncodingError>{
+    let (decoded_hrp, values, variant) = decode_values(address)?;
+    if decoded_hrp != hrp.to_lowercase(){
+        return Err(EncodingError::InvalidHrp);
+    }
+    let (version, program) = values.split_first().ok_or(EncodingError::InvalidProgram)?;
+    let program = convert_bits(program, 5, 8, false)?;
+    validate_program(*version, &program)?;
+    if (*version == 0) != (variant == Variant::Bech32){
+        return Err(EncodingError::InvalidChecksum);
+    }
+    Ok((*version, program))
+}
+
+fn validate_program(version: u8, program: &[u8]) -> Result<(), EncodingError>{
+    if version > 16 || program.len() < 2 || program.len() > 40 || (version == 0 && program.len() != 20 && program.len() != 32){
+        return Err(EncodingError::InvalidProgram);
+    }
+    Ok(())
+}
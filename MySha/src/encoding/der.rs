@@ -0,0 +1,148 @@
+//! [DER] encoding, the binary format of keys and signatures in certificates, PEM files and most tools.
+//!
+//! Every value is a tag, the length of its content, and the content, and sequences hold other values as their content.
+//! Only the few types used by keys and signatures are provided: integers, bit and octet strings, object identifiers and sequences.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{der, EncodingError};
+//! use num_bigint::BigUint;
+//! # fn main() -> Result<(), EncodingError>{
+//! let encoded = der::encode(der::SEQUENCE, &[der::integer(&BigUint::from(128_u8)), der::integer(&BigUint::from(1_u8))].concat());
+//! assert_eq!(encoded, [0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x01]);
+//!
+//! let mut sequence = der::Reader::new(der::Reader::new(&encoded).read(der::SEQUENCE)?);
+//! assert_eq!(sequence.read_integer()?, BigUint::from(128_u8));
+//! assert_eq!(sequence.read_integer()?, BigUint::from(1_u8));
+//! assert!(sequence.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [DER]: https://letsencrypt.org/docs/a-warm-welcome-to-asn1-and-der/
+
+use num_bigint::BigUint;
+
+use super::EncodingError;
+
+pub const INTEGER: u8 = 0x02;
+pub const BIT_STRING: u8 = 0x03;
+pub const OCTET_STRING: u8 = 0x04;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+
+/// Tag of the explicit context specific field `[n]`, used for optional fields of sequences.
+pub const fn context(n: u8) -> u8{
+    0xa0 | n
+}
+
+/// Encodes a value with the tag and content.
+///
+/// Lengths below 128 take one byte, longer ones are written in the following bytes, after a byte with their count.
+pub fn encode(tag: u8, content: &[u8]) -> Vec<u8>{
+    let mut encoded = vec![tag];
+    if content.len() < 0x80{
+        encoded.push(content.len() as u8);
+    }else{
+        let length = content.len().to_be_bytes();
+        let length = &length[length.iter().position(|byte| *byte != 0).unwrap()..];
+        encoded.push(0x80 | length.len() as u8);
+        encoded.extend_from_slice(length);
+    }
+    encoded.extend_from_slice(content);
+    encoded
+}
+
+/// Encodes a non negative integer, with a zero byte before it if its first bit is set, so it isn't read as negative.
+pub fn integer(n: &BigUint) -> Vec<u8>{
+    let mut bytes = n.to_bytes_be();
+    if bytes[0] & 0x80 != 0{
+        bytes.insert(0, 0);
+    }
+    encode(INTEGER, &bytes)
+}
+
+/// Encodes a bit string of whole bytes, the first content byte is the number of unused bits, zero.
+pub fn bit_string(bytes: &[u8]) -> Vec<u8>{
+    encode(BIT_STRING, &[&[0], bytes].concat())
+}
+
+/// Reads the values of DER data, one after the other.
+pub struct Reader<'a>{
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a>{
+    pub fn new(data: &'a [u8]) -> Reader<'a>{
+        Reader{data}
+    }
+
+    /// Whether every value was read.
+    pub fn is_empty(&self) -> bool{
+        self.data.is_empty()
+    }
+
+    /// Tag of the next value, without reading it.
+    pub fn peek(&self) -> Option<u8>{
+        self.data.first().copied()
+    }
+
+    /// Reads the next value, returning its content.
+    ///
+    /// # Errors
+    /// Returns [EncodingError::InvalidDer] if the next value doesn't have the tag, or its length isn't the shortest one
+    /// or goes past the end of the data.
+    pub fn read(&mut self, tag: u8) -> Result<&'a [u8], EncodingError>{
+        let (&found, rest) = self.data.split_first().ok_or(EncodingError::InvalidDer)?;
+        let (&first, mut rest) = rest.split_first().ok_or(EncodingError::InvalidDer)?;
+        if found != tag{
+            return Err(EncodingError::InvalidDer);
+        }
+        let length = if first < 0x80{
+            first as usize
+        }else{
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 || rest.len() < count || rest[0] == 0{
+                return Err(EncodingError::InvalidDer);
+            }
+            let length = rest[..count].iter().fold(0, |length, byte| length << 8 | *byte as usize);
+            rest = &rest[count..];
+            // DER only allows the shortest form
+            if length < 0x80{
+                return Err(EncodingError::InvalidDer);
+            }
+            length
+        };
+        if rest.len() < length{
+            return Err(EncodingError::InvalidDer);
+        }
+        let (content, rest) = rest.split_at(length);
+        self.data = rest;
+        Ok(content)
+    }
+
+    /// Reads a non negative integer.
+    ///
+    /// # Errors
+    /// Returns [EncodingError::InvalidDer] if it is negative, empty, or has a leading zero byte that isn't needed.
+    pub fn read_integer(&mut self) -> Result<BigUint, EncodingError>{
+        let bytes = self.read(INTEGER)?;
+        match bytes{
+            [] => Err(EncodingError::InvalidDer),
+            [first, ..] if first & 0x80 != 0 => Err(EncodingError::InvalidDer),
+            [0, second, ..] if second & 0x80 == 0 => Err(EncodingError::InvalidDer),
+            _ => Ok(BigUint::from_bytes_be(bytes)),
+        }
+    }
+
+    /// Reads a bit string of whole bytes.
+    ///
+    /// # Errors
+    /// Returns [EncodingError::InvalidDer] if it has unused bits.
+    pub fn read_bit_string(&mut self) -> Result<&'a [u8], EncodingError>{
+        match self.read(BIT_STRING)?{
+            [0, bytes @ ..] => Ok(bytes),
+            _ => Err(EncodingError::InvalidDer),
+        }
+    }
+}
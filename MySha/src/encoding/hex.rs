@@ -0,0 +1,108 @@
+//! Hexadecimal encoding, and little endian byte order.
+//!
+//! Hashes, keys and signatures are shown as hex, with two digits for every byte.
+//! Bitcoin shows some values, like transaction ids, with the bytes in reverse order, as little endian,
+//! which is the byte order they are used in, so the `_le` functions and [reverse()] swap the order of the bytes.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{hex, EncodingError};
+//! # fn main() -> Result<(), EncodingError>{
+//! assert_eq!(hex::encode(&[0xde, 0xad, 0xbe, 0xef]), "deadbeef");
+//! assert_eq!(hex::decode_le("deadbeef")?, [0xef, 0xbe, 0xad, 0xde]);
+//!
+//! assert_eq!(hex::decode("dead beef"), Err(EncodingError::InvalidDigit{character: ' ', position: 4}));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::EncodingError;
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Encodes the bytes as lowercase hex.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::hex;
+///
+/// assert_eq!(hex::encode(b"abc"), "616263");
+/// ```
+pub fn encode(data: &[u8]) -> String{
+    let mut encoded = String::with_capacity(data.len() * 2);
+    for byte in data{
+        encoded.push(DIGITS[(byte >> 4) as usize] as char);
+        encoded.push(DIGITS[(byte & 15) as usize] as char);
+    }
+    encoded
+}
+
+/// Encodes the bytes as hex, in reverse order.
+///
+/// Numbers converted to little endian bytes, like with `to_le_bytes()`, are encoded with their least significant byte first.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::hex;
+///
+/// assert_eq!(hex::encode_le(&[0x01, 0x02, 0x03]), "030201");
+/// ```
+pub fn encode_le(data: &[u8]) -> String{
+    let mut data = data.to_vec();
+    data.reverse();
+    encode(&data)
+}
+
+/// Decodes hex text to bytes, with either lowercase or uppercase digits.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::{hex, EncodingError};
+/// # fn main() -> Result<(), EncodingError>{
+/// assert_eq!(hex::decode("616263")?, b"abc");
+/// assert_eq!(hex::decode("DEADbeef")?, [0xde, 0xad, 0xbe, 0xef]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [EncodingError::OddLength] if the text doesn't have two digits for every byte,
+/// and [EncodingError::InvalidDigit] with the first character that isn't a hex digit, and its position.
+pub fn decode(text: &str) -> Result<Vec<u8>, EncodingError>{
+    let digits = text.chars().enumerate().map(|(position, character)|{
+        character.to_digit(16).map(|digit| digit as u8).ok_or(EncodingError::InvalidDigit{character, position})
+    }).collect::<Result<Vec<u8>, EncodingError>>()?;
+    if !digits.len().is_multiple_of(2){
+        return Err(EncodingError::OddLength{length: digits.len()});
+    }
+    Ok(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+/// Decodes hex text to bytes, in reverse order.
+///
+/// # Errors
+/// Same as [decode()].
+pub fn decode_le(text: &str) -> Result<Vec<u8>, EncodingError>{
+    let mut decoded = decode(text)?;
+    decoded.reverse();
+    Ok(decoded)
+}
+
+/// Reverses the order of the bytes of hex text, converting it between big endian and little endian.
+///
+/// The digits are written in lowercase.
+///
+/// # Examples
+/// ```
+/// use mysha::encoding::{hex, EncodingError};
+/// # fn main() -> Result<(), EncodingError>{
+/// assert_eq!(hex::reverse("0a0b0c")?, "0c0b0a");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Same as [decode()].
+pub fn reverse(text: &str) -> Result<String, EncodingError>{
+    Ok(encode_le(&decode(text)?))
+}
@@ -0,0 +1,82 @@
+//! Module for encoding binary data as text.
+//!
+//! Keys, hashes and signatures are bytes, but are usually shown, copied and stored as text.
+//! Every encoding is in its own module, and they all return the same [EncodingError] when decoding invalid text.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{base58, EncodingError};
+//! # fn main() -> Result<(), EncodingError>{
+//! let encoded = base58::encode(b"hello world");
+//!
+//! assert_eq!(encoded, "StV1DL6CwTryKyV");
+//! assert_eq!(base58::decode(&encoded)?, b"hello world");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+pub mod base32;
+#[cfg(feature = "sha256")]
+pub mod base58;
+pub mod base64;
+pub mod bech32;
+#[cfg(feature = "bigint")]
+pub mod der;
+pub mod hex;
+pub mod pem;
+pub mod randomart;
+
+/// The error type implemented for this module, with all possible decoding errors.
+#[derive(Debug, PartialEq)]
+pub enum EncodingError{
+    /// Happens when the text has a character that isn't part of the encoding's alphabet.
+    InvalidCharacter,
+    /// Happens when the checksum of the text doesn't match its data.
+    InvalidChecksum,
+    /// Happens when the text is too short or too long to be valid.
+    InvalidLength,
+    /// Happens when the human readable part of bech32 text is invalid, or isn't the one expected.
+    InvalidHrp,
+    /// Happens when bech32 text has both lowercase and uppercase characters.
+    MixedCase,
+    /// Happens when the bits left after decoding aren't a valid padding.
+    InvalidPadding,
+    /// Happens when the witness version or program of a segwit address are invalid.
+    InvalidProgram,
+    /// Happens when hex text has a character that isn't a hexadecimal digit, at the position, counted in characters from 0.
+    InvalidDigit{
+        character: char,
+        position: usize,
+    },
+    /// Happens when hex text has an odd number of digits, so it isn't a whole number of bytes.
+    OddLength{
+        length: usize,
+    },
+    /// Happens when DER data doesn't have the tags expected, or a length that isn't valid.
+    InvalidDer,
+    /// Happens when PEM text doesn't have a block with matching `BEGIN` and `END` lines.
+    InvalidPem,
+    /// Happens when OpenSSH key data doesn't have the fields expected, or is encrypted with a passphrase.
+    InvalidSsh,
+}
+
+impl fmt::Display for EncodingError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            EncodingError::InvalidCharacter => write!(f, "Invalid character for the encoding."),
+            EncodingError::InvalidChecksum => write!(f, "Invalid checksum."),
+            EncodingError::InvalidLength => write!(f, "Invalid length for the encoding."),
+            EncodingError::InvalidHrp => write!(f, "Invalid human readable part."),
+            EncodingError::MixedCase => write!(f, "Text can't have both lowercase and uppercase characters."),
+            EncodingError::InvalidPadding => write!(f, "Invalid padding."),
+            EncodingError::InvalidProgram => write!(f, "Invalid witness version or program."),
+            EncodingError::InvalidDigit{character, position} => write!(f, "Invalid hexadecimal digit {:?} at position {}.", character, position),
+            EncodingError::OddLength{length} => write!(f, "Odd number of hexadecimal digits ({}), not a whole number of bytes.", length),
+            EncodingError::InvalidDer => write!(f, "Invalid DER data."),
+            EncodingError::InvalidPem => write!(f, "Invalid PEM text, it needs a block between BEGIN and END lines."),
+            EncodingError::InvalidSsh => write!(f, "Invalid OpenSSH key, or one encrypted with a passphrase."),
+        }
+    }
+}
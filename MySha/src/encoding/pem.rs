@@ -0,0 +1,56 @@
+//! [PEM] encoding, DER data as base64 text between `-----BEGIN` and `-----END` lines.
+//!
+//! The label of the lines tells what the data is, like `PUBLIC KEY` or `EC PRIVATE KEY`,
+//! and the base64 is split in lines of 64 characters, so it can be pasted in emails and configuration files.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::{pem, EncodingError};
+//! # fn main() -> Result<(), EncodingError>{
+//! let encoded = pem::encode("DATA", b"hello");
+//! assert_eq!(encoded, "-----BEGIN DATA-----\naGVsbG8=\n-----END DATA-----\n");
+//!
+//! assert_eq!(pem::decode(&encoded)?, (String::from("DATA"), b"hello".to_vec()));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [PEM]: https://datatracker.ietf.org/doc/html/rfc7468
+
+use super::base64::{self, Alphabet};
+use super::EncodingError;
+
+/// Encodes the data with the label.
+pub fn encode(label: &str, data: &[u8]) -> String{
+    let encoded = base64::encode(data, Alphabet::Standard);
+    let mut text = format!("-----BEGIN {}-----\n", label);
+    for line in encoded.as_bytes().chunks(64){
+        text.push_str(std::str::from_utf8(line).unwrap());
+        text.push('\n');
+    }
+    text.push_str(&format!("-----END {}-----\n", label));
+    text
+}
+
+/// Decodes the first block of the text, returning its label and data.
+///
+/// Text before the block, like the comments some tools write, is ignored.
+///
+/// # Errors
+/// Returns [EncodingError::InvalidPem] if there is no block, or its lines don't have the same label,
+/// and the errors of [base64::decode] if the data isn't valid base64.
+pub fn decode(text: &str) -> Result<(String, Vec<u8>), EncodingError>{
+    let mut lines = text.lines().map(str::trim).skip_while(|line| !line.starts_with("-----BEGIN "));
+    let label = lines.next()
+        .and_then(|line| line.strip_prefix("-----BEGIN ")?.strip_suffix("-----"))
+        .ok_or(EncodingError::InvalidPem)?;
+    let end = format!("-----END {}-----", label);
+    let mut encoded = String::new();
+    for line in lines{
+        if line == end{
+            return Ok((label.to_string(), base64::decode(&encoded, Alphabet::Standard)?));
+        }
+        encoded.push_str(line);
+    }
+    Err(EncodingError::InvalidPem)
+}
@@ -0,0 +1,78 @@
+//! The randomart of OpenSSH, a small picture of bytes, to compare hashes and fingerprints at a glance.
+//!
+//! A bishop starts at the center of a 17 by 9 board and, for every pair of bits of the bytes, the lowest first,
+//! moves one square diagonally: right if the first bit is 1 and left if not, down if the second bit is 1 and up if not,
+//! staying on the board at the walls. Every square shows how many times the bishop was there, with the symbols
+//! ` .o+=*BOX@%&#/^`, and the start and the end are `S` and `E`. This is the [drunken bishop] of `ssh-keygen -lv`,
+//! so the art of the fingerprint of a key is the same as the one shown by OpenSSH.
+//!
+//! Different bytes give very different pictures, which people tell apart better than hex, but it is only a visual aid:
+//! pictures that differ in a single square are easy to miss, so it doesn't replace comparing the bytes.
+//!
+//! # Examples
+//! ```
+//! use mysha::encoding::randomart;
+//!
+//! // zero bits always move left and up, into the corner
+//! let art = randomart::draw(&[0x00; 4], "zeros", "");
+//! assert_eq!(art, "\
+//! +-----[zeros]-----+
+//! |E....            |
+//! |     .           |
+//! |      .          |
+//! |       .         |
+//! |        S        |
+//! |                 |
+//! |                 |
+//! |                 |
+//! |                 |
+//! +-----------------+");
+//! ```
+//!
+//! [drunken bishop]: http://www.dirk-loss.de/sshvis/drunken_bishop.pdf
+
+/// Columns of the board.
+pub const WIDTH: usize = 17;
+/// Rows of the board.
+pub const HEIGHT: usize = 9;
+
+/// Symbols of the squares, by how many times the bishop was there, and the start and the end.
+const SYMBOLS: &[u8] = b" .o+=*BOX@%&#/^SE";
+const START: usize = SYMBOLS.len() - 2;
+const END: usize = SYMBOLS.len() - 1;
+
+/// Border line with the label in brackets in its center, cut to fit the board.
+fn border(label: &str) -> String{
+    let label = if label.is_empty() {String::new()} else {format!("[{}]", label)};
+    let label: String = label.chars().take(WIDTH).collect();
+    let length = label.chars().count();
+    let left = (WIDTH - length) / 2;
+    format!("+{}{}{}+", "-".repeat(left), label, "-".repeat(WIDTH - left - length))
+}
+
+/// Draws the randomart of the bytes, with the title in the top border and the footer in the bottom one,
+/// like `ECDSA 256` and `SHA256` in OpenSSH. Empty labels leave the border plain.
+pub fn draw(bytes: &[u8], title: &str, footer: &str) -> String{
+    let mut board = [[0; WIDTH]; HEIGHT];
+    let (mut x, mut y) = (WIDTH / 2, HEIGHT / 2);
+    for byte in bytes{
+        for pair in 0..4{
+            let bits = byte >> (2 * pair);
+            x = if bits & 1 == 1 {(x + 1).min(WIDTH - 1)} else {x.saturating_sub(1)};
+            y = if bits & 2 == 2 {(y + 1).min(HEIGHT - 1)} else {y.saturating_sub(1)};
+            // the count stops at the last symbol before the start and the end
+            if board[y][x] < START - 1{
+                board[y][x] += 1;
+            }
+        }
+    }
+    board[HEIGHT / 2][WIDTH / 2] = START;
+    board[y][x] = END;
+
+    let mut lines = vec![border(title)];
+    for row in board{
+        lines.push(format!("|{}|", row.iter().map(|&count| SYMBOLS[count] as char).collect::<String>()));
+    }
+    lines.push(border(footer));
+    lines.join("\n")
+}
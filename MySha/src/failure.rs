@@ -0,0 +1,184 @@
+//! Exit codes of the tool, and how errors are printed, so scripts can tell the failures apart.
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use mysha::cipher::CipherError;
+use mysha::ecc::EccError;
+use mysha::encoding::EncodingError;
+use mysha::jwt::JwtError;
+use mysha::kdf::KdfError;
+use mysha::otp::OtpError;
+use mysha::selftest::VectorError;
+use mysha::sha256::HashError;
+use mysha::MyshaError;
+
+/// Why the tool failed, with its exit code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Failure{
+    /// A check failed: a signature, proof, token, password, commitment or test vector isn't valid, or a search found nothing.
+    Verification = 1,
+    /// An argument or an input can't be parsed or isn't valid, the same code as the errors of the arguments.
+    Parse = 2,
+    /// A file can't be read or written.
+    File = 3,
+    /// Something failed that shouldn't have, whatever the input.
+    Internal = 4,
+}
+
+impl Failure{
+    fn name(&self) -> &'static str{
+        match self{
+            Failure::Verification => "verification",
+            Failure::Parse => "parse",
+            Failure::File => "file",
+            Failure::Internal => "internal",
+        }
+    }
+
+    /// Exits with the code of the failure, after the result was already printed, like an invalid signature.
+    pub fn exit(self) -> !{
+        std::process::exit(self as i32)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ErrorFormat{
+    /// The message and the error, for people to read
+    Text,
+    /// A json object with the kind, the exit code, the message and the error, in one line
+    Json,
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Sets how [fail] prints the errors, from --error-format.
+pub fn set_format(format: ErrorFormat){
+    JSON.store(format == ErrorFormat::Json, Ordering::Relaxed);
+}
+
+/// If the arguments ask for json errors, for the errors of the arguments themselves, before they are parsed.
+pub fn json_requested() -> bool{
+    let args: Vec<String> = std::env::args().collect();
+    args.iter().any(|arg| arg == "--error-format=json") || args.windows(2).any(|pair| pair[0] == "--error-format" && pair[1] == "json")
+}
+
+#[derive(Serialize)]
+struct JsonError<'a>{
+    kind: &'static str,
+    code: i32,
+    message: &'a str,
+    error: Option<String>,
+}
+
+/// Prints the message and the error that caused it to stderr, as text or json, and exits with the code of the failure.
+pub fn fail(failure: Failure, message: &str, error: Option<&dyn Display>) -> !{
+    if JSON.load(Ordering::Relaxed){
+        let error = JsonError{kind: failure.name(), code: failure as i32, message, error: error.map(|error| error.to_string())};
+        eprintln!("{}", serde_json::to_string(&error).unwrap());
+    }else{
+        match error{
+            Some(error) => eprintln!("{} Error: {}", message, error),
+            None => eprintln!("{}", message),
+        }
+    }
+    failure.exit()
+}
+
+/// Panics are internal errors, printed like the others with --error-format json.
+pub fn set_panic_hook(){
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if JSON.load(Ordering::Relaxed){
+            fail(Failure::Internal, "Internal error.", Some(info));
+        }
+        default(info);
+        Failure::Internal.exit();
+    }));
+}
+
+/// The [Failure] an error is, for the exit code of [Exit][crate::Exit].
+pub trait Kind{
+    fn failure(&self) -> Failure;
+}
+
+impl Kind for std::io::Error{
+    fn failure(&self) -> Failure{
+        Failure::File
+    }
+}
+
+impl Kind for png::EncodingError{
+    fn failure(&self) -> Failure{
+        Failure::File
+    }
+}
+
+impl Kind for HashError{
+    fn failure(&self) -> Failure{
+        match self{
+            HashError::ErrorWithFile => Failure::File,
+            _ => Failure::Parse,
+        }
+    }
+}
+
+impl Kind for CipherError{
+    fn failure(&self) -> Failure{
+        match self{
+            CipherError::AuthenticationFailed => Failure::Verification,
+            _ => Failure::Parse,
+        }
+    }
+}
+
+impl Kind for JwtError{
+    fn failure(&self) -> Failure{
+        match self{
+            JwtError::InvalidSignature | JwtError::Expired | JwtError::NotYetValid => Failure::Verification,
+            _ => Failure::Parse,
+        }
+    }
+}
+
+impl Kind for MyshaError{
+    fn failure(&self) -> Failure{
+        match self{
+            MyshaError::Hash(error) => error.failure(),
+            MyshaError::Cipher(error) => error.failure(),
+            MyshaError::Jwt(error) => error.failure(),
+            _ => Failure::Parse,
+        }
+    }
+}
+
+impl Kind for toml::ser::Error{
+    fn failure(&self) -> Failure{
+        Failure::Internal
+    }
+}
+
+impl Kind for std::time::SystemTimeError{
+    fn failure(&self) -> Failure{
+        Failure::Internal
+    }
+}
+
+/// Errors of keys, curves, encodings and other inputs that are wrong.
+macro_rules! parse_errors{
+    ($($error: ty),*) => {
+        $(
+            impl Kind for $error{
+                fn failure(&self) -> Failure{
+                    Failure::Parse
+                }
+            }
+        )*
+    };
+}
+
+parse_errors!(EccError, EncodingError, KdfError, OtpError, VectorError, qrcode::types::QrError, serde_json::Error, toml::de::Error,
+    std::num::ParseIntError, std::string::FromUtf8Error, String, &str);
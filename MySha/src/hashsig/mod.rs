@@ -0,0 +1,106 @@
+//! Module for hash-based signatures, whose security only relies on sha256, and not on a hard math problem,
+//! so they aren't broken by quantum computers, like [ecc](crate::ecc) signatures would be.
+//!
+//! - [wots] is W-OTS+, the Winternitz one-time signature: a key signs a single message, signing two reveals enough to forge others;
+//! - [xmss] puts 2^h one-time keys in a [Merkle tree](crate::merkle), whose root is the public key, to sign up to 2^h messages.
+//!   It is stateful: the private key holds the index of the next one-time key, and must be saved after every signature,
+//!   or an old copy will sign with a one-time key again.
+//!
+//! Both follow [RFC 8391], with the parameters of XMSS-SHA2_h_256, n = 32 bytes and w = 16, but XMSS is simplified:
+//! the nodes of the tree are the ones of the [merkle](crate::merkle) module, without bitmasks,
+//! the one-time public keys are hashed whole into the leaves, without L-trees, and the message is hashed without a randomizer.
+//! So the signatures aren't compatible with other implementations of XMSS.
+//!
+//! # Examples
+//! ```
+//! use mysha::hashsig::xmss::XmssPrivateKey;
+//! # fn main() -> Result<(), mysha::MyshaError>{
+//! // 2^4 = 16 signatures
+//! let mut private = XmssPrivateKey::generate(4)?;
+//! let public = private.public_key();
+//!
+//! let signature = private.sign(b"hello")?;
+//! assert!(public.verify(b"hello", &signature));
+//! assert!(!public.verify(b"hallo", &signature));
+//! assert_eq!(private.remaining(), 15);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 8391]: https://datatracker.ietf.org/doc/html/rfc8391
+
+use std::fmt;
+
+use crate::sha256::Sha256;
+
+pub mod wots;
+pub mod xmss;
+
+/// Bytes of the hashes, the seeds and the values of the chains.
+pub const N: usize = 32;
+
+/// The error type of this module.
+#[derive(Debug, PartialEq)]
+pub enum HashSigError{
+    /// Happens when encoded keys or signatures don't have the size of their parameters.
+    InvalidLength,
+    /// Happens when the height of an XMSS tree isn't from 1 to [MAX_HEIGHT][xmss::MAX_HEIGHT].
+    InvalidHeight,
+    /// Happens when every one-time key of an XMSS private key was used.
+    KeysExhausted,
+}
+
+impl fmt::Display for HashSigError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            HashSigError::InvalidLength => write!(f, "Invalid length of the hash-based key or signature."),
+            HashSigError::InvalidHeight => write!(f, "The height of the tree must be from 1 to {}.", xmss::MAX_HEIGHT),
+            HashSigError::KeysExhausted => write!(f, "Every one-time key of the private key was used, it can't sign anymore."),
+        }
+    }
+}
+
+/// Domains of the hashes, padded to 32 bytes before the key, so every hash has a different purpose, like in RFC 8391.
+#[derive(Clone, Copy)]
+enum Domain{
+    /// F, the step of a chain.
+    Chain = 0,
+    /// H_msg, the digest of a message.
+    Message = 2,
+    /// PRF, the keys and bitmasks of the chains, and the secret values.
+    Prf = 3,
+}
+
+/// SHA256 of the domain, as a 32 bytes number, followed by the key and the data.
+fn hash(domain: Domain, key: &[u8], data: &[u8]) -> [u8; N]{
+    let mut prefix = [0; N];
+    prefix[N - 1] = domain as u8;
+    let mut hasher = Sha256::new();
+    hasher.update(&prefix);
+    hasher.update(key);
+    hasher.update(data);
+    hasher.finalize().get_bytes()
+}
+
+/// Position of a hash: the one-time key, its chain, the step in the chain, and whether it is the key or the bitmask of the step.
+#[derive(Clone, Copy)]
+struct Address{
+    key: u32,
+    chain: u32,
+    step: u32,
+    mask: u32,
+}
+
+impl Address{
+    fn to_bytes(self) -> [u8; 16]{
+        let mut bytes = [0; 16];
+        for (i, word) in [self.key, self.chain, self.step, self.mask].iter().enumerate(){
+            bytes[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        bytes
+    }
+}
+
+fn prf(seed: &[u8; N], address: Address) -> [u8; N]{
+    hash(Domain::Prf, seed, &address.to_bytes())
+}
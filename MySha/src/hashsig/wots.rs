@@ -0,0 +1,168 @@
+//! W-OTS+, the [Winternitz one-time signature] of [RFC 8391], with n = 32 bytes and w = 16.
+//!
+//! The private key is [LEN] random values, and the public key is the end of a chain of w - 1 hashes from every one of them.
+//! A 32 bytes digest is signed as 64 digits in base 16, followed by 3 digits of their checksum, and a digit d is signed
+//! by revealing the value d steps down its chain. The verifier hashes the rest of the way, w - 1 - d steps, to the public key.
+//!
+//! Anyone can hash further down a chain, which would sign a larger digit, but then a digit of the checksum gets smaller,
+//! and that needs going back up a chain. But two signatures with the same key reveal two points of many chains,
+//! and the digits between them can be forged, so a key must only sign once, and [sign][WotsPrivateKey::sign] takes it by value.
+//!
+//! Every hash of a chain is keyed and masked with values derived from the public seed and its [address],
+//! so the keys of different one-time keys of an [xmss](super::xmss) tree don't share any hash.
+//!
+//! # Examples
+//! ```
+//! use mysha::hashsig::wots::WotsPrivateKey;
+//!
+//! let private = WotsPrivateKey::generate(0);
+//! let public = private.public_key();
+//!
+//! let digest = [7; 32];
+//! let signature = private.sign(&digest);
+//! assert!(public.verify(&digest, &signature));
+//! assert!(!public.verify(&[8; 32], &signature));
+//! ```
+//!
+//! [Winternitz one-time signature]: https://datatracker.ietf.org/doc/html/rfc8391#section-3.1
+//! [address]: https://datatracker.ietf.org/doc/html/rfc8391#section-2.5
+
+use super::{hash, prf, Address, Domain, HashSigError, N};
+use crate::rand;
+
+/// The Winternitz parameter, the length of the chains plus one, and the base of the digits.
+pub const W: u32 = 16;
+/// Digits of the digest, 4 bits each.
+pub const LEN_1: usize = 2 * N;
+/// Digits of the checksum, which is at most 64 * 15 = 960, less than 16^3.
+pub const LEN_2: usize = 3;
+/// Number of chains, the values of keys and signatures.
+pub const LEN: usize = LEN_1 + LEN_2;
+
+/// The digits of the digest in base w, followed by the ones of the checksum.
+fn digits(digest: &[u8; N]) -> [u32; LEN]{
+    let mut digits = [0; LEN];
+    for (i, byte) in digest.iter().enumerate(){
+        digits[2 * i] = (byte >> 4) as u32;
+        digits[2 * i + 1] = (byte & 0xf) as u32;
+    }
+    let checksum: u32 = digits[..LEN_1].iter().map(|digit| W - 1 - digit).sum();
+    for i in 0..LEN_2{
+        digits[LEN_1 + i] = (checksum >> (4 * (LEN_2 - 1 - i))) & 0xf;
+    }
+    digits
+}
+
+/// Hashes the value down the chain, from the step start, the given number of steps.
+fn chain(value: &[u8; N], start: u32, steps: u32, public_seed: &[u8; N], key: u32, chain: u32) -> [u8; N]{
+    let mut value = *value;
+    for step in start..start + steps{
+        let address = Address{key, chain, step, mask: 0};
+        let step_key = prf(public_seed, address);
+        let bitmask = prf(public_seed, Address{mask: 1, ..address});
+        let masked: Vec<u8> = value.iter().zip(bitmask).map(|(a, b)| a ^ b).collect();
+        value = hash(Domain::Chain, &step_key, &masked);
+    }
+    value
+}
+
+/// A W-OTS+ private key, that signs a single digest.
+///
+/// Its values are derived from the secret seed and the index of the key, so a [xmss](super::xmss) private key only keeps the seeds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WotsPrivateKey{
+    secret_seed: [u8; N],
+    public_seed: [u8; N],
+    index: u32,
+}
+
+/// A W-OTS+ public key, the ends of the chains.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WotsPublicKey{
+    public_seed: [u8; N],
+    index: u32,
+    ends: Vec<[u8; N]>,
+}
+
+/// A W-OTS+ signature, a value of every chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WotsSignature{
+    values: Vec<[u8; N]>,
+}
+
+impl WotsPrivateKey{
+    /// Creates the key with the index from the seeds. The secret seed must be secret and random,
+    /// and the same seeds with the same index always give the same key.
+    pub fn new(secret_seed: [u8; N], public_seed: [u8; N], index: u32) -> WotsPrivateKey{
+        WotsPrivateKey{secret_seed, public_seed, index}
+    }
+
+    /// Creates a key with random seeds.
+    pub fn generate(index: u32) -> WotsPrivateKey{
+        let mut secret_seed = [0; N];
+        let mut public_seed = [0; N];
+        rand::fill_random(&mut secret_seed);
+        rand::fill_random(&mut public_seed);
+        WotsPrivateKey::new(secret_seed, public_seed, index)
+    }
+
+    /// The secret value at the start of the chain.
+    fn start(&self, chain: u32) -> [u8; N]{
+        // the secret seed is never a key of the chains, so these never collide with their keys or bitmasks
+        prf(&self.secret_seed, Address{key: self.index, chain, step: 0, mask: 0})
+    }
+
+    /// Returns the public key, the ends of the chains.
+    pub fn public_key(&self) -> WotsPublicKey{
+        let ends = (0..LEN as u32).map(|i| chain(&self.start(i), 0, W - 1, &self.public_seed, self.index, i)).collect();
+        WotsPublicKey{public_seed: self.public_seed, index: self.index, ends}
+    }
+
+    /// Signs the digest, consuming the key, which must never sign again.
+    pub fn sign(self, digest: &[u8; N]) -> WotsSignature{
+        let values = digits(digest).iter().enumerate()
+            .map(|(i, digit)| chain(&self.start(i as u32), 0, *digit, &self.public_seed, self.index, i as u32))
+            .collect();
+        WotsSignature{values}
+    }
+}
+
+impl WotsPublicKey{
+    /// Returns the ends of the chains, one after the other, [LEN] * [N] bytes.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        self.ends.concat()
+    }
+
+    /// Checks that the digest was signed by the private key of this public key.
+    pub fn verify(&self, digest: &[u8; N], signature: &WotsSignature) -> bool{
+        signature.public_key(digest, &self.public_seed, self.index) == *self
+    }
+}
+
+impl WotsSignature{
+    /// Hashes the values of the signature to the ends of the chains, the public key of the private key that signed the digest.
+    ///
+    /// Any signature gives a public key, which must be compared with the expected one, or be in the expected tree.
+    pub fn public_key(&self, digest: &[u8; N], public_seed: &[u8; N], index: u32) -> WotsPublicKey{
+        let ends = digits(digest).iter().zip(&self.values).enumerate()
+            .map(|(i, (digit, value))| chain(value, *digit, W - 1 - digit, public_seed, index, i as u32))
+            .collect();
+        WotsPublicKey{public_seed: *public_seed, index, ends}
+    }
+
+    /// Returns the values, one after the other, [LEN] * [N] bytes.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        self.values.concat()
+    }
+
+    /// Reads the values of a signature.
+    ///
+    /// # Errors
+    /// Returns [HashSigError::InvalidLength] if there aren't [LEN] * [N] bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<WotsSignature, HashSigError>{
+        if bytes.len() != LEN * N{
+            return Err(HashSigError::InvalidLength);
+        }
+        Ok(WotsSignature{values: bytes.chunks(N).map(|value| value.try_into().unwrap()).collect()})
+    }
+}
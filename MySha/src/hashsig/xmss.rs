@@ -0,0 +1,237 @@
+//! A simplified [XMSS], a stateful many-time signature from [W-OTS+](super::wots) one-time keys and a [Merkle tree](crate::merkle).
+//!
+//! A private key of height h has 2^h one-time keys, derived from its secret seed, and the hashes of their public keys are
+//! the leaves of a Merkle tree, whose root is the public key. A signature is the index of the one-time key used,
+//! its W-OTS+ signature of the digest of the message, and the proof that its public key is in the tree.
+//!
+//! Every one-time key must only sign once, so the private key keeps the index of the next one, and [sign][XmssPrivateKey::sign]
+//! moves it forward. The private key must be saved, with [to_bytes][XmssPrivateKey::to_bytes], after every signature and
+//! before the signature is given out: signing again with an older copy reuses a one-time key.
+//!
+//! Generating the key hashes every chain of every one-time key, about 3000 hashes per key, so heights above 16 take long.
+//!
+//! # Examples
+//! ```
+//! use mysha::hashsig::xmss::{XmssPrivateKey, XmssPublicKey, XmssSignature};
+//! # fn main() -> Result<(), mysha::MyshaError>{
+//! let mut private = XmssPrivateKey::generate(2)?;
+//! let public = XmssPublicKey::from_bytes(&private.public_key().to_bytes())?;
+//!
+//! for message in ["a", "b", "c", "d"]{
+//!     let signature = XmssSignature::from_bytes(&private.sign(message.as_bytes())?.to_bytes())?;
+//!     assert!(public.verify(message.as_bytes(), &signature));
+//! }
+//! // the 4 one-time keys were used
+//! assert!(private.sign(b"e").is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [XMSS]: https://datatracker.ietf.org/doc/html/rfc8391#section-4.1
+
+use super::wots::{self, WotsPrivateKey, WotsSignature};
+use super::{hash, Domain, HashSigError, N};
+use crate::merkle::{self, MerkleTree, Proof, ProofStep, Side};
+use crate::rand;
+use crate::sha256::Hash256;
+
+/// Largest height of a tree, 2^20 signatures.
+pub const MAX_HEIGHT: u8 = 20;
+
+fn check_height(height: u8) -> Result<(), HashSigError>{
+    if height == 0 || height > MAX_HEIGHT{
+        return Err(HashSigError::InvalidHeight);
+    }
+    Ok(())
+}
+
+/// The digest signed by the one-time key, H_msg of the message keyed with the root, the public seed and the index.
+fn digest(root: &Hash256, public_seed: &[u8; N], index: u32, message: &[u8]) -> [u8; N]{
+    hash(Domain::Message, &[&root.get_bytes()[..], public_seed, &index.to_be_bytes()].concat(), message)
+}
+
+/// An XMSS private key, with the index of the next one-time key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmssPrivateKey{
+    height: u8,
+    next: u32,
+    secret_seed: [u8; N],
+    public_seed: [u8; N],
+    tree: MerkleTree,
+}
+
+/// An XMSS public key, the root of the tree and the public seed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmssPublicKey{
+    height: u8,
+    public_seed: [u8; N],
+    root: Hash256,
+}
+
+/// An XMSS signature, from [XmssPrivateKey::sign].
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmssSignature{
+    index: u32,
+    wots: WotsSignature,
+    path: Vec<Hash256>,
+}
+
+impl XmssPrivateKey{
+    /// Creates the key of the height from the seeds, with the next one-time key at the index.
+    ///
+    /// # Errors
+    /// Returns [HashSigError::InvalidHeight] if the height isn't from 1 to [MAX_HEIGHT].
+    pub fn new(height: u8, secret_seed: [u8; N], public_seed: [u8; N], next: u32) -> Result<XmssPrivateKey, HashSigError>{
+        check_height(height)?;
+        let leaves = (0..1_u32 << height)
+            .map(|i| merkle::leaf_hash(&WotsPrivateKey::new(secret_seed, public_seed, i).public_key().to_bytes()))
+            .collect();
+        let tree = MerkleTree::from_hashes(leaves).unwrap();
+        Ok(XmssPrivateKey{height, next, secret_seed, public_seed, tree})
+    }
+
+    /// Creates a key of the height with random seeds, that signs 2^height messages.
+    ///
+    /// # Errors
+    /// Returns [HashSigError::InvalidHeight] if the height isn't from 1 to [MAX_HEIGHT].
+    pub fn generate(height: u8) -> Result<XmssPrivateKey, HashSigError>{
+        let mut secret_seed = [0; N];
+        let mut public_seed = [0; N];
+        rand::fill_random(&mut secret_seed);
+        rand::fill_random(&mut public_seed);
+        XmssPrivateKey::new(height, secret_seed, public_seed, 0)
+    }
+
+    /// Returns the public key, the root of the tree.
+    pub fn public_key(&self) -> XmssPublicKey{
+        XmssPublicKey{height: self.height, public_seed: self.public_seed, root: self.tree.root().clone()}
+    }
+
+    /// Returns the index of the next one-time key.
+    pub fn next_index(&self) -> u32{
+        self.next
+    }
+
+    /// Returns the number of messages the key can still sign.
+    pub fn remaining(&self) -> u32{
+        (1_u32 << self.height).saturating_sub(self.next)
+    }
+
+    /// Signs the message with the next one-time key, and moves to the one after it.
+    ///
+    /// # Errors
+    /// Returns [HashSigError::KeysExhausted] if every one-time key was used.
+    pub fn sign(&mut self, message: &[u8]) -> Result<XmssSignature, HashSigError>{
+        if self.remaining() == 0{
+            return Err(HashSigError::KeysExhausted);
+        }
+        let index = self.next;
+        self.next += 1;
+
+        let digest = digest(self.tree.root(), &self.public_seed, index, message);
+        let wots = WotsPrivateKey::new(self.secret_seed, self.public_seed, index).sign(&digest);
+        let path = self.tree.prove(index as usize).unwrap().path.into_iter().map(|step| step.hash).collect();
+        Ok(XmssSignature{index, wots, path})
+    }
+
+    /// Encodes the key as the height, the index of the next one-time key in 4 bytes big endian, the secret seed and the public seed.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        [&[self.height][..], &self.next.to_be_bytes(), &self.secret_seed, &self.public_seed].concat()
+    }
+
+    /// Reads a key encoded by [to_bytes][XmssPrivateKey::to_bytes], building its tree again.
+    ///
+    /// # Errors
+    /// Returns [HashSigError::InvalidLength] if there aren't 1 + 4 + 2 * [N] bytes,
+    /// and [HashSigError::InvalidHeight] if the height isn't from 1 to [MAX_HEIGHT].
+    pub fn from_bytes(bytes: &[u8]) -> Result<XmssPrivateKey, HashSigError>{
+        if bytes.len() != 1 + 4 + 2 * N{
+            return Err(HashSigError::InvalidLength);
+        }
+        let next = u32::from_be_bytes(bytes[1..5].try_into().unwrap());
+        XmssPrivateKey::new(bytes[0], bytes[5..5 + N].try_into().unwrap(), bytes[5 + N..].try_into().unwrap(), next)
+    }
+}
+
+impl XmssPublicKey{
+    /// Returns the height of the tree.
+    pub fn get_height(&self) -> u8{
+        self.height
+    }
+
+    /// Returns the root of the tree.
+    pub fn get_root(&self) -> &Hash256{
+        &self.root
+    }
+
+    /// Checks that the message was signed by the private key of this public key.
+    pub fn verify(&self, message: &[u8], signature: &XmssSignature) -> bool{
+        if signature.path.len() != self.height as usize || signature.index >> self.height != 0{
+            return false;
+        }
+        let digest = digest(&self.root, &self.public_seed, signature.index, message);
+        let leaf = signature.wots.public_key(&digest, &self.public_seed, signature.index).to_bytes();
+
+        // the tree is full, so the bits of the index say on which side every sibling is
+        let path = signature.path.iter().enumerate().map(|(level, hash)|{
+            let side = if signature.index >> level & 1 == 1 {Side::Left} else {Side::Right};
+            ProofStep{side, hash: hash.clone()}
+        }).collect();
+        Proof{index: signature.index as usize, path}.verify(&leaf, &self.root)
+    }
+
+    /// Encodes the key as the height, the public seed and the root.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        [&[self.height][..], &self.public_seed, &self.root.get_bytes()].concat()
+    }
+
+    /// Reads a key encoded by [to_bytes][XmssPublicKey::to_bytes].
+    ///
+    /// # Errors
+    /// Returns [HashSigError::InvalidLength] if there aren't 1 + 2 * [N] bytes,
+    /// and [HashSigError::InvalidHeight] if the height isn't from 1 to [MAX_HEIGHT].
+    pub fn from_bytes(bytes: &[u8]) -> Result<XmssPublicKey, HashSigError>{
+        if bytes.len() != 1 + 2 * N{
+            return Err(HashSigError::InvalidLength);
+        }
+        check_height(bytes[0])?;
+        let root: [u8; N] = bytes[1 + N..].try_into().unwrap();
+        Ok(XmssPublicKey{height: bytes[0], public_seed: bytes[1..1 + N].try_into().unwrap(), root: Hash256::from(root)})
+    }
+}
+
+impl XmssSignature{
+    /// Returns the index of the one-time key that signed.
+    pub fn get_index(&self) -> u32{
+        self.index
+    }
+
+    /// Encodes the signature as the index in 4 bytes big endian, the W-OTS+ signature, and the siblings of the path, from the leaf up.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        let mut bytes = self.index.to_be_bytes().to_vec();
+        bytes.extend(self.wots.to_bytes());
+        for hash in &self.path{
+            bytes.extend(hash.get_bytes());
+        }
+        bytes
+    }
+
+    /// Reads a signature encoded by [to_bytes][XmssSignature::to_bytes], of a tree of any height.
+    ///
+    /// # Errors
+    /// Returns [HashSigError::InvalidLength] if the bytes aren't the ones of a signature of a height from 1 to [MAX_HEIGHT].
+    pub fn from_bytes(bytes: &[u8]) -> Result<XmssSignature, HashSigError>{
+        let size = 4 + wots::LEN * N;
+        if bytes.len() < size || !(bytes.len() - size).is_multiple_of(N){
+            return Err(HashSigError::InvalidLength);
+        }
+        let height = (bytes.len() - size) / N;
+        if height == 0 || height > MAX_HEIGHT as usize{
+            return Err(HashSigError::InvalidLength);
+        }
+        let index = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+        let wots = WotsSignature::from_bytes(&bytes[4..size])?;
+        let path = bytes[size..].chunks(N).map(|hash| Hash256::from(<[u8; N]>::try_from(hash).unwrap())).collect();
+        Ok(XmssSignature{index, wots, path})
+    }
+}
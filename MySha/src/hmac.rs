@@ -0,0 +1,105 @@
+//! Module for [HMAC], message authentication codes built from sha256.
+//!
+//! A hash alone can't authenticate a message, since anyone can hash a changed message,
+//! and hashing `key || message` lets the message be extended without the key, because of how sha256 pads.
+//! HMAC hashes the message with the key twice, `H((key ^ opad) || H((key ^ ipad) || message))`, which is safe,
+//! and is the building block of [PBKDF2](crate::kdf::pbkdf2) and other key derivation functions.
+//!
+//! # Examples
+//! ```
+//! use mysha::hmac;
+//! use mysha::encoding::hex;
+//!
+//! let tag = hmac::hmac_sha256(b"key", b"The quick brown fox jumps over the lazy dog");
+//! assert_eq!(hex::encode(&tag), "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8");
+//!
+//! assert!(hmac::verify(b"key", b"The quick brown fox jumps over the lazy dog", &tag));
+//! ```
+//!
+//! [HMAC]: https://datatracker.ietf.org/doc/html/rfc2104
+
+use crate::digest::Digest;
+use crate::sha256::Sha256;
+
+/// Size of the blocks of sha256, keys longer than it are hashed first.
+pub const BLOCK_SIZE: usize = 64;
+/// Size of the tag, in bytes.
+pub const TAG_SIZE: usize = 32;
+
+/// HMAC with the key already processed, to compute the tags of many messages with the same key.
+///
+/// The hashers of the padded key are kept, so every tag only hashes the message and the inner hash.
+#[derive(Debug, Clone)]
+pub struct Hmac{
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl Hmac{
+    /// Prepares the HMAC with the key, that can have any length.
+    pub fn new(key: &[u8]) -> Hmac{
+        let mut padded = [0; BLOCK_SIZE];
+        if key.len() > BLOCK_SIZE{
+            padded[..32].copy_from_slice(&Sha256::digest(key));
+        }else{
+            padded[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Sha256::new();
+        inner.update(&padded.map(|b| b ^ 0x36));
+        let mut outer = Sha256::new();
+        outer.update(&padded.map(|b| b ^ 0x5c));
+        Hmac{inner, outer}
+    }
+
+    /// Computes the tag of the message.
+    pub fn tag(&self, message: &[u8]) -> [u8; TAG_SIZE]{
+        let mut inner = self.inner.clone();
+        inner.update(message);
+        let mut outer = self.outer.clone();
+        outer.update(&inner.finalize_bytes());
+        outer.finalize_bytes()
+    }
+}
+
+/// Computes the HMAC-SHA256 tag of the message with the key.
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; TAG_SIZE]{
+    Hmac::new(key).tag(message)
+}
+
+/// Computes the HMAC tag of the message with the key and any hash function with blocks of 64 bytes,
+/// like [Sha1][crate::sha1::Sha1] for HOTP and [Sha256], whose tag is the same as [hmac_sha256].
+///
+/// # Examples
+/// ```
+/// use mysha::{hmac, sha256::Sha256};
+///
+/// assert_eq!(hmac::hmac_digest::<Sha256>(b"key", b"message"), hmac::hmac_sha256(b"key", b"message"));
+/// ```
+pub fn hmac_digest<D: Digest>(key: &[u8], message: &[u8]) -> Vec<u8>{
+    let mut padded = [0; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE{
+        let hash = D::hash(key);
+        padded[..hash.len()].copy_from_slice(&hash);
+    }else{
+        padded[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = D::new();
+    inner.update(&padded.map(|b| b ^ 0x36));
+    inner.update(message);
+    let mut outer = D::new();
+    outer.update(&padded.map(|b| b ^ 0x5c));
+    outer.update(&inner.finish());
+    outer.finish()
+}
+
+/// Checks if the tag is the tag of the message, comparing every byte, so the time taken doesn't tell how many bytes matched.
+pub fn verify(key: &[u8], message: &[u8], tag: &[u8]) -> bool{
+    constant_time_eq(&hmac_sha256(key, message), tag)
+}
+
+/// Compares every byte of the slices, taking the same time wherever they differ.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool{
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
@@ -0,0 +1,285 @@
+//! Module for [JSON web tokens], signed with HS256 or ES256K.
+//!
+//! A token is three base64url parts joined by dots: a json header, with the algorithm in `alg`, the json claims,
+//! and the signature of the first two parts, as they are written. The claims are only signed, not encrypted,
+//! anyone can read them, but only the holder of the key can change them.
+//! - HS256 is the [HMAC](crate::hmac) of the parts with a secret shared by who signs and who verifies;
+//! - ES256K is an ECDSA signature over secp256k1 with sha256, [RFC 8812], r and s as 32 bytes each,
+//!   so anyone with the public key can verify it, but only the private key signs.
+//!
+//! [verify] only accepts the algorithm of the key it is given, whatever the header says, so a token signed with
+//! `none`, or with HS256 using a public key as the secret, is rejected. The `exp` and `nbf` claims are checked against
+//! the time given.
+//!
+//! # Examples
+//! ```
+//! use mysha::jwt::{self, SigningKey, VerifyingKey};
+//! use serde_json::json;
+//! # fn main() -> Result<(), jwt::JwtError>{
+//! let secret = b"a secret only the server knows";
+//! let token = jwt::sign(&json!({"sub": "alice", "exp": 1700000000}), SigningKey::Secret(secret))?;
+//!
+//! let verified = jwt::verify(&token, VerifyingKey::Secret(secret), 1600000000)?;
+//! assert_eq!(verified.claims["sub"], "alice");
+//!
+//! assert_eq!(jwt::verify(&token, VerifyingKey::Secret(b"another secret"), 1600000000), Err(jwt::JwtError::InvalidSignature));
+//! assert_eq!(jwt::verify(&token, VerifyingKey::Secret(secret), 1800000000), Err(jwt::JwtError::Expired));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [JSON web tokens]: https://datatracker.ietf.org/doc/html/rfc7519
+//! [RFC 8812]: https://datatracker.ietf.org/doc/html/rfc8812#section-3.2
+
+use std::fmt;
+
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+
+use crate::ecc::{Curve, PrivKey, PubKey, Signature};
+use crate::encoding::base64::{self, Alphabet};
+use crate::hmac;
+use crate::sha256::Sha256;
+
+/// Size of r and s in ES256K signatures.
+const SCALAR_SIZE: usize = 32;
+
+/// The error type of this module.
+#[derive(Debug, PartialEq)]
+pub enum JwtError{
+    /// Happens when the token isn't three base64url parts separated by dots.
+    InvalidFormat,
+    /// Happens when the header or the claims aren't a json object, or a time claim isn't a number.
+    InvalidJson,
+    /// Happens when the algorithm of the header isn't the one of the key, like `none`, or HS256 for a public key.
+    UnsupportedAlgorithm,
+    /// Happens when the key of ES256K isn't on secp256k1.
+    InvalidKey,
+    /// Happens when the signature isn't the one of the header and claims with the key.
+    InvalidSignature,
+    /// Happens when the time is at or after the `exp` claim.
+    Expired,
+    /// Happens when the time is before the `nbf` claim.
+    NotYetValid,
+}
+
+impl fmt::Display for JwtError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            JwtError::InvalidFormat => write!(f, "The token must be three base64url parts separated by dots."),
+            JwtError::InvalidJson => write!(f, "The header and the claims must be json objects, with numbers as times."),
+            JwtError::UnsupportedAlgorithm => write!(f, "The algorithm of the token isn't the one of the key."),
+            JwtError::InvalidKey => write!(f, "ES256K keys must be on secp256k1."),
+            JwtError::InvalidSignature => write!(f, "The signature isn't valid."),
+            JwtError::Expired => write!(f, "The token has expired."),
+            JwtError::NotYetValid => write!(f, "The token isn't valid yet."),
+        }
+    }
+}
+
+/// Algorithms of the `alg` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm{
+    /// HMAC-SHA256
+    HS256,
+    /// ECDSA over secp256k1 with sha256
+    ES256K,
+}
+
+impl Algorithm{
+    /// The name written in the header.
+    pub fn name(&self) -> &'static str{
+        match self{
+            Algorithm::HS256 => "HS256",
+            Algorithm::ES256K => "ES256K",
+        }
+    }
+}
+
+/// The key tokens are signed with, which sets the algorithm.
+#[derive(Debug, Clone, Copy)]
+pub enum SigningKey<'a>{
+    /// The secret of HS256.
+    Secret(&'a [u8]),
+    /// The private key of ES256K, on secp256k1.
+    Private(&'a PrivKey),
+}
+
+impl SigningKey<'_>{
+    /// The algorithm of the key.
+    pub fn algorithm(&self) -> Algorithm{
+        match self{
+            SigningKey::Secret(_) => Algorithm::HS256,
+            SigningKey::Private(_) => Algorithm::ES256K,
+        }
+    }
+}
+
+/// The key tokens are verified with, which sets the only algorithm accepted.
+#[derive(Debug, Clone, Copy)]
+pub enum VerifyingKey<'a>{
+    /// The secret of HS256.
+    Secret(&'a [u8]),
+    /// The public key of ES256K, on secp256k1.
+    Public(&'a PubKey),
+}
+
+impl VerifyingKey<'_>{
+    /// The algorithm of the key.
+    pub fn algorithm(&self) -> Algorithm{
+        match self{
+            VerifyingKey::Secret(_) => Algorithm::HS256,
+            VerifyingKey::Public(_) => Algorithm::ES256K,
+        }
+    }
+}
+
+/// The header and claims of a token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token{
+    /// The json object of the header, with `alg`.
+    pub header: Value,
+    /// The json object of the claims.
+    pub claims: Value,
+}
+
+/// Signs the claims, a json object, with the key, returning the token.
+///
+/// # Examples
+/// ```
+/// use mysha::{ecc::{Curve, KeyPair}, jwt::{self, SigningKey, VerifyingKey}};
+/// use serde_json::json;
+/// # fn main() -> Result<(), mysha::MyshaError>{
+/// let key_pair = KeyPair::new(1234_u32, Curve::secp256k1())?;
+/// let token = jwt::sign(&json!({"sub": "alice"}), SigningKey::Private(&key_pair.private()))?;
+///
+/// assert!(token.starts_with("eyJhbGciOiJFUzI1NksiLCJ0eXAiOiJKV1QifQ."));
+/// assert!(jwt::verify(&token, VerifyingKey::Public(&key_pair.public()), 0).is_ok());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [JwtError::InvalidJson] if the claims aren't a json object, and [JwtError::InvalidKey] if a private key isn't on secp256k1.
+pub fn sign(claims: &Value, key: SigningKey) -> Result<String, JwtError>{
+    if !claims.is_object(){
+        return Err(JwtError::InvalidJson);
+    }
+    let header = json!({"alg": key.algorithm().name(), "typ": "JWT"});
+    let input = format!("{}.{}", encode_json(&header), encode_json(claims));
+    let signature = match key{
+        SigningKey::Secret(secret) => hmac::hmac_sha256(secret, input.as_bytes()).to_vec(),
+        SigningKey::Private(private) => {
+            if *private.get_curve() != Curve::secp256k1(){
+                return Err(JwtError::InvalidKey);
+            }
+            let signature = private.sign_digest::<Sha256>(input.as_bytes()).map_err(|_| JwtError::InvalidKey)?;
+            [signature.get_r(), signature.get_s()].iter().flat_map(|number| {
+                let bytes = number.to_bytes_be();
+                let mut padded = vec![0; SCALAR_SIZE - bytes.len()];
+                padded.extend(bytes);
+                padded
+            }).collect()
+        },
+    };
+    Ok(format!("{}.{}", input, base64::encode(&signature, Alphabet::UrlSafe)))
+}
+
+/// Verifies the signature of the token with the key, and that it is valid at the time, in seconds since the unix epoch,
+/// returning its header and claims.
+///
+/// # Examples
+/// ```
+/// use mysha::jwt::{self, VerifyingKey};
+/// use mysha::encoding::base64::{self, Alphabet};
+/// # fn main() -> Result<(), mysha::MyshaError>{
+/// // the example of RFC 7515, with line breaks in the json
+/// let token = "eyJ0eXAiOiJKV1QiLA0KICJhbGciOiJIUzI1NiJ9.\
+///     eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ.\
+///     dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+/// let secret = base64::decode("AyM1SysPpbyDfgZld3umj1qzKObwVMkoqQ-EstJQLr_T-1qS0gZH75aKtMN3Yj0iPS4hcgUuTwjAzZr1Z9CAow", Alphabet::UrlSafe)?;
+///
+/// let verified = jwt::verify(token, VerifyingKey::Secret(&secret), 1300819379)?;
+/// assert_eq!(verified.claims["iss"], "joe");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [JwtError::UnsupportedAlgorithm] if the algorithm of the header isn't the one of the key,
+/// [JwtError::InvalidSignature] if the signature doesn't match, [JwtError::Expired] or [JwtError::NotYetValid] if the
+/// token isn't valid at the time, and like [decode_unverified] if the token can't be read.
+pub fn verify(token: &str, key: VerifyingKey, time: u64) -> Result<Token, JwtError>{
+    let decoded = decode_unverified(token)?;
+    if decoded.header["alg"] != key.algorithm().name(){
+        return Err(JwtError::UnsupportedAlgorithm);
+    }
+    let (input, signature) = token.rsplit_once('.').ok_or(JwtError::InvalidFormat)?;
+    let signature = base64::decode(signature, Alphabet::UrlSafe).map_err(|_| JwtError::InvalidFormat)?;
+
+    let valid = match key{
+        VerifyingKey::Secret(secret) => hmac::verify(secret, input.as_bytes(), &signature),
+        VerifyingKey::Public(public) => {
+            let curve = public.get_curve().clone();
+            if curve != Curve::secp256k1(){
+                return Err(JwtError::InvalidKey);
+            }
+            if signature.len() != 2 * SCALAR_SIZE{
+                return Err(JwtError::InvalidSignature);
+            }
+            let (r, s) = signature.split_at(SCALAR_SIZE);
+            Signature::new(BigUint::from_bytes_be(r), BigUint::from_bytes_be(s), curve, public.get_public().clone())
+                .is_ok_and(|signature| signature.verify_digest::<Sha256>(input.as_bytes()).unwrap_or(false))
+        },
+    };
+    if !valid{
+        return Err(JwtError::InvalidSignature);
+    }
+
+    if let Some(expiration) = time_claim(&decoded.claims, "exp")?{
+        if time >= expiration{
+            return Err(JwtError::Expired);
+        }
+    }
+    if let Some(not_before) = time_claim(&decoded.claims, "nbf")?{
+        if time < not_before{
+            return Err(JwtError::NotYetValid);
+        }
+    }
+    Ok(decoded)
+}
+
+/// Reads the header and claims of the token, without checking the signature, so they can't be trusted.
+///
+/// # Errors
+/// Returns [JwtError::InvalidFormat] if the token isn't three base64url parts separated by dots,
+/// and [JwtError::InvalidJson] if the header or the claims aren't json objects.
+pub fn decode_unverified(token: &str) -> Result<Token, JwtError>{
+    let parts: Vec<&str> = token.split('.').collect();
+    let [header, claims, _] = parts[..] else {
+        return Err(JwtError::InvalidFormat);
+    };
+    Ok(Token{header: decode_json(header)?, claims: decode_json(claims)?})
+}
+
+fn encode_json(value: &Value) -> String{
+    base64::encode(value.to_string().as_bytes(), Alphabet::UrlSafe)
+}
+
+fn decode_json(part: &str) -> Result<Value, JwtError>{
+    let bytes = base64::decode(part, Alphabet::UrlSafe).map_err(|_| JwtError::InvalidFormat)?;
+    let value: Value = serde_json::from_slice(&bytes).map_err(|_| JwtError::InvalidJson)?;
+    if !value.is_object(){
+        return Err(JwtError::InvalidJson);
+    }
+    Ok(value)
+}
+
+/// The claim as seconds since the unix epoch, if the claims have it.
+fn time_claim(claims: &Value, name: &str) -> Result<Option<u64>, JwtError>{
+    match claims.get(name){
+        None => Ok(None),
+        // times can have fractions of seconds, the whole seconds are enough here
+        Some(value) => value.as_f64().filter(|time| *time >= 0.0).map(|time| Some(time as u64)).ok_or(JwtError::InvalidJson),
+    }
+}
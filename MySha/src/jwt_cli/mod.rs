@@ -0,0 +1,111 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::{Args, Subcommand};
+use serde_json::Value;
+
+use mysha::jwt::{self, SigningKey, VerifyingKey};
+
+use crate::ecc_cli::{read_private_key, read_public_key};
+use crate::encode_cli::get_text;
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct JwtArgs{
+    #[command(subcommand)]
+    subcommand: SubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommand{
+    /// Signs json claims as a token, with HS256 and a secret, or ES256K and an ecc private key
+    Sign(SignArgs),
+    /// Verifies a token and prints its claims, exits with 1 if it isn't valid
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Debug)]
+struct SignArgs{
+    /// json object of the claims, read from stdin if not provided
+    claims: Option<String>,
+
+    /// secret of HS256
+    #[arg(short, long, required_unless_present = "private", conflicts_with = "private")]
+    secret: Option<String>,
+
+    /// ecc toml file with the private key of ES256K, on secp256k1
+    #[arg(short, long)]
+    private: Option<String>,
+
+    /// sets the exp claim to this many seconds from now
+    #[arg(short, long)]
+    expires_in: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs{
+    /// token, read from stdin if not provided
+    token: Option<String>,
+
+    /// secret of HS256
+    #[arg(short, long, required_unless_present = "public", conflicts_with = "public")]
+    secret: Option<String>,
+
+    /// ecc toml file with the public key of ES256K, on secp256k1, or the private key it is derived from
+    #[arg(short, long)]
+    public: Option<String>,
+
+    /// unix time the exp and nbf claims are checked against, in seconds, instead of now
+    #[arg(long)]
+    time: Option<u64>,
+
+    /// Doesn't print anything, the result is only given by the exit status
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+fn now() -> u64{
+    SystemTime::now().duration_since(UNIX_EPOCH).exit("The clock is before 1970.").as_secs()
+}
+
+pub fn json_web_token(args: JwtArgs){
+    match args.subcommand{
+        SubCommand::Sign(args) => {
+            let mut claims: Value = serde_json::from_str(&get_text(args.claims)).exit("Invalid json claims.");
+            if let Some(seconds) = args.expires_in{
+                let claims = claims.as_object_mut().exit("The claims must be a json object.");
+                claims.insert(String::from("exp"), Value::from(now() + seconds));
+            }
+
+            let token = match (args.secret, args.private){
+                (Some(secret), _) => jwt::sign(&claims, SigningKey::Secret(secret.as_bytes())),
+                (None, Some(path)) => jwt::sign(&claims, SigningKey::Private(&read_private_key(&path))),
+                (None, None) => unreachable!(),
+            }.exit("Can't sign the token.");
+            println!("{}", token);
+        },
+        SubCommand::Verify(args) => {
+            let token = get_text(args.token);
+            let time = args.time.unwrap_or_else(now);
+
+            let verified = match (args.secret, args.public){
+                (Some(secret), _) => jwt::verify(&token, VerifyingKey::Secret(secret.as_bytes()), time),
+                (None, Some(path)) => jwt::verify(&token, VerifyingKey::Public(&read_public_key(&path)), time),
+                (None, None) => unreachable!(),
+            };
+            match verified{
+                Ok(verified) => if ! args.quiet{
+                    println!("Token is VALID");
+                    println!("header: {}", verified.header);
+                    println!("claims: {}", serde_json::to_string_pretty(&verified.claims).unwrap());
+                },
+                Err(e) => {
+                    if ! args.quiet{
+                        println!("Token is NOT valid: {}", e);
+                    }
+                    Failure::Verification.exit();
+                },
+            }
+        },
+    }
+}
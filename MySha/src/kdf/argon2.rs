@@ -0,0 +1,389 @@
+//! [Argon2], the memory-hard password hashing function.
+//!
+//! The password and salt are hashed with [BLAKE2b], and used to fill the memory with blocks of 1024 bytes,
+//! every block computed from the previous one and a block chosen from the ones already filled.
+//! The memory is filled again for every iteration, and the key comes from the last blocks,
+//! so it can't be computed without keeping the whole memory, which is what makes guessing expensive on any hardware.
+//!
+//! The memory is split in lanes, that can be filled in parallel, and the way the other block is chosen gives the variants:
+//! - [Variant::Argon2d] chooses it from the data, the fastest to fill but the choices reveal information through timing.
+//! - [Variant::Argon2i] chooses it from a counter, which doesn't depend on the password.
+//! - [Variant::Argon2id] uses Argon2i for the first half of the first iteration and Argon2d after, it is the recommended one.
+//!
+//! This implementation fills the lanes one after the other, the result is the same as filling them in parallel.
+//!
+//! # Examples
+//! ```
+//! use mysha::kdf::{argon2::{self, Params, Variant}, KdfError};
+//! use mysha::encoding::hex;
+//! # fn main() -> Result<(), KdfError>{
+//! // test vector from RFC 9106
+//! let params = Params{variant: Variant::Argon2id, memory: 32, iterations: 3, parallelism: 4};
+//! let key = argon2::derive_keyed(&[0x01; 32], &[0x02; 16], &[0x03; 8], &[0x04; 12], &params, 32)?;
+//!
+//! assert_eq!(hex::encode(&key), "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Argon2]: https://datatracker.ietf.org/doc/html/rfc9106
+//! [BLAKE2b]: https://datatracker.ietf.org/doc/html/rfc7693
+
+use super::KdfError;
+
+/// Version of Argon2 implemented, 1.3.
+pub const VERSION: u32 = 0x13;
+/// Shortest salt allowed, in bytes.
+pub const MIN_SALT_SIZE: usize = 8;
+
+/// Number of slices every lane is split in, the lanes are synchronized after every slice.
+const SYNC_POINTS: usize = 4;
+/// Number of 64 bit words in a block of 1024 bytes.
+const BLOCK_WORDS: usize = 128;
+
+type Block = [u64; BLOCK_WORDS];
+
+/// How the other block used to compute every block is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant{
+    /// Chosen from the data of the previous block.
+    Argon2d,
+    /// Chosen from a counter, independently of the password.
+    Argon2i,
+    /// Argon2i for the first half of the first iteration, and Argon2d after.
+    Argon2id,
+}
+
+impl Variant{
+    /// Name of the variant in encoded password hashes.
+    pub fn name(&self) -> &'static str{
+        match self{
+            Variant::Argon2d => "argon2d",
+            Variant::Argon2i => "argon2i",
+            Variant::Argon2id => "argon2id",
+        }
+    }
+
+    fn id(&self) -> u32{
+        match self{
+            Variant::Argon2d => 0,
+            Variant::Argon2i => 1,
+            Variant::Argon2id => 2,
+        }
+    }
+}
+
+/// The costs of Argon2.
+///
+/// The default is the minimum recommended by OWASP for passwords, Argon2id with 19 MiB of memory, 2 iterations and 1 lane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params{
+    /// Variant of Argon2.
+    pub variant: Variant,
+    /// Memory used in KiB, at least 8 times the parallelism.
+    pub memory: u32,
+    /// Number of times the memory is filled, at least 1.
+    pub iterations: u32,
+    /// Number of lanes, at least 1.
+    pub parallelism: u32,
+}
+
+impl Default for Params{
+    fn default() -> Self{
+        Params{variant: Variant::Argon2id, memory: 19 * 1024, iterations: 2, parallelism: 1}
+    }
+}
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Mixing function of BLAKE2b.
+fn mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64){
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+fn blake2b_compress(h: &mut [u64; 8], block: &[u8], counter: u64, last: bool){
+    let mut m = [0_u64; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks_exact(8)){
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    let mut v = [0_u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= counter;
+    if last{
+        v[14] = !v[14];
+    }
+    for round in 0..12{
+        let s = SIGMA[round % 10];
+        mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+    for i in 0..8{
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// BLAKE2b hash of the data, of 1 to 64 bytes, without key.
+fn blake2b(data: &[u8], length: usize) -> Vec<u8>{
+    let mut h = IV;
+    h[0] ^= 0x01010000 ^ length as u64;
+
+    let blocks = data.len().div_ceil(128).max(1);
+    for i in 0..blocks{
+        let mut block = [0; 128];
+        let chunk = &data[i * 128..data.len().min((i + 1) * 128)];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let last = i == blocks - 1;
+        blake2b_compress(&mut h, &block, (i * 128 + chunk.len()) as u64, last);
+    }
+
+    h.iter().flat_map(|word| word.to_le_bytes()).take(length).collect()
+}
+
+/// Hash of any length, H' in the specification, chaining BLAKE2b hashes when longer than 64 bytes.
+fn blake2b_long(parts: &[&[u8]], length: usize) -> Vec<u8>{
+    let mut input = (length as u32).to_le_bytes().to_vec();
+    for part in parts{
+        input.extend_from_slice(part);
+    }
+    if length <= 64{
+        return blake2b(&input, length);
+    }
+
+    let r = length.div_ceil(32) - 2;
+    let mut v = blake2b(&input, 64);
+    let mut output = v[..32].to_vec();
+    for _ in 1..r{
+        v = blake2b(&v, 64);
+        output.extend_from_slice(&v[..32]);
+    }
+    output.extend(blake2b(&v, length - 32 * r));
+    output
+}
+
+/// Mixing function of the blocks, BLAKE2b's with a multiplication added, so it is expensive in hardware too.
+fn mix_words(v: &mut Block, a: usize, b: usize, c: usize, d: usize){
+    let multiply = |x: u64, y: u64| 2_u64.wrapping_mul(x & 0xffffffff).wrapping_mul(y & 0xffffffff);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(multiply(v[a], v[b]));
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]).wrapping_add(multiply(v[c], v[d]));
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(multiply(v[a], v[b]));
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]).wrapping_add(multiply(v[c], v[d]));
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// Permutation P of the specification, on the 16 words at the indexes.
+fn permute(block: &mut Block, w: [usize; 16]){
+    mix_words(block, w[0], w[4], w[8], w[12]);
+    mix_words(block, w[1], w[5], w[9], w[13]);
+    mix_words(block, w[2], w[6], w[10], w[14]);
+    mix_words(block, w[3], w[7], w[11], w[15]);
+    mix_words(block, w[0], w[5], w[10], w[15]);
+    mix_words(block, w[1], w[6], w[11], w[12]);
+    mix_words(block, w[2], w[7], w[8], w[13]);
+    mix_words(block, w[3], w[4], w[9], w[14]);
+}
+
+/// Compression function G, the block is seen as 8 by 8 registers of 16 bytes, and permuted by rows and then by columns.
+fn compress(x: &Block, y: &Block) -> Block{
+    let mut r = [0; BLOCK_WORDS];
+    for i in 0..BLOCK_WORDS{
+        r[i] = x[i] ^ y[i];
+    }
+    let mut z = r;
+    for row in 0..8{
+        permute(&mut z, std::array::from_fn(|i| 16 * row + i));
+    }
+    for column in 0..8{
+        permute(&mut z, std::array::from_fn(|i| 2 * column + 16 * (i / 2) + i % 2));
+    }
+    for i in 0..BLOCK_WORDS{
+        z[i] ^= r[i];
+    }
+    z
+}
+
+fn to_block(bytes: &[u8]) -> Block{
+    let mut block = [0; BLOCK_WORDS];
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks_exact(8)){
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    block
+}
+
+/// Position of the block being computed.
+struct Position{
+    pass: usize,
+    slice: usize,
+    lane: usize,
+    index: usize,
+}
+
+/// Memory being filled, with its dimensions.
+struct Memory{
+    blocks: Vec<Block>,
+    lanes: usize,
+    lane_length: usize,
+    segment_length: usize,
+}
+
+impl Memory{
+    /// Column of the block referenced from the position, from the 32 bits of the pseudo-random value.
+    fn reference_column(&self, position: &Position, same_lane: bool, pseudo_random: u32) -> usize{
+        let finished_segments = if position.pass == 0 {position.slice * self.segment_length} else {self.lane_length - self.segment_length};
+        // blocks of the current segment can only be referenced in the same lane, and never the previous one
+        let area = if same_lane{
+            finished_segments + position.index - 1
+        }else{
+            finished_segments - (position.index == 0) as usize
+        } as u64;
+
+        let x = (pseudo_random as u64 * pseudo_random as u64) >> 32;
+        let relative = area - 1 - ((area * x) >> 32);
+        let start = if position.pass == 0 || position.slice == SYNC_POINTS - 1 {0} else {(position.slice + 1) * self.segment_length};
+        (start + relative as usize) % self.lane_length
+    }
+
+    fn fill_segment(&mut self, position: &mut Position, variant: Variant, iterations: u32){
+        let data_independent = variant == Variant::Argon2i || (variant == Variant::Argon2id && position.pass == 0 && position.slice < SYNC_POINTS / 2);
+
+        let mut input = [0; BLOCK_WORDS];
+        let mut addresses = [0; BLOCK_WORDS];
+        let zero = [0; BLOCK_WORDS];
+        let next_addresses = |input: &mut Block, addresses: &mut Block|{
+            input[6] += 1;
+            *addresses = compress(&zero, &compress(&zero, input));
+        };
+        if data_independent{
+            input[..6].copy_from_slice(&[position.pass as u64, position.lane as u64, position.slice as u64,
+                self.blocks.len() as u64, iterations as u64, variant.id() as u64]);
+        }
+
+        let start = if position.pass == 0 && position.slice == 0 {2} else {0};
+        if data_independent && start == 2{
+            next_addresses(&mut input, &mut addresses);
+        }
+
+        for index in start..self.segment_length{
+            position.index = index;
+            let column = position.slice * self.segment_length + index;
+            let current = position.lane * self.lane_length + column;
+            let previous = if column == 0 {current + self.lane_length - 1} else {current - 1};
+
+            let pseudo_random = if data_independent{
+                if index % BLOCK_WORDS == 0{
+                    next_addresses(&mut input, &mut addresses);
+                }
+                addresses[index % BLOCK_WORDS]
+            }else{
+                self.blocks[previous][0]
+            };
+
+            let lane = if position.pass == 0 && position.slice == 0 {position.lane} else {(pseudo_random >> 32) as usize % self.lanes};
+            let reference = lane * self.lane_length + self.reference_column(position, lane == position.lane, pseudo_random as u32);
+
+            let block = compress(&self.blocks[previous], &self.blocks[reference]);
+            if position.pass == 0{
+                self.blocks[current] = block;
+            }else{
+                for (word, new) in self.blocks[current].iter_mut().zip(block){
+                    *word ^= new;
+                }
+            }
+        }
+    }
+}
+
+/// Derives a key of the length from the password and salt.
+///
+/// # Errors
+/// Same as [derive_keyed()].
+pub fn derive(password: &[u8], salt: &[u8], params: &Params, length: usize) -> Result<Vec<u8>, KdfError>{
+    derive_keyed(password, salt, &[], &[], params, length)
+}
+
+/// Derives a key of the length from the password and salt, with a secret key and associated data also mixed in.
+///
+/// The secret can be kept apart from the stored hashes, like in a server's configuration,
+/// so the hashes are useless without it.
+///
+/// # Errors
+/// Returns [KdfError::InvalidParameters] if the iterations or parallelism are zero, or the memory is less than 8 KiB per lane,
+/// [KdfError::InvalidSalt] if the salt is shorter than 8 bytes, and [KdfError::InvalidLength] if the length is less than 4 bytes.
+pub fn derive_keyed(password: &[u8], salt: &[u8], secret: &[u8], data: &[u8], params: &Params, length: usize) -> Result<Vec<u8>, KdfError>{
+    if params.iterations == 0 || params.parallelism == 0 || params.parallelism >= 1 << 24 || params.memory < 8 * params.parallelism{
+        return Err(KdfError::InvalidParameters);
+    }
+    if salt.len() < MIN_SALT_SIZE{
+        return Err(KdfError::InvalidSalt);
+    }
+    if length < 4 || length > u32::MAX as usize{
+        return Err(KdfError::InvalidLength);
+    }
+
+    let le = |n: usize| (n as u32).to_le_bytes();
+    let h0 = blake2b(&[
+        &params.parallelism.to_le_bytes()[..], &le(length), &params.memory.to_le_bytes(), &params.iterations.to_le_bytes(),
+        &VERSION.to_le_bytes(), &params.variant.id().to_le_bytes(),
+        &le(password.len()), password, &le(salt.len()), salt, &le(secret.len()), secret, &le(data.len()), data,
+    ].concat(), 64);
+
+    let lanes = params.parallelism as usize;
+    let segment_length = params.memory as usize / (SYNC_POINTS * lanes);
+    let lane_length = segment_length * SYNC_POINTS;
+    let mut memory = Memory{blocks: vec![[0; BLOCK_WORDS]; lane_length * lanes], lanes, lane_length, segment_length};
+
+    for lane in 0..lanes{
+        for column in 0..2{
+            memory.blocks[lane * lane_length + column] = to_block(&blake2b_long(&[&h0, &le(column), &le(lane)], 1024));
+        }
+    }
+
+    for pass in 0..params.iterations as usize{
+        for slice in 0..SYNC_POINTS{
+            for lane in 0..lanes{
+                memory.fill_segment(&mut Position{pass, slice, lane, index: 0}, params.variant, params.iterations);
+            }
+        }
+    }
+
+    let mut last = memory.blocks[lane_length - 1];
+    for lane in 1..lanes{
+        for (word, other) in last.iter_mut().zip(memory.blocks[lane * lane_length + lane_length - 1]){
+            *word ^= other;
+        }
+    }
+    let bytes: Vec<u8> = last.iter().flat_map(|word| word.to_le_bytes()).collect();
+    Ok(blake2b_long(&[&bytes], length))
+}
@@ -0,0 +1,76 @@
+//! [HKDF] with HMAC-SHA256.
+//!
+//! HKDF derives keys from input key material that is already random, but not uniformly, like a shared secret from ECDH,
+//! or a master key that many keys are derived from. It isn't slow, so it must not be used for passwords.
+//!
+//! - [extract()] concentrates the randomness of the input in a 32 byte pseudorandom key, with an optional salt.
+//! - [expand()] derives keys of any length from it, a different key for every info, which says what the key is for.
+//!
+//! # Examples
+//! ```
+//! use mysha::kdf::{hkdf, KdfError};
+//! # fn main() -> Result<(), KdfError>{
+//! let master = [0x0b; 22];
+//!
+//! let encryption_key = hkdf::derive(&master, b"salt", b"encryption", 32)?;
+//! let signing_key = hkdf::derive(&master, b"salt", b"signing", 32)?;
+//!
+//! assert_ne!(encryption_key, signing_key);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [HKDF]: https://datatracker.ietf.org/doc/html/rfc5869
+
+use super::KdfError;
+use crate::hmac::{self, Hmac, TAG_SIZE};
+
+/// Longest key that can be derived, in bytes.
+pub const MAX_LENGTH: usize = 255 * TAG_SIZE;
+
+/// Extracts a pseudorandom key from the input key material, an empty salt being the same as 32 zero bytes.
+pub fn extract(salt: &[u8], ikm: &[u8]) -> [u8; TAG_SIZE]{
+    hmac::hmac_sha256(salt, ikm)
+}
+
+/// Expands the pseudorandom key to a key of the length, for the info.
+///
+/// # Examples
+/// ```
+/// use mysha::kdf::{hkdf, KdfError};
+/// use mysha::encoding::hex;
+/// # fn main() -> Result<(), KdfError>{
+/// // test case 1 from RFC 5869
+/// let prk = hkdf::extract(&hex::decode("000102030405060708090a0b0c").unwrap(), &[0x0b; 22]);
+/// let key = hkdf::expand(&prk, &hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap(), 42)?;
+///
+/// assert_eq!(hex::encode(&key), "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [KdfError::InvalidLength] if the length is zero or longer than [MAX_LENGTH].
+pub fn expand(prk: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, KdfError>{
+    if length == 0 || length > MAX_LENGTH{
+        return Err(KdfError::InvalidLength);
+    }
+
+    let hmac = Hmac::new(prk);
+    let mut key = Vec::with_capacity(length);
+    let mut block = Vec::new();
+    for i in 1..=length.div_ceil(TAG_SIZE) as u8{
+        block = hmac.tag(&[&block, info, &[i]].concat()).to_vec();
+        key.extend_from_slice(&block);
+    }
+    key.truncate(length);
+    Ok(key)
+}
+
+/// Extracts and expands in one call.
+///
+/// # Errors
+/// Same as [expand()].
+pub fn derive(ikm: &[u8], salt: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, KdfError>{
+    expand(&extract(salt, ikm), info, length)
+}
@@ -0,0 +1,61 @@
+//! Module for deriving keys from passwords and other secrets.
+//!
+//! Passwords are short and guessable, so they can't be used as keys directly, and storing their plain hashes
+//! lets an attacker who gets the hashes test billions of guesses per second.
+//! Key derivation functions mix a random salt into the password, so equal passwords give different keys,
+//! and are slow on purpose, so every guess is expensive.
+//!
+//! - [pbkdf2] repeats HMAC-SHA256 many times, it only costs time.
+//! - [scrypt] and [argon2] also fill a large block of memory, so guessing with GPUs and dedicated hardware isn't much cheaper
+//!   than on a normal computer. Argon2 is the one to use for new passwords.
+//! - [hkdf] is fast, it derives keys from secrets that are already random, like shared secrets or master keys.
+//!
+//! The [password] module stores the result as a standard text, with the algorithm, parameters and salt,
+//! so a password can be verified later with only that text.
+//!
+//! # Examples
+//! ```
+//! use mysha::kdf::{password::{self, Algorithm}, KdfError};
+//! # fn main() -> Result<(), KdfError>{
+//! let hash = password::hash(b"correct horse battery staple", &Algorithm::Pbkdf2{iterations: 1000})?;
+//!
+//! assert!(password::verify(b"correct horse battery staple", &hash.to_string())?);
+//! assert!(!password::verify(b"Tr0ub4dor&3", &hash.to_string())?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+pub mod argon2;
+pub mod hkdf;
+pub mod password;
+pub mod pbkdf2;
+pub mod scrypt;
+
+/// The error type implemented for this module, with all possible key derivation errors.
+#[derive(Debug, PartialEq)]
+pub enum KdfError{
+    /// Happens when a cost parameter, like the iterations or the memory, is out of the range allowed by the algorithm.
+    InvalidParameters,
+    /// Happens when the length of the derived key is zero or too long for the algorithm.
+    InvalidLength,
+    /// Happens when the salt is shorter than the algorithm allows.
+    InvalidSalt,
+    /// Happens when an encoded password hash isn't valid.
+    InvalidHash,
+    /// Happens when an encoded password hash uses an algorithm this module doesn't implement.
+    UnsupportedAlgorithm,
+}
+
+impl fmt::Display for KdfError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            KdfError::InvalidParameters => write!(f, "Invalid parameters for the algorithm."),
+            KdfError::InvalidLength => write!(f, "Invalid length for the derived key."),
+            KdfError::InvalidSalt => write!(f, "Salt too short for the algorithm."),
+            KdfError::InvalidHash => write!(f, "Invalid encoded password hash."),
+            KdfError::UnsupportedAlgorithm => write!(f, "Unsupported password hashing algorithm."),
+        }
+    }
+}
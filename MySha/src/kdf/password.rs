@@ -0,0 +1,224 @@
+//! Password hashes in the [PHC string format].
+//!
+//! The hash of a password is stored with everything needed to verify it, the algorithm, its parameters and the salt,
+//! like `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`, with the salt and hash in base64 without padding.
+//! So the parameters can be raised over time, and old hashes are still verified with the parameters they were made with.
+//!
+//! # Examples
+//! ```
+//! use mysha::kdf::{password::{self, Algorithm, PasswordHash}, argon2::Params, KdfError};
+//! # fn main() -> Result<(), KdfError>{
+//! let params = Params{memory: 64, iterations: 1, ..Default::default()};
+//! let hash = password::hash_with_salt(b"hunter2", b"saltsalt", &Algorithm::Argon2(params))?;
+//!
+//! let encoded = hash.to_string();
+//! assert!(encoded.starts_with("$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$"));
+//! assert_eq!(encoded.parse::<PasswordHash>()?, hash);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A salt is generated by [hash()], or with [generate_salt], and kept in the encoded hash, so it doesn't need to be stored apart.
+//! [Algorithm::Sha256] is a single salted sha256, which is fast to guess, to show the difference a salt makes:
+//! ```
+//! use mysha::kdf::password::{self, Algorithm};
+//! # fn main() -> Result<(), mysha::kdf::KdfError>{
+//! // without a salt, everyone with the same password has the same hash
+//! let alice = password::hash_with_salt(b"hunter2", b"", &Algorithm::Sha256)?;
+//! let bob = password::hash_with_salt(b"hunter2", b"", &Algorithm::Sha256)?;
+//! assert_eq!(alice.hash, bob.hash);
+//! assert!(alice.to_string().starts_with("$sha256$$"));
+//!
+//! // with random salts they are different, and every one must be guessed on its own
+//! let alice = password::hash(b"hunter2", &Algorithm::Sha256)?;
+//! let bob = password::hash(b"hunter2", &Algorithm::Sha256)?;
+//! assert_ne!(alice.hash, bob.hash);
+//! assert!(password::verify(b"hunter2", &alice.to_string())?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [PHC string format]: https://github.com/P-H-C/phc-string-format/blob/master/phc-sf-spec.md
+
+use std::{fmt, str::FromStr};
+
+use super::{argon2::{self, Params, Variant}, pbkdf2, KdfError};
+use crate::encoding::base64::{self, Alphabet};
+use crate::hmac::constant_time_eq;
+use crate::sha256::Sha256;
+
+/// Size of the random salts, in bytes.
+pub const SALT_SIZE: usize = 16;
+/// Size of the hashes, in bytes.
+pub const HASH_SIZE: usize = 32;
+
+/// Algorithm used to hash a password, with its parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Algorithm{
+    /// [PBKDF2](pbkdf2) with HMAC-SHA256, `pbkdf2-sha256` in the encoded hash.
+    Pbkdf2{
+        /// Number of iterations.
+        iterations: u32,
+    },
+    /// [Argon2](argon2), with its variant as the name in the encoded hash.
+    Argon2(Params),
+    /// A single sha256 of the salt followed by the password, `sha256` in the encoded hash.
+    ///
+    /// INSECURE, billions of guesses per second can be tried, it is only here to show what salts do and don't do.
+    /// It is the only algorithm that allows an empty salt.
+    Sha256,
+}
+
+impl Algorithm{
+    /// Derives a key of the length with the algorithm.
+    fn derive(&self, password: &[u8], salt: &[u8], length: usize) -> Result<Vec<u8>, KdfError>{
+        match self{
+            Algorithm::Pbkdf2{iterations} => pbkdf2::derive(password, salt, *iterations, length),
+            Algorithm::Argon2(params) => argon2::derive(password, salt, params, length),
+            Algorithm::Sha256 => {
+                if length != HASH_SIZE{
+                    return Err(KdfError::InvalidLength);
+                }
+                let mut hasher = Sha256::new();
+                hasher.update(salt);
+                hasher.update(password);
+                Ok(hasher.finalize_bytes().to_vec())
+            },
+        }
+    }
+}
+
+/// A password hash, shown and parsed as the encoded text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordHash{
+    /// Algorithm the hash was made with.
+    pub algorithm: Algorithm,
+    /// Salt hashed with the password.
+    pub salt: Vec<u8>,
+    /// The hash itself.
+    pub hash: Vec<u8>,
+}
+
+fn encode_base64(data: &[u8]) -> String{
+    base64::encode(data, Alphabet::Standard).trim_end_matches('=').to_owned()
+}
+
+impl fmt::Display for PasswordHash{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match &self.algorithm{
+            Algorithm::Pbkdf2{iterations} => write!(f, "$pbkdf2-sha256$i={},l={}", iterations, self.hash.len())?,
+            Algorithm::Argon2(params) => write!(f, "${}$v={}$m={},t={},p={}", params.variant.name(), argon2::VERSION,
+                params.memory, params.iterations, params.parallelism)?,
+            Algorithm::Sha256 => write!(f, "$sha256")?,
+        }
+        write!(f, "${}${}", encode_base64(&self.salt), encode_base64(&self.hash))
+    }
+}
+
+/// Values of the parameters, as in `m=19456,t=2,p=1`, in the order of the names.
+fn parse_params<const N: usize>(text: &str, names: [&str; N]) -> Result<[Option<u32>; N], KdfError>{
+    let mut values = [None; N];
+    for param in text.split(','){
+        let (name, value) = param.split_once('=').ok_or(KdfError::InvalidHash)?;
+        let i = names.iter().position(|n| *n == name).ok_or(KdfError::InvalidHash)?;
+        values[i] = Some(value.parse().map_err(|_| KdfError::InvalidHash)?);
+    }
+    Ok(values)
+}
+
+impl FromStr for PasswordHash{
+    type Err = KdfError;
+
+    /// Parses an encoded hash.
+    ///
+    /// # Errors
+    /// Returns [KdfError::UnsupportedAlgorithm] if the algorithm or version isn't implemented,
+    /// and [KdfError::InvalidHash] if the text isn't a valid hash for its algorithm.
+    fn from_str(s: &str) -> Result<Self, Self::Err>{
+        let fields: Vec<&str> = s.split('$').collect();
+        if fields.len() < 4 || !fields[0].is_empty(){
+            return Err(KdfError::InvalidHash);
+        }
+
+        let (algorithm, rest) = match fields[1]{
+            "pbkdf2-sha256" => {
+                let [iterations, _] = parse_params(fields[2], ["i", "l"])?;
+                (Algorithm::Pbkdf2{iterations: iterations.ok_or(KdfError::InvalidHash)?}, &fields[3..])
+            },
+            "argon2d" | "argon2i" | "argon2id" => {
+                let variant = match fields[1]{
+                    "argon2d" => Variant::Argon2d,
+                    "argon2i" => Variant::Argon2i,
+                    _ => Variant::Argon2id,
+                };
+                if fields.len() != 6{
+                    return Err(KdfError::InvalidHash);
+                }
+                if fields[2] != format!("v={}", argon2::VERSION){
+                    return Err(KdfError::UnsupportedAlgorithm);
+                }
+                let [memory, iterations, parallelism] = parse_params(fields[3], ["m", "t", "p"])?;
+                let params = Params{
+                    variant,
+                    memory: memory.ok_or(KdfError::InvalidHash)?,
+                    iterations: iterations.ok_or(KdfError::InvalidHash)?,
+                    parallelism: parallelism.ok_or(KdfError::InvalidHash)?,
+                };
+                (Algorithm::Argon2(params), &fields[4..])
+            },
+            "sha256" => (Algorithm::Sha256, &fields[2..]),
+            _ => return Err(KdfError::UnsupportedAlgorithm),
+        };
+
+        let [salt, hash] = rest else {return Err(KdfError::InvalidHash)};
+        let salt = base64::decode(salt, Alphabet::Standard).map_err(|_| KdfError::InvalidHash)?;
+        let hash = base64::decode(hash, Alphabet::Standard).map_err(|_| KdfError::InvalidHash)?;
+        if (salt.is_empty() && algorithm != Algorithm::Sha256) || hash.is_empty(){
+            return Err(KdfError::InvalidHash);
+        }
+        if algorithm == Algorithm::Sha256 && hash.len() != HASH_SIZE{
+            return Err(KdfError::InvalidHash);
+        }
+        Ok(PasswordHash{algorithm, salt, hash})
+    }
+}
+
+/// Generates a random salt of [SALT_SIZE] bytes, like the ones of [hash()].
+pub fn generate_salt() -> [u8; SALT_SIZE]{
+    let mut salt = [0; SALT_SIZE];
+    crate::rand::fill_random(&mut salt);
+    salt
+}
+
+/// Hashes the password with the algorithm and a random salt.
+///
+/// # Errors
+/// Returns the errors of the algorithm, if its parameters are invalid.
+pub fn hash(password: &[u8], algorithm: &Algorithm) -> Result<PasswordHash, KdfError>{
+    hash_with_salt(password, &generate_salt(), algorithm)
+}
+
+/// Hashes the password with the algorithm and the salt, which should be random and different for every password.
+///
+/// # Errors
+/// Same as [hash()], and [KdfError::InvalidSalt] if the salt is too short for the algorithm, or empty for any algorithm but [Algorithm::Sha256].
+pub fn hash_with_salt(password: &[u8], salt: &[u8], algorithm: &Algorithm) -> Result<PasswordHash, KdfError>{
+    if salt.is_empty() && *algorithm != Algorithm::Sha256{
+        return Err(KdfError::InvalidSalt);
+    }
+    Ok(PasswordHash{
+        algorithm: algorithm.clone(),
+        salt: salt.to_vec(),
+        hash: algorithm.derive(password, salt, HASH_SIZE)?,
+    })
+}
+
+/// Checks if the password matches the encoded hash, hashing it with the same algorithm, parameters and salt.
+///
+/// # Errors
+/// Returns the errors of [PasswordHash::from_str()] if the encoded hash is invalid.
+pub fn verify(password: &[u8], encoded: &str) -> Result<bool, KdfError>{
+    let expected: PasswordHash = encoded.parse()?;
+    let hash = expected.algorithm.derive(password, &expected.salt, expected.hash.len())?;
+    Ok(constant_time_eq(&hash, &expected.hash))
+}
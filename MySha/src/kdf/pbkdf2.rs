@@ -0,0 +1,55 @@
+//! [PBKDF2] with HMAC-SHA256.
+//!
+//! Every 32 byte block of the key is the xor of a chain of HMACs of the salt with the password as key,
+//! repeated for the number of iterations, so every guess of the password costs as many HMACs.
+//! OWASP recommends at least 600000 iterations for passwords.
+//!
+//! # Examples
+//! ```
+//! use mysha::kdf::{pbkdf2, KdfError};
+//! use mysha::encoding::hex;
+//! # fn main() -> Result<(), KdfError>{
+//! let key = pbkdf2::derive(b"password", b"salt", 1, 32)?;
+//!
+//! assert_eq!(hex::encode(&key), "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [PBKDF2]: https://datatracker.ietf.org/doc/html/rfc8018#section-5.2
+
+use super::KdfError;
+use crate::hmac::{Hmac, TAG_SIZE};
+
+/// Iterations recommended by OWASP for passwords.
+pub const RECOMMENDED_ITERATIONS: u32 = 600_000;
+
+/// Derives a key of the length from the password and salt.
+///
+/// # Errors
+/// Returns [KdfError::InvalidParameters] if the iterations are zero,
+/// and [KdfError::InvalidLength] if the length is zero.
+pub fn derive(password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Result<Vec<u8>, KdfError>{
+    if iterations == 0{
+        return Err(KdfError::InvalidParameters);
+    }
+    if length == 0 || length.div_ceil(TAG_SIZE) > u32::MAX as usize{
+        return Err(KdfError::InvalidLength);
+    }
+
+    let hmac = Hmac::new(password);
+    let mut key = Vec::with_capacity(length);
+    for i in 1..=length.div_ceil(TAG_SIZE) as u32{
+        let mut u = hmac.tag(&[salt, &i.to_be_bytes()].concat());
+        let mut block = u;
+        for _ in 1..iterations{
+            u = hmac.tag(&u);
+            for (b, u) in block.iter_mut().zip(u){
+                *b ^= u;
+            }
+        }
+        key.extend_from_slice(&block);
+    }
+    key.truncate(length);
+    Ok(key)
+}
@@ -0,0 +1,130 @@
+//! [scrypt], the first memory-hard key derivation function.
+//!
+//! The password and salt are expanded with [PBKDF2](super::pbkdf2), and every block of the result is mixed with ROMix:
+//! `n` values are computed one after the other with the [Salsa20/8] core and kept in memory,
+//! and then read back in an order that depends on the data, so computing the key without keeping them costs much more time.
+//! The memory used is `128 * r * n` bytes, 128 MiB with the parameters recommended by OWASP, `n = 2^17` and `r = 8`.
+//!
+//! # Examples
+//! ```
+//! use mysha::kdf::{scrypt::{self, Params}, KdfError};
+//! use mysha::encoding::hex;
+//! # fn main() -> Result<(), KdfError>{
+//! // test vector from RFC 7914
+//! let params = Params{log_n: 10, r: 8, p: 16};
+//! let key = scrypt::derive(b"password", b"NaCl", &params, 64)?;
+//!
+//! assert_eq!(hex::encode(&key), "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+//!     2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [scrypt]: https://datatracker.ietf.org/doc/html/rfc7914
+//! [Salsa20/8]: https://datatracker.ietf.org/doc/html/rfc7914#section-3
+
+use super::{pbkdf2, KdfError};
+
+/// The costs of scrypt.
+///
+/// The default is the one recommended by OWASP for passwords, `n = 2^17`, `r = 8` and `p = 1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params{
+    /// Base 2 logarithm of `n`, the number of values kept in memory, from 1 to 63.
+    pub log_n: u8,
+    /// Size of the blocks, in units of 128 bytes.
+    pub r: u32,
+    /// Number of blocks mixed independently, which could be done in parallel.
+    pub p: u32,
+}
+
+impl Default for Params{
+    fn default() -> Self{
+        Params{log_n: 17, r: 8, p: 1}
+    }
+}
+
+/// The Salsa20 core with 8 rounds, on 16 words.
+fn salsa20_8(block: &mut [u32; 16]){
+    let mut x = *block;
+    let quarter = |x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize|{
+        x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+        x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+        x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+        x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+    };
+    for _ in 0..4{
+        // columns
+        quarter(&mut x, 0, 4, 8, 12);
+        quarter(&mut x, 5, 9, 13, 1);
+        quarter(&mut x, 10, 14, 2, 6);
+        quarter(&mut x, 15, 3, 7, 11);
+        // rows
+        quarter(&mut x, 0, 1, 2, 3);
+        quarter(&mut x, 5, 6, 7, 4);
+        quarter(&mut x, 10, 11, 8, 9);
+        quarter(&mut x, 15, 12, 13, 14);
+    }
+    for (word, mixed) in block.iter_mut().zip(x){
+        *word = word.wrapping_add(mixed);
+    }
+}
+
+/// BlockMix of the block of `2 * r` parts of 16 words, the outputs of the even parts first and then of the odd ones.
+fn block_mix(block: &[u32], output: &mut [u32]){
+    let parts = block.len() / 16;
+    let mut x: [u32; 16] = block[block.len() - 16..].try_into().unwrap();
+    for i in 0..parts{
+        for (word, other) in x.iter_mut().zip(&block[16 * i..16 * (i + 1)]){
+            *word ^= other;
+        }
+        salsa20_8(&mut x);
+        let position = (i / 2 + (i % 2) * parts / 2) * 16;
+        output[position..position + 16].copy_from_slice(&x);
+    }
+}
+
+/// ROMix of the block, with `n` values.
+fn ro_mix(block: &mut [u32], n: usize){
+    let length = block.len();
+    let mut values = vec![0; n * length];
+    let mut x = block.to_vec();
+    for i in 0..n{
+        values[i * length..(i + 1) * length].copy_from_slice(&x);
+        block_mix(&values[i * length..(i + 1) * length], &mut x);
+    }
+    let mut mixed = vec![0; length];
+    for _ in 0..n{
+        // the first word of the last part, read as the lower bits of a little endian number
+        let j = (x[length - 16] as u64 | (x[length - 15] as u64) << 32) as usize & (n - 1);
+        for (word, value) in x.iter_mut().zip(&values[j * length..(j + 1) * length]){
+            *word ^= value;
+        }
+        block_mix(&x, &mut mixed);
+        std::mem::swap(&mut x, &mut mixed);
+    }
+    block.copy_from_slice(&x);
+}
+
+/// Derives a key of the length from the password and salt.
+///
+/// # Errors
+/// Returns [KdfError::InvalidParameters] if `log_n` isn't from 1 to 63, `r` or `p` are zero, `r * p` is 2^30 or more,
+/// or the memory needed doesn't fit in this machine's addresses, and [KdfError::InvalidLength] if the length is zero.
+pub fn derive(password: &[u8], salt: &[u8], params: &Params, length: usize) -> Result<Vec<u8>, KdfError>{
+    let Params{log_n, r, p} = *params;
+    if log_n == 0 || log_n >= 64 || r == 0 || p == 0 || r as u64 * p as u64 >= 1 << 30{
+        return Err(KdfError::InvalidParameters);
+    }
+    let n = 1_usize.checked_shl(log_n as u32).filter(|n| n.checked_mul(128 * r as usize).is_some()).ok_or(KdfError::InvalidParameters)?;
+
+    let block_size = 128 * r as usize;
+    let bytes = pbkdf2::derive(password, salt, 1, block_size * p as usize)?;
+    let mut mixed = Vec::with_capacity(bytes.len());
+    for block in bytes.chunks(block_size){
+        let mut words: Vec<u32> = block.chunks_exact(4).map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+        ro_mix(&mut words, n);
+        mixed.extend(words.iter().flat_map(|word| word.to_le_bytes()));
+    }
+    pbkdf2::derive(password, &mixed, 1, length)
+}
@@ -0,0 +1,146 @@
+use clap::{Args, Subcommand, ValueEnum};
+
+use mysha::encoding::{base64::{self, Alphabet}, hex};
+use mysha::kdf::{hkdf, pbkdf2, scrypt::{self, Params}};
+
+use crate::encode_cli::{get_bytes, get_text, Input};
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct KdfArgs{
+    #[command(subcommand)]
+    function: Function,
+}
+
+#[derive(Debug, Subcommand)]
+enum Function{
+    /// HKDF with HMAC-SHA256, for input key material that is already random
+    Hkdf(HkdfArgs),
+    /// PBKDF2 with HMAC-SHA256, for passphrases
+    Pbkdf2(Pbkdf2Args),
+    /// scrypt, memory-hard, for passphrases
+    Scrypt(ScryptArgs),
+}
+
+#[derive(Args, Debug)]
+struct KeyArgs{
+    /// passphrase or input key material, read from stdin if not provided
+    input: Option<String>,
+
+    /// input type
+    #[arg(short, long, default_value_t = Input::Text, value_enum)]
+    r#type: Input,
+
+    /// salt in hex
+    #[arg(short, long)]
+    salt: Option<String>,
+
+    /// salt as text, instead of hex
+    #[arg(long, conflicts_with = "salt")]
+    salt_text: Option<String>,
+
+    /// length of the derived key in bytes
+    #[arg(short, long, default_value_t = 32)]
+    length: usize,
+
+    /// how the derived key is shown
+    #[arg(short, long, default_value_t = Format::Hex, value_enum)]
+    format: Format,
+}
+
+#[derive(Args, Debug)]
+struct HkdfArgs{
+    #[command(flatten)]
+    key: KeyArgs,
+
+    /// what the key is for, as text, different infos give independent keys
+    #[arg(long, default_value_t = String::new())]
+    info: String,
+}
+
+#[derive(Args, Debug)]
+struct Pbkdf2Args{
+    #[command(flatten)]
+    key: KeyArgs,
+
+    /// number of iterations
+    #[arg(short, long, default_value_t = pbkdf2::RECOMMENDED_ITERATIONS)]
+    iterations: u32,
+}
+
+#[derive(Args, Debug)]
+struct ScryptArgs{
+    #[command(flatten)]
+    key: KeyArgs,
+
+    /// base 2 logarithm of the cost n, the memory used is 128 * r * n bytes
+    #[arg(long, default_value_t = Params::default().log_n)]
+    log_n: u8,
+
+    /// block size, in units of 128 bytes
+    #[arg(short, default_value_t = Params::default().r)]
+    r: u32,
+
+    /// parallelism
+    #[arg(short, default_value_t = Params::default().p)]
+    p: u32,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+enum Format{
+    /// Hexadecimal, as taken by the keys of encrypt and ecc
+    Hex,
+    /// Base64, with the standard alphabet
+    Base64,
+}
+
+impl KeyArgs{
+    /// The input as bytes, text read from stdin without the trailing newline.
+    fn get_input(&self) -> Vec<u8>{
+        match self.r#type{
+            Input::Text => get_text(self.input.clone()).into_bytes(),
+            _ => get_bytes(self.input.clone(), &self.r#type),
+        }
+    }
+
+    fn get_salt(&self) -> Option<Vec<u8>>{
+        match (&self.salt, &self.salt_text){
+            (Some(salt), _) => Some(hex::decode(salt).exit("Error while parsing the salt.")),
+            (_, Some(salt)) => Some(salt.as_bytes().to_vec()),
+            _ => None,
+        }
+    }
+
+    /// The salt, which passphrases need, so equal passphrases don't give equal keys.
+    fn get_required_salt(&self) -> Vec<u8>{
+        self.get_salt().exit("A salt is needed to derive keys from passphrases, set it with --salt or --salt-text.")
+    }
+
+    fn print(&self, key: &[u8]){
+        match self.format{
+            Format::Hex => println!("{}", hex::encode(key)),
+            Format::Base64 => println!("{}", base64::encode(key, Alphabet::Standard)),
+        }
+    }
+}
+
+pub fn derive_key(args: KdfArgs){
+    match args.function{
+        Function::Hkdf(args) => {
+            let salt = args.key.get_salt().unwrap_or_default();
+            let key = hkdf::derive(&args.key.get_input(), &salt, args.info.as_bytes(), args.key.length).exit("Error while deriving the key.");
+            args.key.print(&key);
+        },
+        Function::Pbkdf2(args) => {
+            let salt = args.key.get_required_salt();
+            let key = pbkdf2::derive(&args.key.get_input(), &salt, args.iterations, args.key.length).exit("Error while deriving the key.");
+            args.key.print(&key);
+        },
+        Function::Scrypt(args) => {
+            let salt = args.key.get_required_salt();
+            let params = Params{log_n: args.log_n, r: args.r, p: args.p};
+            let key = scrypt::derive(&args.key.get_input(), &salt, &params, args.key.length).exit("Error while deriving the key.");
+            args.key.print(&key);
+        },
+    }
+}
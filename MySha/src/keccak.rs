@@ -0,0 +1,176 @@
+//! Module with the [Keccak-256] hash, the hash function of Ethereum.
+//!
+//! Keccak is a sponge: the message is absorbed into a state of 25 words of 64 bits, 136 bytes at a time,
+//! each followed by the permutation keccak-f, and the hash is squeezed out of the first bytes of the state.
+//! It is the winner of the SHA-3 competition, but Ethereum adopted it before the standard changed its padding,
+//! so its hashes are different from the ones of SHA3-256.
+//!
+//! # Examples
+//! ```
+//! use mysha::keccak::Keccak256;
+//!
+//! let hash = Keccak256::digest(b"");
+//! assert_eq!(hash[..4], [0xc5, 0xd2, 0x46, 0x01]);
+//! ```
+//!
+//! [Keccak-256]: https://keccak.team/keccak_specs_summary.html
+
+use std::sync::OnceLock;
+
+use crate::digest::Digest;
+
+/// Bytes absorbed per permutation, the 1600 bits of the state minus twice the 256 bits of the hash.
+const RATE: usize = 136;
+
+/// Round constants of the iota step, computed once from the linear feedback shift register of the specification.
+fn round_constants() -> &'static [u64; 24]{
+    static RC: OnceLock<[u64; 24]> = OnceLock::new();
+    RC.get_or_init(||{
+        let mut register: u8 = 1;
+        let mut constants = [0; 24];
+        for constant in &mut constants{
+            // bit 2^j - 1 of every constant comes from the next output of the register
+            for j in 0..7{
+                if register & 1 == 1{
+                    *constant |= 1 << ((1 << j) - 1);
+                }
+                register = if register & 0x80 != 0 {(register << 1) ^ 0x71} else {register << 1};
+            }
+        }
+        constants
+    })
+}
+
+/// The permutation keccak-f[1600], 24 rounds of theta, rho, pi, chi and iota over the lanes `state[x + 5y]`.
+fn keccak_f(state: &mut [u64; 25]){
+    for constant in round_constants(){
+        // theta, every lane is mixed with the parities of two neighbouring columns
+        let mut parity = [0; 5];
+        for (x, column) in parity.iter_mut().enumerate(){
+            *column = (0..5).fold(0, |acc, y| acc ^ state[x + 5 * y]);
+        }
+        for x in 0..5{
+            let d = parity[(x + 4) % 5] ^ parity[(x + 1) % 5].rotate_left(1);
+            for y in 0..5{
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho and pi, the lanes move to (y, 2x + 3y) and rotate by growing triangular numbers
+        let (mut x, mut y) = (1, 0);
+        let mut current = state[1];
+        for t in 0..24{
+            (x, y) = (y, (2 * x + 3 * y) % 5);
+            let next = state[x + 5 * y];
+            state[x + 5 * y] = current.rotate_left(((t + 1) * (t + 2) / 2 % 64) as u32);
+            current = next;
+        }
+
+        // chi, the only non linear step
+        for y in 0..5{
+            let row: [u64; 5] = state[5 * y..5 * y + 5].try_into().unwrap();
+            for x in 0..5{
+                state[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // iota
+        state[0] ^= constant;
+    }
+}
+
+/// Incremental Keccak-256 hasher.
+///
+/// # Examples
+/// ```
+/// use mysha::keccak::Keccak256;
+///
+/// let mut hasher = Keccak256::new();
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+///
+/// assert_eq!(hasher.finalize(), Keccak256::digest(b"hello world"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Keccak256{
+    state: [u64; 25],
+    buffer: [u8; RATE],
+    buffered: usize,
+}
+
+impl Default for Keccak256{
+    fn default() -> Self{
+        Keccak256::new()
+    }
+}
+
+impl Keccak256{
+    /// Creates a hasher with no data.
+    pub fn new() -> Keccak256{
+        Keccak256{
+            state: [0; 25],
+            buffer: [0; RATE],
+            buffered: 0,
+        }
+    }
+
+    /// Hashes the data in one call.
+    pub fn digest(data: &[u8]) -> [u8; 32]{
+        let mut hasher = Keccak256::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Adds the data to the message being hashed.
+    pub fn update(&mut self, data: &[u8]){
+        for byte in data{
+            self.buffer[self.buffered] = *byte;
+            self.buffered += 1;
+            if self.buffered == RATE{
+                self.absorb();
+            }
+        }
+    }
+
+    /// Pads the message and returns the hash.
+    ///
+    /// The padding is a one bit, zeros, and a one bit at the end of the block, the original Keccak padding,
+    /// where SHA3-256 would start with the bits 011.
+    pub fn finalize(mut self) -> [u8; 32]{
+        self.buffer[self.buffered..].fill(0);
+        self.buffer[self.buffered] ^= 0x01;
+        self.buffer[RATE - 1] ^= 0x80;
+        self.absorb();
+
+        let mut hash = [0; 32];
+        for (chunk, lane) in hash.chunks_exact_mut(8).zip(self.state){
+            chunk.copy_from_slice(&lane.to_le_bytes());
+        }
+        hash
+    }
+
+    /// Xors the full buffer into the state, as little endian lanes, and permutes it.
+    fn absorb(&mut self){
+        for (lane, chunk) in self.state.iter_mut().zip(self.buffer.chunks_exact(8)){
+            *lane ^= u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        keccak_f(&mut self.state);
+        self.buffered = 0;
+    }
+}
+
+impl Digest for Keccak256{
+    const SIZE: usize = 32;
+
+    fn new() -> Keccak256{
+        Keccak256::new()
+    }
+
+    fn update(&mut self, data: &[u8]){
+        Keccak256::update(self, data)
+    }
+
+    fn finish(self) -> Vec<u8>{
+        self.finalize().to_vec()
+    }
+}
@@ -5,19 +5,90 @@
 //! Every different cryptography concept, tool or algorithm is located in a different module, 
 //! so browse the modules to see what is provided.
 //! 
+//! # Features
+//! Every module, other than [digest] and [encoding], is behind a feature of the same name, and the `full` feature enables all of them.
+//! They are all enabled by default, with the `cli` feature of the binary, so a crate that only hashes can depend on
+//! `mysha = {default-features = false, features = ["sha256"]}` and skip num-bigint, rand and the dependencies of the cli.
+//! - `sha256`: [sha256], [hmac] and [base58][encoding::base58];
+//! - `ecc`, `bls`, `cipher`, `ecies`, `hashsig`, `jwt`, `kdf`, `keccak`, `merkle`, `otp`, `ripemd160`, `sha1`, `vrf`, `animation` and `selftest`: the modules, with the ones they use;
+//! - `rand`: [rand], without [random_scalar][rand::random_scalar], which needs `ecc`;
+//! - `bigint`: [der][encoding::der], and the conversions of [Hash256][sha256::Hash256] to big numbers;
+//! - `serde`: serde and serde_json, used by [selftest] and [jwt];
+//! - `parallel`: [sha256_many][sha256::sha256_many] on a thread pool;
+//! - `tokio`: `sha256_file_async` and `sha256_from_async_reader` of [sha256], for async runtimes;
+//! - `tracing`: spans and debug events of hashing, of the blocks of sha256 at the trace level, of scalar multiplication,
+//!   signing and verifying, for the subscriber of the application. Keys and nonces are never recorded, only their sizes;
+//! - `wasm`: the javascript bindings of the `wasm` module, for building the crate for the browser.
+//! 
 //! [cli tool]: https://github.com/lucasmabf/mysha
 
 use core::fmt;
 
+#[cfg(feature = "bls")]
+use bls::BlsError;
+#[cfg(feature = "cipher")]
+use cipher::CipherError;
+#[cfg(feature = "ecc")]
 use ecc::EccError;
+use encoding::EncodingError;
+#[cfg(feature = "hashsig")]
+use hashsig::HashSigError;
+#[cfg(feature = "jwt")]
+use jwt::JwtError;
+#[cfg(feature = "kdf")]
+use kdf::KdfError;
+#[cfg(feature = "otp")]
+use otp::OtpError;
+#[cfg(feature = "sha256")]
 use sha256::HashError;
+#[cfg(feature = "vrf")]
+use vrf::VrfError;
 
+#[cfg(feature = "animation")]
+pub mod animation;
+#[cfg(feature = "bls")]
+pub mod bls;
+#[cfg(feature = "cipher")]
+pub mod cipher;
+pub mod digest;
+#[cfg(feature = "ecc")]
 pub mod ecc;
+#[cfg(feature = "ecies")]
+pub mod ecies;
+pub mod encoding;
+#[cfg(feature = "hashsig")]
+pub mod hashsig;
+#[cfg(feature = "sha256")]
+pub mod hmac;
+#[cfg(feature = "jwt")]
+pub mod jwt;
+#[cfg(feature = "kdf")]
+pub mod kdf;
+#[cfg(feature = "keccak")]
+pub mod keccak;
+#[cfg(feature = "merkle")]
+pub mod merkle;
+#[cfg(feature = "otp")]
+pub mod otp;
+#[cfg(feature = "rand")]
+pub mod rand;
+#[cfg(feature = "ripemd160")]
+pub mod ripemd160;
+#[cfg(feature = "selftest")]
+pub mod selftest;
+#[cfg(feature = "sha1")]
+pub mod sha1;
+#[cfg(feature = "sha256")]
 pub mod sha256;
+#[cfg(feature = "vrf")]
+pub mod vrf;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// Error type for this library
 /// 
 /// This type allows functions to return Errors of different modules of this library.
+/// It only has the variants of the modules of the [enabled features](crate#features).
 /// 
 /// # Examples
 /// 
@@ -34,27 +105,118 @@ pub mod sha256;
 /// ```
 #[derive(Debug)]
 pub enum MyshaError{
+    #[cfg(feature = "ecc")]
     Ecc(EccError),
+    #[cfg(feature = "sha256")]
     Hash(HashError),
+    Encoding(EncodingError),
+    #[cfg(feature = "cipher")]
+    Cipher(CipherError),
+    #[cfg(feature = "kdf")]
+    Kdf(KdfError),
+    #[cfg(feature = "bls")]
+    Bls(BlsError),
+    #[cfg(feature = "otp")]
+    Otp(OtpError),
+    #[cfg(feature = "jwt")]
+    Jwt(JwtError),
+    #[cfg(feature = "vrf")]
+    Vrf(VrfError),
+    #[cfg(feature = "hashsig")]
+    HashSig(HashSigError),
 }
 
 impl fmt::Display for MyshaError{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self{
-            &MyshaError::Ecc(e) => write!(f, "{}", e),
-            &MyshaError::Hash(e) => write!(f, "{}", e),
+        match self{
+            #[cfg(feature = "ecc")]
+            MyshaError::Ecc(e) => write!(f, "{}", e),
+            #[cfg(feature = "sha256")]
+            MyshaError::Hash(e) => write!(f, "{}", e),
+            MyshaError::Encoding(e) => write!(f, "{}", e),
+            #[cfg(feature = "cipher")]
+            MyshaError::Cipher(e) => write!(f, "{}", e),
+            #[cfg(feature = "kdf")]
+            MyshaError::Kdf(e) => write!(f, "{}", e),
+            #[cfg(feature = "bls")]
+            MyshaError::Bls(e) => write!(f, "{}", e),
+            #[cfg(feature = "otp")]
+            MyshaError::Otp(e) => write!(f, "{}", e),
+            #[cfg(feature = "jwt")]
+            MyshaError::Jwt(e) => write!(f, "{}", e),
+            #[cfg(feature = "vrf")]
+            MyshaError::Vrf(e) => write!(f, "{}", e),
+            #[cfg(feature = "hashsig")]
+            MyshaError::HashSig(e) => write!(f, "{}", e),
         }
     }
 }
 
+#[cfg(feature = "ecc")]
 impl From<EccError> for MyshaError{
     fn from(value: EccError) -> Self {
         MyshaError::Ecc(value)
     }
 }
 
+#[cfg(feature = "sha256")]
 impl From<HashError> for MyshaError{
     fn from(value: HashError) -> Self {
         MyshaError::Hash(value)
     }
-}
\ No newline at end of file
+}
+
+impl From<EncodingError> for MyshaError{
+    fn from(value: EncodingError) -> Self {
+        MyshaError::Encoding(value)
+    }
+}
+
+#[cfg(feature = "cipher")]
+impl From<CipherError> for MyshaError{
+    fn from(value: CipherError) -> Self {
+        MyshaError::Cipher(value)
+    }
+}
+
+#[cfg(feature = "kdf")]
+impl From<KdfError> for MyshaError{
+    fn from(value: KdfError) -> Self {
+        MyshaError::Kdf(value)
+    }
+}
+
+#[cfg(feature = "bls")]
+impl From<BlsError> for MyshaError{
+    fn from(value: BlsError) -> Self {
+        MyshaError::Bls(value)
+    }
+}
+
+#[cfg(feature = "otp")]
+impl From<OtpError> for MyshaError{
+    fn from(value: OtpError) -> Self {
+        MyshaError::Otp(value)
+    }
+}
+
+#[cfg(feature = "jwt")]
+impl From<JwtError> for MyshaError{
+    fn from(value: JwtError) -> Self {
+        MyshaError::Jwt(value)
+    }
+}
+
+#[cfg(feature = "vrf")]
+impl From<VrfError> for MyshaError{
+    fn from(value: VrfError) -> Self {
+        MyshaError::Vrf(value)
+    }
+}
+
+#[cfg(feature = "hashsig")]
+impl From<HashSigError> for MyshaError{
+    fn from(value: HashSigError) -> Self {
+        MyshaError::HashSig(value)
+    }
+}
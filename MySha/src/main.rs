@@ -6,6 +6,38 @@ mod sha256_cli;
 use sha256_cli::*;
 mod ecc_cli;
 use ecc_cli::*;
+mod encode_cli;
+use encode_cli::*;
+mod cipher_cli;
+use cipher_cli::*;
+mod otp_cli;
+use otp_cli::*;
+mod totp_cli;
+use totp_cli::*;
+mod jwt_cli;
+use jwt_cli::*;
+mod classical_cli;
+use classical_cli::*;
+mod password_cli;
+use password_cli::*;
+mod crack_cli;
+use crack_cli::*;
+mod kdf_cli;
+use kdf_cli::*;
+mod merkle_cli;
+use merkle_cli::*;
+mod demo_cli;
+use demo_cli::*;
+mod selftest_cli;
+use selftest_cli::*;
+mod shell_cli;
+use shell_cli::*;
+mod config;
+use config::Config;
+mod failure;
+use failure::{fail, ErrorFormat, Failure, Kind};
+mod qr;
+mod timing;
 
 /// my implementations of different cryptography tools in rust
 #[derive(Parser, Debug)]
@@ -15,6 +47,10 @@ use ecc_cli::*;
 struct Args{
     #[command(subcommand)]
     command: Command,
+
+    /// How errors are printed to stderr, json gives their kind and exit code to scripts
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    error_format: ErrorFormat,
 }
 
 #[derive(Debug, Subcommand)]
@@ -23,49 +59,148 @@ enum Command{
     Sha256(HashArgs),
     /// Elliptic Curve Cryptography tool
     Ecc(ECCArgs), 
+    /// Encodes data as text
+    Encode(EncodeArgs),
+    /// Decodes text to data
+    Decode(DecodeArgs),
+    /// Encrypts data with AES or ChaCha20-Poly1305
+    Encrypt(EncryptArgs),
+    /// Decrypts data encrypted with AES or ChaCha20-Poly1305
+    Decrypt(DecryptArgs),
+    /// One-time pad, showing perfect secrecy and key reuse
+    Otp(OtpArgs),
+    /// One-time codes of authenticator apps, TOTP and HOTP with HMAC-SHA-1 or HMAC-SHA256
+    Totp(TotpArgs),
+    /// JSON web tokens, signed with HS256 or ES256K
+    Jwt(JwtArgs),
+    /// Classical ciphers, and cracking them with frequency analysis
+    Classical(ClassicalArgs),
+    /// Password hashing with Argon2 or PBKDF2
+    Password(PasswordArgs),
+    /// Dictionary attack on a sha256 password hash, showing why sha256 alone is a poor password hash
+    Crack(CrackArgs),
+    /// Derives keys from passphrases or other secrets
+    Kdf(KdfArgs),
+    /// Merkle trees and inclusion proofs
+    Merkle(MerkleArgs),
+    /// Demonstrations of how hashes are used
+    Demo(DemoArgs),
+    /// Runs standard test vectors, CAVP and Wycheproof, against the implementations
+    Selftest(SelftestArgs),
+    /// Prints the completion script of a shell
+    Completions(CompletionsArgs),
+    /// Writes the man pages of every subcommand to a directory
+    Manpages(ManpagesArgs),
 }
 
 fn main(){
-    let args = Args::parse();
+    let args = Args::try_parse().unwrap_or_else(|err| {
+        // help and version are printed to stdout, and aren't errors
+        if err.use_stderr() && failure::json_requested(){
+            failure::set_format(ErrorFormat::Json);
+            fail(Failure::Parse, "Invalid arguments.", Some(&err.to_string().trim_end()));
+        }
+        err.exit()
+    });
+    failure::set_format(args.error_format);
+    failure::set_panic_hook();
 
     match args.command{
         Command::Sha256(args) =>{
-            hash(args);
+            hash(args, &Config::load());
         },
         Command::Ecc(args) =>{
-            key_pair(args);
-        }
+            key_pair(args, &Config::load());
+        },
+        Command::Encode(args) =>{
+            encode(args);
+        },
+        Command::Decode(args) =>{
+            decode(args);
+        },
+        Command::Encrypt(args) =>{
+            encrypt(args);
+        },
+        Command::Decrypt(args) =>{
+            decrypt(args);
+        },
+        Command::Otp(args) =>{
+            one_time_pad(args);
+        },
+        Command::Totp(args) =>{
+            totp(args);
+        },
+        Command::Jwt(args) =>{
+            json_web_token(args);
+        },
+        Command::Classical(args) =>{
+            classical(args);
+        },
+        Command::Password(args) =>{
+            password(args);
+        },
+        Command::Crack(args) =>{
+            crack(args);
+        },
+        Command::Kdf(args) =>{
+            derive_key(args);
+        },
+        Command::Merkle(args) =>{
+            merkle(args);
+        },
+        Command::Demo(args) =>{
+            demo(args);
+        },
+        Command::Selftest(args) =>{
+            self_test(args);
+        },
+        Command::Completions(args) =>{
+            completions(args);
+        },
+        Command::Manpages(args) =>{
+            manpages(args);
+        },
     }
 }
 
+/// Exits with the message and the error, and the exit code of its [Failure], see [failure].
 trait Exit{
     type Output;
 
     fn exit(self, msg: &str) -> Self::Output;
+
+    /// Same as [exit][Exit::exit], with the failure given instead of the one of the error.
+    fn exit_as(self, failure: Failure, msg: &str) -> Self::Output;
 }
 
-impl<T, E: Display> Exit for Result<T, E>{
+impl<T, E: Display + Kind> Exit for Result<T, E>{
     type Output = T;
     fn exit(self, msg: &str) -> T{
         match self{
-            Err(e) =>{
-                eprintln!("{} Error: {}", msg, e);
-                std::process::exit(1);
-            },
+            Err(e) => fail(e.failure(), msg, Some(&e)),
+            Ok(t) => t,
+        }
+    }
+
+    fn exit_as(self, failure: Failure, msg: &str) -> T{
+        match self{
+            Err(e) => fail(failure, msg, Some(&e)),
             Ok(t) => t,
         }
     }
 }
 
+/// A missing value is an invalid input, unless another failure is given.
 impl<T> Exit for Option<T>{
     type Output = T;
     fn exit(self, msg: &str) -> T{
+        self.exit_as(Failure::Parse, msg)
+    }
+
+    fn exit_as(self, failure: Failure, msg: &str) -> T{
         match self{
-            None => {
-                eprintln!("{}", msg);
-                std::process::exit(1);
-            },
+            None => fail(failure, msg, None),
             Some(t) => t,
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,154 @@
+//! Module for [Merkle trees] and inclusion proofs.
+//!
+//! The leaves are hashed, and every pair of hashes is hashed together, level after level, up to a single hash, the root.
+//! The root commits to every leaf, and proving that a leaf is in the tree only needs the hashes next to the path
+//! from the leaf to the root, one per level, so a proof for a million leaves has only 20 hashes.
+//!
+//! The hashes follow [RFC 6962]: leaves are hashed as `sha256(0x00 || leaf)` and nodes as `sha256(0x01 || left || right)`,
+//! so a node can't be passed off as a leaf. When a level has an odd number of hashes, the last one moves up unchanged,
+//! instead of being paired with itself like in bitcoin, which lets different lists of leaves have the same root.
+//!
+//! # Examples
+//! ```
+//! use mysha::merkle::MerkleTree;
+//!
+//! let tree = MerkleTree::new(&["alice", "bob", "carol", "dave", "eve"]).unwrap();
+//!
+//! let proof = tree.prove(2).unwrap();
+//! assert_eq!(proof.path.len(), 3);
+//! assert!(proof.verify(b"carol", tree.root()));
+//! assert!(!proof.verify(b"mallory", tree.root()));
+//! ```
+//!
+//! [Merkle trees]: https://en.wikipedia.org/wiki/Merkle_tree
+//! [RFC 6962]: https://datatracker.ietf.org/doc/html/rfc6962#section-2.1
+
+use crate::sha256::{Hash256, Sha256};
+
+/// Hash of a leaf, with the 0x00 prefix.
+pub fn leaf_hash(leaf: &[u8]) -> Hash256{
+    let mut hasher = Sha256::new();
+    hasher.update(&[0x00]);
+    hasher.update(leaf);
+    hasher.finalize()
+}
+
+/// Hash of a node from its children, with the 0x01 prefix.
+pub fn node_hash(left: &Hash256, right: &Hash256) -> Hash256{
+    let mut hasher = Sha256::new();
+    hasher.update(&[0x01]);
+    hasher.update(&left.get_bytes());
+    hasher.update(&right.get_bytes());
+    hasher.finalize()
+}
+
+/// A Merkle tree, keeping the hashes of every level, from the leaves to the root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleTree{
+    levels: Vec<Vec<Hash256>>,
+}
+
+/// Side where the sibling hash goes when hashing up the path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side{
+    /// The sibling is the left child, and the hash so far the right one.
+    Left,
+    /// The sibling is the right child, and the hash so far the left one.
+    Right,
+}
+
+/// A step of an inclusion proof, the sibling of the hash so far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofStep{
+    /// Side of the sibling.
+    pub side: Side,
+    /// Hash of the sibling.
+    pub hash: Hash256,
+}
+
+/// Proof that a leaf is in a tree, the siblings from the leaf up to the root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proof{
+    /// Position of the leaf in the tree.
+    pub index: usize,
+    /// Siblings, from the leaf up.
+    pub path: Vec<ProofStep>,
+}
+
+impl Proof{
+    /// Root of the tree that has the leaf at the end of this path.
+    pub fn root(&self, leaf: &[u8]) -> Hash256{
+        self.path.iter().fold(leaf_hash(leaf), |hash, step| match step.side{
+            Side::Left => node_hash(&step.hash, &hash),
+            Side::Right => node_hash(&hash, &step.hash),
+        })
+    }
+
+    /// Checks that the leaf is in the tree with the root.
+    pub fn verify(&self, leaf: &[u8], root: &Hash256) -> bool{
+        self.root(leaf) == *root
+    }
+}
+
+impl MerkleTree{
+    /// Builds the tree of the leaves, returning None if there are no leaves.
+    pub fn new<T: AsRef<[u8]>>(leaves: &[T]) -> Option<MerkleTree>{
+        MerkleTree::from_hashes(leaves.iter().map(|leaf| leaf_hash(leaf.as_ref())).collect())
+    }
+
+    /// Builds the tree from the hashes of the leaves, returning None if there are no hashes.
+    pub fn from_hashes(hashes: Vec<Hash256>) -> Option<MerkleTree>{
+        if hashes.is_empty(){
+            return None;
+        }
+        let mut levels = vec![hashes];
+        while levels.last().unwrap().len() > 1{
+            let level = levels.last().unwrap().chunks(2).map(|pair| match pair{
+                [left, right] => node_hash(left, right),
+                [last] => last.clone(),
+                _ => unreachable!(),
+            }).collect();
+            levels.push(level);
+        }
+        Some(MerkleTree{levels})
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> &Hash256{
+        &self.levels.last().unwrap()[0]
+    }
+
+    /// Number of leaves.
+    pub fn len(&self) -> usize{
+        self.levels[0].len()
+    }
+
+    /// Always false, since trees have at least one leaf.
+    pub fn is_empty(&self) -> bool{
+        false
+    }
+
+    /// Hashes of every level, the leaves first and the root last.
+    pub fn levels(&self) -> &[Vec<Hash256>]{
+        &self.levels
+    }
+
+    /// Proof of the leaf at the index, or None if there isn't a leaf there.
+    pub fn prove(&self, index: usize) -> Option<Proof>{
+        if index >= self.len(){
+            return None;
+        }
+        let mut path = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1]{
+            let sibling = position ^ 1;
+            // the last hash of an odd level has no sibling, it moves up unchanged
+            if sibling < level.len(){
+                let side = if position % 2 == 1 {Side::Left} else {Side::Right};
+                path.push(ProofStep{side, hash: level[sibling].clone()});
+            }
+            position /= 2;
+        }
+        Some(Proof{index, path})
+    }
+}
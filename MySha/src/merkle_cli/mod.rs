@@ -0,0 +1,204 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use mysha::merkle::{MerkleTree, Proof, ProofStep, Side};
+use mysha::sha256::Hash256;
+
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct MerkleArgs{
+    #[command(subcommand)]
+    subcommand: SubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommand{
+    /// Prints the root of the tree of the leaves
+    Root(RootArgs),
+    /// Writes the proof that a leaf is in the tree to a file
+    Prove(ProveArgs),
+    /// Verifies that a leaf is in the tree with the root, with its proof
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Debug)]
+struct LeavesArgs{
+    /// files that are the leaves, or whose lines are the leaves with --lines, the lines of stdin are the leaves if none are provided
+    files: Vec<String>,
+
+    /// the leaves are the lines of the files, instead of the files
+    #[arg(short, long)]
+    lines: bool,
+}
+
+#[derive(Args, Debug)]
+struct RootArgs{
+    #[command(flatten)]
+    leaves: LeavesArgs,
+
+    /// prints every level of the tree, from the leaves to the root
+    #[arg(short, long)]
+    tree: bool,
+}
+
+#[derive(Args, Debug)]
+struct ProveArgs{
+    /// leaf to be proven, one of the files, or a line with --lines
+    leaf: String,
+
+    #[command(flatten)]
+    leaves: LeavesArgs,
+
+    /// file the proof is written to
+    #[arg(short, long, default_value_t = String::from("proof.toml"))]
+    output: String,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs{
+    /// leaf, as text, or a file with --file
+    leaf: String,
+
+    /// proof file, written by prove
+    proof: String,
+
+    /// root of the tree, in hex
+    root: String,
+
+    /// the leaf is a file, whose content is the leaf
+    #[arg(short, long)]
+    file: bool,
+
+    /// Doesn't print anything, the result is only given by the exit status
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Prints only `valid` or `invalid`, to be parsed by scripts
+    #[arg(long, conflicts_with = "quiet")]
+    porcelain: bool,
+}
+
+/// Proof as written in the toml file.
+#[derive(Serialize, Deserialize, Debug)]
+struct ProofFile{
+    index: usize,
+    path: Vec<ProofFileStep>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ProofFileStep{
+    side: String,
+    hash: String,
+}
+
+impl From<&Proof> for ProofFile{
+    fn from(proof: &Proof) -> Self{
+        let path = proof.path.iter().map(|step| ProofFileStep{
+            side: String::from(if step.side == Side::Left {"left"} else {"right"}),
+            hash: step.hash.get_hex().to_owned(),
+        }).collect();
+        ProofFile{index: proof.index, path}
+    }
+}
+
+impl ProofFile{
+    fn into_proof(self) -> Proof{
+        let path = self.path.into_iter().map(|step| ProofStep{
+            side: match step.side.as_str(){
+                "left" => Side::Left,
+                "right" => Side::Right,
+                _ => Err::<Side, &str>("side must be left or right.").exit("Invalid proof."),
+            },
+            hash: Hash256::from_hex(&step.hash, false).exit("Invalid proof."),
+        }).collect();
+        Proof{index: self.index, path}
+    }
+}
+
+fn read_file(path: &str) -> Vec<u8>{
+    fs::read(path).exit(&format!("Error while reading {}.", path))
+}
+
+impl LeavesArgs{
+    /// Names and contents of the leaves.
+    fn get_leaves(&self) -> Vec<(String, Vec<u8>)>{
+        if !self.lines && !self.files.is_empty(){
+            return self.files.iter().map(|file| (file.clone(), read_file(file))).collect();
+        }
+
+        let mut text = String::new();
+        if self.files.is_empty(){
+            io::stdin().read_to_string(&mut text).exit("Error while reading stdin.");
+        }
+        for file in &self.files{
+            File::open(file).and_then(|mut file| file.read_to_string(&mut text)).exit(&format!("Error while reading {}.", file));
+            if !text.ends_with('\n'){
+                text.push('\n');
+            }
+        }
+        text.lines().map(|line| (line.to_owned(), line.as_bytes().to_vec())).collect()
+    }
+
+    fn get_tree(&self) -> (Vec<(String, Vec<u8>)>, MerkleTree){
+        let leaves = self.get_leaves();
+        let tree = MerkleTree::new(&leaves.iter().map(|(_, leaf)| leaf).collect::<Vec<_>>()).exit("There are no leaves.");
+        (leaves, tree)
+    }
+}
+
+pub fn merkle(args: MerkleArgs){
+    match args.subcommand{
+        SubCommand::Root(args) => {
+            let (leaves, tree) = args.leaves.get_tree();
+            if args.tree{
+                for (i, level) in tree.levels().iter().enumerate(){
+                    println!("level {}:", i);
+                    for (j, hash) in level.iter().enumerate(){
+                        if i == 0{
+                            println!("  {}  {}", hash, leaves[j].0);
+                        }else{
+                            println!("  {}", hash);
+                        }
+                    }
+                }
+                println!("root:");
+            }
+            println!("{}", tree.root());
+        },
+        SubCommand::Prove(args) => {
+            let (leaves, tree) = args.leaves.get_tree();
+            let index = leaves.iter().position(|(name, _)| *name == args.leaf).exit("The leaf isn't one of the leaves.");
+            let proof = tree.prove(index).unwrap();
+
+            let content = toml::to_string(&ProofFile::from(&proof)).exit("Error while parsing to toml.");
+            File::create(&args.output).and_then(|mut file| file.write_all(content.as_bytes())).exit("Error while writing the proof.");
+            println!("leaf {} of {}, proof with {} hashes written to {}", index, tree.len(), proof.path.len(), args.output);
+            println!("root: {}", tree.root());
+        },
+        SubCommand::Verify(args) => {
+            let leaf = if args.file {read_file(&args.leaf)} else {args.leaf.into_bytes()};
+            let content = fs::read_to_string(&args.proof).exit("Error while reading the proof.");
+            let proof = toml::from_str::<ProofFile>(&content).exit("Error while parsing the proof.").into_proof();
+            let root = Hash256::from_hex(&args.root.to_lowercase(), false).exit("Invalid root.");
+
+            let valid = proof.verify(&leaf, &root);
+            if args.porcelain{
+                println!("{}", if valid {"valid"} else {"invalid"});
+            }else if ! args.quiet{
+                if valid{
+                    println!("Leaf IS in the tree");
+                }else{
+                    println!("Leaf is NOT in the tree, the proof leads to {}", proof.root(&leaf));
+                }
+            }
+            if ! valid{
+                Failure::Verification.exit();
+            }
+        },
+    }
+}
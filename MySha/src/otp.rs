@@ -0,0 +1,152 @@
+//! Module for one-time codes of authenticator apps, [HOTP] and [TOTP], built on [HMAC](crate::hmac).
+//!
+//! HOTP takes the HMAC of a counter with a shared secret, and turns 4 bytes of it into a code of 6 to 9 digits.
+//! TOTP is HOTP with the number of periods, usually of 30 seconds, since the unix epoch as the counter,
+//! so the server and the app get the same code without talking to each other, as long as their clocks agree.
+//!
+//! RFC 4226 defines HOTP with HMAC-SHA-1, the [Algorithm] of the secrets of most apps and the default one here.
+//! RFC 6238 also allows HMAC-SHA256, for apps set up with `algorithm=SHA256` in the `otpauth://` link of the secret.
+//!
+//! This module is different from the one-time pad of `cipher::otp`, these codes are passwords that can
+//! only be used once, not an encryption.
+//!
+//! # Examples
+//! ```
+//! use mysha::otp::{self, Algorithm};
+//! # fn main() -> Result<(), otp::OtpError>{
+//! // the secret of the SHA-1 examples of RFC 6238, as shown by authenticator apps
+//! let secret = otp::parse_secret("GEZD GNBV GY3T QOJQ GEZD GNBV GY3T QOJQ")?;
+//!
+//! assert_eq!(otp::totp(&secret, 59, 30, 8, Algorithm::Sha1)?, "94287082");
+//! assert_eq!(otp::remaining(59, 30), 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [HOTP]: https://datatracker.ietf.org/doc/html/rfc4226
+//! [TOTP]: https://datatracker.ietf.org/doc/html/rfc6238
+
+use std::fmt;
+
+use crate::encoding::base32;
+use crate::hmac::hmac_digest;
+use crate::sha1::Sha1;
+use crate::sha256::Sha256;
+
+/// Number of digits of the codes of most apps.
+pub const DEFAULT_DIGITS: u32 = 6;
+/// Seconds every TOTP code is valid for, in most apps.
+pub const DEFAULT_PERIOD: u64 = 30;
+
+/// Hash function of the HMAC of the codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm{
+    /// HMAC-SHA-1, of RFC 4226 and the default of authenticator apps.
+    #[default]
+    Sha1,
+    /// HMAC-SHA256, for secrets with `algorithm=SHA256`.
+    Sha256,
+}
+
+/// The error type of this module.
+#[derive(Debug, PartialEq)]
+pub enum OtpError{
+    /// Happens when the secret isn't base32, or is empty.
+    InvalidSecret,
+    /// Happens when the number of digits isn't from 6 to 9, RFC 4226 requires at least 6,
+    /// and 4 bytes of the HMAC are only enough for 9.
+    InvalidDigits,
+    /// Happens when the period of TOTP is 0 seconds.
+    InvalidPeriod,
+}
+
+impl fmt::Display for OtpError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            OtpError::InvalidSecret => write!(f, "The secret must be base32 text, like the ones shown by authenticator apps."),
+            OtpError::InvalidDigits => write!(f, "The codes must have from 6 to 9 digits."),
+            OtpError::InvalidPeriod => write!(f, "The period must be at least one second."),
+        }
+    }
+}
+
+/// Reads a base32 secret as shown by authenticator apps, in uppercase or lowercase, with or without spaces, dashes and padding.
+///
+/// # Errors
+/// Returns [OtpError::InvalidSecret] if the secret isn't base32, or is empty.
+pub fn parse_secret(text: &str) -> Result<Vec<u8>, OtpError>{
+    let text: String = text.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+    match base32::decode(&text){
+        Ok(secret) if !secret.is_empty() => Ok(secret),
+        _ => Err(OtpError::InvalidSecret),
+    }
+}
+
+/// Returns the HOTP code of the counter, with the digits, zero padded.
+///
+/// The last 4 bits of the HMAC of the counter, as 8 big endian bytes, are the offset of the 4 bytes that are read
+/// as a number, without the highest bit, and its last digits are the code.
+///
+/// # Examples
+/// The test values of RFC 4226, appendix D:
+/// ```
+/// use mysha::otp::{self, Algorithm};
+/// # fn main() -> Result<(), otp::OtpError>{
+/// let secret = b"12345678901234567890";
+/// let codes = ["755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583", "399871", "520489"];
+///
+/// for (counter, code) in codes.iter().enumerate(){
+///     assert_eq!(otp::hotp(secret, counter as u64, 6, Algorithm::Sha1)?, *code);
+/// }
+/// assert_eq!(otp::hotp(secret, 0, 6, Algorithm::Sha256)?, "875740");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [OtpError::InvalidDigits] if the digits aren't from 6 to 9.
+pub fn hotp(secret: &[u8], counter: u64, digits: u32, algorithm: Algorithm) -> Result<String, OtpError>{
+    if !(6..=9).contains(&digits){
+        return Err(OtpError::InvalidDigits);
+    }
+    let tag = match algorithm{
+        Algorithm::Sha1 => hmac_digest::<Sha1>(secret, &counter.to_be_bytes()),
+        Algorithm::Sha256 => hmac_digest::<Sha256>(secret, &counter.to_be_bytes()),
+    };
+    let offset = (tag[tag.len() - 1] & 0x0f) as usize;
+    let number = u32::from_be_bytes([tag[offset], tag[offset + 1], tag[offset + 2], tag[offset + 3]]) & 0x7fff_ffff;
+    Ok(format!("{:0width$}", number % 10_u32.pow(digits), width = digits as usize))
+}
+
+/// Returns the TOTP code at the time, in seconds since the unix epoch, with the period in seconds.
+///
+/// # Examples
+/// The SHA-1 and SHA256 test values of RFC 6238, appendix B, whose secrets are the digits repeated to the size of the hash:
+/// ```
+/// use mysha::otp::{self, Algorithm};
+/// # fn main() -> Result<(), otp::OtpError>{
+/// let sha1 = b"12345678901234567890";
+/// let codes = [(59, "94287082"), (1111111109, "07081804"), (1111111111, "14050471"),
+///     (1234567890, "89005924"), (2000000000, "69279037"), (20000000000, "65353130")];
+///
+/// for (time, code) in codes{
+///     assert_eq!(otp::totp(sha1, time, 30, 8, Algorithm::Sha1)?, code);
+/// }
+/// assert_eq!(otp::totp(b"12345678901234567890123456789012", 59, 30, 8, Algorithm::Sha256)?, "46119246");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [OtpError::InvalidPeriod] if the period is 0, and like [hotp] if the digits aren't valid.
+pub fn totp(secret: &[u8], time: u64, period: u64, digits: u32, algorithm: Algorithm) -> Result<String, OtpError>{
+    if period == 0{
+        return Err(OtpError::InvalidPeriod);
+    }
+    hotp(secret, time / period, digits, algorithm)
+}
+
+/// Seconds the TOTP code at the time is still valid for, from 1 to the period.
+pub fn remaining(time: u64, period: u64) -> u64{
+    period - time % period.max(1)
+}
@@ -0,0 +1,138 @@
+use clap::{Args, Subcommand};
+
+use mysha::cipher::otp;
+use mysha::encoding::hex;
+
+use crate::encode_cli::{get_bytes, get_text, print_bytes, Input, Output};
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct OtpArgs{
+    #[command(subcommand)]
+    subcommand: SubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommand{
+    /// Encrypts a message with a random key, or the key provided
+    Encrypt(EncryptArgs),
+    /// Decrypts data with the key
+    Decrypt(DecryptArgs),
+    /// Encrypts two messages with the same key, showing what the encrypted data reveals
+    Reuse(ReuseArgs),
+}
+
+#[derive(Args, Debug)]
+struct EncryptArgs{
+    /// message to be encrypted, read from stdin if not provided
+    message: Option<String>,
+
+    /// key in hex, at least as long as the message, random if not provided
+    #[arg(short, long)]
+    key: Option<String>,
+
+    /// message type
+    #[arg(short, long, default_value_t = Input::Text, value_enum)]
+    r#type: Input,
+
+    /// shows the key that decrypts the encrypted data to this other message, of the same length
+    #[arg(long)]
+    decoy: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct DecryptArgs{
+    /// encrypted data in hex, read from stdin if not provided
+    data: Option<String>,
+
+    /// key in hex, at least as long as the data
+    #[arg(short, long)]
+    key: String,
+
+    /// how the decrypted message is shown
+    #[arg(short, long, default_value_t = Output::Text, value_enum)]
+    r#type: Output,
+}
+
+#[derive(Args, Debug)]
+struct ReuseArgs{
+    /// first message
+    first: String,
+
+    /// second message
+    second: String,
+
+    /// key in hex, at least as long as the longer message, random if not provided
+    #[arg(short, long)]
+    key: Option<String>,
+}
+
+/// Printable ascii characters of the bytes, with `.` for the others.
+fn get_printable(bytes: &[u8]) -> String{
+    bytes.iter().map(|b| if b.is_ascii_graphic() || *b == b' ' {*b as char} else {'.'}).collect()
+}
+
+/// Prints the bytes as hex, spaced, followed by their printable characters.
+fn show(label: &str, bytes: &[u8]){
+    let encoded = hex::encode(bytes);
+    let spaced = encoded.as_bytes().chunks(2).map(|pair| std::str::from_utf8(pair).unwrap()).collect::<Vec<_>>().join(" ");
+    println!("{:<14}{}  |{}|", label, spaced, get_printable(bytes));
+}
+
+fn get_key(key: Option<String>, length: usize) -> Vec<u8>{
+    match key{
+        Some(key) => hex::decode(&key).exit("Error while parsing the key."),
+        None => otp::random_key(length),
+    }
+}
+
+pub fn one_time_pad(args: OtpArgs){
+    match args.subcommand{
+        SubCommand::Encrypt(args) => {
+            let message = get_bytes(args.message, &args.r#type);
+            let key = get_key(args.key, message.len());
+            let encrypted = otp::encrypt(&message, &key).exit("Error while encrypting.");
+            show("message:", &message);
+            show("key:", &key[..message.len()]);
+            show("encrypted:", &encrypted);
+
+            if let Some(decoy) = args.decoy{
+                let decoy_key = otp::key_for(&encrypted, decoy.as_bytes()).exit("Error while finding the key of the decoy.");
+                println!("\nPerfect secrecy: another key decrypts the same data to the decoy, so without the key every message is possible.");
+                show("decoy key:", &decoy_key);
+                show("decrypted:", &otp::decrypt(&encrypted, &decoy_key).unwrap());
+            }
+        },
+        SubCommand::Decrypt(args) => {
+            let data = hex::decode(&get_text(args.data)).exit("Error while parsing the encrypted data.");
+            let key = hex::decode(&args.key).exit("Error while parsing the key.");
+            let message = otp::decrypt(&data, &key).exit("Error while decrypting.");
+            print_bytes(&message, &args.r#type);
+        },
+        SubCommand::Reuse(args) => {
+            let (first, second) = (args.first.as_bytes(), args.second.as_bytes());
+            let key = get_key(args.key, first.len().max(second.len()));
+            let first_encrypted = otp::encrypt(first, &key).exit("Error while encrypting.");
+            let second_encrypted = otp::encrypt(second, &key).exit("Error while encrypting.");
+            show("key:", &key[..first.len().max(second.len())]);
+            show("encrypted 1:", &first_encrypted);
+            show("encrypted 2:", &second_encrypted);
+
+            let leak = otp::xor(&first_encrypted, &second_encrypted);
+            println!("\nThe key was reused, so it cancels out: the xor of the encrypted data is the xor of the messages.");
+            show("xor data:", &leak);
+            show("xor messages:", &otp::xor(first, second));
+
+            // a letter xored with a space is the same letter with the other case
+            let letters: String = leak.iter().map(|b| match b{
+                0 => '=',
+                b if b.is_ascii_alphabetic() => *b as char,
+                _ => '.',
+            }).collect();
+            println!("\nWhere one message has a space, the xor is the letter of the other with its case swapped, and = marks equal characters:");
+            println!("{:<14}{}", "letters:", letters);
+            println!("\nSo knowing the first message reveals the second:");
+            show("xor ^ first:", &otp::xor(&leak, first));
+        },
+    }
+}
@@ -0,0 +1,200 @@
+use std::time::{Duration, Instant};
+
+use clap::{Args, Subcommand, ValueEnum};
+
+use mysha::encoding::hex;
+use mysha::kdf::{argon2::{Params, Variant}, password, pbkdf2};
+
+use crate::encode_cli::get_text;
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct PasswordArgs{
+    #[command(subcommand)]
+    subcommand: SubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommand{
+    /// Hashes a password with a random salt, printing the encoded hash to be stored
+    Hash(PasswordHashArgs),
+    /// Verifies a password against an encoded hash
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Debug)]
+struct PasswordHashArgs{
+    /// password, read from stdin if not provided
+    password: Option<String>,
+
+    /// hashing algorithm
+    #[arg(short, long, default_value_t = Algorithm::Argon2id, value_enum)]
+    algorithm: Algorithm,
+
+    /// memory used by argon2, in KiB
+    #[arg(short, long, default_value_t = 19 * 1024)]
+    memory: u32,
+
+    /// iterations, 2 by default for argon2 and 600000 for pbkdf2
+    #[arg(short, long)]
+    iterations: Option<u32>,
+
+    /// lanes of argon2
+    #[arg(short, long, default_value_t = 1)]
+    parallelism: u32,
+
+    /// salt as text, random if no salt is provided
+    #[arg(long, conflicts_with_all = ["salt_hex", "no_salt"])]
+    salt: Option<String>,
+
+    /// salt in hex, instead of text
+    #[arg(long, conflicts_with = "no_salt")]
+    salt_hex: Option<String>,
+
+    /// hashes without a salt, only sha256 allows it, to show why salts are needed
+    #[arg(long)]
+    no_salt: bool,
+
+    /// measures this machine and suggests parameters that take the target time, instead of hashing
+    #[arg(long)]
+    benchmark: bool,
+
+    /// time in milliseconds hashing should take with the suggested parameters
+    #[arg(long, default_value_t = 500, requires = "benchmark")]
+    target: u64,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs{
+    /// encoded hash, as printed by hash
+    hash: String,
+
+    /// password, read from stdin if not provided
+    password: Option<String>,
+
+    /// Doesn't print anything, the result is only given by the exit status
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Prints only `valid` or `invalid`, to be parsed by scripts
+    #[arg(long, conflicts_with = "quiet")]
+    porcelain: bool,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+enum Algorithm{
+    /// Argon2id, the recommended one
+    Argon2id,
+    /// Argon2i, memory accesses independent of the password
+    Argon2i,
+    /// Argon2d, memory accesses dependent on the password
+    Argon2d,
+    /// PBKDF2 with HMAC-SHA256
+    Pbkdf2,
+    /// A single salted sha256, INSECURE, to show what salts do
+    Sha256,
+}
+
+fn get_algorithm(args: &PasswordHashArgs) -> password::Algorithm{
+    let variant = match args.algorithm{
+        Algorithm::Argon2id => Variant::Argon2id,
+        Algorithm::Argon2i => Variant::Argon2i,
+        Algorithm::Argon2d => Variant::Argon2d,
+        Algorithm::Pbkdf2 => return password::Algorithm::Pbkdf2{iterations: args.iterations.unwrap_or(pbkdf2::RECOMMENDED_ITERATIONS)},
+        Algorithm::Sha256 => return password::Algorithm::Sha256,
+    };
+    password::Algorithm::Argon2(Params{
+        variant,
+        memory: args.memory,
+        iterations: args.iterations.unwrap_or(Params::default().iterations),
+        parallelism: args.parallelism,
+    })
+}
+
+/// Time taken to hash a password with the algorithm.
+fn time(algorithm: &password::Algorithm) -> Duration{
+    let start = Instant::now();
+    password::hash_with_salt(b"benchmark", &[0; password::SALT_SIZE], algorithm).exit("Invalid parameters.");
+    start.elapsed()
+}
+
+/// Measures the algorithm with the parameters given, and scales the iterations of pbkdf2, or the memory of argon2, to the target time.
+fn benchmark(algorithm: password::Algorithm, target: Duration){
+    match algorithm{
+        password::Algorithm::Pbkdf2{..} => {
+            let measured = 10_000;
+            let elapsed = time(&password::Algorithm::Pbkdf2{iterations: measured});
+            let per_second = measured as f64 / elapsed.as_secs_f64();
+            let iterations = (per_second * target.as_secs_f64()) as u32;
+            println!("pbkdf2-sha256 runs {:.0} iterations per second on this machine", per_second);
+
+            let suggested = password::Algorithm::Pbkdf2{iterations: iterations.max(1)};
+            println!("{} iterations take {} ms", iterations, time(&suggested).as_millis());
+            if iterations < pbkdf2::RECOMMENDED_ITERATIONS{
+                println!("warning: that is less than the {} iterations recommended by OWASP, consider argon2id", pbkdf2::RECOMMENDED_ITERATIONS);
+            }
+            println!("\nmysha password hash -a pbkdf2 -i {}", iterations);
+        },
+        password::Algorithm::Argon2(params) => {
+            let elapsed = time(&password::Algorithm::Argon2(params.clone()));
+            println!("{} with {} KiB, {} iterations and {} lanes takes {} ms on this machine",
+                params.variant.name(), params.memory, params.iterations, params.parallelism, elapsed.as_millis());
+
+            // the time grows with the memory, so the memory is scaled, keeping whole MiB
+            let memory = (params.memory as f64 * target.as_secs_f64() / elapsed.as_secs_f64()) as u32 / 1024 * 1024;
+            let memory = memory.max(8 * params.parallelism);
+            let suggested = Params{memory, ..params.clone()};
+            println!("{} KiB take {} ms", memory, time(&password::Algorithm::Argon2(suggested.clone())).as_millis());
+            if memory < Params::default().memory{
+                println!("warning: that is less than the {} KiB recommended by OWASP, lower the iterations or raise the target", Params::default().memory);
+            }
+
+            println!("\nmysha password hash -a {} -m {} -i {} -p {}", params.variant.name(), memory, suggested.iterations, suggested.parallelism);
+        },
+        password::Algorithm::Sha256 => {
+            println!("sha256 takes {:?} on this machine, and has no cost to raise to the target, use argon2id", time(&algorithm));
+        },
+    }
+}
+
+pub fn password(args: PasswordArgs){
+    match args.subcommand{
+        SubCommand::Hash(args) => {
+            let algorithm = get_algorithm(&args);
+            if args.benchmark{
+                benchmark(algorithm, Duration::from_millis(args.target));
+                return;
+            }
+
+            let password = get_text(args.password);
+            let salt = match (&args.salt, &args.salt_hex){
+                (Some(salt), _) => salt.as_bytes().to_vec(),
+                (_, Some(salt)) => hex::decode(salt).exit("Error while parsing the salt."),
+                _ if args.no_salt => Vec::new(),
+                _ => password::generate_salt().to_vec(),
+            };
+            if algorithm == password::Algorithm::Sha256{
+                eprintln!("Warning: a single sha256 is INSECURE for passwords, every guess is a single hash.");
+            }
+            let hash = password::hash_with_salt(password.as_bytes(), &salt, &algorithm).exit("Error while hashing the password.");
+            println!("{}", hash);
+        },
+        SubCommand::Verify(args) => {
+            let password = get_text(args.password);
+            let valid = password::verify(password.as_bytes(), &args.hash).exit("Error while verifying the password.");
+            if args.porcelain{
+                println!("{}", if valid {"valid"} else {"invalid"});
+            }else if ! args.quiet{
+                if valid{
+                    println!("Password IS valid");
+                }else{
+                    println!("Password is NOT valid");
+                }
+            }
+            if ! valid{
+                Failure::Verification.exit();
+            }
+        },
+    }
+}
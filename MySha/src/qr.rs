@@ -0,0 +1,81 @@
+//! QR codes of the values printed by the commands.
+//!
+//! Hashes, public keys and signatures are long hex strings, the QR codes let them be moved to phones
+//! or air-gapped devices without retyping them. They can be printed on the terminal, or written to png or svg files.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use qrcode::{Color, QrCode};
+use qrcode::render::{svg, unicode::Dense1x2};
+
+use crate::Exit;
+
+/// Size of every module of the qr code in png files, in pixels.
+const MODULE_SIZE: usize = 8;
+/// Light modules around the qr code, so it can be read.
+const QUIET_ZONE: usize = 4;
+
+fn code(data: &str) -> QrCode{
+    QrCode::new(data).exit("Error while creating the qr code.")
+}
+
+/// Prints the qr code of the data on the terminal, with two modules on every character.
+///
+/// Dark modules are printed as blanks and light modules as blocks, so it can be read on terminals with dark backgrounds.
+pub fn print(data: &str){
+    let qr = code(data).render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build();
+    println!("{}", qr);
+}
+
+/// Adds the index of the value to the file name, before the extension, when there are many values to write.
+pub fn get_name_indexed(path: &str, index: usize, count: usize) -> String{
+    if count == 1{
+        return path.to_owned();
+    }
+    match path.rfind('.').filter(|dot| ! path[*dot..].contains(['/', '\\'])){
+        Some(dot) => format!("{}-{}{}", &path[..dot], index, &path[dot..]),
+        None => format!("{}-{}", path, index),
+    }
+}
+
+/// Writes the qr code of the data to a file, as svg if its name ends with .svg, and as png if not.
+/// The .png extension is added if the name has neither.
+pub fn write(data: &str, path: &str){
+    let qr = code(data);
+    if path.ends_with(".svg"){
+        let image = qr.render::<svg::Color>().build();
+        let mut file = File::create(path).exit("Error while creating the qr code file.");
+        file.write_all(image.as_bytes()).exit("Error while writing the qr code file.");
+        return;
+    }
+
+    let path = if ! path.ends_with(".png"){
+        path.to_owned() + ".png"
+    }else{
+        path.to_owned()
+    };
+    let width = qr.width();
+    let modules = qr.to_colors();
+    let size = (width + 2 * QUIET_ZONE) * MODULE_SIZE;
+    let mut pixels = vec![255_u8; size * size];
+    for (i, module) in modules.iter().enumerate(){
+        if *module == Color::Light{
+            continue;
+        }
+        let (x, y) = ((i % width + QUIET_ZONE) * MODULE_SIZE, (i / width + QUIET_ZONE) * MODULE_SIZE);
+        for row in y..y + MODULE_SIZE{
+            pixels[row * size + x..row * size + x + MODULE_SIZE].fill(0);
+        }
+    }
+
+    let file = File::create(&path).exit("Error while creating the qr code file.");
+    let mut encoder = png::Encoder::new(BufWriter::new(file), size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().exit("Error while writing the qr code file.");
+    writer.write_image_data(&pixels).exit("Error while writing the qr code file.");
+}
@@ -0,0 +1,93 @@
+//! Module for generating random values.
+//!
+//! All the randomness used by the crate, like private keys and the nonces of signatures, comes from this module,
+//! so there is a single place to check where the entropy comes from.
+//! [fill_random()] and [random_scalar()] use the random generator of the operating system.
+//!
+//! [DeterministicRng] generates the same values for the same seed, for tests and demos that need to be repeatable,
+//! with ChaCha20, whose output is specified, so the values don't change between builds or versions of the dependencies.
+//! It must never be used for real keys.
+//!
+//! # Examples
+//! ```
+//! use mysha::ecc::Curve;
+//! use mysha::rand;
+//!
+//! let curve = Curve::secp256k1();
+//!
+//! let private_key = rand::random_scalar(&curve);
+//! assert!(private_key < *curve.get_n());
+//!
+//! let mut salt = [0; 16];
+//! rand::fill_random(&mut salt);
+//! ```
+
+#[cfg(feature = "ecc")]
+use num_bigint::{BigUint, RandBigInt};
+use ::rand::{rngs::OsRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+#[cfg(feature = "ecc")]
+use crate::ecc::Curve;
+
+/// Fills the bytes with random bytes from the operating system.
+pub fn fill_random(bytes: &mut [u8]){
+    OsRng.fill_bytes(bytes);
+}
+
+/// Returns a random number from 1 to the order of the curve minus 1, valid as a private key or a nonce.
+///
+/// # Panics
+///
+/// Panics if the order of the curve is 0 or 1, as there is no number in the range.
+#[cfg(feature = "ecc")]
+pub fn random_scalar(curve: &Curve) -> BigUint{
+    OsRng.gen_biguint_range(&BigUint::from(1_u8), curve.get_n())
+}
+
+/// Random generator that always generates the same values for the same seed.
+///
+/// It is ChaCha20 keyed with the seed, so the same seed gives the same bytes on every platform and build.
+///
+/// # Examples
+/// ```
+/// use mysha::ecc::Curve;
+/// use mysha::rand::DeterministicRng;
+///
+/// let curve = Curve::secp256k1();
+///
+/// let mut a = DeterministicRng::new(42);
+/// let mut b = DeterministicRng::new(42);
+///
+/// assert_eq!(a.random_scalar(&curve), b.random_scalar(&curve));
+///
+/// let mut bytes = [0; 8];
+/// DeterministicRng::new(42).fill_random(&mut bytes);
+/// assert_eq!(bytes, [31, 118, 229, 38, 81, 10, 227, 106]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeterministicRng(ChaCha20Rng);
+
+impl DeterministicRng{
+    /// Creates the generator from the seed, the key of ChaCha20 is the seed in little endian followed by zeros.
+    pub fn new(seed: u64) -> DeterministicRng{
+        let mut key = [0; 32];
+        key[..8].copy_from_slice(&seed.to_le_bytes());
+        DeterministicRng(ChaCha20Rng::from_seed(key))
+    }
+
+    /// Same as [fill_random()], with the next bytes of the generator.
+    pub fn fill_random(&mut self, bytes: &mut [u8]){
+        self.0.fill_bytes(bytes);
+    }
+
+    /// Same as [random_scalar()], with the next bytes of the generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the order of the curve is 0 or 1, as there is no number in the range.
+    #[cfg(feature = "ecc")]
+    pub fn random_scalar(&mut self, curve: &Curve) -> BigUint{
+        self.0.gen_biguint_range(&BigUint::from(1_u8), curve.get_n())
+    }
+}
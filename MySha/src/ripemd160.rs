@@ -0,0 +1,196 @@
+//! Module with the [RIPEMD-160] hash, which Bitcoin applies to the sha256 of public keys and scripts for their 20 byte hashes.
+//!
+//! It is a Merkle-Damgård construction like sha256, with blocks of 64 bytes, but with little endian words and lengths,
+//! and two parallel lines of 80 rounds over the same block, with different constants, orders of the words and rotations,
+//! combined at the end of every block.
+//!
+//! # Examples
+//! ```
+//! use mysha::ripemd160::Ripemd160;
+//!
+//! let hash = Ripemd160::digest(b"abc");
+//! assert_eq!(hash[..4], [0x8e, 0xb2, 0x08, 0xf7]);
+//! ```
+//!
+//! [RIPEMD-160]: https://homes.esat.kuleuven.be/~bosselae/ripemd160.html
+
+use crate::digest::Digest;
+
+/// Initial hash values, the same as SHA-1.
+const INITIAL_STATE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// Constants of the left line, one for each 16 rounds.
+const K_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+/// Constants of the right line.
+const K_RIGHT: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// Word of the block read by every round of the left line.
+const R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+/// Word of the block read by every round of the right line.
+const R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+/// Rotation of every round of the left line.
+const S_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+/// Rotation of every round of the right line.
+const S_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// Boolean function of the group of 16 rounds, the right line uses them in the reverse order.
+fn f(group: usize, x: u32, y: u32, z: u32) -> u32{
+    match group{
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+/// Incremental RIPEMD-160 hasher.
+///
+/// # Examples
+/// ```
+/// use mysha::ripemd160::Ripemd160;
+///
+/// let mut hasher = Ripemd160::new();
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+///
+/// assert_eq!(hasher.finalize(), Ripemd160::digest(b"hello world"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ripemd160{
+    state: [u32; 5],
+    buffer: [u8; 64],
+    length: u64,
+}
+
+impl Default for Ripemd160{
+    fn default() -> Self{
+        Ripemd160::new()
+    }
+}
+
+impl Ripemd160{
+    /// Creates a hasher with no data.
+    pub fn new() -> Ripemd160{
+        Ripemd160{
+            state: INITIAL_STATE,
+            buffer: [0; 64],
+            length: 0,
+        }
+    }
+
+    /// Hashes the data in one call.
+    pub fn digest(data: &[u8]) -> [u8; 20]{
+        let mut hasher = Ripemd160::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Adds the data to the message being hashed.
+    pub fn update(&mut self, mut data: &[u8]){
+        let buffered = (self.length % 64) as usize;
+        self.length += data.len() as u64;
+        if buffered > 0{
+            let needed = (64 - buffered).min(data.len());
+            self.buffer[buffered..buffered + needed].copy_from_slice(&data[..needed]);
+            data = &data[needed..];
+            if buffered + needed < 64{
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks{
+            self.compress(block.try_into().unwrap());
+        }
+        let remainder = blocks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+    }
+
+    /// Pads the message, like sha256 but with the length in little endian, and returns the hash.
+    pub fn finalize(mut self) -> [u8; 20]{
+        let bits = self.length.wrapping_mul(8);
+        let mut padding = [0; 72];
+        padding[0] = 0x80;
+        let zeros = (119 - (self.length % 64) as usize) % 64;
+        padding[zeros + 1..zeros + 9].copy_from_slice(&bits.to_le_bytes());
+        self.update(&padding[..zeros + 9]);
+
+        let mut hash = [0; 20];
+        for (chunk, word) in hash.chunks_exact_mut(4).zip(self.state){
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        hash
+    }
+
+    fn compress(&mut self, block: &[u8; 64]){
+        let mut x = [0_u32; 16];
+        for (i, chunk) in block.chunks_exact(4).enumerate(){
+            x[i] = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let [mut al, mut bl, mut cl, mut dl, mut el] = self.state;
+        let [mut ar, mut br, mut cr, mut dr, mut er] = self.state;
+        for j in 0..80{
+            let group = j / 16;
+            let t = al.wrapping_add(f(group, bl, cl, dl)).wrapping_add(x[R_LEFT[j]]).wrapping_add(K_LEFT[group])
+                .rotate_left(S_LEFT[j]).wrapping_add(el);
+            (al, el, dl, cl, bl) = (el, dl, cl.rotate_left(10), bl, t);
+
+            let t = ar.wrapping_add(f(4 - group, br, cr, dr)).wrapping_add(x[R_RIGHT[j]]).wrapping_add(K_RIGHT[group])
+                .rotate_left(S_RIGHT[j]).wrapping_add(er);
+            (ar, er, dr, cr, br) = (er, dr, cr.rotate_left(10), br, t);
+        }
+
+        // the lines are combined with the state rotated by one word
+        let [h0, h1, h2, h3, h4] = self.state;
+        self.state = [
+            h1.wrapping_add(cl).wrapping_add(dr),
+            h2.wrapping_add(dl).wrapping_add(er),
+            h3.wrapping_add(el).wrapping_add(ar),
+            h4.wrapping_add(al).wrapping_add(br),
+            h0.wrapping_add(bl).wrapping_add(cr),
+        ];
+    }
+}
+
+impl Digest for Ripemd160{
+    const SIZE: usize = 20;
+
+    fn new() -> Ripemd160{
+        Ripemd160::new()
+    }
+
+    fn update(&mut self, data: &[u8]){
+        Ripemd160::update(self, data)
+    }
+
+    fn finish(self) -> Vec<u8>{
+        self.finalize().to_vec()
+    }
+}
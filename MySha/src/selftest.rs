@@ -0,0 +1,276 @@
+//! Module for running standard test vectors against the implementations of this crate.
+//!
+//! Two formats are read:
+//! - [CAVP] response files, `.rsp`, of the NIST validation program, for sha256: the short and long messages files,
+//!   with `Len`, `Msg` and `MD`, and the Monte Carlo file, with a `Seed` and a `MD` for every `COUNT`;
+//! - [Wycheproof] json files, of ECDSA verification over secp256k1 with sha256, with signatures in DER (`EcdsaVerify`)
+//!   or as r and s concatenated (`EcdsaP1363Verify`). They include many invalid signatures, all of which must be rejected.
+//!
+//! Vectors that can't be run, like CAVP signature files of the NIST curves or Wycheproof groups of other curves and hashes,
+//! are counted as skipped, so one file can be run whole.
+//!
+//! # Examples
+//! ```
+//! use mysha::selftest;
+//! # fn main() -> Result<(), selftest::VectorError>{
+//! let report = selftest::run("Len = 24\nMsg = 616263\nMD = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n")?;
+//! assert_eq!(report.passed(), 1);
+//! assert!(report.is_ok());
+//!
+//! assert!(selftest::builtin().is_ok());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [CAVP]: https://csrc.nist.gov/projects/cryptographic-algorithm-validation-program/secure-hashing
+//! [Wycheproof]: https://github.com/C2SP/wycheproof
+
+use std::fmt;
+
+use num_bigint::BigUint;
+use serde_json::Value;
+
+use crate::ecc::{Curve, Point, PubKey, Signature};
+use crate::encoding::hex;
+use crate::sha256::Sha256;
+
+/// Vectors of [builtin], from FIPS 180-4 and the short messages file of CAVP.
+const BUILTIN: &str = "\
+# FIPS 180-4 examples, and SHA256ShortMsg.rsp
+Len = 0
+Msg = 00
+MD = e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+
+Len = 8
+Msg = d3
+MD = 28969cdfa74a12c82f3bad960b0b000aca2ac329deea5c2328ebc6f2ba9802c1
+
+Len = 24
+Msg = 616263
+MD = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+
+Len = 448
+Msg = 6162636462636465636465666465666765666768666768696768696a68696a6b696a6b6c6a6b6c6d6b6c6d6e6c6d6e6f6d6e6f706e6f7071
+MD = 248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1
+";
+
+/// The error type of this module, for files that can't be read as test vectors.
+#[derive(Debug, PartialEq)]
+pub enum VectorError{
+    /// Happens when the text is neither a CAVP response file nor a Wycheproof json file.
+    UnknownFormat,
+    /// Happens when a line of a CAVP file, or a field of a Wycheproof file, isn't what the format expects, at the line
+    /// or test case id.
+    InvalidVector{
+        id: String,
+    },
+}
+
+impl fmt::Display for VectorError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            VectorError::UnknownFormat => write!(f, "The file isn't a CAVP response file or a Wycheproof json file."),
+            VectorError::InvalidVector{id} => write!(f, "Invalid test vector {}.", id),
+        }
+    }
+}
+
+/// The result of one vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorResult{
+    /// Where the vector is, like `Len = 24` or `tcId 3`.
+    pub id: String,
+    /// Whether the implementation gave the expected result.
+    pub passed: bool,
+    /// What was expected, and the comment of the vector, if it has one.
+    pub comment: String,
+}
+
+/// The results of the vectors of a file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report{
+    /// Every vector run, in the order of the file.
+    pub results: Vec<VectorResult>,
+    /// Vectors that weren't run, since they are of algorithms this crate doesn't implement.
+    pub skipped: usize,
+}
+
+impl Report{
+    /// Number of vectors that passed.
+    pub fn passed(&self) -> usize{
+        self.results.iter().filter(|result| result.passed).count()
+    }
+
+    /// Number of vectors that failed.
+    pub fn failed(&self) -> usize{
+        self.results.len() - self.passed()
+    }
+
+    /// Whether every vector run passed.
+    pub fn is_ok(&self) -> bool{
+        self.failed() == 0
+    }
+
+    fn push(&mut self, id: String, passed: bool, comment: String){
+        self.results.push(VectorResult{id, passed, comment});
+    }
+}
+
+/// Runs the vectors of the text, as a Wycheproof file if it is json, or as a CAVP file otherwise.
+///
+/// # Errors
+/// Same as [run_cavp] and [run_wycheproof].
+pub fn run(text: &str) -> Result<Report, VectorError>{
+    if text.trim_start().starts_with('{'){
+        run_wycheproof(text)
+    }else{
+        run_cavp(text)
+    }
+}
+
+/// Runs the vectors that are part of the crate, a few sha256 messages, for a quick check without files.
+pub fn builtin() -> Report{
+    run_cavp(BUILTIN).unwrap()
+}
+
+fn decode(text: &str, id: &str) -> Result<Vec<u8>, VectorError>{
+    hex::decode(text).map_err(|_| VectorError::InvalidVector{id: id.to_string()})
+}
+
+/// Runs the sha256 vectors of a CAVP response file.
+///
+/// # Errors
+/// Returns [VectorError::InvalidVector] if a message or hash isn't hex, or a `Len` isn't a number of bytes of the message,
+/// and [VectorError::UnknownFormat] if there are no vectors in the text.
+pub fn run_cavp(text: &str) -> Result<Report, VectorError>{
+    let mut report = Report::default();
+    let (mut length, mut message, mut count) = (None, None, String::new());
+    // the Monte Carlo file has a seed, that is replaced by the result of every count
+    let mut seed: Option<[u8; 32]> = None;
+    let mut fields = 0;
+
+    for (number, line) in text.lines().enumerate(){
+        let line = line.trim();
+        let id = format!("line {}", number + 1);
+        if line.is_empty() || line.starts_with('#') || line.starts_with('['){
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(VectorError::InvalidVector{id});
+        };
+        let (key, value) = (key.trim(), value.trim());
+        fields += 1;
+        match key{
+            "Len" => length = Some(value.parse::<usize>().map_err(|_| VectorError::InvalidVector{id: id.clone()})?),
+            "Msg" => message = Some(decode(value, &id)?),
+            "Seed" => seed = Some(decode(value, &id)?.try_into().map_err(|_| VectorError::InvalidVector{id})?),
+            "COUNT" => count = value.to_string(),
+            "MD" => {
+                let expected = decode(value, &id)?;
+                if let Some(current) = &seed{
+                    let result = monte_carlo(current);
+                    report.push(format!("COUNT = {}", count), result[..] == expected[..], String::from("Monte Carlo"));
+                    seed = Some(result);
+                    continue;
+                }
+                let (Some(bits), Some(data)) = (length.take(), message.take()) else {
+                    return Err(VectorError::InvalidVector{id});
+                };
+                if !bits.is_multiple_of(8) || bits / 8 > data.len(){
+                    return Err(VectorError::InvalidVector{id});
+                }
+                let passed = Sha256::digest(&data[..bits / 8])[..] == expected[..];
+                report.push(format!("Len = {}", bits), passed, format!("{} byte message", bits / 8));
+            },
+            // signature files, of curves this crate doesn't have
+            "Result" => report.skipped += 1,
+            _ => (),
+        }
+    }
+    if fields == 0{
+        return Err(VectorError::UnknownFormat);
+    }
+    Ok(report)
+}
+
+/// One count of the Monte Carlo test: every hash is of the three before it, starting with the seed three times,
+/// and the thousandth is the result.
+fn monte_carlo(seed: &[u8; 32]) -> [u8; 32]{
+    let mut last = [*seed; 3];
+    for _ in 0..1000{
+        last = [last[1], last[2], Sha256::digest(&last.concat())];
+    }
+    last[2]
+}
+
+/// Runs the ECDSA vectors of a Wycheproof json file.
+///
+/// Vectors with the result `acceptable`, like signatures with a high s, pass whether they are accepted or not.
+///
+/// # Errors
+/// Returns [VectorError::UnknownFormat] if the text isn't json with test groups,
+/// and [VectorError::InvalidVector] if a key, message or signature of a secp256k1 group isn't hex.
+pub fn run_wycheproof(text: &str) -> Result<Report, VectorError>{
+    let json: Value = serde_json::from_str(text).map_err(|_| VectorError::UnknownFormat)?;
+    let groups = json["testGroups"].as_array().ok_or(VectorError::UnknownFormat)?;
+    let mut report = Report::default();
+
+    for group in groups{
+        let tests = group["tests"].as_array().map_or(&[][..], |tests| &tests[..]);
+        let p1363 = match group["type"].as_str(){
+            Some("EcdsaVerify") => false,
+            Some("EcdsaP1363Verify") => true,
+            _ => {
+                report.skipped += tests.len();
+                continue;
+            },
+        };
+        let key = if group["key"].is_object() {&group["key"]} else {&group["publicKey"]};
+        if key["curve"] != "secp256k1" || group["sha"] != "SHA-256"{
+            report.skipped += tests.len();
+            continue;
+        }
+        let public = wycheproof_key(key)?;
+
+        for test in tests{
+            let id = format!("tcId {}", test["tcId"]);
+            let field = |name: &str| decode(test[name].as_str().unwrap_or_default(), &id);
+            let (message, signature) = (field("msg")?, field("sig")?);
+            let expected = test["result"].as_str().unwrap_or_default();
+            let accepted = verify_wycheproof(&public, &message, &signature, p1363);
+            let passed = match expected{
+                "valid" => accepted,
+                "invalid" => !accepted,
+                _ => true,
+            };
+            let comment = match test["comment"].as_str(){
+                Some(comment) if !comment.is_empty() => format!("{}, {}", expected, comment),
+                _ => expected.to_string(),
+            };
+            report.push(id, passed, comment);
+        }
+    }
+    Ok(report)
+}
+
+fn wycheproof_key(key: &Value) -> Result<PubKey, VectorError>{
+    let invalid = || VectorError::InvalidVector{id: format!("key {}", key)};
+    let coordinate = |name: &str| key[name].as_str().and_then(|text| hex::decode(text).ok()).ok_or_else(invalid);
+    let point = Point::point(BigUint::from_bytes_be(&coordinate("wx")?), BigUint::from_bytes_be(&coordinate("wy")?));
+    PubKey::new(point, Curve::secp256k1()).map_err(|_| invalid())
+}
+
+/// Whether the signature is accepted, any error reading it is a rejection.
+fn verify_wycheproof(public: &PubKey, message: &[u8], signature: &[u8], p1363: bool) -> bool{
+    let (curve, point) = (public.get_curve().clone(), public.get_public().clone());
+    let signature = if p1363{
+        if signature.len() != 64{
+            return false;
+        }
+        let (r, s) = signature.split_at(32);
+        Signature::new(BigUint::from_bytes_be(r), BigUint::from_bytes_be(s), curve, point).ok()
+    }else{
+        Signature::from_der(signature, curve, point).ok()
+    };
+    signature.is_some_and(|signature| signature.verify_digest::<Sha256>(message).unwrap_or(false))
+}
@@ -0,0 +1,47 @@
+use std::fs;
+
+use clap::Args;
+
+use mysha::selftest::{self, Report};
+
+use crate::failure::Failure;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct SelftestArgs{
+    /// CAVP .rsp files of sha256, or Wycheproof .json files of ECDSA over secp256k1, the builtin sha256 vectors are run if none are provided
+    files: Vec<String>,
+
+    /// only prints the vectors that failed, and the totals
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+fn print_report(name: &str, report: &Report, quiet: bool){
+    for result in &report.results{
+        if !result.passed{
+            println!("FAIL {}: {} ({})", name, result.id, result.comment);
+        }else if !quiet{
+            println!("PASS {}: {} ({})", name, result.id, result.comment);
+        }
+    }
+    println!("{}: {} passed, {} failed, {} skipped", name, report.passed(), report.failed(), report.skipped);
+}
+
+pub fn self_test(args: SelftestArgs){
+    let mut ok = true;
+    if args.files.is_empty(){
+        let report = selftest::builtin();
+        print_report("builtin", &report, args.quiet);
+        ok = report.is_ok();
+    }
+    for file in &args.files{
+        let text = fs::read_to_string(file).exit("Error while reading the file.");
+        let report = selftest::run(&text).exit(&format!("Can't run the vectors of {}.", file));
+        print_report(file, &report, args.quiet);
+        ok &= report.is_ok();
+    }
+    if !ok{
+        Failure::Verification.exit();
+    }
+}
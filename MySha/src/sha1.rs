@@ -0,0 +1,153 @@
+//! Module with the [SHA-1] hash, the hash of the object ids of git.
+//!
+//! SHA-1 is broken: two different messages with the same hash were found in 2017, and chosen prefix collisions are practical,
+//! so it must not be used for signatures or anything an attacker chooses. It is only here to compute and check the ids of git,
+//! which still uses it by default, alongside sha256 object formats.
+//!
+//! Its structure is the one of sha256, a Merkle-Damgård construction with the same padding and blocks of 64 bytes,
+//! but with a state of 5 words and 80 simpler rounds.
+//!
+//! # Examples
+//! ```
+//! use mysha::sha1::Sha1;
+//!
+//! let hash = Sha1::digest(b"abc");
+//! assert_eq!(hash[..4], [0xa9, 0x99, 0x3e, 0x36]);
+//! ```
+//!
+//! [SHA-1]: https://csrc.nist.gov/pubs/fips/180-4/upd1/final
+
+use crate::digest::Digest;
+
+/// Initial hash values of the specification.
+const INITIAL_STATE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// Round constants, one for each 20 rounds.
+const K: [u32; 4] = [0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xca62c1d6];
+
+/// Incremental SHA-1 hasher.
+///
+/// # Examples
+/// ```
+/// use mysha::sha1::Sha1;
+///
+/// let mut hasher = Sha1::new();
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+///
+/// assert_eq!(hasher.finalize(), Sha1::digest(b"hello world"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sha1{
+    state: [u32; 5],
+    buffer: [u8; 64],
+    length: u64,
+}
+
+impl Default for Sha1{
+    fn default() -> Self{
+        Sha1::new()
+    }
+}
+
+impl Sha1{
+    /// Creates a hasher with no data.
+    pub fn new() -> Sha1{
+        Sha1{
+            state: INITIAL_STATE,
+            buffer: [0; 64],
+            length: 0,
+        }
+    }
+
+    /// Hashes the data in one call.
+    pub fn digest(data: &[u8]) -> [u8; 20]{
+        let mut hasher = Sha1::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Adds the data to the message being hashed.
+    pub fn update(&mut self, mut data: &[u8]){
+        let buffered = (self.length % 64) as usize;
+        self.length += data.len() as u64;
+        if buffered > 0{
+            let needed = (64 - buffered).min(data.len());
+            self.buffer[buffered..buffered + needed].copy_from_slice(&data[..needed]);
+            data = &data[needed..];
+            if buffered + needed < 64{
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks{
+            self.compress(block.try_into().unwrap());
+        }
+        let remainder = blocks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+    }
+
+    /// Pads the message, like sha256, and returns the hash.
+    pub fn finalize(mut self) -> [u8; 20]{
+        let bits = self.length.wrapping_mul(8);
+        let mut padding = [0; 72];
+        padding[0] = 0x80;
+        let zeros = (119 - (self.length % 64) as usize) % 64;
+        padding[zeros + 1..zeros + 9].copy_from_slice(&bits.to_be_bytes());
+        self.update(&padding[..zeros + 9]);
+
+        let mut hash = [0; 20];
+        for (chunk, word) in hash.chunks_exact_mut(4).zip(self.state){
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        hash
+    }
+
+    fn compress(&mut self, block: &[u8; 64]){
+        let mut w = [0_u32; 80];
+        for (i, chunk) in block.chunks_exact(4).enumerate(){
+            w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+        // the rotation by one is the only difference with SHA-0, which was withdrawn for it
+        for i in 16..80{
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = self.state;
+        for (i, word) in w.iter().enumerate(){
+            let f = match i / 20{
+                0 => (b & c) | (!b & d),
+                2 => (b & c) | (b & d) | (c & d),
+                _ => b ^ c ^ d,
+            };
+            let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(K[i / 20]).wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = t;
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e]){
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+impl Digest for Sha1{
+    const SIZE: usize = 20;
+
+    fn new() -> Sha1{
+        Sha1::new()
+    }
+
+    fn update(&mut self, data: &[u8]){
+        Sha1::update(self, data)
+    }
+
+    fn finish(self) -> Vec<u8>{
+        self.finalize().to_vec()
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::OnceLock;
+
+use super::{constants, Hash256};
+use crate::digest::Digest;
+
+/// Initial hash values, computed once from the primes like in [sha256()][super::sha256()].
+fn initial_state() -> [u32; 8]{
+    static A: OnceLock<[u32; 8]> = OnceLock::new();
+    *A.get_or_init(|| constants::initialize_a().try_into().unwrap())
+}
+
+/// Round constants, computed once from the primes like in [sha256()][super::sha256()].
+fn round_constants() -> &'static [u32; 64]{
+    static K: OnceLock<[u32; 64]> = OnceLock::new();
+    K.get_or_init(|| constants::initialize_k().try_into().unwrap())
+}
+
+/// Incremental sha256 hasher working directly on bytes.
+///
+/// It computes the same hashes as [sha256()][super::sha256()], but keeps the words as numbers instead of binary text,
+/// so it is much faster, and data can be added in parts, as it arrives.
+/// It is the hash used by the [hmac][crate::hmac] and [kdf][crate::kdf] modules, which hash many times.
+///
+/// # Examples
+/// ```
+/// use mysha::sha256::{sha256, InputType, HashError, Sha256};
+/// # fn main() -> Result<(), HashError>{
+/// let mut hasher = Sha256::new();
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+///
+/// assert_eq!(hasher.finalize(), sha256("hello world", InputType::Text)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sha256{
+    state: [u32; 8],
+    buffer: [u8; 64],
+    length: u64,
+}
+
+impl Default for Sha256{
+    fn default() -> Self{
+        Sha256::new()
+    }
+}
+
+impl Sha256{
+    /// Creates a hasher with no data.
+    pub fn new() -> Sha256{
+        Sha256{
+            state: initial_state(),
+            buffer: [0; 64],
+            length: 0,
+        }
+    }
+
+    /// Hashes the data in one call.
+    pub fn digest(data: &[u8]) -> [u8; 32]{
+        let mut hasher = Sha256::new();
+        hasher.update(data);
+        hasher.finalize_bytes()
+    }
+
+    /// Adds the data to the message being hashed.
+    pub fn update(&mut self, mut data: &[u8]){
+        let buffered = (self.length % 64) as usize;
+        self.length += data.len() as u64;
+        if buffered > 0{
+            let needed = (64 - buffered).min(data.len());
+            self.buffer[buffered..buffered + needed].copy_from_slice(&data[..needed]);
+            data = &data[needed..];
+            if buffered + needed < 64{
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks{
+            self.compress(block.try_into().unwrap());
+        }
+        let remainder = blocks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+    }
+
+    /// Pads the message and returns the hash.
+    pub fn finalize(self) -> Hash256{
+        Hash256::from(self.finalize_bytes())
+    }
+
+    /// Pads the message and returns the bytes of the hash.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = self.length)))]
+    pub fn finalize_bytes(mut self) -> [u8; 32]{
+        let bits = self.length.wrapping_mul(8);
+        let mut padding = [0; 72];
+        padding[0] = 0x80;
+        let zeros = (119 - (self.length % 64) as usize) % 64;
+        padding[zeros + 1..zeros + 9].copy_from_slice(&bits.to_be_bytes());
+        self.update(&padding[..zeros + 9]);
+
+        let mut hash = [0; 32];
+        for (chunk, word) in hash.chunks_exact_mut(4).zip(self.state){
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        hash
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "sha256_block", level = "trace", skip_all))]
+    fn compress(&mut self, block: &[u8; 64]){
+        let k = round_constants();
+        let mut w = [0_u32; 64];
+        for (i, chunk) in block.chunks_exact(4).enumerate(){
+            w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+        for i in 16..64{
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ w[i - 15] >> 3;
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ w[i - 2] >> 10;
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64{
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(k[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(majority);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]){
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+impl Digest for Sha256{
+    const SIZE: usize = 32;
+
+    fn new() -> Sha256{
+        Sha256::new()
+    }
+
+    fn update(&mut self, data: &[u8]){
+        Sha256::update(self, data)
+    }
+
+    fn finish(self) -> Vec<u8>{
+        self.finalize_bytes().to_vec()
+    }
+}
@@ -1,5 +1,6 @@
 pub mod binary_handling{
     use super::super::HashError;
+    use crate::encoding::{hex, EncodingError};
 
     pub fn get_binary_message(message: &str) -> String{
         let bytes = message.to_owned().into_bytes();
@@ -25,10 +26,12 @@ pub mod binary_handling{
         let mut bits = String::new();
         let mut message = String::from(message);
         if le{
-            if message.len() % 2 != 0{
-                return Err(HashError::NotWholeBytes);
-            }
-            message = (0..message.len()).step_by(2).rev().map(|i| &message[i..i+2]).collect();
+            message = hex::reverse(&message).map_err(|err|{
+                match err{
+                    EncodingError::OddLength{..} => HashError::NotWholeBytes,
+                    _ => HashError::InvalidHex,
+                }
+            })?;
         }
         for hex in message.chars(){
             bits += format!("{:04b}", u8::from_str_radix(hex.to_string().as_str(), 16).map_err(|_| HashError::InvalidHex)?).as_str();
@@ -43,7 +46,7 @@ pub mod binary_handling{
 
         *message += "1";
 
-        while (message.len() + 64) % 512 != 0{
+        while !(message.len() + 64).is_multiple_of(512){
             *message += "0";
         }
 
@@ -53,22 +56,24 @@ pub mod binary_handling{
     pub fn get_message_blocks(message: &str) -> Vec<String>{
         let mut message_blocks = Vec::new();
 
-        for i in (0..message.len()).step_by(512){
-            message_blocks.push(String::from(&message[i..i+512]));
+        for block in message.as_bytes().chunks(512){
+            message_blocks.push(String::from_utf8_lossy(block).into_owned());
         }
 
 
         message_blocks
     }
 
-    pub fn get_message_schedule(block: &str) -> Vec<u32>{
+    /// The 16 words of the block, failing with [HashError::InvalidBinary] if it has anything other than bits.
+    pub fn get_message_schedule(block: &str) -> Result<Vec<u32>, HashError>{
         let mut message_schedule = Vec::new();
 
-        for i in (0..block.len()).step_by(32){
-            message_schedule.push(u32::from_str_radix(&block[i..i+32], 2).unwrap());
+        for word in block.as_bytes().chunks(32){
+            let word = std::str::from_utf8(word).ok().and_then(|word| u32::from_str_radix(word, 2).ok());
+            message_schedule.push(word.ok_or(HashError::InvalidBinary)?);
         }
 
-        message_schedule
+        Ok(message_schedule)
 
     }
 }
@@ -78,7 +83,7 @@ pub mod operations{
     pub fn add(a: u32, b: u32) -> u32{
         let a:u64 = a as u64;
         let b:u64 = b as u64;
-        let bits:u32 = ((a + b) % (2 as u64).pow(32)) as u32;
+        let bits:u32 = ((a + b) % 2_u64.pow(32)) as u32;
         bits
     }
 
@@ -150,7 +155,7 @@ pub mod operations{
 pub mod constants{
     pub fn get_primes(n: u8) -> Vec<f64>{
         let mut primes = Vec::new();
-        primes.push(2 as f64);
+        primes.push(2_f64);
         let mut i: f64 = 3.0;
         while primes.len() < n.into(){
             let mut is_prime = true;
@@ -162,7 +167,7 @@ pub mod constants{
             }
 
             if is_prime{
-                primes.push(i as f64);
+                primes.push(i);
             }
 
             i += 1.0;
@@ -177,7 +182,7 @@ pub mod constants{
         let primes = get_primes(8);
 
         for i in primes{
-            a.push(((i.sqrt() - i.sqrt().trunc()) * (2 as f64).powf(32.0)) as u32)
+            a.push(((i.sqrt() - i.sqrt().trunc()) * 2_f64.powf(32.0)) as u32)
         }
 
         a
@@ -189,7 +194,7 @@ pub mod constants{
         let primes = get_primes(64);
 
         for i in primes{
-            k.push(((i.cbrt() - i.cbrt().trunc()) * (2 as f64).powf(32.0)) as u32)
+            k.push(((i.cbrt() - i.cbrt().trunc()) * 2_f64.powf(32.0)) as u32)
         }
 
         k
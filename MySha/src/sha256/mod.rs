@@ -15,18 +15,33 @@
 //! ```
 //! 
 //! **Warning** : the hashing algorithm isn't optimized in the most efficient and fast way.
-//! 
+//! The [Sha256] hasher computes the same hashes working directly on bytes, for when speed matters.
+//!
+//! To follow the steps of a hash, the bits and padding, the words of the message schedule, and a to h after every round,
+//! `mysha::animation::walkthrough`, with the `animation` feature, hashes like [sha256()] and gives every step as an event
+//! to a `Renderer`, which can be a closure. The animation and the `--trace` files of the cli are both renderers of it.
+//!
 //! [sha256 algorithm]: https://en.wikipedia.org/wiki/SHA-2
 
 
-use std::{fmt, fs::File, io::Read};
-use num_bigint::{BigUint, BigInt};
+use std::{cmp::Ordering, fmt, io::Read};
+#[cfg(feature = "tokio")]
+use std::path::Path;
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+#[cfg(feature = "bigint")]
+use num_bigint::{BigUint, BigInt, Sign};
 
-mod helper_functions;
+pub(crate) mod helper_functions;
 use helper_functions::*;
-use num_traits::Num;
+mod hasher;
+pub use hasher::Sha256;
+mod truncated;
+pub use truncated::TruncatedHash;
+use crate::encoding;
 
 /// Enum used to define the input type provided to the [sha256()] function.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputType{
     /// Treats the input as an utf-8 text
     Text,
@@ -34,7 +49,7 @@ pub enum InputType{
     Binary,
     /// treats the input as a little endian binary value, inverse byte order
     LeBinary,
-    /// Treats the input as a file
+    /// Treats the input as a file, its path. Hashing fails with [HashError::ErrorWithFile] on wasm32-unknown-unknown, which has no file system
     File,
     /// Treats the input as a hexadecimal value
     Hex,
@@ -44,11 +59,41 @@ pub enum InputType{
     Decimal,
 }
 
+/// How [InputType::Hex] and [InputType::LeHex] treat an odd number of digits.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OddLength{
+    /// Every digit is 4 bits, so the message ends in half a byte. Little endian input still needs whole bytes.
+    #[default]
+    Nibbles,
+    /// A zero is added at the start, so `abc` is read as `0abc`, like a number.
+    PadLeft,
+    /// Odd input is an error, [HashError::NotWholeBytes].
+    Reject,
+}
+
+/// Options for parsing hex input, see [sha256_with_options()] and [normalize_hex()].
+///
+/// The default is strict, only hex digits are accepted, and an odd number of them is read as [OddLength::Nibbles].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ParseOptions{
+    /// Accepts hex as pasted from other tools: a `0x` prefix, and spaces, newlines and colons between the digits are removed.
+    pub lenient: bool,
+    /// What to do with an odd number of digits.
+    pub odd_length: OddLength,
+}
+
+impl ParseOptions{
+    /// Lenient options, that also pad odd input to whole bytes.
+    pub fn lenient() -> ParseOptions{
+        ParseOptions{lenient: true, odd_length: OddLength::PadLeft}
+    }
+}
+
 /// The return type of the hashing process
 /// 
-/// To create a Hash256, refer to the [from_hex][Hash256::from_hex()] method.
-# [derive(Debug, Clone, PartialEq)]
-pub struct Hash256(String);
+/// To create a Hash256, refer to the [from_hex][Hash256::from_hex()] method, or convert it from the 32 bytes of a digest.
+# [derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hash256(pub(crate) String);
 
 impl fmt::Display for Hash256{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
@@ -56,27 +101,87 @@ impl fmt::Display for Hash256{
     }
 }
 
+/// Hashes are ordered as big endian numbers, so they can be sorted, kept in a [BTreeMap][std::collections::BTreeMap],
+/// and compared with a target, like in proof of work.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// let mut hashes = vec![sha256("a", InputType::Text)?, sha256("b", InputType::Text)?, sha256("c", InputType::Text)?];
+/// hashes.sort();
+/// assert!(hashes[0].get_hex() < hashes[1].get_hex() && hashes[1].get_hex() < hashes[2].get_hex());
+///
+/// let target = Hash256::from_hex("0000ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", false)?;
+/// assert!(sha256("hello", InputType::Text)? > target);
+/// # Ok(())
+/// # }
+/// ```
+impl Ord for Hash256{
+    fn cmp(&self, other: &Self) -> Ordering{
+        self.get_bytes().cmp(&other.get_bytes())
+    }
+}
+
+impl PartialOrd for Hash256{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering>{
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "bigint")]
 impl From<&Hash256> for BigInt{
     fn from(value: &Hash256) -> Self {
-        BigInt::from_str_radix(&value.0, 16).unwrap()
+        BigInt::from_bytes_be(Sign::Plus, &value.get_bytes())
     }
 }
 
+#[cfg(feature = "bigint")]
 impl From<&Hash256> for BigUint{
     fn from(value: &Hash256) -> Self {
-        BigUint::from_str_radix(&value.0, 16).unwrap()
+        BigUint::from_bytes_be(&value.get_bytes())
     }
 }
 
+#[cfg(feature = "bigint")]
 impl From<Hash256> for BigInt{
     fn from(value: Hash256) -> Self {
-        BigInt::from_str_radix(&value.0, 16).unwrap()
+        BigInt::from_bytes_be(Sign::Plus, &value.get_bytes())
     }
 }
 
+#[cfg(feature = "bigint")]
 impl From<Hash256> for BigUint{
     fn from(value: Hash256) -> Self {
-        BigUint::from_str_radix(&value.0, 16).unwrap()
+        BigUint::from_bytes_be(&value.get_bytes())
+    }
+}
+
+impl From<[u8; 32]> for Hash256{
+    fn from(value: [u8; 32]) -> Self {
+        Hash256(encoding::hex::encode(&value))
+    }
+}
+
+/// Wraps a digest from a raw buffer, checking that it has 32 bytes.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// let digest = sha256("hello", InputType::Text)?.get_bytes();
+///
+/// assert_eq!(Hash256::try_from(&digest[..])?, sha256("hello", InputType::Text)?);
+/// assert!(Hash256::try_from(&digest[..31]).is_err());
+/// # Ok(())
+/// # }
+/// ```
+impl TryFrom<&[u8]> for Hash256{
+    type Error = HashError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: [u8; 32] = value.try_into().map_err(|_| HashError::InvalidHash)?;
+        Ok(Hash256::from(bytes))
     }
 }
 
@@ -125,8 +230,7 @@ impl Hash256{
                 }
             }
             if le{
-               let hex: String = (0..hex.len()).step_by(2).rev().map(|i|&hex[i..i+2]).collect();
-               Ok(Hash256(hex))
+               Ok(Hash256(encoding::hex::reverse(hex).map_err(|_| HashError::InvalidHash)?))
             }else{
                 Ok(Hash256(hex.to_owned()))
             }
@@ -141,9 +245,129 @@ impl Hash256{
 
     /// Returns the hex digest of the hash in little endian byte order.
     pub fn get_hex_le(&self) -> String{
-        let le_hex = self.get_hex();
-        let le_hex: String = (0..le_hex.len()).step_by(2).rev().map(|i|&le_hex[i..i+2]).collect();
-        le_hex
+        let mut bytes = self.get_bytes();
+        bytes.reverse();
+        encoding::hex::encode(&bytes)
+    }
+
+    /// Returns the bytes of the hash.
+    pub fn get_bytes(&self) -> [u8; 32]{
+        // every constructor checks the 64 hex digits, so there is nothing to fail here
+        let mut bytes = [0; 32];
+        for (byte, digits) in bytes.iter_mut().zip(self.0.as_bytes().chunks(2)){
+            *byte = digits.iter().fold(0, |byte, digit| byte << 4 | (*digit as char).to_digit(16).unwrap_or(0) as u8);
+        }
+        bytes
+    }
+
+    /// Returns the [randomart][encoding::randomart] of the hash, the picture `ssh-keygen -lv` draws of fingerprints,
+    /// with `SHA256` in the bottom border, to compare hashes at a glance.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::sha256::*;
+    /// let hash = sha256("hello", InputType::Text).unwrap();
+    ///
+    /// println!("{}", hash.randomart());
+    /// assert_eq!(hash.randomart().lines().count(), 11);
+    /// assert_ne!(hash.randomart(), sha256("hallo", InputType::Text).unwrap().randomart());
+    /// ```
+    pub fn randomart(&self) -> String{
+        encoding::randomart::draw(&self.get_bytes(), "", "SHA256")
+    }
+
+    /// Returns the first bits of the hash, see [TruncatedHash].
+    ///
+    /// # Errors
+    /// Returns [HashError::InvalidTruncation] if the bits aren't from 1 to 256.
+    pub fn truncate_bits(&self, bits: u32) -> Result<TruncatedHash, HashError>{
+        TruncatedHash::new(&self.get_bytes(), bits)
+    }
+
+    /// Returns the first bytes of the hash, see [TruncatedHash].
+    ///
+    /// # Errors
+    /// Returns [HashError::InvalidTruncation] if the bytes aren't from 1 to 32.
+    pub fn truncate_bytes(&self, bytes: usize) -> Result<TruncatedHash, HashError>{
+        let bits = u32::try_from(bytes).map_err(|_| HashError::InvalidTruncation)?;
+        self.truncate_bits(bits.saturating_mul(8))
+    }
+
+    /// Returns the number of zero bits at the start of the hash, the difficulty of proof of work that counts zeros.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::sha256::*;
+    /// # fn main() -> Result<(), HashError>{
+    /// let hash = Hash256::from_hex("00000fffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", false)?;
+    /// assert_eq!(hash.leading_zero_bits(), 20);
+    /// assert_eq!(Hash256::from([0; 32]).leading_zero_bits(), 256);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn leading_zero_bits(&self) -> u32{
+        let bytes = self.get_bytes();
+        match bytes.iter().position(|byte| *byte != 0){
+            Some(i) => i as u32 * 8 + bytes[i].leading_zeros(),
+            None => 256,
+        }
+    }
+
+    /// Returns if the hash, as a big endian number, is at most the target, the check of proof of work.
+    ///
+    /// Bitcoin reads its hashes as little endian numbers, so their reversed bytes, [get_hex_le][Hash256::get_hex_le],
+    /// are the ones to compare with a target of [from_compact][Hash256::from_compact].
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::sha256::*;
+    /// # fn main() -> Result<(), HashError>{
+    /// let target = Hash256::from_compact(0x1d00ffff)?;
+    /// let genesis = Hash256::from_hex("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f", false)?;
+    /// assert!(genesis.meets_target(&target));
+    /// assert!(!sha256("hello", InputType::Text)?.meets_target(&target));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn meets_target(&self, target: &Hash256) -> bool{
+        self <= target
+    }
+
+    /// Decodes a target from its compact form, the bits field of bitcoin block headers.
+    ///
+    /// The upper byte is the length of the target in bytes, and the lower 3 bytes its first bytes, so
+    /// `0x1d00ffff` is `ffff` followed by 26 zero bytes. The top bit of the 3 bytes is a sign, which targets never have.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mysha::sha256::*;
+    /// # fn main() -> Result<(), HashError>{
+    /// let target = Hash256::from_compact(0x1d00ffff)?;
+    /// assert_eq!(target.get_hex(), "00000000ffff0000000000000000000000000000000000000000000000000000");
+    /// assert_eq!(target.leading_zero_bits(), 32);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [HashError::InvalidTarget] if the target is negative, or larger than 256 bits.
+    pub fn from_compact(bits: u32) -> Result<Hash256, HashError>{
+        let length = (bits >> 24) as usize;
+        let mantissa = bits & 0x007fffff;
+        if bits & 0x00800000 != 0 && mantissa != 0{
+            return Err(HashError::InvalidTarget);
+        }
+        let mut bytes = [0; 32];
+        for (i, byte) in mantissa.to_be_bytes()[1..].iter().enumerate(){
+            // the bytes of lengths below 3 are shifted out, the ones of lengths above 32 don't fit
+            match (32 + i).checked_sub(length){
+                Some(position) if position < 32 => bytes[position] = *byte,
+                Some(_) => (),
+                None if *byte != 0 => return Err(HashError::InvalidTarget),
+                None => (),
+            }
+        }
+        Ok(Hash256::from(bytes))
     }
 }
 
@@ -159,12 +383,18 @@ pub enum HashError{
     InvalidHex,
     /// Happens when the number provided can't be interpreted as a decimal number.
     InvalidDecimal,
-    /// Can happen for various reasons, like error opening file, reading file, etc.
+    /// Can happen for various reasons, like error opening file, reading file or another reader, etc.
     ErrorWithFile,
     /// Happens when the input type should be in little endian, but the input doesn't have a whole number of bytes.
     NotWholeBytes,
-    /// Happens when trying to convert an invalid hex value to a hash.
+    /// Happens when trying to convert an invalid hex value, or a buffer that doesn't have 32 bytes, to a hash.
     InvalidHash,
+    /// Happens when [sha256_with_rounds] is asked for more than the 64 rounds of sha256.
+    InvalidRounds,
+    /// Happens when a hash is truncated to no bits, or more than its 256.
+    InvalidTruncation,
+    /// Happens when compact bits decode to a negative target, or one larger than 256 bits.
+    InvalidTarget,
 }
 
 impl fmt::Display for HashError{
@@ -176,7 +406,10 @@ impl fmt::Display for HashError{
             HashError::InvalidDecimal => write!(f, "Invalid value for decimal."),
             HashError::ErrorWithFile => write!(f, "Error while handling file."),
             HashError::NotWholeBytes => write!(f, "You can't use little endian if you don't provide a whole number of bytes"),
-            HashError::InvalidHash => write!(f, "Invalid hex for a hash."),
+            HashError::InvalidHash => write!(f, "Invalid value for a hash."),
+            HashError::InvalidRounds => write!(f, "sha256 has {} rounds, it can't run more.", ROUNDS),
+            HashError::InvalidTruncation => write!(f, "A hash can only be truncated to 1 to 256 bits."),
+            HashError::InvalidTarget => write!(f, "The compact bits give a negative target, or one larger than 256 bits."),
         }
     }
 }
@@ -221,38 +454,73 @@ impl fmt::Display for HashError{
 /// 
 /// [sha256 algorithm]: https://en.wikipedia.org/wiki/SHA-2
 pub fn sha256(message: &str, input_type: InputType) -> Result<Hash256, HashError>{
-    let mut bits = match input_type{
-        InputType::Binary => {
-            binary_handling::validate_bits(message)?;
-            message.to_string()
-        },
-        InputType::LeBinary => {
-            binary_handling::validate_bits(message)?;
-            if message.len() % 8 != 0{
-                return Err(HashError::NotWholeBytes);
-            }
-            (0..message.len()).step_by(8).rev().map(|i| &message[i..i+8]).collect()
-        }
-        InputType::Text => binary_handling::get_binary_message(message),
-        InputType::Hex => binary_handling::get_bits_hex(message, false)?,
-        InputType::LeHex => binary_handling::get_bits_hex(message, true)?,
-        InputType::Decimal => format!("{:b}", message.parse::<i128>().map_err(|err|{
-            match err.kind(){
-                std::num::IntErrorKind::PosOverflow => HashError::DecimalTooBig,
-                _ => HashError::InvalidDecimal
-            }   
-        })?),
-        InputType::File => {
-            let mut file = File::open(message).map_err(|_| HashError::ErrorWithFile)?;
-            let mut content = String::new();
-            file.read_to_string(&mut content).map_err(|_| HashError::ErrorWithFile)?;
-            binary_handling::get_binary_message(&content)
-        },
-    };
+    sha256_with_options(message, input_type, &ParseOptions::default())
+}
+
+/// Same as [sha256()], with the options used to parse hex input.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// let options = ParseOptions::lenient();
+/// let pasted = sha256_with_options("0x2C:F2:4D BA", InputType::Hex, &options)?;
+///
+/// assert_eq!(pasted, sha256("2cf24dba", InputType::Hex)?);
+/// assert_eq!(sha256_with_options("abc", InputType::Hex, &options)?, sha256("0abc", InputType::Hex)?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Same as [sha256()].
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(message, options), ret))]
+pub fn sha256_with_options(message: &str, input_type: InputType, options: &ParseOptions) -> Result<Hash256, HashError>{
+    hash_rounds(message, input_type, options, ROUNDS)
+}
+
+/// Rounds of the compression function of sha256.
+pub const ROUNDS: usize = 64;
+
+/// Same as [sha256()], but the compression function of every block only runs its first rounds, out of [ROUNDS].
+///
+/// **This isn't sha256, and it is insecure**: with few rounds, a change of the message only reaches a few words of the state,
+/// and collisions and preimages are easy to find. It is here to see how the diffusion builds up round after round,
+/// [sha256_with_rounds] with 64 rounds is the same as [sha256()].
+/// Published attacks on sha256 reach 31 rounds for collisions, and about 45 for preimages.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// assert_eq!(sha256_with_rounds("abc", InputType::Text, 64)?, sha256("abc", InputType::Text)?);
+///
+/// // after one round, only a and e depend on the message, so the other six words are the same for every one-block message
+/// let a = sha256_with_rounds("abc", InputType::Text, 1)?;
+/// let b = sha256_with_rounds("abd", InputType::Text, 1)?;
+/// assert_eq!(a.get_hex()[8..32], b.get_hex()[8..32]);
+/// assert_ne!(a.get_hex()[..8], b.get_hex()[..8]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Same as [sha256()], and [HashError::InvalidRounds] if there are more than [ROUNDS] rounds.
+pub fn sha256_with_rounds(message: &str, input_type: InputType, rounds: usize) -> Result<Hash256, HashError>{
+    if rounds > ROUNDS{
+        return Err(HashError::InvalidRounds);
+    }
+    hash_rounds(message, input_type, &ParseOptions::default(), rounds)
+}
+
+fn hash_rounds(message: &str, input_type: InputType, options: &ParseOptions, rounds: usize) -> Result<Hash256, HashError>{
+    let mut bits = get_bits(message, input_type, options)?;
 
     binary_handling::pad(&mut bits);
 
     let message_blocks = binary_handling::get_message_blocks(&bits);
+    #[cfg(feature = "tracing")]
+    tracing::debug!(bits = bits.len(), blocks = message_blocks.len(), "padded the message");
 
     let a = constants::initialize_a();
 
@@ -262,7 +530,9 @@ pub fn sha256(message: &str, input_type: InputType) -> Result<Hash256, HashError
 
 
     for block in message_blocks.iter(){
-        let mut message_schedule = binary_handling::get_message_schedule(block);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("sha256_block").entered();
+        let mut message_schedule = binary_handling::get_message_schedule(block)?;
 
         for i in 16..64{
             message_schedule.push(operations::addn(vec![operations::l_sigma1(message_schedule[i - 2]), message_schedule[i - 7], operations::l_sigma0(message_schedule[i - 15]), message_schedule[i - 16]]));
@@ -271,7 +541,7 @@ pub fn sha256(message: &str, input_type: InputType) -> Result<Hash256, HashError
         let (mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h) = (a0, b0, c0, d0, e0, f0, g0, h0);
 
                 
-        for (i, m) in message_schedule.iter().enumerate(){
+        for (i, m) in message_schedule.iter().enumerate().take(rounds){
             let t1 = operations::addn(vec![operations::u_sigma1(e), operations::choice(e, f, g), h, k[i], *m]);
             let t2 = operations::add(operations::u_sigma0(a), operations::majority(a, b, c));
 
@@ -296,5 +566,233 @@ pub fn sha256(message: &str, input_type: InputType) -> Result<Hash256, HashError
     }
 
     let hash256 = format!("{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}", a0, b0, c0, d0, e0, f0, g0, h0);
-    return Ok(Hash256(hash256));
+    Ok(Hash256(hash256))
+}
+
+/// Hashes every message, all of the same input type, returning the results in the order of the messages.
+///
+/// With the `parallel` feature, the messages are hashed by the thread pool of [rayon], so hashing thousands of them uses every core,
+/// without it they are hashed one after the other. The results are the same either way.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// let hashes = sha256_many(["abc", "", "not hex"], InputType::Hex);
+///
+/// assert_eq!(hashes[0].as_ref().unwrap(), &sha256("abc", InputType::Hex)?);
+/// assert_eq!(hashes[1].as_ref().unwrap(), &sha256("", InputType::Hex)?);
+/// assert!(hashes[2].is_err());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [rayon]: https://crates.io/crates/rayon
+pub fn sha256_many<I>(messages: I, input_type: InputType) -> Vec<Result<Hash256, HashError>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str> + Sync,
+{
+    let messages: Vec<I::Item> = messages.into_iter().collect();
+    hash_all(&messages, input_type)
+}
+
+#[cfg(feature = "parallel")]
+fn hash_all<T: AsRef<str> + Sync>(messages: &[T], input_type: InputType) -> Vec<Result<Hash256, HashError>>{
+    use rayon::prelude::*;
+    messages.par_iter().map(|message| sha256(message.as_ref(), input_type)).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn hash_all<T: AsRef<str> + Sync>(messages: &[T], input_type: InputType) -> Vec<Result<Hash256, HashError>>{
+    messages.iter().map(|message| sha256(message.as_ref(), input_type)).collect()
+}
+
+/// Hashes everything the reader gives, until its end, in chunks, without keeping it all in memory.
+///
+/// It uses the [Sha256] hasher, so it works for sockets, pipes of other processes, or decompressed streams of any size.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// let reader = std::io::Cursor::new(b"abc");
+///
+/// assert_eq!(sha256_from_reader(reader)?, sha256("abc", InputType::Text)?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [HashError::ErrorWithFile] if reading fails.
+pub fn sha256_from_reader(mut reader: impl Read) -> Result<Hash256, HashError>{
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 64 * 1024];
+    loop{
+        match reader.read(&mut buffer){
+            Ok(0) => break,
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => return Err(HashError::ErrorWithFile),
+        }
+    }
+    Ok(hasher.finalize())
+}
+
+/// Hashes everything the async reader gives, like [sha256_from_reader], but waiting for the data without blocking the runtime,
+/// so a service can hash an upload as it arrives.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), HashError>{
+/// let reader = &b"abc"[..];
+///
+/// assert_eq!(sha256_from_async_reader(reader).await?, sha256("abc", InputType::Text)?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [HashError::ErrorWithFile] if reading fails.
+#[cfg(feature = "tokio")]
+pub async fn sha256_from_async_reader(mut reader: impl AsyncRead + Unpin) -> Result<Hash256, HashError>{
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop{
+        match reader.read(&mut buffer).await{
+            Ok(0) => break,
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => return Err(HashError::ErrorWithFile),
+        }
+    }
+    Ok(hasher.finalize())
+}
+
+/// Hashes the contents of the file, opened and read with [tokio], with [sha256_from_async_reader].
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), HashError>{
+/// std::fs::write("upload.txt", "abc").unwrap();
+///
+/// assert_eq!(sha256_file_async("upload.txt").await?, sha256("upload.txt", InputType::File)?);
+/// # std::fs::remove_file("upload.txt").unwrap();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [HashError::ErrorWithFile] if the file can't be opened or read.
+#[cfg(feature = "tokio")]
+pub async fn sha256_file_async(path: impl AsRef<Path>) -> Result<Hash256, HashError>{
+    let file = tokio::fs::File::open(path).await.map_err(|_| HashError::ErrorWithFile)?;
+    sha256_from_async_reader(file).await
+}
+
+/// Hashes the data with a tag, as `SHA256(SHA256(tag) || SHA256(tag) || data)`, the tagged hash of [BIP-340].
+///
+/// Hashes with different tags can't be equal for the same data, so a hash computed for one purpose, like the challenge of
+/// a Schnorr signature, can't be reused for another. Since the prefix is 64 bytes, the state after it can be computed once for a tag.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// let hash = tagged_hash("BIP0340/challenge", b"abc");
+///
+/// assert_eq!(hash.get_hex(), "770a5b7e7c304bbcc3ea107343ff951dd404312ef418db0c3b94e2ebfbb50087");
+/// assert_ne!(hash, tagged_hash("BIP0340/aux", b"abc"));
+/// ```
+///
+/// [BIP-340]: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#design
+pub fn tagged_hash(tag: &str, data: &[u8]) -> Hash256{
+    let tag = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(&tag);
+    hasher.update(&tag);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Normalizes hex input to lowercase digits, following the options.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// assert_eq!(normalize_hex("0xDE AD:be:EF", &ParseOptions::lenient())?, "deadbeef");
+/// assert_eq!(normalize_hex("fff", &ParseOptions::lenient())?, "0fff");
+///
+/// let strict = ParseOptions{odd_length: OddLength::Reject, ..ParseOptions::default()};
+/// assert!(normalize_hex("fff", &strict).is_err());
+/// assert!(normalize_hex("0xff", &strict).is_err());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [HashError::InvalidHex] if there is anything that isn't a digit left, and [HashError::NotWholeBytes]
+/// if the number of digits is odd and the options reject it.
+pub fn normalize_hex(hex: &str, options: &ParseOptions) -> Result<String, HashError>{
+    let mut hex = hex;
+    if options.lenient{
+        hex = hex.trim();
+        hex = hex.strip_prefix("0x").or_else(|| hex.strip_prefix("0X")).unwrap_or(hex);
+    }
+    let mut digits = String::new();
+    for c in hex.chars(){
+        match c{
+            c if c.is_ascii_hexdigit() => digits.push(c.to_ascii_lowercase()),
+            c if options.lenient && (c.is_whitespace() || c == ':') => (),
+            _ => return Err(HashError::InvalidHex),
+        }
+    }
+    if digits.len() % 2 == 1{
+        match options.odd_length{
+            OddLength::Nibbles => (),
+            OddLength::PadLeft => digits.insert(0, '0'),
+            OddLength::Reject => return Err(HashError::NotWholeBytes),
+        }
+    }
+    Ok(digits)
+}
+
+/// Gets the bits of the message, interpreted as the [InputType].
+pub(crate) fn get_bits(message: &str, input_type: InputType, options: &ParseOptions) -> Result<String, HashError>{
+    Ok(match input_type{
+        InputType::Binary => {
+            binary_handling::validate_bits(message)?;
+            message.to_string()
+        },
+        InputType::LeBinary => {
+            binary_handling::validate_bits(message)?;
+            if !message.len().is_multiple_of(8){
+                return Err(HashError::NotWholeBytes);
+            }
+            (0..message.len()).step_by(8).rev().map(|i| &message[i..i+8]).collect()
+        }
+        InputType::Text => binary_handling::get_binary_message(message),
+        InputType::Hex => binary_handling::get_bits_hex(&normalize_hex(message, options)?, false)?,
+        InputType::LeHex => binary_handling::get_bits_hex(&normalize_hex(message, options)?, true)?,
+        InputType::Decimal => format!("{:b}", message.parse::<i128>().map_err(|err|{
+            match err.kind(){
+                std::num::IntErrorKind::PosOverflow => HashError::DecimalTooBig,
+                _ => HashError::InvalidDecimal
+            }   
+        })?),
+        // there is no file system in the browser
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        InputType::File => return Err(HashError::ErrorWithFile),
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        InputType::File => {
+            let mut file = std::fs::File::open(message).map_err(|_| HashError::ErrorWithFile)?;
+            let mut content = String::new();
+            file.read_to_string(&mut content).map_err(|_| HashError::ErrorWithFile)?;
+            binary_handling::get_binary_message(&content)
+        },
+    })
 }
@@ -0,0 +1,78 @@
+use std::fmt;
+
+use super::HashError;
+use crate::encoding;
+
+/// The first bits of a digest, from [Hash256::truncate_bits][super::Hash256::truncate_bits] or
+/// [Hash256::truncate_bytes][super::Hash256::truncate_bytes].
+///
+/// Truncated hashes are shorter ids, like the 7 digits git shows of a commit, and smaller digests for demonstrations:
+/// finding two messages with the same n bits takes about 2^(n/2) hashes, and a message with given n bits 2^n.
+/// The bits after the truncation are zero in the last byte, so truncated hashes of the same bits compare equal.
+///
+/// # Examples
+/// ```
+/// # use mysha::sha256::*;
+/// # fn main() -> Result<(), HashError>{
+/// let hash = sha256("hello", InputType::Text)?;
+///
+/// // a short id, shown with as many hex digits as the bits have
+/// let id = hash.truncate_bits(28)?;
+/// assert_eq!(id.to_string(), "2cf24db");
+/// assert_eq!(id.to_u64(), Some(0x2cf24db));
+///
+/// assert_eq!(hash.truncate_bytes(4)?.get_bytes(), &[0x2c, 0xf2, 0x4d, 0xba]);
+/// assert!(hash.truncate_bits(257).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TruncatedHash{
+    bytes: Vec<u8>,
+    bits: u32,
+}
+
+impl TruncatedHash{
+    /// Truncates the digest to its first bits.
+    ///
+    /// # Errors
+    /// Returns [HashError::InvalidTruncation] if the bits aren't from 1 to 256.
+    pub fn new(digest: &[u8; 32], bits: u32) -> Result<TruncatedHash, HashError>{
+        if bits == 0 || bits > 256{
+            return Err(HashError::InvalidTruncation);
+        }
+        let mut bytes = digest[..bits.div_ceil(8) as usize].to_vec();
+        if !bits.is_multiple_of(8){
+            *bytes.last_mut().unwrap() &= 0xff << (8 - bits % 8);
+        }
+        Ok(TruncatedHash{bytes, bits})
+    }
+
+    /// Returns the number of bits kept.
+    pub fn get_bits(&self) -> u32{
+        self.bits
+    }
+
+    /// Returns the bytes of the bits kept, the bits after them zero.
+    pub fn get_bytes(&self) -> &[u8]{
+        &self.bytes
+    }
+
+    /// Returns the bits as a number, if there are at most 64.
+    pub fn to_u64(&self) -> Option<u64>{
+        if self.bits > 64{
+            return None;
+        }
+        let mut padded = [0; 8];
+        padded[..self.bytes.len()].copy_from_slice(&self.bytes);
+        Some(u64::from_be_bytes(padded) >> (64 - self.bits))
+    }
+}
+
+/// Shows the bytes in hex, with a digit less when the last one only has zero bits after the truncation.
+impl fmt::Display for TruncatedHash{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        let hex = encoding::hex::encode(&self.bytes);
+        write!(f, "{}", &hex[..self.bits.div_ceil(4) as usize])
+    }
+}
@@ -1,19 +1,146 @@
-use std::io::{self, Write, stdout};
-use std::{thread, time::Duration};
+use std::fmt::Display;
+use std::io::{Write, stdout};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
+use std::{thread, time::{Duration, Instant}};
 
+use mysha::animation::Phase;
+
+use crate::failure::Kind;
 use crate::Exit;
+use super::record::Recorder;
+use super::screen::{self, Screen};
+use super::terminal::{self, read_key, Key, Clear, MoveTo, MoveToNextLine, MoveToPreviousLine, MoveRight, MoveLeft, SavePosition, RestorePosition, SetStyle, ResetStyle};
+use super::theme::{theme, themed};
+
+static START_AT: AtomicU8 = AtomicU8::new(Phase::Padding as u8);
+static REACHED: AtomicBool = AtomicBool::new(true);
+static SKIP: AtomicBool = AtomicBool::new(false);
+static SPEED: AtomicU64 = AtomicU64::new(0x3ff0000000000000); // 1.0_f64
+
+/// Sets the speed of the animation, every pause is divided by it.
+pub fn set_speed(speed: f64){
+    SPEED.store(speed.to_bits(), Ordering::SeqCst);
+}
+
+/// Sets the phase the animation starts at, every phase before it is fast forwarded.
+pub fn start_at(phase: Phase){
+    START_AT.store(phase as u8, Ordering::SeqCst);
+    REACHED.store(phase == Phase::Padding, Ordering::SeqCst);
+}
+
+/// Marks the beginning of a new phase of the animation.
+pub fn phase(phase: Phase){
+    SKIP.store(false, Ordering::SeqCst);
+    if phase as u8 >= START_AT.load(Ordering::SeqCst){
+        REACHED.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Skips what is left of the current phase.
+pub fn skip(){
+    SKIP.store(true, Ordering::SeqCst);
+}
+
+fn fast_forward() -> bool{
+    ! REACHED.load(Ordering::SeqCst) || SKIP.load(Ordering::SeqCst)
+}
+
+/// Listens for the skip key while the animation is running, without waiting for enter.
+pub fn listen_keys(){
+    thread::spawn(||{
+        loop{
+            match read_key(){
+                Ok(Key::Char('s' | 'S')) => skip(),
+                Ok(Key::CtrlC) => {
+                    leave();
+                    std::process::exit(0);
+                },
+                Ok(_) => (),
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+static SCREEN: Mutex<Option<Screen>> = Mutex::new(None);
+
+/// Switches to the alternative screen, where the animation is drawn through a [Screen].
+///
+/// If `keys` is true, keys are read as soon as they are pressed, and are not shown.
+/// If `record` is some file, the animation is also recorded to it, see [Recorder].
+pub fn enter(keys: bool, record: Option<&str>){
+    let backend = match record{
+        Some(path) => Box::new(Recorder::create(path, terminal::backend(), "mysha sha256").exit("Error while creating the recording file.")),
+        None => terminal::backend(),
+    };
+    let mut screen = Screen::new(backend);
+    screen.enter(keys).exit("error while handling stdout.");
+    *SCREEN.lock().unwrap() = Some(screen);
+}
+
+/// Gets the terminal back to its previous state.
+pub fn leave(){
+    let screen = SCREEN.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(mut screen) = screen{
+        screen.leave().exit("error while handling stdout.");
+    }
+}
+
+/// Columns available for the animation.
+pub fn cols() -> usize{
+    match SCREEN.lock().unwrap().as_ref(){
+        Some(screen) => screen.cols(),
+        None => screen::MIN_COLS,
+    }
+}
+
+/// Draws the screen again if the terminal was resized.
+fn redraw_resized(){
+    if let Some(screen) = SCREEN.lock().unwrap().as_mut(){
+        if screen.resize(){
+            screen.render().exit("error while handling stdout.");
+        }
+    }
+}
 
-pub fn printf(m: &str){
-    print!("{}", m);
-    stdout().flush().exit("error while handling stdout.");
+/// Same as [Exit], but leaves the animation screen before exiting.
+pub trait ExitAnimation{
+    type Output;
+
+    fn exit_animation(self, msg: &str) -> Self::Output;
+}
+
+impl<T, E: Display + Kind> ExitAnimation for Result<T, E>{
+    type Output = T;
+    fn exit_animation(self, msg: &str) -> T{
+        if self.is_err(){
+            leave();
+        }
+        self.exit(msg)
+    }
+}
+
+pub fn printf(m: impl Display){
+    match SCREEN.lock().unwrap().as_mut(){
+        Some(screen) => {
+            screen.write(&m.to_string());
+            screen.resize();
+            screen.render().exit("error while handling stdout.");
+        },
+        None => {
+            print!("{}", m);
+            stdout().flush().exit("error while handling stdout.");
+        },
+    }
 }
 
 pub fn clear(){
-    printf("\x1b[2J");
+    printf(Clear::All);
 }
 
 pub fn top(){
-    printf("\x1b[H"); // cursor on 1, 1
+    printf(MoveTo(0, 0));
 }
 
 pub fn cleartop(){
@@ -21,97 +148,121 @@ pub fn cleartop(){
     top();
 }
 
-pub fn blink(m: &str){
-    printf(format!("\x1b[5m\x1b[4m\x1b[36m{}\x1b[m", m).as_str());
+pub fn blink(m: impl Display){
+    printf(format!("{}{}{}", SetStyle(themed(theme().highlight)), m, ResetStyle).as_str());
+}
+
+/// Prints `prefix` and `bits`, shortened to fit in `rows` lines, with the bits from `from` to `to` blinking.
+pub fn print_bits(prefix: &str, bits: &str, from: usize, to: usize, rows: usize){
+    let width = (cols() * rows).saturating_sub(prefix.len() + 1);
+    let (before, kept, after) = screen::fit_around(bits, from, to, width);
+    printf(format!("{}{}", prefix, before));
+    if ! kept.is_empty(){
+        blink(kept);
+    }
+    printf(&after);
+}
+
+/// Prints a list item for a message block, shortened to fit in one line.
+pub fn print_block(block: &str, highlight: bool){
+    let line = format!("    \"{}\",\n", screen::fit(block, cols().saturating_sub(8)));
+    if highlight{
+        blink(&line);
+    }else{
+        printf(&line);
+    }
 }
 
 pub fn wait(enter: bool, time: u64){
+    if fast_forward(){
+        return;
+    }
     if enter{
-        let mut s = String::new();
-        printf("\x1b7");
-        printf("\x1b[1000E");
-        printf("\x1b[F\x1b[1000C\x1b[15DPress Enter");
-        io::stdin().read_line(&mut s).exit("Error while waiting Enter.");
-        if s != "\r\n"{
-            printf("\x1b[m\x1b[?25h"); // make cursor visible
-            printf("\x1b[?1049l"); // disable alternative buffer, get back to previous state
-            eprintln!("You are not supposed to write anything, so the animation will work propperly. just press Enter.");
-            std::process::exit(0);
+        printf(SavePosition);
+        printf(MoveToNextLine(1000));
+        printf(format!("{}{}{}Press Enter", MoveToPreviousLine(1), MoveRight(1000), MoveLeft(15)));
+        match read_key().exit_animation("Error while waiting Enter."){
+            Key::Enter => (),
+            Key::Char('s' | 'S') => skip(),
+            Key::CtrlC => {
+                leave();
+                std::process::exit(0);
+            },
+            _ => {
+                leave();
+                eprintln!("You are not supposed to write anything, so the animation will work propperly. just press Enter, or s to skip the current phase.");
+                std::process::exit(0);
+            },
         }
-        printf("\x1b[F\x1b[1000C\x1b[15D\x1b[0J");
-        printf("\x1b8");
+        printf(format!("{}{}{}{}", MoveToPreviousLine(1), MoveRight(1000), MoveLeft(15), Clear::FromCursorDown));
+        printf(RestorePosition);
     }else{
-        thread::sleep(Duration::from_millis(time));
+        let time = Duration::from_millis(time).div_f64(f64::from_bits(SPEED.load(Ordering::SeqCst)));
+        let start = Instant::now();
+        while ! fast_forward(){
+            let elapsed = start.elapsed();
+            if elapsed >= time{
+                break;
+            }
+            thread::sleep((time - elapsed).min(Duration::from_millis(10)));
+            redraw_resized();
+        }
     }
 }
 
 pub mod binary_handling_animated{
-    use super::super::helper_functions;
-    use super::{printf, wait, blink, cleartop, top};
-
-    pub fn pad(message: &mut String){
-        let size = message.len();
+    use super::{printf, wait, blink, cleartop, top, print_bits};
+    use super::super::terminal::*;
+    use super::super::theme::{theme, themed};
 
-        *message += "1";
-        printf("\x1b[32m1\x1b[m");
+    pub fn pad(bits: &str, padded: &str){
+        printf(format!("{}1{}", SetStyle(themed(theme().padding_one)), ResetStyle));
         wait(false, 200);
 
-        printf("\x1b[33m");
-        while (message.len() + 64) % 512 != 0{
-            *message += "0";
+        printf(SetStyle(themed(theme().padding_zeros)));
+        for _ in bits.len() + 1..padded.len() - 64{
             printf("0");
             wait(false, 10);
 
         }
-        printf("\x1b[m");
-
-        let size = format!("{:064b}", size);
+        printf(ResetStyle);
 
-        *message += size.as_str();
-        printf("\x1b[36m");
-        printf(format!("{}", size).as_str());
-        printf("\x1b[m\n");
+        printf(SetStyle(themed(theme().padding_size)));
+        printf(&padded[padded.len() - 64..]);
+        printf(format!("{}\n", ResetStyle));
         wait(false, 500);
 
     }
 
-    pub fn get_message_schedule(enter: bool, block: &str, index: usize) -> Vec<u32>{
-        let mut message_schedule = Vec::new();
-
-        printf(format!("message block[{}]: {}", index, &block).as_str());
+    pub fn block_words(enter: bool, block: &str, index: usize, words: &[u32]){
+        print_bits(&format!("message block[{}]: ", index), block, 0, 0, 1);
         wait(enter, 1000);
 
-        for i in (0..block.len()).step_by(32){
+        for (i, n) in words.iter().enumerate(){
             top();
-            let n = u32::from_str_radix(&block[i..i+32], 2).unwrap();
-            message_schedule.push(n);
-
-            printf(format!("message block[{}]: {}", index, &block[0..i]).as_str());
-            blink(&block[i..i+32]);
-            println!("{}\n", &block[i+32..]);
+            print_bits(&format!("message block[{}]: ", index), block, i * 32, i * 32 + 32, 1);
+            printf("\n\n");
 
 
-            println!("message schedule: \n");
+            printf("message schedule: \n\n");
 
-            for j in 0..16{
-                if j < (i / 32){
-                    println!("w{:02}: {:032b}", j, message_schedule[j]);
-                }else if j == (i / 32){
-                    printf(format!("w{:02}: ", j).as_str());
-                    blink(format!("{:032b}\n", n).as_str());
-                }else{
-                    println!("w{:02}: ", j);
-                }
+            for (j, w) in words[..i].iter().enumerate(){
+                printf(format!("w{:02}: {:032b}\n", j, w));
+            }
+            printf(format!("w{:02}: ", i).as_str());
+            blink(format!("{:032b}\n", n).as_str());
+            for j in i + 1..16{
+                printf(format!("w{:02}: \n", j));
             }
             wait(enter, 400);
 
         }
 
-        printf("\x1b7");
-        printf(format!("\x1b[F\x1b[2Kw15: {:032b}", message_schedule[15]).as_str());
-        printf(format!("\x1b[Hmessage block[{}]: {}", index, block).as_str());
-        printf("\x1b8");
-        message_schedule
+        printf(SavePosition);
+        printf(format!("{}{}w15: {:032b}", MoveToPreviousLine(1), Clear::CurrentLine, words[15]).as_str());
+        top();
+        print_bits(&format!("message block[{}]: ", index), block, 0, 0, 1);
+        printf(RestorePosition);
     }
 
     pub fn rotr(mut n: u32, rot: u32) -> u32{
@@ -119,17 +270,17 @@ pub mod binary_handling_animated{
             n = n.rotate_right(1);
             printf(format!("{:032b}", n).as_str());
             wait(false, 200);
-            printf("\x1b[32D")
+            printf(MoveLeft(32))
         }
         n
     }
 
     pub fn shr(mut n: u32, sh: u32) -> u32{
         for _ in 0..sh{
-            n = n >> 1;
+            n >>= 1;
             printf(format!("{:032b}", n).as_str());
             wait(false, 100);
-            printf("\x1b[32D");
+            printf(MoveLeft(32));
         }
         n
     }
@@ -139,164 +290,164 @@ pub mod binary_handling_animated{
         for i in 0..33{
             printf(format!("{:>32}", &result[(32 - i)..]).as_str());
             wait(false, 200);
-            printf("\x1b[32D");
+            printf(MoveLeft(32));
         }
     }
 
     pub fn l_sigma0(enter: bool, sample: &str){
-        println!("sigma 0\n");
-        println!("x      : {}", sample);
-        println!("{:->41}", "");
-        println!("ROTR 7 : {}", sample);
-        println!("ROTR 18: {}", sample);
-        println!("SHR 3  : {}", sample);
+        printf("sigma 0\n\n");
+        printf(format!("x      : {}\n", sample));
+        printf(format!("{:->41}\n", ""));
+        printf(format!("ROTR 7 : {}\n", sample));
+        printf(format!("ROTR 18: {}\n", sample));
+        printf(format!("SHR 3  : {}\n", sample));
         wait(enter, 2000);
-        printf("\x1b[3A\x1b[9C");
+        printf(format!("{}{}", MoveUp(3), MoveRight(9)));
         let n1 = rotr(u32::from_str_radix(sample, 2).unwrap(), 7);
         wait(enter, 500);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         let n2 = rotr(u32::from_str_radix(sample, 2).unwrap(), 18);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         wait(enter, 500);
         let n3 = shr(u32::from_str_radix(sample, 2).unwrap(), 3);
         wait(enter, 500);
-        printf("\x1b[A\x1b[32C XOR");
-        printf("\x1b[B\x1b[4D XOR\n");
-        println!("{: >9}{:->32}", "", "");
-        print!("{: >9}", "");
+        printf(format!("{}{} XOR", MoveUp(1), MoveRight(32)));
+        printf(format!("{}{} XOR\n", MoveDown(1), MoveLeft(4)));
+        printf(format!("{: >9}{:->32}\n", "", ""));
+        printf(format!("{: >9}", ""));
         wait(enter, 500);
         xor(n1, n2, n3);
         wait(enter, 500);
-        printf("\x1b[H\x1b[2E\x1b[J");
+        printf(format!("{}{}{}", MoveTo(0, 0), MoveToNextLine(2), Clear::FromCursorDown));
     }
 
     pub fn l_sigma1(enter: bool, sample: &str){
-        println!("sigma 1\n");
-        println!("x      : {}", sample);
-        println!("{:->41}", "");
-        println!("ROTR 17: {}", sample);
-        println!("ROTR 19: {}", sample);
-        println!("SHR 10 : {}", sample);
+        printf("sigma 1\n\n");
+        printf(format!("x      : {}\n", sample));
+        printf(format!("{:->41}\n", ""));
+        printf(format!("ROTR 17: {}\n", sample));
+        printf(format!("ROTR 19: {}\n", sample));
+        printf(format!("SHR 10 : {}\n", sample));
         wait(enter, 2000);
-        printf("\x1b[3A\x1b[9C");
+        printf(format!("{}{}", MoveUp(3), MoveRight(9)));
         let n1 = rotr(u32::from_str_radix(sample, 2).unwrap(), 17);
         wait(enter, 500);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         let n2 = rotr(u32::from_str_radix(sample, 2).unwrap(), 19);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         wait(enter, 500);
         let n3 = shr(u32::from_str_radix(sample, 2).unwrap(), 10);
         wait(enter, 500);
-        printf("\x1b[A\x1b[32C XOR");
-        printf("\x1b[B\x1b[4D XOR\n");
-        println!("{: >9}{:->32}", "", "");
-        print!("{: >9}", "");
+        printf(format!("{}{} XOR", MoveUp(1), MoveRight(32)));
+        printf(format!("{}{} XOR\n", MoveDown(1), MoveLeft(4)));
+        printf(format!("{: >9}{:->32}\n", "", ""));
+        printf(format!("{: >9}", ""));
         wait(enter, 500);
         xor(n1, n2, n3);
         wait(enter, 500);
-        printf("\x1b[H\x1b[2E\x1b[J");
+        printf(format!("{}{}{}", MoveTo(0, 0), MoveToNextLine(2), Clear::FromCursorDown));
     }
 
     pub fn u_sigma0(enter: bool, sample: &str){
-        println!("SIGMA 0\n");
-        println!("x      : {}", sample);
-        println!("{:->41}", "");
-        println!("ROTR 2 : {}", sample);
-        println!("ROTR 13: {}", sample);
-        println!("ROTR 22: {}", sample);
+        printf("SIGMA 0\n\n");
+        printf(format!("x      : {}\n", sample));
+        printf(format!("{:->41}\n", ""));
+        printf(format!("ROTR 2 : {}\n", sample));
+        printf(format!("ROTR 13: {}\n", sample));
+        printf(format!("ROTR 22: {}\n", sample));
         wait(enter, 2000);
-        printf("\x1b[3A\x1b[9C");
+        printf(format!("{}{}", MoveUp(3), MoveRight(9)));
         let n1 = rotr(u32::from_str_radix(sample, 2).unwrap(), 2);
         wait(enter, 500);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         let n2 = rotr(u32::from_str_radix(sample, 2).unwrap(), 13);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         wait(enter, 500);
         let n3 = rotr(u32::from_str_radix(sample, 2).unwrap(), 22);
         wait(enter, 500);
-        printf("\x1b[A\x1b[32C XOR");
-        printf("\x1b[B\x1b[4D XOR\n");
-        println!("{: >9}{:->32}", "", "");
-        print!("{: >9}", "");
+        printf(format!("{}{} XOR", MoveUp(1), MoveRight(32)));
+        printf(format!("{}{} XOR\n", MoveDown(1), MoveLeft(4)));
+        printf(format!("{: >9}{:->32}\n", "", ""));
+        printf(format!("{: >9}", ""));
         wait(enter, 500);
         xor(n1, n2, n3);
         wait(enter, 500);
-        printf("\x1b[H\x1b[2E\x1b[J");
+        printf(format!("{}{}{}", MoveTo(0, 0), MoveToNextLine(2), Clear::FromCursorDown));
     }
     
     pub fn u_sigma1(enter: bool, sample: &str){
-        println!("SIGMA 1\n");
-        println!("x      : {}", sample);
-        println!("{:->41}", "");
-        println!("ROTR 6 : {}", sample);
-        println!("ROTR 11: {}", sample);
-        println!("ROTR 25: {}", sample);
+        printf("SIGMA 1\n\n");
+        printf(format!("x      : {}\n", sample));
+        printf(format!("{:->41}\n", ""));
+        printf(format!("ROTR 6 : {}\n", sample));
+        printf(format!("ROTR 11: {}\n", sample));
+        printf(format!("ROTR 25: {}\n", sample));
         wait(enter, 2000);
-        printf("\x1b[3A\x1b[9C");
+        printf(format!("{}{}", MoveUp(3), MoveRight(9)));
         let n1 = rotr(u32::from_str_radix(sample, 2).unwrap(), 6);
         wait(enter, 500);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         let n2 = rotr(u32::from_str_radix(sample, 2).unwrap(), 11);
-        printf("\x1b[B");
+        printf(MoveDown(1));
         wait(enter, 500);
         let n3 = rotr(u32::from_str_radix(sample, 2).unwrap(), 25);
         wait(enter, 500);
-        printf("\x1b[A\x1b[32C XOR");
-        printf("\x1b[B\x1b[4D XOR\n");
-        println!("{: >9}{:->32}", "", "");
-        print!("{: >9}", "");
+        printf(format!("{}{} XOR", MoveUp(1), MoveRight(32)));
+        printf(format!("{}{} XOR\n", MoveDown(1), MoveLeft(4)));
+        printf(format!("{: >9}{:->32}\n", "", ""));
+        printf(format!("{: >9}", ""));
         wait(enter, 500);
         xor(n1, n2, n3);
         wait(enter, 500);
-        printf("\x1b[H\x1b[2E\x1b[J");
+        printf(format!("{}{}{}", MoveTo(0, 0), MoveToNextLine(2), Clear::FromCursorDown));
     }
 
     pub fn choice(enter: bool, sample1: &str, sample2: &str, sample3: &str){
-        println!("choice\n\n");
-        println!("x: {}", sample1);
-        println!("y: {}", sample2);
-        println!("z: {}", sample3);
-        println!("{:->35}", "");
+        printf("choice\n\n\n");
+        printf(format!("x: {}\n", sample1));
+        printf(format!("y: {}\n", sample2));
+        printf(format!("z: {}\n", sample3));
+        printf(format!("{:->35}\n", ""));
         wait(enter, 2000);
         for i in 0..32{
-            printf(format!("\x1b[5F\x1b[{}C\u{2193}\x1b[0K", (31 - i) + 3).as_str());
+            printf(format!("{}{}\u{2193}{}", MoveToPreviousLine(5), MoveRight((31 - i) + 3), Clear::UntilNewLine).as_str());
             if &sample1[(31 - i)..(32 - i)] == "1"{
-                printf("\x1b[2E\x1b[36C\u{2190}\x1b[B\x1b[D\x1b[0K\x1b[2E");
-                printf(format!("\x1b[{}C{}", (31 - i) + 3, &sample2[(31 - i)..(32 - i)]).as_str());
+                printf(format!("{}{}\u{2190}{}{}{}{}", MoveToNextLine(2), MoveRight(36), MoveDown(1), MoveLeft(1), Clear::UntilNewLine, MoveToNextLine(2)));
+                printf(format!("{}{}", MoveRight((31 - i) + 3), &sample2[(31 - i)..(32 - i)]).as_str());
             }else{
-                printf("\x1b[3E\x1b[36C\u{2190}\x1b[A\x1b[D\x1b[0K\x1b[3E");
-                printf(format!("\x1b[{}C{}", (31 - i) + 3, &sample3[(31 - i)..(32 - i)]).as_str());
+                printf(format!("{}{}\u{2190}{}{}{}{}", MoveToNextLine(3), MoveRight(36), MoveUp(1), MoveLeft(1), Clear::UntilNewLine, MoveToNextLine(3)));
+                printf(format!("{}{}", MoveRight((31 - i) + 3), &sample3[(31 - i)..(32 - i)]).as_str());
 
             }
             wait(enter, 500);
         }
-        printf("\x1b[H\x1b[2E\x1b[J");
+        printf(format!("{}{}{}", MoveTo(0, 0), MoveToNextLine(2), Clear::FromCursorDown));
 
     }
 
     pub fn majority(enter: bool, sample1: &str, sample2: &str, sample3: &str){
-        println!("majority\n\n");
-        println!("x: {}", sample1);
-        println!("y: {}", sample2);
-        println!("z: {}", sample3);
-        println!("{:->35}", "");
+        printf("majority\n\n\n");
+        printf(format!("x: {}\n", sample1));
+        printf(format!("y: {}\n", sample2));
+        printf(format!("z: {}\n", sample3));
+        printf(format!("{:->35}\n", ""));
         wait(enter, 2000);
         for i in 0..32{
-            printf(format!("\x1b[5F\x1b[{}C\u{2193}\x1b[0K\x1b[5E", (31 - i) + 3).as_str());
-            if &sample1[(31 - i)..(32 - i)] == &sample2[(31 - i)..(32 - i)]{
-                printf(format!("\x1b[{}C{}", (31 - i) + 3, &sample1[(31 - i)..(32 - i)]).as_str());
+            printf(format!("{}{}\u{2193}{}{}", MoveToPreviousLine(5), MoveRight((31 - i) + 3), Clear::UntilNewLine, MoveToNextLine(5)).as_str());
+            if sample1[(31 - i)..(32 - i)] == sample2[(31 - i)..(32 - i)]{
+                printf(format!("{}{}", MoveRight((31 - i) + 3), &sample1[(31 - i)..(32 - i)]).as_str());
             }else{
-                printf(format!("\x1b[{}C{}", (31 - i) + 3, &sample3[(31 - i)..(32 - i)]).as_str());
+                printf(format!("{}{}", MoveRight((31 - i) + 3), &sample3[(31 - i)..(32 - i)]).as_str());
 
             }
             wait(enter, 500);
         }
-        printf("\x1b[H\x1b[2E\x1b[J");
+        printf(format!("{}{}{}", MoveTo(0, 0), MoveToNextLine(2), Clear::FromCursorDown));
     }
 
     pub fn animate_operations(enter: bool){
         cleartop();
-        println!("Operations\n");
+        printf("Operations\n\n");
         wait(enter, 500);
         let sample = "00000000111111110000000011111111";
         
@@ -318,144 +469,144 @@ pub mod binary_handling_animated{
         cleartop();
     }
 
-    pub fn animate_a(enter: bool){
+    pub fn animate_a(enter: bool, primes: &[u32]){
         wait(enter, 1000);
-        let primes = helper_functions::constants::get_primes(8);
-        for i in 0..8{
-            println!("{} = \u{221a}{}", (i as u8 + 97) as char, primes[i]);
+        let primes: Vec<f64> = primes[..8].iter().map(|prime| *prime as f64).collect();
+        for (i, prime) in primes.iter().enumerate(){
+            printf(format!("{} = \u{221a}{}\n", (i as u8 + 97) as char, prime));
         }
         wait(enter, 1000);
-        printf("\x1b[8F");
-        for i in 0..8{
-            printf("\x1b[2K");
-            println!("{} = {:.10}", (i as u8 + 97) as char, primes[i].sqrt());
+        printf(MoveToPreviousLine(8));
+        for (i, prime) in primes.iter().enumerate(){
+            printf(Clear::CurrentLine);
+            printf(format!("{} = {:.10}\n", (i as u8 + 97) as char, prime.sqrt()));
         }
         wait(enter, 1000);
-        printf("\x1b[8F");
-        for i in 0..8{
-            printf("\x1b[2K");
-            println!("{} = {:.10} * 2**32", (i as u8 + 97) as char, primes[i].sqrt() - primes[i as usize].sqrt().trunc());
+        printf(MoveToPreviousLine(8));
+        for (i, prime) in primes.iter().enumerate(){
+            printf(Clear::CurrentLine);
+            printf(format!("{} = {:.10} * 2**32\n", (i as u8 + 97) as char, prime.sqrt() - prime.sqrt().trunc()));
         }
         wait(enter, 1000);
-        printf("\x1b[8F");
-        for i in 0..8{
-            printf("\x1b[2K");
-            println!("{} = {}", (i as u8 + 97) as char, ((primes[i].sqrt() - primes[i].sqrt().trunc()) * (2 as f64).powf(32.0)) as u32);
+        printf(MoveToPreviousLine(8));
+        for (i, prime) in primes.iter().enumerate(){
+            printf(Clear::CurrentLine);
+            printf(format!("{} = {}\n", (i as u8 + 97) as char, ((prime.sqrt() - prime.sqrt().trunc()) * 2_f64.powf(32.0)) as u32));
         }
         wait(enter, 1000);
-        printf("\x1b[8F");
-        for i in 0..8{
-            printf("\x1b[2K");
-            println!("{} = {:032b}", (i as u8 + 97) as char, ((primes[i].sqrt() - primes[i].sqrt().trunc()) * (2 as f64).powf(32.0)) as u32);
+        printf(MoveToPreviousLine(8));
+        for (i, prime) in primes.iter().enumerate(){
+            printf(Clear::CurrentLine);
+            printf(format!("{} = {}{:032b}{}\n", (i as u8 + 97) as char, SetStyle(themed(theme().constants)), ((prime.sqrt() - prime.sqrt().trunc()) * 2_f64.powf(32.0)) as u32, ResetStyle));
         }
     }
 
-    pub fn animate_k(enter: bool){
-        println!("Constants K\n");
-        println!("{:->12}", "");
+    pub fn animate_k(enter: bool, primes: &[u32]){
+        printf("Constants K\n\n");
+        printf(format!("{:->12}\n", ""));
         wait(enter, 1000);
         
-        let primes = helper_functions::constants::get_primes(64);
+        let primes: Vec<f64> = primes.iter().map(|prime| *prime as f64).collect();
 
         for i in 0..68{
             if i < 64{
                 printf(format!("K{:02}: \u{221b}{}", i, primes[i]).as_str());
             }
             
-            printf("\x1b7");
-            if i as i8 - 1 >= 0 && i as i8 - 1 < 64{
-                printf(format!("\x1b[F\x1b[5C{:.10}\x1b[0K", primes[i - 1].cbrt()).as_str());
+            printf(SavePosition);
+            if i as i8 > 0 && i as i8 - 1 < 64{
+                printf(format!("{}{}{:.10}{}", MoveToPreviousLine(1), MoveRight(5), primes[i - 1].cbrt(), Clear::UntilNewLine).as_str());
 
             }
             if i as i8 - 2 >= 0 && i as i8 - 2 < 64{
-                printf(format!("\x1b[F\x1b[5C{:.10} * 2**32\x1b[0K", (primes[i - 2].cbrt() - primes[i - 2].cbrt().trunc())).as_str());
+                printf(format!("{}{}{:.10} * 2**32{}", MoveToPreviousLine(1), MoveRight(5), (primes[i - 2].cbrt() - primes[i - 2].cbrt().trunc()), Clear::UntilNewLine).as_str());
             }
             if i as i8 - 3 >= 0 && i as i8 - 3 < 64{
-                printf(format!("\x1b[F\x1b[5C{}\x1b[0K", ((primes[i - 3].cbrt() - primes[i - 3].cbrt().trunc()) * (2 as f64).powf(32.0)) as u32).as_str());
+                printf(format!("{}{}{}{}", MoveToPreviousLine(1), MoveRight(5), ((primes[i - 3].cbrt() - primes[i - 3].cbrt().trunc()) * 2_f64.powf(32.0)) as u32, Clear::UntilNewLine).as_str());
 
             }
             if i as i8 - 4 >= 0 && i as i8 - 4 < 64{
-                printf(format!("\x1b[F\x1b[5C{:032b}\x1b[0K", ((primes[i - 4].cbrt() - primes[i - 4].cbrt().trunc()) * (2 as f64).powf(32.0)) as u32).as_str());
+                printf(format!("{}{}{}{:032b}{}{}", MoveToPreviousLine(1), MoveRight(5), SetStyle(themed(theme().constants)), ((primes[i - 4].cbrt() - primes[i - 4].cbrt().trunc()) * 2_f64.powf(32.0)) as u32, ResetStyle, Clear::UntilNewLine).as_str());
             }
 
-            printf("\x1b8");
+            printf(RestorePosition);
             if i < 64{
-                println!();
+                printf("\n");
             }
             wait(enter, 500);
         }
     }
 
-    pub fn save_a(a: &Vec<u32>, enter: bool){
+    pub fn save_a(a: &[u32], enter: bool){
         for a in a.iter().rev(){
-            blink(format!("\x1b[F\x1b[4C{:032b}", a).as_str());
+            blink(format!("{}{}{:032b}", MoveToPreviousLine(1), MoveRight(4), a).as_str());
         }
         wait(enter, 800);
 
-        printf("\x1b[1000C\x1b[36D");
+        printf(format!("{}{}", MoveRight(1000), MoveLeft(36)));
 
         for (k, a) in a.iter().enumerate(){
-            blink(format!("{} = {:032b}\x1b[B\x1b[36D", (k as u8 + 97) as char, a).as_str());
+            blink(format!("{} = {:032b}{}{}", (k as u8 + 97) as char, a, MoveDown(1), MoveLeft(36)).as_str());
         }
         wait(enter, 500);
-        printf("\x1b[8A");
+        printf(MoveUp(8));
 
         for (k, a) in a.iter().enumerate(){
-            printf(format!("{} = {:032b}\x1b[B\x1b[36D", (k as u8 + 97) as char, a).as_str());
+            printf(format!("{} = {:032b}{}{}", (k as u8 + 97) as char, a, MoveDown(1), MoveLeft(36)).as_str());
         }
 
         for a in a.iter().rev(){
-            printf(format!("\x1b[F\x1b[4C{:032b}", a).as_str());
+            printf(format!("{}{}{:032b}", MoveToPreviousLine(1), MoveRight(4), a).as_str());
         }
-        printf("\x1b[8E");
+        printf(MoveToNextLine(8));
     }
 
-    pub fn restore_a(a: &Vec<u32>, enter:bool, erase:bool){
-        printf("\x1b7");
+    pub fn restore_a(a: &[u32], enter:bool, erase:bool){
+        printf(SavePosition);
 
-        printf("\x1b[1000C\x1b[36D");
+        printf(format!("{}{}", MoveRight(1000), MoveLeft(36)));
         for (k, a) in a.iter().enumerate(){
-            blink(format!("{} = {:032b}\x1b[B\x1b[36D", (k as u8 + 97) as char, a).as_str());
+            blink(format!("{} = {:032b}{}{}", (k as u8 + 97) as char, a, MoveDown(1), MoveLeft(36)).as_str());
         }
-        printf("\x1b8");
+        printf(RestorePosition);
         wait(enter, 800);
 
         for a in a.iter(){
-            blink(format!("{:032b}\x1b[B\x1b[32D", a).as_str());
+            blink(format!("{:032b}{}{}", a, MoveDown(1), MoveLeft(32)).as_str());
         }
-        printf("\x1b8");
+        printf(RestorePosition);
         wait(enter, 500);
         
         if erase{
             for a in a.iter(){
-                printf(format!("\x1b[0K{:032b} (mod 2**32)\x1b[B\x1b[44D", a).as_str());
+                printf(format!("{}{:032b} (mod 2**32){}{}", Clear::UntilNewLine, a, MoveDown(1), MoveLeft(44)).as_str());
             }
         }else{
 
             for a in a.iter(){
-                printf(format!("{:032b}\x1b[B\x1b[32D", a).as_str());
+                printf(format!("{:032b}{}{}", a, MoveDown(1), MoveLeft(32)).as_str());
             }
-            printf("\x1b8");
+            printf(RestorePosition);
 
-            printf("\x1b[1000C\x1b[36D");
+            printf(format!("{}{}", MoveRight(1000), MoveLeft(36)));
             for (k, a) in a.iter().enumerate(){
-                printf(format!("{} = {:032b}\x1b[B\x1b[36D", (k as u8 + 97) as char, a).as_str());
+                printf(format!("{} = {:032b}{}{}", (k as u8 + 97) as char, a, MoveDown(1), MoveLeft(36)).as_str());
             }
         }
 
-        printf("\x1b8");
+        printf(RestorePosition);
     }
 
-    pub fn keep_a(a: &Vec<u32>){
-        printf("\x1b7\x1b[0H");
+    pub fn keep_a(a: &[u32]){
+        printf(format!("{}{}", SavePosition, MoveTo(0, 0)));
 
-        printf(format!("{: >530}\x1b[11E\x1b[1000C\x1b[36D", "").as_str());
+        printf(format!("{}{}{}", MoveToNextLine(11), MoveRight(1000), MoveLeft(36)));
 
         for (k, a) in a.iter().enumerate(){
-            printf(format!("{} = {:032b}\x1b[B\x1b[36D", (k as u8 + 97) as char, a).as_str());
+            printf(format!("{} = {:032b}{}{}", (k as u8 + 97) as char, a, MoveDown(1), MoveLeft(36)).as_str());
         }
 
-        printf("\x1b8")
+        printf(RestorePosition)
     }
 
 
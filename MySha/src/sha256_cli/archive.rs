@@ -0,0 +1,198 @@
+//! Hashes of the files inside tar and zip archives, without extracting them, for `--archive`.
+//!
+//! Tar archives, also compressed with gzip, are read as a stream of 512 byte headers, every one followed by the data of its file.
+//! Zip archives are read from their central directory, at the end, that lists every file and where its data is.
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+
+use flate2::read::{DeflateDecoder, GzDecoder};
+
+use mysha::sha256::Sha256;
+
+/// A file of an archive, with its path inside the archive.
+pub struct Member{
+    pub name: String,
+    pub hash: [u8; 32],
+}
+
+fn invalid(message: &str) -> io::Error{
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Hashes everything the reader gives, in chunks.
+fn hash_reader(mut reader: impl Read) -> io::Result<[u8; 32]>{
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 64 * 1024];
+    loop{
+        match reader.read(&mut buffer)?{
+            0 => return Ok(hasher.finalize_bytes()),
+            n => hasher.update(&buffer[..n]),
+        }
+    }
+}
+
+/// Hashes every regular file of the archive, a tar, a tar compressed with gzip, or a zip, told apart by their first bytes.
+pub fn hash_members(path: &str) -> io::Result<Vec<Member>>{
+    let mut magic = [0; 4];
+    let read = File::open(path)?.read(&mut magic)?;
+    let file = BufReader::new(File::open(path)?);
+    match magic[..read]{
+        [b'P', b'K', 3, 4] | [b'P', b'K', 5, 6] => zip_members(file),
+        [0x1f, 0x8b, ..] => tar_members(GzDecoder::new(file)),
+        _ => tar_members(file),
+    }
+}
+
+/// Text of a field of a tar header, until its first zero.
+fn tar_text(field: &[u8]) -> String{
+    let end = field.iter().position(|&byte| byte == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Number of a tar header, in octal text, or in base 256 when its first bit is set, for files of 8 GiB or more.
+fn tar_number(field: &[u8]) -> io::Result<u64>{
+    if field[0] & 0x80 != 0{
+        return Ok(field[1..].iter().fold((field[0] & 0x7f) as u64, |n, &byte| n << 8 | byte as u64));
+    }
+    let text = tar_text(field);
+    let text = text.trim_matches(|c| c == ' ' || c == '\0');
+    if text.is_empty(){
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8).map_err(|_| invalid("invalid number in a tar header"))
+}
+
+/// Reads the data of a member, and the padding to the next 512 bytes.
+fn tar_data(reader: &mut impl Read, size: u64) -> io::Result<Vec<u8>>{
+    let mut data = Vec::new();
+    reader.take(size).read_to_end(&mut data)?;
+    if data.len() as u64 != size{
+        return Err(invalid("the tar archive ends in the middle of a file"));
+    }
+    io::copy(&mut reader.take(size.next_multiple_of(512) - size), &mut io::sink())?;
+    Ok(data)
+}
+
+/// The path of the records of a pax extended header, like `30 path=a/very/long/name.txt\n`.
+fn pax_path(data: &[u8]) -> Option<String>{
+    let mut rest = data;
+    while !rest.is_empty(){
+        let space = rest.iter().position(|&byte| byte == b' ')?;
+        let length: usize = std::str::from_utf8(&rest[..space]).ok()?.parse().ok()?;
+        let record = rest.get(space + 1..length)?;
+        if let Some(path) = record.strip_prefix(b"path="){
+            return Some(String::from_utf8_lossy(path.strip_suffix(b"\n").unwrap_or(path)).into_owned());
+        }
+        rest = &rest[length..];
+    }
+    None
+}
+
+fn tar_members(mut reader: impl Read) -> io::Result<Vec<Member>>{
+    let mut members = Vec::new();
+    // the name of the next member, when a gnu or pax header before it has one too long for its own header
+    let mut long_name = None;
+    let mut empty = true;
+    loop{
+        let mut header = [0; 512];
+        match reader.read_exact(&mut header){
+            Ok(()) => {},
+            // some archives end without the two zero blocks
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof && !empty => break,
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Err(invalid("it isn't a tar, tar.gz or zip archive")),
+            Err(err) => return Err(err),
+        }
+        if header.iter().all(|&byte| byte == 0){
+            break;
+        }
+        let checksum = tar_number(&header[148..156])?;
+        let sum: u64 = header.iter().enumerate().map(|(i, &byte)| if (148..156).contains(&i) {b' ' as u64} else {byte as u64}).sum();
+        if checksum != sum{
+            return Err(invalid("invalid tar header checksum, it isn't a tar, tar.gz or zip archive"));
+        }
+        empty = false;
+
+        let size = tar_number(&header[124..136])?;
+        match header[156]{
+            b'L' => long_name = Some(tar_text(&tar_data(&mut reader, size)?)),
+            b'x' => long_name = pax_path(&tar_data(&mut reader, size)?).or(long_name),
+            // regular files
+            b'0' | 0 | b'7' => {
+                let name = long_name.take().unwrap_or_else(||{
+                    let (prefix, name) = (tar_text(&header[345..500]), tar_text(&header[..100]));
+                    if &header[257..262] == b"ustar" && !prefix.is_empty() {format!("{}/{}", prefix, name)} else {name}
+                });
+                let hash = hash_reader((&mut reader).take(size))?;
+                io::copy(&mut (&mut reader).take(size.next_multiple_of(512) - size), &mut io::sink())?;
+                members.push(Member{name, hash});
+            },
+            // directories, links and the other types have no data of their own to hash
+            _ => {
+                long_name = None;
+                tar_data(&mut reader, size)?;
+            },
+        }
+    }
+    Ok(members)
+}
+
+fn u16_at(data: &[u8], at: usize) -> io::Result<usize>{
+    let bytes = data.get(at..at + 2).ok_or_else(|| invalid("the zip archive is cut short"))?;
+    Ok(u16::from_le_bytes(bytes.try_into().unwrap()) as usize)
+}
+
+fn u32_at(data: &[u8], at: usize) -> io::Result<usize>{
+    let bytes = data.get(at..at + 4).ok_or_else(|| invalid("the zip archive is cut short"))?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+}
+
+fn zip_members(mut reader: impl Read) -> io::Result<Vec<Member>>{
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    // the end of central directory record is in the last 22 bytes, followed by a comment of up to 65535 bytes
+    let search = data.len().saturating_sub(22 + 65535);
+    let end = (search..data.len().saturating_sub(21)).rev()
+        .find(|&i| data[i..i + 4] == [b'P', b'K', 5, 6])
+        .ok_or_else(|| invalid("the end of the central directory of the zip archive wasn't found"))?;
+    let entries = u16_at(&data, end + 10)?;
+    let mut at = u32_at(&data, end + 16)?;
+    if entries == 0xffff || at == 0xffffffff{
+        return Err(invalid("zip64 archives aren't supported"));
+    }
+
+    let mut members = Vec::new();
+    for _ in 0..entries{
+        if data.get(at..at + 4) != Some(&[b'P', b'K', 1, 2]){
+            return Err(invalid("invalid entry in the central directory of the zip archive"));
+        }
+        let method = u16_at(&data, at + 10)?;
+        let compressed = u32_at(&data, at + 20)?;
+        let (name_length, extra_length, comment_length) = (u16_at(&data, at + 28)?, u16_at(&data, at + 30)?, u16_at(&data, at + 32)?);
+        let local = u32_at(&data, at + 42)?;
+        let name = data.get(at + 46..at + 46 + name_length).ok_or_else(|| invalid("the zip archive is cut short"))?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        at += 46 + name_length + extra_length + comment_length;
+        if name.ends_with('/'){
+            continue;
+        }
+        if compressed == 0xffffffff{
+            return Err(invalid("zip64 archives aren't supported"));
+        }
+
+        // the data is after the local header, whose name and extra field can differ from the ones of the central directory
+        if data.get(local..local + 4) != Some(&[b'P', b'K', 3, 4]){
+            return Err(invalid("invalid local header in the zip archive"));
+        }
+        let start = local + 30 + u16_at(&data, local + 26)? + u16_at(&data, local + 28)?;
+        let content = data.get(start..start + compressed).ok_or_else(|| invalid("the zip archive is cut short"))?;
+        let hash = match method{
+            0 => Sha256::digest(content),
+            8 => hash_reader(DeflateDecoder::new(content))?,
+            _ => return Err(invalid(&format!("{} uses compression method {}, only stored and deflate are supported", name, method))),
+        };
+        members.push(Member{name, hash});
+    }
+    Ok(members)
+}
@@ -1,20 +1,46 @@
-use clap::{Args, ValueEnum};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
 use std::io::{self, IsTerminal, BufRead, Write, Read};
-use std::fs::File;
-use mysha::sha256::{sha256, InputType, HashError, Hash256};
-
-mod animation;
+use mysha::sha256::{sha256_with_rounds, Sha256, normalize_hex, ROUNDS, InputType, HashError, Hash256, OddLength, ParseOptions};
+use mysha::animation::{walkthrough, Phase, Renderer};
+use mysha::digest::git_blob_id;
+use mysha::sha1::Sha1;
+use mysha::encoding::{hex, randomart};
+
+pub(crate) mod animation;
+mod archive;
+mod record;
+mod screen;
+pub(crate) mod terminal;
+mod theme;
+pub use theme::Theme;
+mod trace;
+use trace::{Trace, MessageTrace};
+mod renderer;
+use renderer::Animation;
 use animation::*;
-mod helper_functions;
-use helper_functions::*;
 
+use crate::failure::{fail, Failure, Kind};
 use crate::Exit;
+use crate::config::{self, Config};
+use crate::qr;
+use crate::timing;
 
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct HashArgs{
+    #[command(subcommand)]
+    command: Option<HashCommand>,
+
     /// messages to be hashed
     messages: Vec<String>,
 
+    /// File listing messages to be hashed, one per line, after the ones given as arguments, skipping blank lines and lines starting with #.
+    /// Can be repeated
+    #[arg(long, value_name = "FILE")]
+    input_file: Vec<String>,
+
     /// Turn on animation
     #[arg(short, long)]
     animation: bool,
@@ -27,24 +53,119 @@ pub struct HashArgs{
     #[arg(short, long)]
     verbose: bool,
 
-    /// Input Type
-    #[arg(short, long, default_value_t = Type::Text, value_enum)]
-    r#type: Type,
+    /// Input Type [default: text]
+    #[arg(short, long, value_enum)]
+    r#type: Option<Type>,
+
+    /// Accepts hex as pasted from other tools, with a 0x prefix, spaces and colons, padding an odd number of digits with a zero
+    #[arg(long)]
+    lenient_hex: bool,
+
+    /// What is done with an odd number of hex digits [default: nibbles, or pad with --lenient-hex]
+    #[arg(long, value_enum)]
+    odd_length: Option<OddHex>,
 
     /// disables extra explanations on animation
-    #[arg(short, long)]
+    #[arg(short, long, overrides_with = "no_faster")]
     faster: bool,
 
+    /// Turns extra explanations on again, if --faster is set in the config file
+    #[arg(long, overrides_with = "faster")]
+    no_faster: bool,
+
+    /// Speed of the animation, 2 is twice as fast [default: 1]
+    #[arg(long)]
+    speed: Option<f64>,
+
     /// Turn off separate by lines on stdin passed by |
     #[arg(short, long)]
     separate_off: bool,
 
+    /// Separates stdin passed by | on NUL bytes instead of lines, for messages with newlines, like the names of find -print0
+    #[arg(short = '0', long, conflicts_with = "separate_off")]
+    null_input: bool,
+
     /// Display output as little endian
-    #[arg(short, long)]
+    #[arg(short, long, overrides_with = "no_little_endian")]
     little_endian: bool,
+
+    /// Display output as big endian, if --little-endian is set in the config file
+    #[arg(long, overrides_with = "little_endian")]
+    no_little_endian: bool,
+
+    /// Phase the animation starts at, the phases before it are skipped
+    #[arg(long, value_parser = phase_parser())]
+    start_at: Option<Phase>,
+
+    /// Records the animation to an asciinema cast file
+    #[arg(long, value_name = "FILE")]
+    record: Option<String>,
+
+    /// Writes every intermediate value to a file, as json if it ends with .json and toml if not
+    #[arg(long, value_name = "FILE")]
+    trace: Option<String>,
+
+    /// Prints the hashes as qr codes
+    #[arg(long)]
+    qr: bool,
+
+    /// Writes the qr codes of the hashes to a file, as svg if it ends with .svg and png if not
+    #[arg(long, value_name = "FILE")]
+    qr_file: Option<String>,
+
+    /// Prints to stderr how long every message took to hash, and the throughput, without the animation
+    #[arg(long)]
+    time: bool,
+
+    /// Prints the randomart of every hash, the picture of ssh-keygen, to compare hashes at a glance
+    #[arg(long)]
+    randomart: bool,
+
+    /// Ends every result with a NUL byte instead of a newline, for xargs -0 and messages with newlines
+    #[arg(long, conflicts_with_all = ["json_lines", "randomart", "qr"])]
+    print0: bool,
+
+    /// Prints every result as the index, the message and the hash separated by tabs, with tabs, newlines and backslashes of the message escaped
+    #[arg(long, conflicts_with_all = ["json_lines", "verbose", "randomart", "qr"])]
+    tsv: bool,
+
+    /// Prints every result as a json object with the index, the input and the digest, one per line
+    #[arg(long, conflicts_with_all = ["verbose", "randomart", "qr"])]
+    json_lines: bool,
+
+    /// Runs only the first rounds of the 64 of every block. INSECURE, it isn't sha256, to see how the mixing builds up
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=ROUNDS as i64), conflicts_with_all = ["animation", "trace"])]
+    rounds: Option<u8>,
+
+    /// Hashes every file inside the messages, tar, tar.gz or zip archives, without extracting them, and then the list of those hashes
+    #[arg(long, conflicts_with_all = ["animation", "trace", "rounds", "type"])]
+    archive: bool,
+
+    /// Prints the object ids git gives to the file, with SHA-1 and sha256, like `git hash-object`
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["messages", "input_file", "animation", "trace", "rounds", "type", "archive"])]
+    git_blob: Option<String>,
 }
 
-#[derive(Debug, Clone, ValueEnum, PartialEq)]
+/// Parses the phases of the animation, with their descriptions as help.
+fn phase_parser() -> impl TypedValueParser<Value = Phase>{
+    PossibleValuesParser::new(Phase::ALL.map(|phase| PossibleValue::new(phase.name()).help(phase.description())))
+        .map(|phase| phase.parse::<Phase>().unwrap())
+}
+
+#[derive(Debug, Subcommand)]
+enum HashCommand{
+    /// Shows again the hashing process saved with --trace
+    Replay(ReplayArgs),
+}
+
+#[derive(Args, Debug)]
+struct ReplayArgs{
+    /// trace file
+    file: String,
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Type{
     /// String
     Text,
@@ -59,18 +180,103 @@ pub enum Type{
     /// Little endian hexadecimal number
     LeHex,
     /// Decimal number
-    Decimal
+    Decimal,
+    /// Detected from every message: an existing file, hex with 0x or digits and letters, binary, decimal, or text
+    Auto,
+}
+
+impl Type{
+    /// Type of the message: the type itself, or the one detected if it is auto.
+    ///
+    /// Hex needs a 0x prefix, or an even number of digits with at least a number among them,
+    /// so words like `cafe` stay text, and only zeros and ones are binary, not decimal or hex.
+    pub fn resolve(&self, message: &str) -> Type{
+        if *self != Type::Auto{
+            return self.clone();
+        }
+        let is_hex = |hex: &str| !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit());
+        let digits = message.strip_prefix('-').unwrap_or(message);
+        if std::path::Path::new(message).is_file(){
+            Type::File
+        }else if message.strip_prefix("0x").is_some_and(is_hex){
+            Type::Hex
+        }else if !message.is_empty() && message.chars().all(|c| c == '0' || c == '1'){
+            Type::Binary
+        }else if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()){
+            Type::Decimal
+        }else if is_hex(message) && message.len().is_multiple_of(2) && message.chars().any(|c| c.is_ascii_digit()){
+            Type::Hex
+        }else{
+            Type::Text
+        }
+    }
+
+    /// Input type of the library for the message, resolving auto, with the message as it is given to the library,
+    /// without the 0x prefix of detected hex.
+    pub fn input<'a>(&self, message: &'a str) -> (InputType, &'a str){
+        let r#type = self.resolve(message);
+        let message = match (self, &r#type){
+            (Type::Auto, Type::Hex) => message.strip_prefix("0x").unwrap_or(message),
+            _ => message,
+        };
+        (r#type.input_type(), message)
+    }
+
+    fn input_type(&self) -> InputType{
+        match self{
+            Type::Binary => InputType::Binary,
+            Type::LeBinary => InputType::LeBinary,
+            Type::Text => InputType::Text,
+            Type::File => InputType::File,
+            Type::Hex => InputType::Hex,
+            Type::LeHex => InputType::LeHex,
+            Type::Decimal => InputType::Decimal,
+            Type::Auto => unreachable!("auto is resolved to another type"),
+        }
+    }
+
+    fn name(&self) -> String{
+        self.to_possible_value().unwrap().get_name().to_owned()
+    }
 }
 
-pub fn hash(args: HashArgs) {
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+enum OddHex{
+    /// The last digit is half a byte
+    Nibbles,
+    /// A zero is added at the start, like a number
+    Pad,
+    /// An odd number of digits is an error
+    Reject,
+}
+
+pub fn hash(args: HashArgs, config: &Config) {
+    if let Some(HashCommand::Replay(args)) = args.command{
+        Trace::read(&args.file).replay();
+        return;
+    }
+    if let Some(path) = &args.git_blob{
+        let content = std::fs::read(path).exit("Error while oppening the file.");
+        println!("sha1:   {}", hex::encode(&git_blob_id::<Sha1>(&content)));
+        println!("sha256: {}", hex::encode(&git_blob_id::<Sha256>(&content)));
+        return;
+    }
+
     let mut messages = args.messages;
+    messages.extend(read_input_files(&args.input_file));
     let mut animation = args.animation;
     let mut enter = args.enter;
     let verbose = args.verbose;
-    let type_input = args.r#type;
+    let type_input = args.r#type.or(config.sha256.r#type.clone()).unwrap_or(Type::Text);
     let s = args.separate_off;
-    let f = args.faster;
-    let le = args.little_endian;
+    let f = config::flag(args.faster, args.no_faster, config.sha256.faster);
+    let le = config::flag(args.little_endian, args.no_little_endian, config.sha256.little_endian);
+    let speed = args.speed.or(config.sha256.speed).unwrap_or(1.0);
+    set_speed((speed.is_finite() && speed > 0.0).then_some(speed).exit("Error: the speed of the animation must be a positive number."));
+    if let Some(phase) = args.start_at{
+        start_at(phase);
+    }
+    theme::set_theme(config.theme);
     
     if ! io::stdin().is_terminal(){
         enter = false;
@@ -80,6 +286,12 @@ pub fn hash(args: HashArgs) {
             io::stdin().read_to_string(&mut m).expect("Error while geting stdin passed.");
             messages.push(m);
 
+        }else if args.null_input{
+            let mut m = String::new();
+            io::stdin().read_to_string(&mut m).expect("Error while geting stdin passed.");
+            // a NUL ends every message, so the one find -print0 writes after the last name doesn't add an empty message
+            messages.extend(m.split_terminator('\0').map(String::from));
+
         }else{
             let stdin = io::stdin().lock().lines();
             for line in stdin{
@@ -93,7 +305,7 @@ pub fn hash(args: HashArgs) {
         animation = false;
     }
 
-    if messages.len() == 0{
+    if messages.is_empty(){
         print!("Message to hash:  ");
         std::io::stdout().flush().unwrap();
         let mut message = String::new();
@@ -101,388 +313,224 @@ pub fn hash(args: HashArgs) {
         messages.push(message.replace(['\n', '\r'], ""));
     }
 
+    if args.archive{
+        hash_archives(&messages, verbose);
+        return;
+    }
+
+    let types: Vec<Type> = messages.iter().map(|message| type_input.resolve(message)).collect();
+    if verbose && type_input == Type::Auto{
+        for (i, (message, r#type)) in messages.iter().zip(&types).enumerate(){
+            println!("[{}]({}) read as {}", i, message, r#type.name());
+        }
+    }
+
+    if types.iter().any(|r#type| matches!(r#type, Type::Hex | Type::LeHex)){
+        // a detected hex can have a 0x prefix
+        let lenient = args.lenient_hex || type_input == Type::Auto;
+        let mut options = if lenient {ParseOptions::lenient()} else {ParseOptions::default()};
+        if let Some(odd) = &args.odd_length{
+            options.odd_length = match odd{
+                OddHex::Nibbles => OddLength::Nibbles,
+                OddHex::Pad => OddLength::PadLeft,
+                OddHex::Reject => OddLength::Reject,
+            };
+        }
+        for (message, r#type) in messages.iter_mut().zip(&types){
+            if matches!(r#type, Type::Hex | Type::LeHex){
+                *message = normalize_hex(message, &options).exit("Error while parsing hexadecimal value.");
+            }
+        }
+    }
+
+    if let Some(path) = &args.trace{
+        let messages = messages.iter().zip(&types).map(|(message, r#type)| {
+            let mut trace = MessageTrace::new(message);
+            walk(message, r#type, &mut trace);
+            trace
+        }).collect();
+        Trace{messages}.write(path);
+    }
+
+    let output = Output{
+        format: if args.tsv {Format::Tsv} else if args.json_lines {Format::JsonLines} else if verbose {Format::Verbose} else {Format::Hash},
+        end: if args.print0 {'\0'} else {'\n'},
+    };
+
+    let rounds = args.rounds.map_or(ROUNDS, usize::from);
+    if rounds < ROUNDS{
+        eprintln!("Warning: only {} of the {} rounds, this isn't sha256 and it is insecure.", rounds, ROUNDS);
+    }
+
     if ! animation{
 
         for (index_message, message) in messages.iter().enumerate(){
 
-            let hash = match type_input{
-                Type::Binary => sha256(message, InputType::Binary).exit("Error while parsing binary value. invalid binary input."),
-                Type::LeBinary => sha256(message, InputType::LeBinary).exit("Error while parsing little endian binary value."),
-                Type::Text => sha256(message, InputType::Text).unwrap(),
-                Type::File => sha256(message, InputType::File).exit("Error while oppening the file."),
-                Type::Hex => sha256(message, InputType::Hex).exit("Error while parsing hexadecimal value. Invalid Hex input."),
-                Type::LeHex => sha256(message, InputType::LeHex).exit("Error while parsing little endian hexadecimal value."),
-                Type::Decimal => sha256(message, InputType::Decimal).unwrap_or_else(|err| {
-                    match err{
-                        HashError::DecimalTooBig => eprintln!("Error while parsing number. Number is too big, try using type hex."),
-                        _ => eprintln!("Error while parsing number. Invalid input."),
-                    };
-                    std::process::exit(1);
+            let label = format!("[{}]({})", index_message, message);
+            let size = input_size(message, &types[index_message]);
+            let hash = timing::time(args.time, &label, size, || match types[index_message]{
+                Type::Binary => sha256_with_rounds(message, InputType::Binary, rounds).exit("Error while parsing binary value. invalid binary input."),
+                Type::LeBinary => sha256_with_rounds(message, InputType::LeBinary, rounds).exit("Error while parsing little endian binary value."),
+                Type::Text => sha256_with_rounds(message, InputType::Text, rounds).unwrap(),
+                Type::File => sha256_with_rounds(message, InputType::File, rounds).exit("Error while oppening the file."),
+                Type::Hex => sha256_with_rounds(message, InputType::Hex, rounds).exit("Error while parsing hexadecimal value. Invalid Hex input."),
+                Type::LeHex => sha256_with_rounds(message, InputType::LeHex, rounds).exit("Error while parsing little endian hexadecimal value."),
+                Type::Decimal => sha256_with_rounds(message, InputType::Decimal, rounds).unwrap_or_else(|err| match err{
+                    HashError::DecimalTooBig => fail(Failure::Parse, "Error while parsing number. Number is too big, try using type hex.", None),
+                    _ => fail(Failure::Parse, "Error while parsing number. Invalid input.", None),
                 }),
-            };
-
-            if verbose{
-                print!("[{}]({:70}", index_message, message.to_owned() + "): ");
+                Type::Auto => unreachable!("auto is resolved to another type"),
+            });
 
-            }
-            if le{
-                println!("{}", hash.get_hex_le());
+            let hash = if le{
+                hash.get_hex_le()
             }else{
-                println!("{}", hash);
-            }
+                hash.get_hex().to_owned()
+            };
+            output.print(index_message, message, &hash);
+            show_randomart(&hash, args.randomart);
+            show_qr(&hash, index_message, messages.len(), args.qr, args.qr_file.as_deref());
         }
     }else{
         ctrlc::set_handler(|| {
-            printf("\x1b[m\x1b[?25h"); // make cursor visible
-            printf("\x1b[?1049l"); // disable alternative buffer, get back to previous state
+            leave();
             std::process::exit(0);
         }).expect("Error initializing program");
 
-        printf("\x1b[?1049h"); // create alternative buffer
-        printf("\x1b[?25l"); // make cursor invisible
+        animation::enter(io::stdin().is_terminal(), args.record.as_deref());
+        if ! enter && io::stdin().is_terminal(){
+            listen_keys();
+        }
 
         let mut hashes = Vec::new();
-        for (index_message, message) in messages.iter().enumerate(){
-            cleartop();
-            if messages.len() != 1{
-                printf("messages: [");
-                for (i, m) in messages.iter().enumerate(){
-                    if i  == messages.len() - 1{
-                        if i != index_message{
-                            printf(format!("{:?}]", m).as_str());
-                        }else{
-                            blink(format!("{:?}", m).as_str());
-                            printf("]");
-                        }
-                    }else{
-                        if i != index_message{
-                            printf(format!("{:?}, ", m).as_str());
-                        }else{
-                            blink(format!("{:?}", m).as_str());
-                            printf(", ");
-                        }
-                    }
-                }
-                wait(enter, 2000);
-                printf(format!("\nmessage: {}", message).as_str());  
-                wait(enter, 1000);
-            }
-            cleartop();
-            if type_input == Type::Text{
-                println!("message: {}", message);
-                wait(enter, 1000);
-            }else if type_input == Type::Hex{
-                println!("Hex value: {}", message);
-                wait(enter, 1000);
-            }else if type_input == Type::LeHex{
-                println!("Little endian hex value: {}", message);
-                wait(enter, 1000);
-            }else if type_input == Type::Decimal{
-                println!("Decimal value: {}", message);
-                wait(enter, 1000);
-            }else if type_input == Type::LeBinary{
-                println!("Little endian binary value: {}", message);
-                wait(enter, 1000);
-            }
-            
-            let mut bits = match type_input{
-                Type::Binary => {
-                    binary_handling::validate_bits(message).exit("\x1b[m\x1b[?25h\x1b[?1049lError while parsing binary value. invalid binary input.");
-                    message.to_owned()
-                },
-                Type::LeBinary => {
-                    binary_handling:: validate_bits(message).exit("\x1b[m\x1b[?25h\x1b[?1049lError while parsing binary value. invalid binary input.");
-                    if message.len() % 8 != 0{
-                        Err::<Hash256, HashError>(HashError::NotWholeBytes).exit("\x1b[m\x1b[?25h\x1b[?1049lError while parsing binary value.");
-                    }
-                    (0..message.len()).step_by(8).rev().map(|i| &message[i..i+8]).collect()
-                }
-                Type::Text => binary_handling::get_binary_message(message),
-                Type::File => {
-                    let mut file = File::open(message).exit("\x1b[m\x1b[?25h\x1b[?1049lError while oppening the file.");
-                    let mut content = String::new();
-                    file.read_to_string(&mut content).exit("Error while reading the file");
-                    
-                    binary_handling::get_binary_message(&content)
-                },
-                Type::Hex => binary_handling::get_bits_hex(message, false).exit("\x1b[m\x1b[?25h\x1b[?1049lError while parsing hexadecimal value."),
-                Type::LeHex => binary_handling::get_bits_hex(message, true).exit("\x1b[m\x1b[?25h\x1b[?1049lError while parsing hexadecimal value."),
-                Type::Decimal => format!("{:b}", message.parse::<i128>().unwrap_or_else(|err| {
-                    printf("\x1b[m\x1b[?25h"); // make cursor visible
-                    printf("\x1b[?1049l"); // disable alternative buffer, get back to previous state
-                    match err.kind(){
-                        std::num::IntErrorKind::PosOverflow => eprintln!("Error while parsing number. Number is too big, try using type hex."),
-                        _ => eprintln!("Error while parsing number. Invalid input."),
-                    };
-                    std::process::exit(1);
-                })),
-            };
+        for index_message in 0..messages.len(){
+            let mut animation = Animation::new(&messages, index_message, &types[index_message], enter, f, le);
+            walk(&messages[index_message], &types[index_message], &mut animation);
+            hashes.push(animation.hash256);
+        }
 
-            printf(format!("bits: {}", bits).as_str());
-            wait(enter, 1000);
-
-            binary_handling_animated::pad(&mut bits);
-            wait(enter, 1000);
-
-            let message_blocks = binary_handling::get_message_blocks(&bits);
-
-            println!("\nmessage blocks: [");
-            wait(enter, 500);
-            for (j, i) in message_blocks.iter().enumerate(){
-                printf("\x1b[H");
-                if type_input == Type::Text{
-                    println!("message: {}", message);
-                }else if type_input == Type::Hex{
-                    println!("Hex value: {}", message);
-                }else if type_input == Type::LeHex{
-                    println!("Little endian hex value: {}", message);
-                }else if type_input == Type::Decimal{
-                    println!("Decimal value: {}", message);
-                }else if type_input == Type::LeBinary{
-                    println!("Little endian binary value: {}", message);
-                }
-                printf(format!("bits: {}", &bits[0..j * 512]).as_str());
-                blink(format!("{}", &bits[j * 512..(j * 512) + 512]).as_str());
-                printf(format!("{}\n\n\n", &bits[(j * 512) + 512..]).as_str());
-                
-                for k in 0..j + 1{
-                    if k == j{
-                        blink(format!("    {:#?},\n", i).as_str());
-                    }else{
-                        printf(format!("    {:#?},\n", message_blocks[k]).as_str());
-                    }
-                }
-
-                wait(enter, 1000);
-            }
-            printf("\x1b[H");
-            if type_input == Type::Text{
-                println!("message: {}", message);
-            }else if type_input == Type::Hex{
-                println!("Hex value: {}", message);
-            }else if type_input == Type::LeHex{
-                println!("Little endian hex value: {}", message);
-            }else if type_input == Type::Decimal{
-                println!("Decimal value: {}", message);
-            }else if type_input == Type::LeBinary{
-                println!("Little endian binary value: {}", message);
-            }
-            println!("bits: {}", bits);
-            println!("\x1b[Emessage blocks: {:#?}", message_blocks);
-            
-            wait(enter, 1000);
-            cleartop();
-
-            if index_message == 0 && !f{
-                binary_handling_animated::animate_operations(enter);
-                binary_handling_animated::animate_k(enter);
-            }
+        leave();
+        for (i, hash256) in hashes.iter().enumerate(){
+            output.print(i, &messages[i], hash256);
+            show_randomart(hash256, args.randomart);
+            show_qr(hash256, i, hashes.len(), args.qr, args.qr_file.as_deref());
+        }
+    }
 
-            let mut a0 = constants::initialize_a();
-
-            let k = constants::initialize_k();
-
-            for (index_block, block) in message_blocks.iter().enumerate(){
-                cleartop();
-                if index_block > 0{
-                    binary_handling_animated::keep_a(&a0);
-                }
-                let mut message_schedule = binary_handling_animated::get_message_schedule(enter, block, index_block);
-
-
-                printf("w16: ");
-                printf("\x1b[34C");
-                printf("= sigma1(t-2) + (t-7) + sigma0(t-15) + (t-16) (mod 2**32)");
-                wait(enter, 2000);
-
-
-
-                for i in 16..64{
-                    let new = operations::addn(vec![operations::l_sigma1(message_schedule[i - 2]), message_schedule[i - 7], operations::l_sigma0(message_schedule[i - 15]), message_schedule[i - 16]]);
-                    message_schedule.push(new);
-
-                    printf(format!("\x1b[16F\x1b[37C -> {:032b}", message_schedule[i - 16]).as_str());
-
-                    printf(format!("\x1b[E\x1b[37C -> sigma0 = {:032b}", operations::l_sigma0(message_schedule[i - 15])).as_str());
-
-                    printf(format!("\x1b[8E\x1b[37C -> {:032b}", message_schedule[i - 7]).as_str());
-
-                    printf(format!("\x1b[5E\x1b[37C -> sigma1 = {:032b}", operations::l_sigma1(message_schedule[i - 2])).as_str());
-
-                    printf("\x1b[2E");
-                    
-                    if i != 16{
-                        for j in 1..17{
-                            printf(format!("\x1b[Fw{:02}: {:032b}", i - j, message_schedule[i - j]).as_str());
-                        }
-                        printf("\x1b[16E");
-                    }
-                    
-
-                    printf(format!("w{:02}: {:032b}", i, new).as_str());
-
-                    wait(enter, 350);
-                }
-                wait(false, 1000);
-
-                cleartop();
-                if index_block > 0{
-                    binary_handling_animated::keep_a(&a0);
-                }
-                printf(format!("message block[{}]: {}\n", index_block, block).as_str());
-
-                println!("\nCompression: \n");
-                println!("W00: ");
-                println!("K00: \n");
-                println!("T1 =");
-                println!("T2 = \n\n");
-                
-                if index_block == 0{
-                    binary_handling_animated::animate_a(enter);
-                    wait(enter, 500);
-                    binary_handling_animated::save_a(&a0, enter);
-                }else{
-                    for i in 97..105 as u8{
-                        println!("{} = ", i as char)
-                    }
-                    printf("\x1b[8F\x1b[4C");
-                    wait(enter, 500);
-                    binary_handling_animated::restore_a(&a0, enter, false);
-                    printf("\x1b[8E")
-                }
-                wait(enter, 400);
-                printf("\x1b[12F\x1b[4C SIGMA1(e) + choice(e, f, g) + h + K00 + W00 (mod 2**32)");
-                printf("\x1b[E\x1b[4C SIGMA0(a) + majority(a, b, c) (mod 2**32)\x1b[11E");
-                
-                wait(enter, 1000);
-
-                let mut a = a0.clone();
-
-                for (i, m) in message_schedule.iter().enumerate(){
-                    printf(format!("\x1b[15FW{:02}: {:032b}", i, m).as_str());
-                    printf(format!("\x1b[EK{:02}: {:032b}", i, k[i]).as_str());
-                    printf(format!("\x1b[2E\x1b[40C{:02} + W{:02}\x1b[12E", i, i).as_str());
-                    if i < 3{
-                        wait(enter, 1000);
-                    }else{
-                        wait(enter, 200);
-                    }
-
-                    let t1 = operations::addn(vec![operations::u_sigma1(a[4]), operations::choice(a[4], a[5], a[6]), a[7], k[i], *m]);
-                    let t2 = operations::add(operations::u_sigma0(a[0]), operations::majority(a[0], a[1], a[2]));
-                    printf(format!("\x1b[12F\x1b[61C = {:032b}", t1).as_str());
-                    printf(format!("\x1b[E\x1b[61C = {:032b}\x1b[11E", t2).as_str());
-                    if i < 3{
-                        wait(enter, 1000);
-                    }else{
-                        wait(enter, 200);
-                    }
-
-                    for j in 0..8{
-                        if j == 0{
-                            printf("\x1b[8F\x1b[36C\u{2193}           \x1b[E");
-                        }else{
-                            printf("\x1b[36C\u{2193}           \x1b[E")
-                        }
-                    }
-                    if i < 3{
-                        wait(enter, 1000);
-                    }else{
-                        wait(enter, 200);
-                    }
-
-                    for j in 0..8{
-                        if j == 7{
-                            a[7 - j] = operations::add(t1, t2);
-                            printf(format!("\x1b[F\x1b[4C{: >32} -> T1 + T2\x1b[8E", "").as_str());
-                        }else if j == 3{
-                            a[7 - j] = operations::add(a[7 - j - 1], t1);
-                            printf(format!("\x1b[F\x1b[4C{:032b} + T1", a[7 - j - 1]).as_str());
-                        }else{
-                            printf(format!("\x1b[F\x1b[4C{:032b} ", a[7 - j - 1]).as_str());
-                            a[7 - j] = a[7 - j - 1];
-                        }
-                    }
-                    if i < 3{
-                        wait(enter, 1000);
-                    }else{
-                        wait(enter, 200);
-                    }
-
-                    printf(format!("\x1b[4F\x1b[4C{:032b}", a[4]).as_str());
-                    printf(format!("\x1b[4F\x1b[4C{:032b}\x1b[8E", a[0]).as_str());
-                    if i < 3{
-                        wait(enter, 1000);
-                    }else{
-                        wait(enter, 200);
-                    }
-                }
-
-                for _ in 0..8{
-                    printf("\x1b[F\x1b[36C +         \x1b[8D");
-                }
-                wait(enter, 1000);
-                binary_handling_animated::restore_a(&a0, enter, true);
-                wait(enter, 1000);
-
-                printf("\x1b[F\x1b[E");
-                for k in 0..8{
-                    a0[k] = operations::add(a[k], a0[k]);
-                    printf(format!("\x1b[4C{:032b}{: >47}\x1b[E", a0[k], "").as_str());
-                }
-                wait(enter, 800);
-
-                if message_blocks.len() == index_block + 1{
-                    for i in a0.iter().rev(){
-                        printf(format!("\x1b[F\x1b[36C = {:08x}", i).as_str());
-                    }
-                    printf("\x1b[8E\n");
-                    wait(enter, 500);
-
-                    printf("hash:");
-                    wait(enter, 200);
-                    let mut hash256 = String::new();
-                    for (i, j) in a0.iter().enumerate(){
-                        blink(format!("\x1b[{}F\x1b[39C{:08x}\x1b[{}E", 9 - i, j, 9 - i).as_str());
-                        if i != 0{
-                            printf(format!("hash: \x1b[{}C{:08x}", i * 8, j).as_str());
-                        }else{
-                            printf(format!("hash: {:08x}", j).as_str());
-                        }
-                        hash256 += &format!("{:08x}", j);
-                        wait(enter,500);
-                        printf(format!("\x1b[{}F\x1b[39C{:08x}\x1b[{}E", 9 - i, j, 9 - i).as_str());
-                    }
-                    if le{
-                        printf("\n\nle hex: ");
-                        wait(enter, 200);
-                        hash256 = (0..hash256.len()).step_by(2).rev().map(|i|{
-                            printf(&format!("\x1b[2Fhash: {}", &hash256[0..i]));
-                            blink(&hash256[i..i+2]);
-                            printf(&hash256[i+2..]);
-                            printf(&format!("\x1b[2Ele hex:\x1b[{}C{}",1 + hash256.len() - i - 2, &hash256[i..i+2]));
-                            wait(enter, 500);
-                            &hash256[i..i+2]
-                        }).collect();
-                        printf(&format!("\x1b[2Fhash: {}", &hash256[hash256.len()-2..]));
-                    }
-
-                    hashes.push(hash256);
-                    wait(enter, 1000);
-                }else{
-                    binary_handling_animated::save_a(&a0, enter);
-                }
-            }        
+}
+
+/// Prints the hash of every file of the archives, sorted by path like the lines of sha256sum,
+/// and then the hash of those lines as the digest of the whole archive.
+///
+/// The digest only depends on the paths and contents of the files, not on the format, compression or dates of the archive,
+/// so a tar and a zip of the same files have the same one.
+fn hash_archives(paths: &[String], verbose: bool){
+    for (i, path) in paths.iter().enumerate(){
+        let mut members = archive::hash_members(path).exit("Error while reading the archive.");
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+        if verbose{
+            println!("[{}]({}) {} files", i, path, members.len());
         }
+        let manifest: String = members.iter().map(|member| format!("{}  {}\n", hex::encode(&member.hash), member.name)).collect();
+        print!("{}", manifest);
+        println!("{}  {}", hex::encode(&Sha256::digest(manifest.as_bytes())), path);
+    }
+}
 
-        printf("\x1b[?25h"); // make cursor visible
-        printf("\x1b[?1049l"); // disable alternative buffer, get back to previous state
-        for (i, hash256) in hashes.iter().enumerate(){
-            if verbose{
-                print!("[{}]({:70}", i, messages[i].to_owned() + "): ");
-            }
-            println!("{}", hash256);
+/// How every result is printed, from the output flags.
+enum Format{
+    /// Only the hash.
+    Hash,
+    /// The index and the message before the hash, for people to read.
+    Verbose,
+    /// The index, the message and the hash separated by tabs.
+    Tsv,
+    /// A json object with the index, the input and the digest.
+    JsonLines,
+}
+
+struct Output{
+    format: Format,
+    /// What ends every result, a newline or the NUL of --print0.
+    end: char,
+}
+
+impl Output{
+    /// Prints the result of the message, in the format of the output flags.
+    fn print(&self, index: usize, message: &str, hash: &str){
+        match self.format{
+            Format::Hash => print!("{}", hash),
+            Format::Verbose => print!("[{}]({:70}{}", index, message.to_owned() + "): ", hash),
+            Format::Tsv => print!("{}\t{}\t{}", index, escape_tsv(message), hash),
+            Format::JsonLines => print!("{}", serde_json::to_string(&JsonRecord{index, input: message, digest: hash}).unwrap()),
         }
+        print!("{}", self.end);
     }
+}
+
+/// A line of --json-lines, with the fields in this order.
+#[derive(Serialize)]
+struct JsonRecord<'a>{
+    index: usize,
+    input: &'a str,
+    digest: &'a str,
+}
+
+/// The message with backslashes, tabs, newlines and carriage returns escaped, so it is one field of a tsv line.
+fn escape_tsv(message: &str) -> String{
+    message.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
 
+/// Messages of the listing files of --input-file, one per line, without blank lines and comments, the lines starting with #.
+pub(crate) fn read_input_files(paths: &[String]) -> Vec<String>{
+    let mut messages = Vec::new();
+    for path in paths{
+        let content = std::fs::read_to_string(path).exit(&format!("Error while reading the input file {}.", path));
+        messages.extend(content.lines().filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#')).map(str::to_owned));
+    }
+    messages
+}
+
+/// Number of bytes hashed for the message, for the throughput of --time.
+fn input_size(message: &str, r#type: &Type) -> Option<u64>{
+    match r#type{
+        Type::Text => Some(message.len() as u64),
+        Type::File => std::fs::metadata(message).ok().map(|metadata| metadata.len()),
+        Type::Hex | Type::LeHex => Some(message.len() as u64 / 2),
+        Type::Binary | Type::LeBinary => Some(message.len() as u64 / 8),
+        Type::Decimal | Type::Auto => None,
+    }
+}
+
+/// Prints the randomart of the hash as it is shown, in big or little endian, if asked to.
+fn show_randomart(hash: &str, print: bool){
+    if print{
+        println!("{}", randomart::draw(&hex::decode(hash).unwrap(), "", "SHA256"));
+    }
+}
+
+/// Prints the qr code of the hash, and writes it to a file, if asked to.
+fn show_qr(hash: &str, index: usize, count: usize, print: bool, file: Option<&str>){
+    if print{
+        qr::print(hash);
+    }
+    if let Some(path) = file{
+        qr::write(hash, &qr::get_name_indexed(path, index, count));
+    }
+}
+
+/// Hashes the message giving every step to the renderer, exits with an error message if the input is invalid.
+fn walk(message: &str, type_input: &Type, renderer: &mut impl Renderer) -> Hash256{
+    let input_type = type_input.input_type();
+    walkthrough(message, input_type, renderer).unwrap_or_else(|err| {
+        leave();
+        match (type_input, &err){
+            (Type::Decimal, HashError::DecimalTooBig) => fail(Failure::Parse, "Error while parsing number. Number is too big, try using type hex.", None),
+            (Type::Decimal, _) => fail(Failure::Parse, "Error while parsing number. Invalid input.", None),
+            (Type::Binary | Type::LeBinary, _) => fail(err.failure(), "Error while parsing binary value.", Some(&err)),
+            (Type::File, _) => fail(err.failure(), "Error while oppening the file.", Some(&err)),
+            (Type::Hex | Type::LeHex, _) => fail(err.failure(), "Error while parsing hexadecimal value.", Some(&err)),
+            (Type::Text | Type::Auto, _) => fail(err.failure(), "Error while hashing the text.", Some(&err)),
+        }
+    })
 }
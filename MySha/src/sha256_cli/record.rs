@@ -0,0 +1,127 @@
+//! Recording of the animation to asciinema cast files.
+//!
+//! The [Recorder] is a [Backend] that draws on another backend, and also writes everything drawn,
+//! as escape sequences and with the time it was drawn at, to a file in the [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) format.
+//! The file can then be played with `asciinema play`, or published to be played in a browser.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use super::screen::{MIN_COLS, MIN_ROWS};
+use super::terminal::{Backend, Clear, MoveTo, ResetStyle, SetStyle, Style};
+
+#[derive(Serialize)]
+struct Header{
+    version: u8,
+    width: usize,
+    height: usize,
+    timestamp: u64,
+    title: String,
+    env: Env,
+}
+
+#[derive(Serialize)]
+struct Env{
+    #[serde(rename = "TERM", skip_serializing_if = "Option::is_none")]
+    term: Option<String>,
+    #[serde(rename = "SHELL", skip_serializing_if = "Option::is_none")]
+    shell: Option<String>,
+}
+
+/// Adds the .cast extension to the file name, if it doesn't have it.
+fn get_name_cast(filename: &str) -> String{
+    if ! filename.ends_with(".cast"){
+        filename.to_owned() + ".cast"
+    }else{
+        filename.to_owned()
+    }
+}
+
+pub struct Recorder{
+    backend: Box<dyn Backend>,
+    file: BufWriter<File>,
+    start: Instant,
+    size: (usize, usize),
+    frame: String,
+}
+
+impl Recorder{
+    /// Creates the cast file, and writes its header with the current size of the terminal.
+    pub fn create(path: &str, backend: Box<dyn Backend>, title: &str) -> io::Result<Recorder>{
+        let mut file = BufWriter::new(File::create(get_name_cast(path))?);
+        let size = backend.size().unwrap_or((MIN_COLS, MIN_ROWS));
+        let header = Header{
+            version: 2,
+            width: size.0,
+            height: size.1,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|t| t.as_secs()).unwrap_or(0),
+            title: title.to_owned(),
+            env: Env{
+                term: std::env::var("TERM").ok(),
+                shell: std::env::var("SHELL").ok(),
+            },
+        };
+        writeln!(file, "{}", serde_json::to_string(&header)?)?;
+        Ok(Recorder{backend, file, start: Instant::now(), size, frame: String::new()})
+    }
+
+    /// Writes an event to the file, with the time since the recording started.
+    fn event(&mut self, code: &str, data: &str) -> io::Result<()>{
+        let time = (self.start.elapsed().as_secs_f64() * 1e6).round() / 1e6;
+        writeln!(self.file, "{}", serde_json::to_string(&(time, code, data))?)
+    }
+}
+
+impl Backend for Recorder{
+    fn enter(&mut self, keys: bool) -> io::Result<()>{
+        self.backend.enter(keys)
+    }
+
+    fn leave(&mut self) -> io::Result<()>{
+        self.flush()?;
+        self.file.flush()?;
+        self.backend.leave()
+    }
+
+    fn size(&self) -> Option<(usize, usize)>{
+        self.backend.size()
+    }
+
+    fn clear(&mut self) -> io::Result<()>{
+        self.frame += &format!("{}{}", ResetStyle, Clear::All);
+        self.backend.clear()
+    }
+
+    fn move_to(&mut self, col: usize, row: usize) -> io::Result<()>{
+        self.frame += &MoveTo(col, row).to_string();
+        self.backend.move_to(col, row)
+    }
+
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()>{
+        self.frame += &format!("{}{}", SetStyle(style), text);
+        self.backend.print(text, style)
+    }
+
+    fn clear_line(&mut self) -> io::Result<()>{
+        self.frame += &format!("{}{}", ResetStyle, Clear::UntilNewLine);
+        self.backend.clear_line()
+    }
+
+    /// Draws on the terminal, and writes what was drawn since the last flush as one frame.
+    fn flush(&mut self) -> io::Result<()>{
+        if let Some(size) = self.backend.size(){
+            if size != self.size{
+                self.size = size;
+                self.event("r", &format!("{}x{}", size.0, size.1))?;
+            }
+        }
+        if ! self.frame.is_empty(){
+            let frame = std::mem::take(&mut self.frame);
+            self.event("o", &frame)?;
+        }
+        self.backend.flush()
+    }
+}
@@ -0,0 +1,362 @@
+//! The sha256 animation, as a [Renderer] of the steps of a [walkthrough][mysha::animation::walkthrough()].
+
+use mysha::animation::{Algorithm, Phase, Renderer, Sha256, Step};
+
+use super::animation::*;
+use super::terminal::{MoveLeft, MoveRight, MoveTo, MoveToNextLine, MoveToPreviousLine};
+use super::Type;
+
+/// Animates the hashing of one of the messages.
+pub struct Animation<'a>{
+    pub messages: &'a [String],
+    pub index_message: usize,
+    pub type_input: &'a Type,
+    pub enter: bool,
+    pub faster: bool,
+    pub le: bool,
+    /// Hash shown at the end of the animation, in little endian if `le` is set.
+    pub hash256: String,
+    bits: String,
+    message_blocks: Vec<String>,
+    index_block: usize,
+    block: String,
+    a0: Vec<u32>,
+    a: Vec<u32>,
+    message_schedule: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl<'a> Animation<'a>{
+    pub fn new(messages: &'a [String], index_message: usize, type_input: &'a Type, enter: bool, faster: bool, le: bool) -> Animation<'a>{
+        Animation{
+            messages,
+            index_message,
+            type_input,
+            enter,
+            faster,
+            le,
+            hash256: String::new(),
+            bits: String::new(),
+            message_blocks: Vec::new(),
+            index_block: 0,
+            block: String::new(),
+            a0: Vec::new(),
+            a: Vec::new(),
+            message_schedule: Vec::new(),
+            primes: Vec::new(),
+        }
+    }
+
+    fn print_message(&self){
+        let message = &self.messages[self.index_message];
+        match self.type_input{
+            Type::Text => printf(format!("message: {}\n", message)),
+            Type::Hex => printf(format!("Hex value: {}\n", message)),
+            Type::LeHex => printf(format!("Little endian hex value: {}\n", message)),
+            Type::Decimal => printf(format!("Decimal value: {}\n", message)),
+            Type::LeBinary => printf(format!("Little endian binary value: {}\n", message)),
+            Type::Binary | Type::File | Type::Auto => (),
+        }
+    }
+
+    fn print_messages(&self){
+        let enter = self.enter;
+        let messages = self.messages;
+        printf("messages: [");
+        for (i, m) in messages.iter().enumerate(){
+            if i  == messages.len() - 1{
+                if i != self.index_message{
+                    printf(format!("{:?}]", m).as_str());
+                }else{
+                    blink(format!("{:?}", m).as_str());
+                    printf("]");
+                }
+            }else{
+                if i != self.index_message{
+                    printf(format!("{:?}, ", m).as_str());
+                }else{
+                    blink(format!("{:?}", m).as_str());
+                    printf(", ");
+                }
+            }
+        }
+        wait(enter, 2000);
+        printf(format!("\nmessage: {}", messages[self.index_message]).as_str());
+        wait(enter, 1000);
+    }
+
+    fn blocks(&mut self, message_blocks: &[String]){
+        let enter = self.enter;
+        let bits = &self.bits;
+        printf("\nmessage blocks: [\n");
+        wait(enter, 500);
+        for (j, i) in message_blocks.iter().enumerate(){
+            printf(MoveTo(0, 0));
+            self.print_message();
+            print_bits("bits: ", bits, j * Sha256::BLOCK_BITS, (j + 1) * Sha256::BLOCK_BITS, 5);
+            printf("\n\n\n");
+
+            for previous in &message_blocks[..j]{
+                print_block(previous, false);
+            }
+            print_block(i, true);
+
+            wait(enter, 1000);
+        }
+        printf(MoveTo(0, 0));
+        self.print_message();
+        print_bits("bits: ", bits, 0, 0, 5);
+        printf(format!("\n{}message blocks: [\n", MoveToNextLine(1)));
+        for block in message_blocks.iter(){
+            print_block(block, false);
+        }
+        printf("]\n");
+
+        wait(enter, 1000);
+        cleartop();
+        self.message_blocks = message_blocks.to_vec();
+    }
+
+    fn schedule_word(&mut self, i: usize, new: u32, sigma0: u32, sigma1: u32){
+        let enter = self.enter;
+        let message_schedule = &mut self.message_schedule;
+        message_schedule.push(new);
+
+        printf(format!("{}{} -> {:032b}", MoveToPreviousLine(16), MoveRight(37), message_schedule[i - 16]).as_str());
+
+        printf(format!("{}{} -> sigma0 = {:032b}", MoveToNextLine(1), MoveRight(37), sigma0).as_str());
+
+        printf(format!("{}{} -> {:032b}", MoveToNextLine(8), MoveRight(37), message_schedule[i - 7]).as_str());
+
+        printf(format!("{}{} -> sigma1 = {:032b}", MoveToNextLine(5), MoveRight(37), sigma1).as_str());
+
+        printf(MoveToNextLine(2));
+
+        if i != 16{
+            for j in 1..17{
+                printf(format!("{}w{:02}: {:032b}", MoveToPreviousLine(1), i - j, message_schedule[i - j]).as_str());
+            }
+            printf(MoveToNextLine(16));
+        }
+
+
+        printf(format!("w{:02}: {:032b}", i, new).as_str());
+
+        wait(enter, 350);
+        if i == Sha256::ROUNDS - 1{
+            wait(false, 1000);
+        }
+    }
+
+    fn compression(&mut self){
+        let enter = self.enter;
+        cleartop();
+        if self.index_block > 0{
+            binary_handling_animated::keep_a(&self.a0);
+        }
+        print_bits(&format!("message block[{}]: ", self.index_block), &self.block, 0, 0, 1);
+        printf("\n");
+
+        printf("\nCompression: \n\n");
+        printf("W00: \n");
+        printf("K00: \n\n");
+        printf("T1 =\n");
+        printf("T2 = \n\n\n");
+
+        if self.index_block == 0{
+            binary_handling_animated::animate_a(enter, &self.primes);
+            wait(enter, 500);
+            binary_handling_animated::save_a(&self.a0, enter);
+        }else{
+            for i in 97..105_u8{
+                printf(format!("{} = \n", i as char))
+            }
+            printf(format!("{}{}", MoveToPreviousLine(8), MoveRight(4)));
+            wait(enter, 500);
+            binary_handling_animated::restore_a(&self.a0, enter, false);
+            printf(MoveToNextLine(8))
+        }
+        wait(enter, 400);
+        printf(format!("{}{} SIGMA1(e) + choice(e, f, g) + h + K00 + W00 (mod 2**32)", MoveToPreviousLine(12), MoveRight(4)));
+        printf(format!("{}{} SIGMA0(a) + majority(a, b, c) (mod 2**32){}", MoveToNextLine(1), MoveRight(4), MoveToNextLine(11)));
+
+        wait(enter, 1000);
+
+        self.a = self.a0.clone();
+    }
+
+    fn round(&mut self, i: usize, m: u32, k: u32, t1: u32, t2: u32, state: &[u32]){
+        let enter = self.enter;
+        let a = &self.a;
+        let pause = || if i < 3{
+            wait(enter, 1000);
+        }else{
+            wait(enter, 200);
+        };
+
+        printf(format!("{}W{:02}: {:032b}", MoveToPreviousLine(15), i, m).as_str());
+        printf(format!("{}K{:02}: {:032b}", MoveToNextLine(1), i, k).as_str());
+        printf(format!("{}{}{:02} + W{:02}{}", MoveToNextLine(2), MoveRight(40), i, i, MoveToNextLine(12)).as_str());
+        pause();
+
+        printf(format!("{}{} = {:032b}", MoveToPreviousLine(12), MoveRight(61), t1).as_str());
+        printf(format!("{}{} = {:032b}{}", MoveToNextLine(1), MoveRight(61), t2, MoveToNextLine(11)).as_str());
+        pause();
+
+        for j in 0..8{
+            if j == 0{
+                printf(format!("{}{}\u{2193}           {}", MoveToPreviousLine(8), MoveRight(36), MoveToNextLine(1)));
+            }else{
+                printf(format!("{}\u{2193}           {}", MoveRight(36), MoveToNextLine(1)))
+            }
+        }
+        pause();
+
+        for j in 0..8{
+            if j == 7{
+                printf(format!("{}{}{: >32} -> T1 + T2{}", MoveToPreviousLine(1), MoveRight(4), "", MoveToNextLine(8)).as_str());
+            }else if j == 3{
+                printf(format!("{}{}{:032b} + T1", MoveToPreviousLine(1), MoveRight(4), a[7 - j - 1]).as_str());
+            }else{
+                printf(format!("{}{}{:032b} ", MoveToPreviousLine(1), MoveRight(4), a[7 - j - 1]).as_str());
+            }
+        }
+        pause();
+
+        printf(format!("{}{}{:032b}", MoveToPreviousLine(4), MoveRight(4), state[4]).as_str());
+        printf(format!("{}{}{:032b}{}", MoveToPreviousLine(4), MoveRight(4), state[0], MoveToNextLine(8)).as_str());
+        pause();
+
+        self.a = state.to_vec();
+    }
+
+    fn finalization(&mut self){
+        let enter = self.enter;
+        for _ in 0..8{
+            printf(format!("{}{} +         {}", MoveToPreviousLine(1), MoveRight(36), MoveLeft(8)));
+        }
+        wait(enter, 1000);
+        binary_handling_animated::restore_a(&self.a0, enter, true);
+        wait(enter, 1000);
+    }
+
+    fn block_hash(&mut self, index: usize, hash: &[u32]){
+        let enter = self.enter;
+        self.a0 = hash.to_vec();
+
+        printf(format!("{}{}", MoveToPreviousLine(1), MoveToNextLine(1)));
+        for h in hash{
+            printf(format!("{}{:032b}{: >47}{}", MoveRight(4), h, "", MoveToNextLine(1)).as_str());
+        }
+        wait(enter, 800);
+
+        if self.message_blocks.len() != index + 1{
+            binary_handling_animated::save_a(hash, enter);
+            return;
+        }
+
+        for i in hash.iter().rev(){
+            printf(format!("{}{} = {:08x}", MoveToPreviousLine(1), MoveRight(36), i).as_str());
+        }
+        printf(format!("{}\n", MoveToNextLine(8)));
+        wait(enter, 500);
+
+        printf("hash:");
+        wait(enter, 200);
+        let mut hash256 = String::new();
+        for (i, j) in hash.iter().enumerate(){
+            blink(format!("{}{}{:08x}{}", MoveToPreviousLine(9 - i), MoveRight(39), j, MoveToNextLine(9 - i)).as_str());
+            if i != 0{
+                printf(format!("hash: {}{:08x}", MoveRight(i * 8), j).as_str());
+            }else{
+                printf(format!("hash: {:08x}", j).as_str());
+            }
+            hash256 += &format!("{:08x}", j);
+            wait(enter,500);
+            printf(format!("{}{}{:08x}{}", MoveToPreviousLine(9 - i), MoveRight(39), j, MoveToNextLine(9 - i)).as_str());
+        }
+        if self.le{
+            printf("\n\nle hex: ");
+            wait(enter, 200);
+            hash256 = (0..hash256.len()).step_by(2).rev().map(|i|{
+                printf(format!("{}hash: {}", MoveToPreviousLine(2), &hash256[0..i]));
+                blink(&hash256[i..i+2]);
+                printf(&hash256[i+2..]);
+                printf(format!("{}le hex:{}{}", MoveToNextLine(2), MoveRight(1 + hash256.len() - i - 2), &hash256[i..i+2]));
+                wait(enter, 500);
+                &hash256[i..i+2]
+            }).collect();
+            printf(format!("{}hash: {}", MoveToPreviousLine(2), &hash256[hash256.len()-2..]));
+        }
+
+        self.hash256 = hash256;
+        wait(enter, 1000);
+    }
+}
+
+impl Renderer for Animation<'_>{
+    fn render(&mut self, step: &Step){
+        let enter = self.enter;
+        if let Step::Phase(p) = step{
+            phase(*p);
+        }
+        match step{
+            Step::Phase(Phase::Padding) => {
+                cleartop();
+                if self.messages.len() != 1{
+                    self.print_messages();
+                }
+                cleartop();
+                if *self.type_input != Type::Binary && *self.type_input != Type::File{
+                    self.print_message();
+                    wait(enter, 1000);
+                }
+            },
+            Step::Bits(bits) => {
+                printf(format!("bits: {}", bits).as_str());
+                wait(enter, 1000);
+                self.bits = bits.to_owned();
+            },
+            Step::Padded(padded) => {
+                binary_handling_animated::pad(&self.bits, padded);
+                wait(enter, 1000);
+                self.bits = padded.to_owned();
+            },
+            Step::Blocks(message_blocks) => self.blocks(message_blocks),
+            Step::Phase(Phase::Constants) => (),
+            Step::Constants{primes, ..} => {
+                self.primes = primes.to_owned();
+                if self.index_message == 0 && !self.faster{
+                    binary_handling_animated::animate_operations(enter);
+                    binary_handling_animated::animate_k(enter, primes);
+                }
+            },
+            Step::Block{index, block, hash} => {
+                self.index_block = *index;
+                self.block = block.to_owned();
+                self.a0 = hash.to_owned();
+            },
+            Step::Phase(Phase::Schedule) => {
+                cleartop();
+                if self.index_block > 0{
+                    binary_handling_animated::keep_a(&self.a0);
+                }
+            },
+            Step::BlockWords(words) => {
+                binary_handling_animated::block_words(enter, &self.block, self.index_block, words);
+                self.message_schedule = words.to_vec();
+
+                printf("w16: ");
+                printf(MoveRight(34));
+                printf("= sigma1(t-2) + (t-7) + sigma0(t-15) + (t-16) (mod 2**32)");
+                wait(enter, 2000);
+            },
+            Step::ScheduleWord{index, w, sigma0, sigma1} => self.schedule_word(*index, *w, *sigma0, *sigma1),
+            Step::Phase(Phase::Compression) => self.compression(),
+            Step::Round{index, w, k, t1, t2, state} => self.round(*index, *w, *k, *t1, *t2, state),
+            Step::Phase(Phase::Finalization) => self.finalization(),
+            Step::BlockHash{index, hash, ..} => self.block_hash(*index, hash),
+            Step::Hash(_) => (),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Virtual screen used by the animation.
+//!
+//! Everything the animation prints goes through [Screen::write], that interprets the escape sequences
+//! of the [terminal][super::terminal] commands on a grid of cells, and then draws the grid line by line through a [Backend].
+//! Since the terminal only ever receives whole lines at absolute positions, lines never wrap on their own,
+//! and when the terminal is resized the grid is simply drawn again with the new size.
+
+use std::io;
+
+use super::terminal::{Backend, Style};
+
+/// Smallest size the animation layout fits in, smaller terminals only see part of it.
+pub const MIN_COLS: usize = 120;
+pub const MIN_ROWS: usize = 26;
+
+#[derive(Clone, Copy, PartialEq)]
+struct Cell{
+    ch: char,
+    style: Style,
+}
+
+const BLANK: Cell = Cell{ch: ' ', style: Style{bold: false, blink: false, underline: false, reverse: false, color: None}};
+
+pub struct Screen{
+    backend: Box<dyn Backend>,
+    cells: Vec<Vec<Cell>>,
+    cols: usize,
+    rows: usize,
+    row: usize,
+    col: usize,
+    saved: (usize, usize),
+    style: Style,
+    terminal: (usize, usize),
+    shown: Vec<Vec<Cell>>,
+}
+
+impl Screen{
+    pub fn new(backend: Box<dyn Backend>) -> Screen{
+        let terminal = backend.size().unwrap_or((MIN_COLS, MIN_ROWS));
+        let (cols, rows) = (terminal.0.max(MIN_COLS), terminal.1.max(MIN_ROWS));
+        Screen{
+            backend,
+            cells: vec![vec![BLANK; cols]; rows],
+            cols,
+            rows,
+            row: 0,
+            col: 0,
+            saved: (0, 0),
+            style: Style::default(),
+            terminal,
+            shown: Vec::new(),
+        }
+    }
+
+    /// Gets the terminal ready for the animation, see [Backend::enter].
+    pub fn enter(&mut self, keys: bool) -> io::Result<()>{
+        self.backend.enter(keys)
+    }
+
+    /// Gets the terminal back to its previous state.
+    pub fn leave(&mut self) -> io::Result<()>{
+        self.backend.leave()
+    }
+
+    /// Columns available for the layout.
+    pub fn cols(&self) -> usize{
+        self.cols
+    }
+
+    /// Interprets the text and escape sequences in `m` on the grid.
+    pub fn write(&mut self, m: &str){
+        let mut chars = m.chars();
+        while let Some(c) = chars.next(){
+            match c{
+                '\x1b' => match chars.next(){
+                    Some('[') => {
+                        let mut params = String::new();
+                        let mut command = None;
+                        for c in chars.by_ref(){
+                            if c.is_ascii_digit() || c == ';' || c == '?'{
+                                params.push(c);
+                            }else{
+                                command = Some(c);
+                                break;
+                            }
+                        }
+                        if let Some(command) = command{
+                            self.csi(&params, command);
+                        }
+                    },
+                    Some('7') => self.saved = (self.row, self.col),
+                    Some('8') => (self.row, self.col) = self.saved,
+                    _ => (),
+                },
+                '\n' => self.line_feed(),
+                '\r' => self.col = 0,
+                c => {
+                    if self.col >= self.cols{
+                        self.line_feed();
+                    }
+                    self.cells[self.row][self.col] = Cell{ch: c, style: self.style};
+                    self.col += 1;
+                },
+            }
+        }
+    }
+
+    fn line_feed(&mut self){
+        self.col = 0;
+        if self.row + 1 >= self.rows{
+            self.cells.remove(0);
+            self.cells.push(vec![BLANK; self.cols]);
+        }else{
+            self.row += 1;
+        }
+    }
+
+    fn csi(&mut self, params: &str, command: char){
+        if params.starts_with('?'){
+            return; // cursor visibility and alternative buffer are handled by the terminal itself
+        }
+        let args: Vec<usize> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+        let n = args[0].max(1);
+        match command{
+            'A' => self.row = self.row.saturating_sub(n),
+            'B' => self.row = (self.row + n).min(self.rows - 1),
+            'C' => self.col = (self.col + n).min(self.cols - 1),
+            'D' => self.col = self.col.min(self.cols - 1).saturating_sub(n),
+            'E' => (self.row, self.col) = ((self.row + n).min(self.rows - 1), 0),
+            'F' => (self.row, self.col) = (self.row.saturating_sub(n), 0),
+            'H' => {
+                self.row = (n - 1).min(self.rows - 1);
+                self.col = (args.get(1).copied().unwrap_or(1).max(1) - 1).min(self.cols - 1);
+            },
+            'J' => match args[0]{
+                2 => self.cells = vec![vec![BLANK; self.cols]; self.rows],
+                _ => {
+                    self.clear_line_from(self.col);
+                    for row in self.row + 1..self.rows{
+                        self.cells[row] = vec![BLANK; self.cols];
+                    }
+                },
+            },
+            'K' => match args[0]{
+                2 => self.cells[self.row] = vec![BLANK; self.cols],
+                _ => self.clear_line_from(self.col),
+            },
+            'm' => self.style.apply(&args),
+            _ => (),
+        }
+    }
+
+    fn clear_line_from(&mut self, col: usize){
+        for cell in self.cells[self.row].iter_mut().skip(col){
+            *cell = BLANK;
+        }
+    }
+
+    /// Resizes the grid if the terminal changed size, returns true when it did.
+    pub fn resize(&mut self) -> bool{
+        let terminal = self.backend.size().unwrap_or((MIN_COLS, MIN_ROWS));
+        if terminal == self.terminal{
+            return false;
+        }
+        self.terminal = terminal;
+        let (cols, rows) = (terminal.0.max(MIN_COLS), terminal.1.max(MIN_ROWS));
+        for line in self.cells.iter_mut(){
+            line.resize(cols, BLANK);
+        }
+        self.cells.resize(rows, vec![BLANK; cols]);
+        self.cols = cols;
+        self.rows = rows;
+        self.row = self.row.min(rows - 1);
+        self.col = self.col.min(cols - 1);
+        self.shown.clear();
+        true
+    }
+
+    /// Draws the lines that changed since the last time on the terminal.
+    ///
+    /// If the terminal is smaller than the layout, only the part that fits is drawn,
+    /// and the last line asks for a bigger terminal.
+    pub fn render(&mut self) -> io::Result<()>{
+        let (cols, rows) = self.terminal;
+        let too_small = cols < MIN_COLS || rows < MIN_ROWS;
+        let visible = if too_small{ rows.saturating_sub(1) }else{ rows };
+        if self.shown.is_empty(){
+            self.backend.clear()?;
+        }
+        self.shown.resize(rows, Vec::new());
+        for row in 0..visible.min(self.rows){
+            let line: Vec<Cell> = self.cells[row].iter().take(cols).copied().collect();
+            if self.shown[row] != line{
+                self.draw_line(row, &line)?;
+                self.shown[row] = line;
+            }
+        }
+        if too_small && rows > 0{
+            let warning = format!("terminal too small, resize it to at least {}x{}", MIN_COLS, MIN_ROWS);
+            let style = Style{reverse: true, ..Style::default()};
+            let warning: Vec<Cell> = warning.chars().take(cols).map(|ch| Cell{ch, style}).collect();
+            if self.shown[rows - 1] != warning{
+                self.draw_line(rows - 1, &warning)?;
+                self.shown[rows - 1] = warning;
+            }
+        }
+        if self.row < visible && self.col < cols{
+            self.backend.move_to(self.col, self.row)?; // keeps what is typed on the right place
+        }
+        self.backend.flush()
+    }
+
+    /// Draws a line, printing together the cells that have the same style.
+    fn draw_line(&mut self, row: usize, line: &[Cell]) -> io::Result<()>{
+        self.backend.move_to(0, row)?;
+        for run in line.chunk_by(|a, b| a.style == b.style){
+            let text: String = run.iter().map(|cell| cell.ch).collect();
+            self.backend.print(&text, run[0].style)?;
+        }
+        self.backend.clear_line()
+    }
+}
+
+/// Shortens a line to fit `width` columns, replacing its middle with "...".
+pub fn fit(s: &str, width: usize) -> String{
+    let len = s.chars().count();
+    if len <= width || width < 5{
+        return s.to_owned();
+    }
+    let left = (width - 3) / 2;
+    let right = width - 3 - left;
+    let start: String = s.chars().take(left).collect();
+    let end: String = s.chars().skip(len - right).collect();
+    format!("{}...{}", start, end)
+}
+
+/// Shortens a line to fit `width` columns, keeping the part from `from` to `to` visible,
+/// returns the text before, the kept part and the text after it.
+pub fn fit_around(s: &str, from: usize, to: usize, width: usize) -> (String, &str, String){
+    let (before, kept, after) = (&s[..from], &s[from..to], &s[to..]);
+    if s.len() <= width{
+        return (before.to_owned(), kept, after.to_owned());
+    }
+    let room = width.saturating_sub(kept.len() + 6) / 2;
+    let before = if before.len() > room + 3{
+        format!("...{}", &before[before.len() - room..])
+    }else{
+        before.to_owned()
+    };
+    let room = width.saturating_sub(kept.len() + before.len() + 3);
+    let after = if after.len() > room + 3{
+        format!("{}...", &after[..room])
+    }else{
+        after.to_owned()
+    };
+    (before, kept, after)
+}
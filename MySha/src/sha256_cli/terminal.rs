@@ -0,0 +1,487 @@
+//! Terminal abstraction used by the animation.
+//!
+//! The animation describes cursor movement, clearing and styles with the commands in this module,
+//! in the same spirit as crossterm commands, they can be formatted together with text and are interpreted
+//! by the [Screen][super::screen::Screen]. The screen is then drawn on the terminal through a [Backend].
+//!
+//! Two backends are provided: [AnsiBackend], that writes escape sequences and works on any VT100 compatible terminal,
+//! and, with the `crossterm` feature (enabled by default), `CrosstermBackend`, that also works on Windows consoles.
+
+use std::fmt;
+use std::io::{self, Write, stdout};
+
+use serde::Deserialize;
+
+/// Moves the cursor up n lines.
+pub struct MoveUp(pub usize);
+/// Moves the cursor down n lines.
+pub struct MoveDown(pub usize);
+/// Moves the cursor right n columns.
+pub struct MoveRight(pub usize);
+/// Moves the cursor left n columns.
+pub struct MoveLeft(pub usize);
+/// Moves the cursor to the beginning of the nth next line.
+pub struct MoveToNextLine(pub usize);
+/// Moves the cursor to the beginning of the nth previous line.
+pub struct MoveToPreviousLine(pub usize);
+/// Moves the cursor to the column and row, starting at 0.
+pub struct MoveTo(pub usize, pub usize);
+/// Saves the cursor position.
+pub struct SavePosition;
+/// Restores the cursor position saved with [SavePosition].
+pub struct RestorePosition;
+
+/// Clears part of the screen.
+pub enum Clear{
+    /// The whole screen
+    All,
+    /// From the cursor to the end of the screen
+    FromCursorDown,
+    /// The line the cursor is on
+    CurrentLine,
+    /// From the cursor to the end of the line
+    UntilNewLine,
+}
+
+/// Colors used by the animation, the 16 colors of ANSI terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Color{
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl Color{
+    const ALL: [Color; 16] = [
+        Color::Black, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan, Color::White,
+        Color::BrightBlack, Color::BrightRed, Color::BrightGreen, Color::BrightYellow, Color::BrightBlue, Color::BrightMagenta, Color::BrightCyan, Color::BrightWhite,
+    ];
+
+    fn code(&self) -> u8{
+        let index = Color::ALL.iter().position(|color| color == self).unwrap() as u8;
+        if index < 8{
+            30 + index
+        }else{
+            90 + index - 8
+        }
+    }
+
+    fn from_code(code: usize) -> Option<Color>{
+        match code{
+            30..=37 => Some(Color::ALL[code - 30]),
+            90..=97 => Some(Color::ALL[code - 90 + 8]),
+            _ => None,
+        }
+    }
+}
+
+/// Style of the text.
+///
+/// It can be read from the config file as a table, like `{color = "bright_cyan", bold = true}`, see [Theme][super::theme::Theme].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Style{
+    pub bold: bool,
+    pub blink: bool,
+    pub underline: bool,
+    pub reverse: bool,
+    pub color: Option<Color>,
+}
+
+impl Style{
+    /// Style used to highlight what is being explained.
+    pub fn highlight() -> Style{
+        Style{blink: true, underline: true, color: Some(Color::Cyan), ..Style::default()}
+    }
+
+    /// Plain style with a color.
+    pub fn color(color: Color) -> Style{
+        Style{color: Some(color), ..Style::default()}
+    }
+
+    /// Applies the select graphic rendition parameters of an escape sequence.
+    pub fn apply(&mut self, params: &[usize]){
+        for param in params{
+            match param{
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                4 => self.underline = true,
+                5 => self.blink = true,
+                7 => self.reverse = true,
+                _ => if let Some(color) = Color::from_code(*param){
+                    self.color = Some(color);
+                },
+            }
+        }
+    }
+}
+
+/// Sets the style of the text printed after it.
+pub struct SetStyle(pub Style);
+/// Resets the style to the default.
+pub struct ResetStyle;
+
+impl fmt::Display for MoveUp{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[{}A", self.0)
+    }
+}
+
+impl fmt::Display for MoveDown{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[{}B", self.0)
+    }
+}
+
+impl fmt::Display for MoveRight{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[{}C", self.0)
+    }
+}
+
+impl fmt::Display for MoveLeft{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[{}D", self.0)
+    }
+}
+
+impl fmt::Display for MoveToNextLine{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[{}E", self.0)
+    }
+}
+
+impl fmt::Display for MoveToPreviousLine{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[{}F", self.0)
+    }
+}
+
+impl fmt::Display for MoveTo{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[{};{}H", self.1 + 1, self.0 + 1)
+    }
+}
+
+impl fmt::Display for SavePosition{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b7")
+    }
+}
+
+impl fmt::Display for RestorePosition{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b8")
+    }
+}
+
+impl fmt::Display for Clear{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            Clear::All => write!(f, "\x1b[2J"),
+            Clear::FromCursorDown => write!(f, "\x1b[0J"),
+            Clear::CurrentLine => write!(f, "\x1b[2K"),
+            Clear::UntilNewLine => write!(f, "\x1b[0K"),
+        }
+    }
+}
+
+impl fmt::Display for SetStyle{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[0")?;
+        if self.0.bold{
+            write!(f, ";1")?;
+        }
+        if self.0.blink{
+            write!(f, ";5")?;
+        }
+        if self.0.underline{
+            write!(f, ";4")?;
+        }
+        if self.0.reverse{
+            write!(f, ";7")?;
+        }
+        if let Some(color) = self.0.color{
+            write!(f, ";{}", color.code())?;
+        }
+        write!(f, "m")
+    }
+}
+
+impl fmt::Display for ResetStyle{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "\x1b[m")
+    }
+}
+
+/// Keys the animation reacts to.
+#[derive(Debug, PartialEq)]
+pub enum Key{
+    Enter,
+    Char(char),
+    CtrlC,
+    Other,
+}
+
+/// Where the [Screen][super::screen::Screen] is drawn.
+pub trait Backend: Send{
+    /// Switches to the alternative screen, hides the cursor, and starts reading keys one by one if `keys` is true.
+    fn enter(&mut self, keys: bool) -> io::Result<()>;
+    /// Gets the terminal back to its previous state.
+    fn leave(&mut self) -> io::Result<()>;
+    /// Size of the terminal, as columns and rows, if it can be known.
+    fn size(&self) -> Option<(usize, usize)>;
+    /// Clears the whole terminal.
+    fn clear(&mut self) -> io::Result<()>;
+    /// Moves the cursor to the column and row, starting at 0.
+    fn move_to(&mut self, col: usize, row: usize) -> io::Result<()>;
+    /// Prints text with a style, at the cursor.
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()>;
+    /// Clears from the cursor to the end of the line.
+    fn clear_line(&mut self) -> io::Result<()>;
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+/// Reads a key pressed by the user.
+///
+/// Only works as expected after the backend entered with keys enabled.
+#[cfg(feature = "crossterm")]
+pub fn read_key() -> io::Result<Key>{
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+    loop{
+        if let Event::Key(key) = event::read()?{
+            if key.kind == KeyEventKind::Release{
+                continue;
+            }
+            return Ok(match key.code{
+                KeyCode::Enter => Key::Enter,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Key::CtrlC,
+                KeyCode::Char(c) => Key::Char(c),
+                _ => Key::Other,
+            });
+        }
+    }
+}
+
+/// Reads a key pressed by the user.
+///
+/// Only works as expected after the backend entered with keys enabled.
+#[cfg(not(feature = "crossterm"))]
+pub fn read_key() -> io::Result<Key>{
+    use std::io::Read;
+
+    let mut byte = [0_u8];
+    if io::stdin().read(&mut byte)? == 0{
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(match byte[0]{
+        b'\n' | b'\r' => Key::Enter,
+        3 => Key::CtrlC,
+        b if b.is_ascii_graphic() => Key::Char(b as char),
+        _ => Key::Other,
+    })
+}
+
+/// Backend that writes escape sequences, for VT100 compatible terminals.
+#[derive(Default)]
+#[cfg_attr(feature = "crossterm", allow(dead_code))]
+pub struct AnsiBackend{
+    #[cfg(unix)]
+    original: Option<libc::termios>,
+}
+
+#[cfg(unix)]
+#[cfg_attr(feature = "crossterm", allow(dead_code))]
+impl AnsiBackend{
+    /// Turns off line buffering and echo of stdin, so keys are read as soon as they are pressed.
+    fn no_echo(&mut self){
+        // SAFETY: termios is a plain C struct, and it is only used after tcgetattr filled it.
+        unsafe{
+            let mut term: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut term) != 0{
+                return;
+            }
+            self.original = Some(term);
+            term.c_lflag &= !(libc::ICANON | libc::ECHO);
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &term);
+        }
+    }
+
+    fn restore(&mut self){
+        if let Some(term) = self.original.take(){
+            // SAFETY: restores the settings previously returned by tcgetattr.
+            unsafe{
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &term);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl AnsiBackend{
+    fn no_echo(&mut self){}
+
+    fn restore(&mut self){}
+}
+
+impl Backend for AnsiBackend{
+    fn enter(&mut self, keys: bool) -> io::Result<()>{
+        if keys{
+            self.no_echo();
+        }
+        print!("\x1b[?1049h"); // create alternative buffer
+        print!("\x1b[?25l"); // make cursor invisible
+        stdout().flush()
+    }
+
+    fn leave(&mut self) -> io::Result<()>{
+        self.restore();
+        print!("\x1b[m\x1b[?25h"); // make cursor visible
+        print!("\x1b[?1049l"); // disable alternative buffer, get back to previous state
+        stdout().flush()
+    }
+
+    fn size(&self) -> Option<(usize, usize)>{
+        let (terminal_size::Width(w), terminal_size::Height(h)) = terminal_size::terminal_size()?;
+        Some((w as usize, h as usize))
+    }
+
+    fn clear(&mut self) -> io::Result<()>{
+        write!(stdout(), "{}{}", ResetStyle, Clear::All)
+    }
+
+    fn move_to(&mut self, col: usize, row: usize) -> io::Result<()>{
+        write!(stdout(), "{}", MoveTo(col, row))
+    }
+
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()>{
+        write!(stdout(), "{}{}", SetStyle(style), text)
+    }
+
+    fn clear_line(&mut self) -> io::Result<()>{
+        write!(stdout(), "{}{}", ResetStyle, Clear::UntilNewLine)
+    }
+
+    fn flush(&mut self) -> io::Result<()>{
+        stdout().flush()
+    }
+}
+
+/// Backend built on crossterm, that also works on Windows consoles without VT100 support.
+#[cfg(feature = "crossterm")]
+#[derive(Default)]
+pub struct CrosstermBackend{
+    raw: bool,
+}
+
+#[cfg(feature = "crossterm")]
+impl Backend for CrosstermBackend{
+    fn enter(&mut self, keys: bool) -> io::Result<()>{
+        use crossterm::{cursor, terminal, execute};
+
+        if keys{
+            terminal::enable_raw_mode()?;
+            self.raw = true;
+        }
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)
+    }
+
+    fn leave(&mut self) -> io::Result<()>{
+        use crossterm::{cursor, style, terminal, execute};
+
+        if self.raw{
+            terminal::disable_raw_mode()?;
+            self.raw = false;
+        }
+        execute!(stdout(), style::ResetColor, style::SetAttribute(style::Attribute::Reset), cursor::Show, terminal::LeaveAlternateScreen)
+    }
+
+    fn size(&self) -> Option<(usize, usize)>{
+        let (w, h) = crossterm::terminal::size().ok()?;
+        Some((w as usize, h as usize))
+    }
+
+    fn clear(&mut self) -> io::Result<()>{
+        use crossterm::{style, terminal, queue};
+
+        queue!(stdout(), style::SetAttribute(style::Attribute::Reset), terminal::Clear(terminal::ClearType::All))
+    }
+
+    fn move_to(&mut self, col: usize, row: usize) -> io::Result<()>{
+        crossterm::queue!(stdout(), crossterm::cursor::MoveTo(col as u16, row as u16))
+    }
+
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()>{
+        use crossterm::{style::{self, Attribute}, queue};
+
+        let mut out = stdout();
+        queue!(out, style::SetAttribute(Attribute::Reset), style::ResetColor)?;
+        if style.bold{
+            queue!(out, style::SetAttribute(Attribute::Bold))?;
+        }
+        if style.blink{
+            queue!(out, style::SetAttribute(Attribute::SlowBlink))?;
+        }
+        if style.underline{
+            queue!(out, style::SetAttribute(Attribute::Underlined))?;
+        }
+        if style.reverse{
+            queue!(out, style::SetAttribute(Attribute::Reverse))?;
+        }
+        if let Some(color) = style.color{
+            let color = match color{
+                Color::Black => style::Color::Black,
+                Color::Red => style::Color::DarkRed,
+                Color::Green => style::Color::DarkGreen,
+                Color::Yellow => style::Color::DarkYellow,
+                Color::Blue => style::Color::DarkBlue,
+                Color::Magenta => style::Color::DarkMagenta,
+                Color::Cyan => style::Color::DarkCyan,
+                Color::White => style::Color::Grey,
+                Color::BrightBlack => style::Color::DarkGrey,
+                Color::BrightRed => style::Color::Red,
+                Color::BrightGreen => style::Color::Green,
+                Color::BrightYellow => style::Color::Yellow,
+                Color::BrightBlue => style::Color::Blue,
+                Color::BrightMagenta => style::Color::Magenta,
+                Color::BrightCyan => style::Color::Cyan,
+                Color::BrightWhite => style::Color::White,
+            };
+            queue!(out, style::SetForegroundColor(color))?;
+        }
+        queue!(out, style::Print(text))
+    }
+
+    fn clear_line(&mut self) -> io::Result<()>{
+        use crossterm::{style, terminal, queue};
+
+        queue!(stdout(), style::SetAttribute(style::Attribute::Reset), style::ResetColor, terminal::Clear(terminal::ClearType::UntilNewLine))
+    }
+
+    fn flush(&mut self) -> io::Result<()>{
+        stdout().flush()
+    }
+}
+
+/// Returns the backend for the current platform and features.
+pub fn backend() -> Box<dyn Backend>{
+    #[cfg(feature = "crossterm")]
+    return Box::<CrosstermBackend>::default();
+    #[cfg(not(feature = "crossterm"))]
+    return Box::<AnsiBackend>::default();
+}
@@ -0,0 +1,67 @@
+//! Colors and styles of the animation.
+//!
+//! The theme is read from the `[theme]` section of the config file, every style is a table with the optional keys
+//! `color`, `bold`, `blink`, `underline` and `reverse`, and the missing keys of the section keep their default:
+//!
+//! ```toml
+//! [theme]
+//! blink = false
+//! highlight = {color = "bright_yellow", bold = true, reverse = true}
+//! padding_one = {color = "bright_green", bold = true}
+//! padding_zeros = {color = "bright_white"}
+//! padding_size = {color = "bright_cyan", bold = true}
+//! constants = {color = "bright_magenta"}
+//! ```
+
+use std::sync::Mutex;
+
+use serde::Deserialize;
+
+use super::terminal::{Color, Style};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme{
+    /// Turns blinking on or off for every style, blinking text can be hard to read on projectors
+    pub blink: bool,
+    /// What is being explained, like the bits being converted or the word being calculated
+    pub highlight: Style,
+    /// The 1 added after the message bits in the padding
+    pub padding_one: Style,
+    /// The zeros added in the padding
+    pub padding_zeros: Style,
+    /// The size of the message at the end of the padding
+    pub padding_size: Style,
+    /// The initial hash values and the constants K, once calculated
+    pub constants: Style,
+}
+
+impl Default for Theme{
+    fn default() -> Theme{
+        Theme{
+            blink: true,
+            highlight: Style::highlight(),
+            padding_one: Style::color(Color::Green),
+            padding_zeros: Style::color(Color::Yellow),
+            padding_size: Style::color(Color::Cyan),
+            constants: Style::default(),
+        }
+    }
+}
+
+static THEME: Mutex<Option<Theme>> = Mutex::new(None);
+
+/// Sets the theme used by the animation.
+pub fn set_theme(theme: Theme){
+    *THEME.lock().unwrap() = Some(theme);
+}
+
+/// Theme used by the animation, the default one if none was set.
+pub fn theme() -> Theme{
+    THEME.lock().unwrap().unwrap_or_default()
+}
+
+/// Applies the theme's blink setting to one of its styles.
+pub fn themed(style: Style) -> Style{
+    Style{blink: style.blink && theme().blink, ..style}
+}
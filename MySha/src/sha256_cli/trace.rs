@@ -0,0 +1,166 @@
+//! Trace of every intermediate value of the hashing process.
+//!
+//! A [Trace] is written to a TOML file, or a JSON file if the name ends with .json,
+//! so runs can be studied offline, diffed between inputs, and shown again with `mysha sha256 replay`.
+//! Words are written as 8 digit hexadecimal strings, and bit strings as they are in the animation.
+
+use std::fs::File;
+use std::io::{Read, Write};
+
+use mysha::animation::{Renderer, Step};
+use serde::{Deserialize, Serialize};
+
+use crate::Exit;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Trace{
+    pub messages: Vec<MessageTrace>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MessageTrace{
+    pub message: String,
+    pub bits: String,
+    pub padded: String,
+    pub blocks: Vec<BlockTrace>,
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BlockTrace{
+    pub block: String,
+    /// hash values before the block, a to h
+    pub initial: Vec<String>,
+    /// message schedule, w00 to w63
+    pub schedule: Vec<String>,
+    pub rounds: Vec<RoundTrace>,
+    /// hash values after the compressed block is added to them
+    pub result: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RoundTrace{
+    pub w: String,
+    pub k: String,
+    pub t1: String,
+    pub t2: String,
+    /// working variables after the round, a to h
+    pub state: Vec<String>,
+}
+
+fn hex(word: u32) -> String{
+    format!("{:08x}", word)
+}
+
+impl MessageTrace{
+    pub fn new(message: &str) -> MessageTrace{
+        MessageTrace{
+            message: message.to_owned(),
+            bits: String::new(),
+            padded: String::new(),
+            blocks: Vec::new(),
+            hash: String::new(),
+        }
+    }
+}
+
+impl Renderer for MessageTrace{
+    /// Keeps the values of every step of the walkthrough.
+    fn render(&mut self, step: &Step){
+        match step{
+            Step::Bits(bits) => self.bits = bits.to_owned(),
+            Step::Padded(padded) => self.padded = padded.to_owned(),
+            Step::Block{block, hash, ..} => self.blocks.push(BlockTrace{
+                block: block.to_owned(),
+                initial: hash.iter().map(|x| hex(*x)).collect(),
+                schedule: Vec::new(),
+                rounds: Vec::new(),
+                result: Vec::new(),
+            }),
+            Step::BlockWords(words) => if let Some(block) = self.blocks.last_mut(){
+                block.schedule = words.iter().map(|x| hex(*x)).collect();
+            },
+            Step::ScheduleWord{w, ..} => if let Some(block) = self.blocks.last_mut(){
+                block.schedule.push(hex(*w));
+            },
+            Step::Round{w, k, t1, t2, state, ..} => if let Some(block) = self.blocks.last_mut(){
+                block.rounds.push(RoundTrace{
+                    w: hex(*w),
+                    k: hex(*k),
+                    t1: hex(*t1),
+                    t2: hex(*t2),
+                    state: state.iter().map(|x| hex(*x)).collect(),
+                });
+            },
+            Step::BlockHash{hash, ..} => if let Some(block) = self.blocks.last_mut(){
+                block.result = hash.iter().map(|x| hex(*x)).collect();
+            },
+            Step::Hash(hash) => self.hash = hash.to_string(),
+            _ => (),
+        }
+    }
+}
+
+impl Trace{
+    /// Writes the trace to a file, as JSON if its name ends with .json, and as TOML if not.
+    pub fn write(&self, path: &str){
+        let content = if path.ends_with(".json"){
+            serde_json::to_string_pretty(self).exit("Error while parsing to json.")
+        }else{
+            toml::to_string(self).exit("Error while parsing to toml.")
+        };
+        let mut file = File::create(path).exit("Error while creating the trace file.");
+        file.write_all(content.as_bytes()).exit("Error while writing to the trace file.");
+    }
+
+    /// Reads a trace written by [Trace::write].
+    pub fn read(path: &str) -> Trace{
+        let mut file = File::open(path).exit("Error while opening the trace file.");
+        let mut content = String::new();
+        file.read_to_string(&mut content).exit("Error while reading the trace file.");
+        if path.ends_with(".json"){
+            serde_json::from_str(&content).exit("Error while parsing the json trace.")
+        }else{
+            toml::from_str(&content).exit("Error while parsing the toml trace.")
+        }
+    }
+
+    /// Prints the whole hashing process of every message in the trace.
+    pub fn replay(&self){
+        for (index_message, message) in self.messages.iter().enumerate(){
+            if self.messages.len() != 1{
+                println!("[{}]", index_message);
+            }
+            println!("message: {}", message.message);
+            println!("bits: {}", message.bits);
+            println!("padded: {}", message.padded);
+
+            for (index_block, block) in message.blocks.iter().enumerate(){
+                println!();
+                println!("message block[{}]: {}", index_block, block.block);
+                println!();
+                println!("message schedule:");
+                for (i, w) in block.schedule.iter().enumerate(){
+                    let word = u32::from_str_radix(w, 16).unwrap_or(0);
+                    println!("    w{:02}: {:032b} {}", i, word, w);
+                }
+                println!();
+                println!("compression:");
+                println!("    {:<5} {:<8} {:<8} {:<8} {:<8} a        b        c        d        e        f        g        h", "", "W", "K", "T1", "T2");
+                println!("    {:<5} {:<8} {:<8} {:<8} {:<8} {}", "", "", "", "", "", block.initial.join(" "));
+                for (i, round) in block.rounds.iter().enumerate(){
+                    println!("    {:<5} {:<8} {:<8} {:<8} {:<8} {}", i, round.w, round.k, round.t1, round.t2, round.state.join(" "));
+                }
+                println!();
+                println!("hash values:");
+                for (i, (initial, result)) in block.initial.iter().zip(block.result.iter()).enumerate(){
+                    let round = block.rounds.last().map(|r| r.state[i].as_str()).unwrap_or("");
+                    println!("    {} = {} + {} = {}", (i as u8 + 97) as char, initial, round, result);
+                }
+            }
+            println!();
+            println!("hash: {}", message.hash);
+            println!();
+        }
+    }
+}
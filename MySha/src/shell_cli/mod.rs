@@ -0,0 +1,30 @@
+use std::{fs, io};
+
+use clap::{Args, CommandFactory};
+use clap_complete::Shell;
+
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct CompletionsArgs{
+    /// shell the completion script is for
+    shell: Shell,
+}
+
+#[derive(Args, Debug)]
+pub struct ManpagesArgs{
+    /// directory the man pages are written to, it is created if it doesn't exist
+    dir: String,
+}
+
+/// Prints the completion script of every subcommand and flag of mysha, for the shell to source.
+pub fn completions(args: CompletionsArgs){
+    clap_complete::generate(args.shell, &mut crate::Args::command(), "mysha", &mut io::stdout());
+}
+
+/// Writes a man page for mysha and one for every subcommand, like mysha-ecc-sign.1, to the directory.
+pub fn manpages(args: ManpagesArgs){
+    fs::create_dir_all(&args.dir).exit("Error while creating the directory.");
+    clap_mangen::generate_to(crate::Args::command(), &args.dir).exit("Error while writing the man pages.");
+    println!("man pages written to {}", args.dir);
+}
@@ -0,0 +1,38 @@
+//! Wall-clock time of the operations of the commands, printed with `--time`.
+//!
+//! The times go to stderr, so the hashes and signatures printed on stdout can still be piped to other tools.
+
+use std::time::{Duration, Instant};
+
+/// Runs the operation, and if enabled prints how long it took, with the throughput when the size of its input is known.
+pub fn time<T>(enabled: bool, label: &str, bytes: Option<u64>, operation: impl FnOnce() -> T) -> T{
+    if ! enabled{
+        return operation();
+    }
+    let start = Instant::now();
+    let result = operation();
+    report(label, start.elapsed(), bytes);
+    result
+}
+
+fn report(label: &str, elapsed: Duration, bytes: Option<u64>){
+    match bytes{
+        Some(bytes) if bytes > 0 && ! elapsed.is_zero() => {
+            let rate = bytes as f64 / elapsed.as_secs_f64();
+            eprintln!("time {}: {:.3?}, {} bytes, {}", label, elapsed, bytes, throughput(rate));
+        },
+        _ => eprintln!("time {}: {:.3?}", label, elapsed),
+    }
+}
+
+/// The rate in bytes per second, with the largest unit that keeps it above 1.
+fn throughput(rate: f64) -> String{
+    let units = ["B/s", "KB/s", "MB/s", "GB/s"];
+    let mut rate = rate;
+    let mut unit = 0;
+    while rate >= 1000.0 && unit < units.len() - 1{
+        rate /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", rate, units[unit])
+}
@@ -0,0 +1,80 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::{Args, ValueEnum};
+
+use mysha::otp::{self, Algorithm, DEFAULT_DIGITS, DEFAULT_PERIOD};
+
+use crate::encode_cli::get_text;
+use crate::Exit;
+
+#[derive(Args, Debug)]
+pub struct TotpArgs{
+    /// base32 secret, as shown by authenticator apps, read from stdin if not provided, so it isn't kept in the shell history
+    secret: Option<String>,
+
+    /// number of digits of the code
+    #[arg(short, long, default_value_t = DEFAULT_DIGITS)]
+    digits: u32,
+
+    /// hash of the HMAC, the algorithm of the otpauth:// link of the secret
+    #[arg(short, long, value_enum, default_value_t = HashAlgorithm::Sha1)]
+    algorithm: HashAlgorithm,
+
+    /// seconds every code is valid for
+    #[arg(short, long, default_value_t = DEFAULT_PERIOD)]
+    period: u64,
+
+    /// unix time the code is for, in seconds, instead of now
+    #[arg(long, conflicts_with = "counter")]
+    time: Option<u64>,
+
+    /// prints the HOTP code of the counter instead, which has no validity window
+    #[arg(short, long)]
+    counter: Option<u64>,
+
+    /// only prints the code
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+enum HashAlgorithm{
+    /// HMAC-SHA-1, the default of authenticator apps
+    Sha1,
+    /// HMAC-SHA256
+    Sha256,
+}
+
+impl HashAlgorithm{
+    fn algorithm(&self) -> Algorithm{
+        match self{
+            HashAlgorithm::Sha1 => Algorithm::Sha1,
+            HashAlgorithm::Sha256 => Algorithm::Sha256,
+        }
+    }
+}
+
+pub fn totp(args: TotpArgs){
+    let secret = otp::parse_secret(&get_text(args.secret)).exit("Invalid secret.");
+
+    if let Some(counter) = args.counter{
+        let code = otp::hotp(&secret, counter, args.digits, args.algorithm.algorithm()).exit("Can't compute the code.");
+        if args.quiet{
+            println!("{}", code);
+        }else{
+            println!("{} (counter {})", code, counter);
+        }
+        return;
+    }
+
+    let time = args.time.unwrap_or_else(|| {
+        SystemTime::now().duration_since(UNIX_EPOCH).exit("The clock is before 1970.").as_secs()
+    });
+    let code = otp::totp(&secret, time, args.period, args.digits, args.algorithm.algorithm()).exit("Can't compute the code.");
+    if args.quiet{
+        println!("{}", code);
+    }else{
+        let remaining = otp::remaining(time, args.period);
+        println!("{} (valid for {} more second{})", code, remaining, if remaining == 1 {""} else {"s"});
+    }
+}
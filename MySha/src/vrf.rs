@@ -0,0 +1,253 @@
+//! Module for a [verifiable random function], ECVRF over secp256k1 with sha256, following [RFC 9381].
+//!
+//! A VRF is a hash only the holder of a private key can compute, that anyone with the public key can check:
+//! [prove] returns a [Proof] for the input, whose [hash][Proof::hash] is random looking but always the same for the same
+//! key and input, and [verify] checks the proof and returns that hash. So the owner of the key can't choose the output,
+//! like a hash, and can't lie about it, like a signature, which makes it a fair lottery or leader election.
+//!
+//! The input is hashed to a point H, with try and increment, and the proof holds Gamma, the private key times H,
+//! with a Schnorr like proof that the same private key is behind the public key and Gamma.
+//! The output is the hash of Gamma. The nonce is deterministic, from RFC 6979, so the proof is also always the same.
+//!
+//! RFC 9381 only defines suites for P-256 and Ed25519, this one follows its P-256 try and increment suite with secp256k1,
+//! and the suite byte 0xFE used by other secp256k1 implementations, but it isn't a registered suite.
+//!
+//! # Examples
+//! ```
+//! use mysha::{ecc::{Curve, KeyPair}, vrf, MyshaError};
+//! # fn main() -> Result<(), MyshaError>{
+//! let key_pair = KeyPair::new(1234_u32, Curve::secp256k1())?;
+//!
+//! let proof = vrf::prove(&key_pair.private(), b"round 42")?;
+//! let output = vrf::verify(&key_pair.public(), b"round 42", &proof)?;
+//! assert_eq!(output, proof.hash());
+//!
+//! // the output can't be moved to another input
+//! assert!(vrf::verify(&key_pair.public(), b"round 43", &proof).is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [verifiable random function]: https://en.wikipedia.org/wiki/Verifiable_random_function
+//! [RFC 9381]: https://datatracker.ietf.org/doc/html/rfc9381#section-5
+
+use std::fmt;
+
+use num_bigint::{BigInt, BigUint};
+
+use crate::ecc::{Curve, EccError, Point, PrivKey, PubKey};
+use crate::hmac::Hmac;
+use crate::sha256::{Hash256, Sha256};
+use crate::MyshaError;
+
+/// Size of an encoded proof: the compressed Gamma, c and s.
+pub const PROOF_SIZE: usize = POINT_SIZE + CHALLENGE_SIZE + SCALAR_SIZE;
+
+const SUITE: u8 = 0xfe;
+const SCALAR_SIZE: usize = 32;
+/// Compressed SEC1 points, 02 or 03 for the parity of y, followed by x.
+const POINT_SIZE: usize = 1 + SCALAR_SIZE;
+/// The challenge is the first half of a hash, enough for 128 bits of security.
+const CHALLENGE_SIZE: usize = 16;
+
+/// The error type of this module.
+#[derive(Debug, PartialEq)]
+pub enum VrfError{
+    /// Happens when an encoded proof doesn't have [PROOF_SIZE] bytes.
+    InvalidLength,
+    /// Happens when the Gamma of a proof isn't a point of secp256k1, or s isn't below its order.
+    InvalidEncoding,
+    /// Happens when the proof isn't the one of the input and the public key.
+    InvalidProof,
+}
+
+impl fmt::Display for VrfError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            VrfError::InvalidLength => write!(f, "A VRF proof must have {} bytes.", PROOF_SIZE),
+            VrfError::InvalidEncoding => write!(f, "Invalid encoding of the VRF proof."),
+            VrfError::InvalidProof => write!(f, "The VRF proof isn't valid for the input and public key."),
+        }
+    }
+}
+
+/// The proof of a VRF output, from [prove].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof{
+    gamma: Point,
+    c: BigUint,
+    s: BigUint,
+}
+
+impl Proof{
+    /// The output of the VRF, the same for every proof of the same key and input.
+    ///
+    /// This doesn't check the proof, the output of a proof from someone else must come from [verify].
+    pub fn hash(&self) -> Hash256{
+        // the cofactor of secp256k1 is 1, so Gamma isn't multiplied by it
+        hash(&[&[SUITE, 0x03], &point_to_bytes(&self.gamma)[..], &[0x00]])
+    }
+
+    /// Encodes the proof as [PROOF_SIZE] bytes.
+    pub fn to_bytes(&self) -> Vec<u8>{
+        [&point_to_bytes(&self.gamma)[..], &padded(&self.c, CHALLENGE_SIZE), &padded(&self.s, SCALAR_SIZE)].concat()
+    }
+
+    /// Reads a proof encoded by [to_bytes][Proof::to_bytes].
+    ///
+    /// # Errors
+    /// Returns [VrfError::InvalidLength] if the bytes aren't [PROOF_SIZE] long,
+    /// and [VrfError::InvalidEncoding] if Gamma isn't a point of secp256k1, or s isn't below its order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Proof, VrfError>{
+        if bytes.len() != PROOF_SIZE{
+            return Err(VrfError::InvalidLength);
+        }
+        let (gamma, rest) = bytes.split_at(POINT_SIZE);
+        let (c, s) = rest.split_at(CHALLENGE_SIZE);
+        let s = BigUint::from_bytes_be(s);
+        if &s >= Curve::secp256k1().get_n(){
+            return Err(VrfError::InvalidEncoding);
+        }
+        let gamma = bytes_to_point(gamma).ok_or(VrfError::InvalidEncoding)?;
+        Ok(Proof{gamma, c: BigUint::from_bytes_be(c), s})
+    }
+}
+
+fn hash(parts: &[&[u8]]) -> Hash256{
+    let mut hasher = Sha256::new();
+    for part in parts{
+        hasher.update(part);
+    }
+    hasher.finalize()
+}
+
+fn padded(n: &BigUint, size: usize) -> Vec<u8>{
+    let bytes = n.to_bytes_be();
+    let mut padded = vec![0; size - bytes.len()];
+    padded.extend(bytes);
+    padded
+}
+
+fn point_to_bytes(point: &Point) -> Vec<u8>{
+    match point.get_xy(){
+        Some((x, y)) => [&[0x02 + y.bit(0) as u8], &padded(x, SCALAR_SIZE)[..]].concat(),
+        // Gamma, H and the public key are never the point at infinity
+        None => vec![0x00],
+    }
+}
+
+/// Reads a compressed point of secp256k1, or [None] if x doesn't have a point.
+fn bytes_to_point(bytes: &[u8]) -> Option<Point>{
+    let curve = Curve::secp256k1();
+    let p = curve.get_p();
+    let (prefix, x) = bytes.split_first()?;
+    if x.len() != SCALAR_SIZE || !matches!(prefix, 0x02 | 0x03){
+        return None;
+    }
+    let x = BigUint::from_bytes_be(x);
+    if &x >= p{
+        return None;
+    }
+    // p is 3 modulo 4, so a square root of a square is its power to (p + 1) / 4
+    let square = (x.modpow(&BigUint::from(3_u8), p) + curve.get_b() as u32) % p;
+    let mut y = square.modpow(&((p + 1_u8) >> 2), p);
+    if y.modpow(&BigUint::from(2_u8), p) != square{
+        return None;
+    }
+    if y.bit(0) != (*prefix == 0x03){
+        y = p - y;
+    }
+    Some(Point::point(x, y))
+}
+
+/// Hashes the input to a point with try and increment, the first counter whose hash is the x of a point.
+fn encode_to_curve(public: &[u8], input: &[u8]) -> Result<Point, EccError>{
+    for counter in 0..=u8::MAX{
+        let x = hash(&[&[SUITE, 0x01], public, input, &[counter, 0x00]]);
+        if let Some(point) = bytes_to_point(&[&[0x02], &x.get_bytes()[..]].concat()){
+            return Ok(point);
+        }
+    }
+    // every try has a chance of about a half, so all 256 failing never happens
+    Err(EccError::NotOnCurve)
+}
+
+/// The challenge c, from the points of the proof, as a number of [CHALLENGE_SIZE] bytes.
+fn challenge(points: [&Point; 5]) -> BigUint{
+    let mut parts: Vec<Vec<u8>> = vec![vec![SUITE, 0x02]];
+    parts.extend(points.iter().map(|point| point_to_bytes(point)));
+    parts.push(vec![0x00]);
+    let hash = hash(&parts.iter().map(Vec::as_slice).collect::<Vec<_>>());
+    BigUint::from_bytes_be(&hash.get_bytes()[..CHALLENGE_SIZE])
+}
+
+/// Deterministic nonce of RFC 6979 with HMAC-SHA256, from the private key and H.
+fn nonce(private: &BigUint, h: &[u8]) -> BigUint{
+    let n = Curve::secp256k1().get_n().clone();
+    let private = padded(private, SCALAR_SIZE);
+    let digest = padded(&(BigUint::from_bytes_be(&Sha256::digest(h)) % &n), SCALAR_SIZE);
+
+    let mut v = [0x01; 32];
+    let mut k = [0x00; 32];
+    for separator in [0x00, 0x01]{
+        k = Hmac::new(&k).tag(&[&v[..], &[separator], &private, &digest].concat());
+        v = Hmac::new(&k).tag(&v);
+    }
+    loop{
+        v = Hmac::new(&k).tag(&v);
+        let candidate = BigUint::from_bytes_be(&v);
+        if candidate > BigUint::from(0_u8) && candidate < n{
+            return candidate;
+        }
+        k = Hmac::new(&k).tag(&[&v[..], &[0x00]].concat());
+        v = Hmac::new(&k).tag(&v);
+    }
+}
+
+/// Proves the output of the VRF for the input with the private key, on secp256k1.
+///
+/// # Errors
+///
+/// This can fail with [UnsupportedCurve][EccError::UnsupportedCurve] if the key isn't of secp256k1.
+pub fn prove(private: &PrivKey, input: &[u8]) -> Result<Proof, MyshaError>{
+    let curve = private.get_curve();
+    if *curve != Curve::secp256k1(){
+        return Err(EccError::UnsupportedCurve.into());
+    }
+    let x = private.get_private();
+    let public = curve.multiply_generator(BigInt::from(x.clone()))?;
+    let h = encode_to_curve(&point_to_bytes(&public), input)?;
+    let h_bytes = point_to_bytes(&h);
+
+    let gamma = curve.multiply(&h, BigInt::from(x.clone()))?;
+    let k = nonce(x, &h_bytes);
+    let u = curve.multiply_generator(BigInt::from(k.clone()))?;
+    let v = curve.multiply(&h, BigInt::from(k.clone()))?;
+    let c = challenge([&public, &h, &gamma, &u, &v]);
+    let s = (k + &c * x) % curve.get_n();
+    Ok(Proof{gamma, c, s})
+}
+
+/// Verifies the proof of the input with the public key, on secp256k1, returning the output of the VRF.
+///
+/// # Errors
+///
+/// This can fail with [VrfError::InvalidProof] if the proof isn't the one of the input and public key,
+/// and with [UnsupportedCurve][EccError::UnsupportedCurve] if the key isn't of secp256k1.
+pub fn verify(public: &PubKey, input: &[u8], proof: &Proof) -> Result<Hash256, MyshaError>{
+    let curve = public.get_curve();
+    if *curve != Curve::secp256k1(){
+        return Err(EccError::UnsupportedCurve.into());
+    }
+    let y = public.get_public();
+    let h = encode_to_curve(&point_to_bytes(y), input)?;
+
+    let c = BigInt::from(proof.c.clone());
+    // U = sB - cY and V = sH - cGamma, the nonce points if the proof is valid
+    let u = curve.add(&curve.multiply_generator(BigInt::from(proof.s.clone()))?, &curve.multiply(y, -c.clone())?)?;
+    let v = curve.add(&curve.multiply(&h, BigInt::from(proof.s.clone()))?, &curve.multiply(&proof.gamma, -c)?)?;
+    if challenge([y, &h, &proof.gamma, &u, &v]) != proof.c{
+        return Err(VrfError::InvalidProof.into());
+    }
+    Ok(proof.hash())
+}
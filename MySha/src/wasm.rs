@@ -0,0 +1,117 @@
+//! Module with the javascript bindings of the crate, for the browser, with the `wasm` feature.
+//!
+//! The crate is built as a WebAssembly module with [wasm-pack], which also writes the javascript that loads it,
+//! so a page can hash and sign with the same code as the cli:
+//! ```sh
+//! wasm-pack build --target web -- --no-default-features --features wasm
+//! ```
+//!
+//! Keys, signatures and hashes are passed as hexadecimal text, and the curve is always secp256k1:
+//! - a private key is the number, as 64 characters;
+//! - a public key is x and y one after the other, 128 characters, like [to_bytes][PubKey::to_bytes];
+//! - a signature is r and s one after the other, 128 characters.
+//!
+//! Errors are thrown as javascript `Error`s, with the message of the error of the crate.
+//! The random numbers come from `crypto.getRandomValues` of the browser, and there is no file system,
+//! so the `file` input type always fails.
+//!
+//! # Examples
+//! ```js
+//! import init, {sha256, generatePrivateKey, publicKey, sign, verify} from "./pkg/mysha.js";
+//!
+//! await init();
+//! console.log(sha256("abc", "text")); // ba7816bf...
+//!
+//! const privateKey = generatePrivateKey();
+//! const signature = sign(privateKey, "Hello, World!");
+//! console.log(verify(publicKey(privateKey), "Hello, World!", signature)); // true
+//! ```
+//!
+//! [wasm-pack]: https://rustwasm.github.io/wasm-pack/
+
+use std::fmt;
+
+use num_bigint::BigUint;
+use wasm_bindgen::prelude::*;
+
+use crate::ecc::{Curve, KeyPair, PrivKey, PubKey, Signature};
+use crate::encoding::hex;
+use crate::rand;
+use crate::sha256::{self, InputType};
+
+fn js_error(err: impl fmt::Display) -> JsError{
+    JsError::new(&err.to_string())
+}
+
+fn input_type(name: &str) -> Result<InputType, JsError>{
+    Ok(match name{
+        "text" => InputType::Text,
+        "binary" => InputType::Binary,
+        "le-binary" => InputType::LeBinary,
+        "file" => InputType::File,
+        "hex" => InputType::Hex,
+        "le-hex" => InputType::LeHex,
+        "decimal" => InputType::Decimal,
+        _ => return Err(JsError::new(&format!("Unknown input type {}.", name))),
+    })
+}
+
+fn key_pair(private_key: &str) -> Result<KeyPair, JsError>{
+    let private = PrivKey::from_hex(private_key, &Curve::secp256k1()).map_err(js_error)?;
+    KeyPair::from_private(&private).map_err(js_error)
+}
+
+/// Returns the sha256 hash of the message, as hexadecimal.
+///
+/// The input type is named like in the cli: `text`, `binary`, `le-binary`, `file`, `hex`, `le-hex` or `decimal`.
+#[wasm_bindgen]
+pub fn sha256(message: &str, input_type_name: &str) -> Result<String, JsError>{
+    let hash = sha256::sha256(message, input_type(input_type_name)?).map_err(js_error)?;
+    Ok(hash.get_hex().to_string())
+}
+
+/// Returns a random private key of secp256k1.
+#[wasm_bindgen(js_name = generatePrivateKey)]
+pub fn generate_private_key() -> String{
+    let curve = Curve::secp256k1();
+    // random_scalar is always a valid private key
+    PrivKey::new(rand::random_scalar(&curve), curve).map(|private| private.to_hex()).unwrap_or_default()
+}
+
+/// Returns the public key of the private key.
+#[wasm_bindgen(js_name = publicKey)]
+pub fn public_key(private_key: &str) -> Result<String, JsError>{
+    Ok(hex::encode(&key_pair(private_key)?.public().to_bytes()))
+}
+
+/// Signs the text message with the private key, returning the signature.
+#[wasm_bindgen]
+pub fn sign(private_key: &str, message: &str) -> Result<String, JsError>{
+    let signature = key_pair(private_key)?.sign(message, InputType::Text).map_err(js_error)?;
+    let size = Curve::secp256k1().coordinate_size();
+    let mut bytes = Vec::with_capacity(2 * size);
+    for number in [signature.get_r(), signature.get_s()]{
+        let number = number.to_bytes_be();
+        bytes.extend(vec![0; size - number.len()]);
+        bytes.extend(number);
+    }
+    Ok(hex::encode(&bytes))
+}
+
+/// Whether the signature of the text message is valid for the public key.
+///
+/// A public key or signature that can't be read is an error, a valid one that doesn't match is false.
+#[wasm_bindgen]
+pub fn verify(public_key: &str, message: &str, signature: &str) -> Result<bool, JsError>{
+    let curve = Curve::secp256k1();
+    let size = curve.coordinate_size();
+    let (public, signature) = (hex::decode(public_key).map_err(js_error)?, hex::decode(signature).map_err(js_error)?);
+    if public.len() != 2 * size || signature.len() != 2 * size{
+        return Err(JsError::new("The public key and the signature must have 64 bytes."));
+    }
+    let public = PubKey::from_affine_bytes(&public[..size], &public[size..], curve.clone()).map_err(js_error)?;
+    let (r, s) = signature.split_at(size);
+    let signature = Signature::new(BigUint::from_bytes_be(r), BigUint::from_bytes_be(s), curve, public.get_public().clone())
+        .map_err(js_error)?;
+    signature.verify(message, InputType::Text).map_err(js_error)
+}